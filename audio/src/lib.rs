@@ -1,6 +1,8 @@
 use std::sync::atomic::{AtomicU64, Ordering};
 
 mod audio;
+mod loudness;
+pub mod metadata;
 pub mod player;
 pub mod recorder;
 mod ringbuffer;
@@ -0,0 +1,38 @@
+use anyhow::Result;
+use ebur128::EbuR128;
+use log::debug;
+
+// Samples are brought to the EBU R128 target, but never so far that their loudest peak clips..
+const TARGET_LOUDNESS: f64 = -23.0;
+const PEAK_CEILING: f64 = -1.0;
+
+/// Works out the gain which normalises everything added to the meter, None if it was too quiet
+/// to measure. The meter needs to have been created with both Mode::I and Mode::SAMPLE_PEAK.
+pub(crate) fn get_normalised_gain(ebu_r128: &EbuR128, channels: u32) -> Result<Option<f64>> {
+    let mut loudness = ebu_r128.loudness_global()?;
+    if loudness == f64::NEG_INFINITY {
+        debug!("Unable to Obtain loudness in Mode I, trying M..");
+        loudness = ebu_r128.loudness_momentary()?;
+    }
+
+    if loudness == f64::NEG_INFINITY {
+        debug!("Unable to Obtain loudness in Mode M..");
+        return Ok(None);
+    }
+
+    let gain_db = TARGET_LOUDNESS - loudness;
+    let mut gain = f64::powf(10., gain_db / 20.);
+
+    let mut peak: f64 = 0.;
+    for channel in 0..channels {
+        peak = peak.max(ebu_r128.sample_peak(channel)?);
+    }
+
+    let ceiling = f64::powf(10., PEAK_CEILING / 20.);
+    if peak > 0. && peak * gain > ceiling {
+        debug!("Limiting Gain to keep the Peak below {}dBFS", PEAK_CEILING);
+        gain = ceiling / peak;
+    }
+
+    Ok(Some(gain))
+}
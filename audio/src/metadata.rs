@@ -0,0 +1,138 @@
+use anyhow::{bail, Result};
+use std::fs::File;
+use std::io::ErrorKind::UnexpectedEof;
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::errors::Error;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::probe::{Hint, ProbeResult};
+use symphonia::default::get_codecs;
+
+// Nothing draws a waveform wider than this, and each point is a float in the response, so a
+// request for more is almost certainly a mistake..
+const MAX_WAVEFORM_POINTS: usize = 4096;
+
+#[derive(Debug, Copy, Clone)]
+pub struct SampleInfo {
+    pub duration_ms: Option<u64>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+/// Reads the basic details of a sample from its headers, without decoding the audio.
+pub fn get_sample_info(file: &Path) -> Result<SampleInfo> {
+    let probe = probe_file(file)?;
+    let track = match probe.format.default_track() {
+        Some(track) => track,
+        None => bail!("Unable to find Default Track"),
+    };
+
+    let params = &track.codec_params;
+    let sample_rate = match params.sample_rate {
+        Some(rate) => rate,
+        None => bail!("Unable to Determine the Audio File's Sample Rate"),
+    };
+    let channels = match params.channels {
+        Some(channels) => channels.count() as u16,
+        None => bail!("Unable to obtain channel count"),
+    };
+
+    // Not all formats (mainly MP3s without a header) know their length without decoding..
+    let duration_ms = params
+        .n_frames
+        .map(|frames| frames * 1000 / sample_rate as u64);
+
+    Ok(SampleInfo {
+        duration_ms,
+        sample_rate,
+        channels,
+    })
+}
+
+/// Decodes the sample and returns the peak level (0.0 - 1.0) of each of `points` evenly sized
+/// sections of the file, suitable for drawing a waveform. `points` must be between 1 and 4096.
+pub fn get_waveform(file: &Path, points: usize) -> Result<Vec<f32>> {
+    if points == 0 {
+        bail!("Waveform must contain at least one point");
+    }
+    if points > MAX_WAVEFORM_POINTS {
+        bail!("Waveform can contain at most {} points", MAX_WAVEFORM_POINTS);
+    }
+
+    let mut probe = probe_file(file)?;
+    let reader = &mut probe.format;
+
+    let track = match reader.default_track() {
+        Some(track) => track,
+        None => bail!("Unable to find Default Track"),
+    };
+    let track_id = track.id;
+    let mut decoder = get_codecs().make(&track.codec_params, &Default::default())?;
+
+    // Grab the peak of each frame across all channels..
+    let mut peaks: Vec<f32> = vec![];
+    let mut sample_buffer = None;
+    loop {
+        let packet = match reader.next_packet() {
+            Ok(packet) => packet,
+            Err(Error::IoError(e)) if e.kind() == UnexpectedEof => break,
+            Err(e) => bail!(e),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // Skip over any corrupt packets..
+            Err(Error::DecodeError(_)) => continue,
+            Err(e) => bail!(e),
+        };
+
+        let channels = decoded.spec().channels.count();
+        let buffer = sample_buffer.get_or_insert_with(|| {
+            SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec())
+        });
+        buffer.copy_interleaved_ref(decoded);
+
+        for frame in buffer.samples().chunks(channels) {
+            peaks.push(frame.iter().fold(0.0_f32, |peak, s| peak.max(s.abs())));
+        }
+    }
+
+    if peaks.is_empty() {
+        return Ok(vec![0.0; points]);
+    }
+
+    // Now split the frames into our points, and take the loudest of each..
+    let mut waveform = Vec::with_capacity(points);
+    for point in 0..points {
+        let start = point * peaks.len() / points;
+        let end = ((point + 1) * peaks.len() / points).max(start + 1);
+        let peak = peaks[start..end.min(peaks.len())]
+            .iter()
+            .fold(0.0_f32, |peak, s| peak.max(*s));
+        waveform.push(peak.min(1.0));
+    }
+
+    Ok(waveform)
+}
+
+fn probe_file(file: &Path) -> Result<ProbeResult> {
+    let mut hint = Hint::new();
+    if let Some(extension) = file.extension() {
+        if let Some(extension_str) = extension.to_str() {
+            hint.with_extension(extension_str);
+        }
+    }
+
+    let stream = MediaSourceStream::new(Box::new(File::open(file)?), Default::default());
+    Ok(symphonia::default::get_probe().format(
+        &hint,
+        stream,
+        &Default::default(),
+        &Default::default(),
+    )?)
+}
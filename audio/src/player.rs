@@ -10,6 +10,7 @@ use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
 use std::sync::{Arc, Mutex};
 
 use crate::audio::{get_output, AudioSpecification};
+use crate::loudness::get_normalised_gain;
 use crate::AtomicF64;
 use symphonia::core::audio::{Layout, SampleBuffer, SignalSpec};
 use symphonia::core::errors::Error;
@@ -157,7 +158,8 @@ impl Player {
 
         if let Some(rate) = sample_rate {
             if self.process_only {
-                ebu_r128 = Some(EbuR128::new(channels as u32, rate, Mode::I)?);
+                let mode = Mode::I | Mode::SAMPLE_PEAK;
+                ebu_r128 = Some(EbuR128::new(channels as u32, rate, mode)?);
             } else {
                 if let Some(fade_duration) = self.fade_duration {
                     // Calculate the Change in Volume per sample..
@@ -376,22 +378,11 @@ impl Player {
 
         if let Some(ebu_r128) = ebu_r128 {
             // Calculate Gain..
-            let mut loudness = ebu_r128.loudness_global()?;
-            if loudness == f64::NEG_INFINITY {
-                debug!("Unable to Obtain loudness in Mode I, trying M..");
-                loudness = ebu_r128.loudness_momentary()?;
-            }
-
-            if loudness == f64::NEG_INFINITY {
-                debug!("Unable to Obtain loudness in Mode M, Setting Default..");
-                self.normalized_gain.store(1.0, Ordering::Relaxed);
-            } else {
-                let target = -23.0;
-                let gain_db = target - loudness;
-                let value = f64::powf(10., gain_db / 20.);
-
-                self.normalized_gain.store(value, Ordering::Relaxed);
-            }
+            let gain = get_normalised_gain(&ebu_r128, channels as u32)?.unwrap_or_else(|| {
+                debug!("Unable to Obtain loudness, Setting Default..");
+                1.0
+            });
+            self.normalized_gain.store(gain, Ordering::Relaxed);
         }
         decoder.finalize();
 
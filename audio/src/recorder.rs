@@ -18,6 +18,7 @@ use rb::{Producer, RbConsumer, RbProducer, SpscRb, RB};
 use symphonia::core::audio::{Layout, SignalSpec};
 
 use crate::audio::{get_input, AudioInput, AudioSpecification};
+use crate::loudness::get_normalised_gain;
 use crate::ringbuffer::RingBuffer;
 use crate::{get_audio_inputs, AtomicF64};
 
@@ -263,7 +264,7 @@ impl BufferedRecorder {
         let mut ebu_prep_r128 = EbuR128::new(2, 48000, Mode::SAMPLE_PEAK)?;
 
         // EBU Rec is here to perform the needed gain calculations on what has already been recorded
-        let mut ebu_rec_r128 = EbuR128::new(2, 48000, Mode::I)?;
+        let mut ebu_rec_r128 = EbuR128::new(2, 48000, Mode::I | Mode::SAMPLE_PEAK)?;
 
         // Whether we're writing to a file.
         let mut writing = false;
@@ -328,28 +329,19 @@ impl BufferedRecorder {
             fs::remove_file(path)?;
         } else {
             // We have noise recorded, try to normalise it..
-            let mut loudness = ebu_rec_r128.loudness_global()?;
-            if loudness == f64::NEG_INFINITY {
-                debug!("Unable to Obtain loudness in Mode I, trying M..");
-                loudness = ebu_rec_r128.loudness_momentary()?;
-            }
-
-            if loudness == f64::NEG_INFINITY {
-                debug!("Unable to Obtain loudness in Mode M, Setting Default..");
-                state.gain.store(1.0, Ordering::Relaxed);
-            } else {
-                let target = -23.0;
-                let gain_db = target - loudness;
-                let value = f64::powf(10., gain_db / 20.);
+            match get_normalised_gain(&ebu_rec_r128, 2)? {
+                None => {
+                    debug!("Unable to Obtain loudness, Setting Default..");
+                    state.gain.store(1.0, Ordering::Relaxed);
+                }
 
                 // If we need to multiply the input by over 200, we're pulling in something
                 // *FAR* to quiet to handle properly, so we'll reject it.
-                if value > 200. {
+                Some(value) if value > 200. => {
                     debug!("Received Noise too quiet, cannot handle sanely, Cancelling.");
                     fs::remove_file(path)?;
-                } else {
-                    state.gain.store(value, Ordering::Relaxed);
                 }
+                Some(value) => state.gain.store(value, Ordering::Relaxed),
             }
         }
 
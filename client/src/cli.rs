@@ -1,21 +1,23 @@
 use clap::{ArgAction, Args, Parser, Subcommand};
 
 use goxlr_types::{
-    AnimationMode, Button, ButtonColourGroups, ButtonColourOffStyle, ChannelName,
-    CompressorAttackTime, CompressorRatio, CompressorReleaseTime, EchoStyle, EffectBankPresets,
-    EncoderColourTargets, EqFrequencies, FaderDisplayStyle, FaderName, GateTimes, GenderStyle,
-    HardTuneSource, HardTuneStyle, InputDevice, MegaphoneStyle, MiniEqFrequencies, Mix,
-    MuteFunction, MuteState, OutputDevice, PitchStyle, ReverbStyle, RobotRange, RobotStyle,
-    SampleBank, SampleButtons, SamplePlayOrder, SamplePlaybackMode, SimpleColourTargets,
+    AnimationMode, AttachStatePolicy, Button, ButtonColourGroups, ButtonColourOffStyle,
+    ChannelName, CompressorAttackTime, CompressorRatio, CompressorReleaseTime, EchoStyle,
+    EffectBankPresets, EncoderColourTargets, EncoderName, EqFrequencies, FaderDisplayStyle,
+    FaderName, GateTimes, GenderStyle, HardTuneSource, HardTuneStyle, InputDevice, MegaphoneStyle,
+    MiniEqFrequencies, Mix, MuteFunction, MuteLightingStyle, MuteState, OutputDevice, PitchStyle,
+    ProfileConflictResolution, ProfileMergeSection, ReverbStyle, RobotRange, RobotStyle,
+    SampleBank, SampleButtons, SamplePlayOrder, SamplePlaybackMode, SimpleColourTargets, ToneBand,
     WaterfallDirection,
 };
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Parser, Debug)]
 #[command(about, version, author)]
 #[command(arg_required_else_help = true)]
 pub struct Cli {
-    /// The specific device's serial number to execute commands on.
+    /// The specific device's serial number (or label) to execute commands on.
     /// This field is optional if you have exactly one GoXLR, but required if you have more.
     #[arg(long)]
     pub device: Option<String>,
@@ -35,6 +37,10 @@ pub struct Cli {
     #[arg(long, num_args=0..=1, default_missing_value="http://localhost:14564")]
     pub use_http: Option<String>,
 
+    /// The API token to present when using HTTP to reach a daemon on another machine
+    #[arg(long, requires = "use_http")]
+    pub token: Option<String>,
+
     #[command(flatten, next_help_heading = "Microphone controls")]
     pub microphone_controls: MicrophoneControls,
 
@@ -92,6 +98,12 @@ pub enum SubCommands {
         command: SubmixCommands,
     },
 
+    /// Save, Delete and Apply named sets of Channel Volumes
+    VolumePresets {
+        #[command(subcommand)]
+        command: VolumePresetCommands,
+    },
+
     /// Configure the Bleep Button
     BleepVolume {
         /// Set Bleep Button Volume
@@ -126,6 +138,38 @@ pub enum SubCommands {
         enabled: bool,
     },
 
+    /// Temporarily send only one input to the Headphones
+    Solo {
+        /// The input to solo, omit to restore the normal routing
+        #[arg(value_enum)]
+        input: Option<InputDevice>,
+    },
+
+    /// Cuts everything from every output except the Headphones
+    Panic {
+        /// Whether the outputs are cut, false restores the normal routing
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
+    /// Stops the configured buttons and faders on the device from doing anything
+    Lock {
+        /// Whether the controls are locked, false makes them work again
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
+    /// Temporarily cuts every input from an output, without changing the routing
+    MuteOutput {
+        /// The output to mute
+        #[arg(value_enum)]
+        output: OutputDevice,
+
+        /// Whether the output is muted [true | false]
+        #[arg(value_parser, action = ArgAction::Set)]
+        muted: bool,
+    },
+
     /// Commands to control the GoXLR lighting
     Lighting {
         #[command(subcommand)]
@@ -147,6 +191,67 @@ pub enum SubCommands {
         #[clap[subcommand]]
         command: DeviceSettings,
     },
+
+    /// Undo the last change made to the device
+    Undo,
+
+    /// Redo the last undone change
+    Redo,
+
+    /// Resync with the device, and apply the default profiles to everything
+    ResetDevice,
+
+    /// Talk to the GoXLR directly (the daemon must be stopped), and print a diagnostic report
+    Doctor,
+
+    /// Manage the tokens used to access the API over the network
+    ApiToken {
+        #[command(subcommand)]
+        command: ApiTokenCommands,
+    },
+
+    /// Print the most recent changes made to the devices, and who made them, as JSON lines
+    Audit {
+        /// Only show changes made to this device
+        #[arg(long)]
+        serial: Option<String>,
+
+        /// How many changes to show
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+#[command(arg_required_else_help = true)]
+pub enum ApiTokenCommands {
+    /// List the names and scopes of the existing tokens
+    List,
+
+    /// Create a token (replacing any existing token with the same name), and print it
+    Create {
+        /// A name to identify the token by
+        name: String,
+
+        /// What the token is allowed to do
+        #[arg(value_enum, default_value = "read-only")]
+        scope: ApiTokenScope,
+    },
+
+    /// Revoke a token
+    Remove {
+        /// The name of the token to remove
+        name: String,
+    },
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, Debug)]
+pub enum ApiTokenScope {
+    /// Can read the status, but not change anything
+    ReadOnly,
+
+    /// Can run commands against the daemon and devices
+    Control,
 }
 
 fn percent_value(s: &str) -> Result<u8, String> {
@@ -238,6 +343,16 @@ pub enum ProfileAction {
         profile_name: String,
     },
 
+    /// Take parts of another profile, keeping the rest of the current one
+    Merge {
+        /// The name of the profile to merge from
+        profile_name: String,
+
+        /// The parts of the profile to take
+        #[arg(value_enum, required = true)]
+        sections: Vec<ProfileMergeSection>,
+    },
+
     /// Save the currently running profile
     Save,
 
@@ -246,6 +361,19 @@ pub enum ProfileAction {
         /// The new Profile Name
         profile_name: String,
     },
+
+    /// Discard any unsaved changes, and reload the profile from disk
+    Discard,
+
+    /// Replace the running profile and mic profile with the built in defaults
+    Reset,
+
+    /// Resolve a profile which has been changed on disk while loaded
+    ResolveConflict {
+        /// How to handle the changed file
+        #[arg(value_enum)]
+        resolution: ProfileConflictResolution,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -288,6 +416,32 @@ pub enum MicrophoneCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+#[command(arg_required_else_help = true)]
+pub enum VolumePresetCommands {
+    /// Saves the current channel volumes and output trims as a preset
+    Save {
+        /// The name of the preset
+        name: String,
+    },
+
+    /// Removes a volume preset
+    Delete {
+        /// The name of the preset
+        name: String,
+    },
+
+    /// Applies a volume preset, optionally fading to it
+    Apply {
+        /// The name of the preset
+        name: String,
+
+        /// How long to fade to the new volumes over, in milliseconds
+        #[arg(long, default_value_t = 0)]
+        fade: u32,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 #[command(arg_required_else_help = true)]
 pub enum SubmixCommands {
@@ -486,7 +640,47 @@ pub enum FaderCommands {
         #[command(subcommand)]
         command: Scribbles,
     },
+
+    /// Manage Fader Pages (switchable sets of Fader assignments)
+    Pages {
+        #[command(subcommand)]
+        command: FaderPages,
+    },
 }
+
+#[derive(Subcommand, Debug)]
+pub enum FaderPages {
+    /// Create a new Page from the current Fader layout
+    Add {
+        /// The name of the new Page
+        name: String,
+    },
+
+    /// Remove a Page
+    Remove {
+        /// The index of the Page to remove
+        page: usize,
+    },
+
+    /// Rename a Page
+    Rename {
+        /// The index of the Page to rename
+        page: usize,
+
+        /// The new name of the Page
+        name: String,
+    },
+
+    /// Switch to a specific Page
+    Load {
+        /// The index of the Page to load
+        page: usize,
+    },
+
+    /// Switch to the next Page
+    Next,
+}
+
 #[derive(Subcommand, Debug)]
 pub enum Scribbles {
     /// Change a Scribble Icon
@@ -509,6 +703,16 @@ pub enum Scribbles {
         text: String,
     },
 
+    /// Temporarily show lines of text on a Scribble, without changing the profile
+    Display {
+        /// The Fader to Change
+        #[arg(value_enum)]
+        fader: FaderName,
+
+        /// The lines to display (up to 3), omit to show the profile's scribble again
+        lines: Vec<String>,
+    },
+
     /// Change the Number field (Top Left)
     Number {
         /// The Fader to Change
@@ -529,6 +733,27 @@ pub enum Scribbles {
         #[arg(value_parser, action = ArgAction::Set)]
         inverted: bool,
     },
+
+    /// Draws a bar down the left side of a scribble display
+    LeftBar {
+        /// The Fader to Change
+        #[arg(value_enum)]
+        fader: FaderName,
+
+        /// Whether the bar is shown
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
+    /// Replaces the Scribble's image in the profile with a PNG, and displays it
+    Image {
+        /// The Fader to Change
+        #[arg(value_enum)]
+        fader: FaderName,
+
+        /// The PNG file to use
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -542,6 +767,31 @@ pub enum CoughCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+#[command(arg_required_else_help = true)]
+pub enum VoiceMeterCommands {
+    /// Enable or Disable the Voice Meter
+    Enabled {
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
+    /// How quiet the mic can be while still lighting the meter (0 - 100)
+    Sensitivity {
+        #[arg(value_parser=percent_value)]
+        sensitivity: u8,
+    },
+
+    /// The colours at the bottom and top of the meter
+    Colours {
+        /// The colour of the first fader (RRGGBB)
+        low: String,
+
+        /// The colour of the last fader (RRGGBB)
+        high: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 #[command(arg_required_else_help = true)]
 pub enum LightingCommands {
@@ -554,6 +804,31 @@ pub enum LightingCommands {
     /// Sets the Global GoXLR Colour
     Global { colour: String },
 
+    /// Sets the Lighting Brightness for the current profile (0 - 100)
+    Brightness {
+        #[arg(value_parser=percent_value)]
+        brightness: u8,
+    },
+
+    /// Sets the Lighting Brightness for the device, applied on top of the profile (0 - 100)
+    GlobalBrightness {
+        #[arg(value_parser=percent_value)]
+        brightness: u8,
+    },
+
+    /// How long to fade the lighting over when switching profiles, 0 to switch instantly
+    Crossfade {
+        /// The duration in Milliseconds
+        #[arg(value_parser, action = ArgAction::Set)]
+        duration: u16,
+    },
+
+    /// Configure the Voice Meter, which lights the faders based on the mic level
+    VoiceMeter {
+        #[command(subcommand)]
+        command: VoiceMeterCommands,
+    },
+
     /// Configure Lighting for a specific fader
     Fader {
         #[command(subcommand)]
@@ -603,6 +878,18 @@ pub enum LightingCommands {
         /// The Knob Colour
         colour_three: String,
     },
+
+    /// Writes the device's full lighting setup to a JSON file
+    Export {
+        /// Where to write the lighting
+        path: PathBuf,
+    },
+
+    /// Replaces the device's lighting with one written by 'export'
+    Import {
+        /// The JSON file to read
+        path: PathBuf,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -707,6 +994,17 @@ pub enum ButtonLightingCommands {
         #[arg(value_enum)]
         off_style: ButtonColourOffStyle,
     },
+
+    /// Set how a Mute button is lit while muted
+    MutedStyle {
+        /// The Mute Button to change
+        #[arg(value_enum)]
+        button: Button,
+
+        /// How the button should be presented when muted
+        #[arg(value_enum)]
+        muted_style: MuteLightingStyle,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -818,6 +1116,39 @@ pub enum EffectsCommands {
         #[arg(value_parser, action = ArgAction::Set)]
         enabled: bool,
     },
+
+    /// Set the Reverb, Echo, Pitch or Gender level using any of its representations
+    Level {
+        #[arg(value_enum)]
+        encoder: EncoderName,
+
+        #[arg(value_enum)]
+        unit: EffectLevelUnit,
+
+        #[arg(allow_hyphen_values = true)]
+        value: i32,
+    },
+
+    /// Sets how many steps of an encoder it takes to cover its whole range
+    Sensitivity {
+        #[arg(value_enum)]
+        encoder: EncoderName,
+
+        /// The number of steps [1 - 100], omit to use the knob's own resolution
+        steps: Option<u8>,
+    },
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, Debug)]
+pub enum EffectLevelUnit {
+    /// The knob position
+    Raw,
+
+    /// How far along the knob's range
+    Percent,
+
+    /// The value sent to the GoXLR (dB for Reverb and Echo, semitones for Pitch)
+    Value,
 }
 
 #[derive(Subcommand, Debug)]
@@ -1126,11 +1457,33 @@ pub enum SamplerCommands {
         #[arg(value_parser=percent_value_float)]
         stop_position: f32,
     },
+
+    Trim {
+        #[arg(value_enum)]
+        bank: SampleBank,
+
+        #[arg(value_enum)]
+        button: SampleButtons,
+
+        sample_id: usize,
+
+        #[arg(value_parser=percent_value_float)]
+        start_position: f32,
+
+        #[arg(value_parser=percent_value_float)]
+        stop_position: f32,
+    },
 }
 
 #[derive(Subcommand, Debug)]
 #[command(arg_required_else_help = true)]
 pub enum DeviceSettings {
+    /// Gives the device a name which can be used instead of its serial, omit to remove it
+    Label {
+        /// The device's new label
+        label: Option<String>,
+    },
+
     /// How long to Hold a Mute button before it Mutes to All
     MuteHoldDuration {
         /// The Duration to Hold
@@ -1145,6 +1498,13 @@ pub enum DeviceSettings {
         duration: u16,
     },
 
+    /// Whether samples should be played back at a similar loudness
+    SampleNormalisation {
+        /// Whether the setting is enabled
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
     /// Enable Mic Monitoring when FX are enabled
     MonitorWithFx {
         /// Whether the setting is enabled
@@ -1165,4 +1525,118 @@ pub enum DeviceSettings {
         #[arg(value_parser, action = ArgAction::Set)]
         enabled: bool,
     },
+
+    /// Whether the Profile or the Device's current fader volumes are used on attach
+    AttachStatePolicy {
+        /// Which side 'wins' when the device is attached
+        #[arg(value_enum)]
+        policy: AttachStatePolicy,
+    },
+
+    /// Flash a button on the device when its action fails
+    ErrorFeedback {
+        /// Whether failures are shown on the device
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+    },
+
+    /// Profiles to try, in order, if the device's profile can't be loaded when it's attached
+    ProfileFallbacks {
+        /// The profile names, omit to clear the list
+        profiles: Vec<String>,
+    },
+
+    /// Caps the maximum volume of a channel, omit the limit to remove it
+    VolumeLimit {
+        /// The channel to limit
+        #[arg(value_enum)]
+        channel: ChannelName,
+
+        /// The maximum volume as a percentage [0 - 100]
+        #[arg(value_parser=percent_value)]
+        limit: Option<u8>,
+    },
+
+    /// Trims the output level of Headphones or Line Out when not assigned to a fader
+    OutputTrim {
+        /// The output to trim (Headphones or LineOut)
+        #[arg(value_enum)]
+        channel: ChannelName,
+
+        /// The output level as a percentage of the channel volume [0 - 100]
+        #[arg(value_parser=percent_value)]
+        trim: u8,
+    },
+
+    /// Cuts or boosts the bass or treble of Headphones or the Stream Mix, if the firmware allows
+    OutputTone {
+        /// The output to change (Headphones or BroadcastMix)
+        #[arg(value_enum)]
+        output: OutputDevice,
+
+        #[arg(value_enum)]
+        band: ToneBand,
+
+        /// The gain in dB [-6 - 6]
+        #[arg(allow_hyphen_values = true)]
+        gain: i8,
+    },
+
+    /// Lowers the volume of other channels while the Microphone is in use
+    Ducking {
+        /// Whether ducking is enabled
+        #[arg(value_parser, action = ArgAction::Set)]
+        enabled: bool,
+
+        /// The channels to lower [default: Music and Game]
+        #[arg(long, value_enum, num_args = 1..)]
+        channels: Vec<ChannelName>,
+
+        /// The Microphone level (in dB) which triggers ducking [-72 - 0]
+        #[arg(long, allow_hyphen_values = true, default_value_t = -30)]
+        threshold: i8,
+
+        /// How far to lower the channels, as a percentage of their volume [0 - 100]
+        #[arg(long, value_parser=percent_value, default_value_t = 50)]
+        attenuation: u8,
+
+        /// How long it takes to lower the channels, in milliseconds
+        #[arg(long, default_value_t = 100)]
+        attack: u16,
+
+        /// How long it takes to bring the channels back up, in milliseconds
+        #[arg(long, default_value_t = 1000)]
+        release: u16,
+    },
+
+    /// Chooses which controls stop working while the device is locked
+    InteractionLock {
+        /// How presses on a locked button are handled
+        #[arg(value_enum)]
+        mode: InteractionLockMode,
+
+        /// The buttons to lock [default: the Fader Mutes and Cough]
+        #[arg(long, value_enum, num_args = 1..)]
+        buttons: Vec<Button>,
+
+        /// The faders to lock [default: all of them]
+        #[arg(long, value_enum, num_args = 1..)]
+        faders: Vec<FaderName>,
+    },
+
+    /// Shows the currently playing media on a fader's scribble (Linux only)
+    NowPlaying {
+        /// The fader to show it on, leave empty to turn this off
+        #[arg(value_enum)]
+        fader: Option<FaderName>,
+    },
+}
+
+#[derive(clap::ValueEnum, Copy, Clone, Debug)]
+pub enum InteractionLockMode {
+    /// Locked buttons do nothing
+    Ignore,
+
+    /// Locked buttons need holding before they act as a normal press
+    Hold,
 }
@@ -0,0 +1,176 @@
+/*
+   Talks to any attached GoXLRs directly (without the daemon), running through the basics of
+   communicating with them, and prints a report which can be pasted into a bug report.
+
+   The daemon holds onto the devices while it's running, so it needs to be stopped first.
+*/
+
+use std::time::Duration;
+
+use anyhow::Result;
+use goxlr_usb::device::base::{FullGoXLRDevice, GoXLRDevice};
+use goxlr_usb::device::{find_devices, from_device, get_version};
+use goxlr_usb::error::ConnectError;
+use tokio::sync::mpsc;
+
+// How many requests to send when timing the device's responses
+const TIMING_REQUESTS: u32 = 50;
+
+pub async fn run_doctor() -> Result<()> {
+    println!("GoXLR Doctor");
+    println!("Client Version: {}", env!("CARGO_PKG_VERSION"));
+    println!(
+        "Platform: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+
+    let (interface, version) = get_version();
+    println!("Driver Interface: {:?} {}", interface, version);
+
+    let devices = find_devices();
+    println!("Devices Found: {}", devices.len());
+
+    for device in devices {
+        println!();
+        check_device(device).await;
+    }
+    Ok(())
+}
+
+async fn check_device(device: GoXLRDevice) {
+    let identifier = match device.identifier() {
+        Some(identifier) => format!(" ({})", identifier),
+        None => String::new(),
+    };
+    println!(
+        "Device on Bus {}, Address {}{}",
+        device.bus_number(),
+        device.address(),
+        identifier
+    );
+
+    // Neither of these are acted on here, but they need to stay open while the device is..
+    let (disconnect_tx, _disconnect_rx) = mpsc::channel(1);
+    let (event_tx, _event_rx) = mpsc::channel(1);
+
+    let mut goxlr = match from_device(device, disconnect_tx, event_tx, false) {
+        Ok(goxlr) => goxlr,
+        Err(e) => {
+            let denied = matches!(e.downcast_ref(), Some(ConnectError::PermissionDenied));
+            report("Open", Err(e));
+            if denied {
+                println!(
+                    "  (The udev rules may be missing, run 'goxlr-daemon --install-udev-rules')"
+                );
+            } else {
+                println!("  (If the GoXLR Utility is running, stop it and try again)");
+            }
+            return;
+        }
+    };
+
+    report(
+        "Kernel Driver Active",
+        Ok(describe(goxlr.kernel_driver_active())),
+    );
+
+    report(
+        "Descriptor",
+        goxlr.get_descriptor().map(|usb| {
+            format!(
+                "{:04x}:{:04x} v{}.{}.{}, {} {}",
+                usb.vendor_id(),
+                usb.product_id(),
+                usb.device_version().0,
+                usb.device_version().1,
+                usb.device_version().2,
+                usb.device_manufacturer(),
+                usb.product_name()
+            )
+        }),
+    );
+
+    report(
+        "Serial",
+        goxlr
+            .get_serial_number()
+            .map(|(serial, date)| format!("{} (Manufactured {})", serial, date)),
+    );
+
+    report(
+        "Firmware",
+        goxlr.get_firmware_version().map(|versions| {
+            format!(
+                "{}, DICE {}, FPGA Count {}",
+                versions.firmware, versions.dice, versions.fpga_count
+            )
+        }),
+    );
+
+    check_timings(goxlr.as_mut());
+
+    goxlr.stop_polling();
+}
+
+fn check_timings(goxlr: &mut dyn FullGoXLRDevice) {
+    // Start from a known index, any response which comes back with a different one is counted..
+    if let Err(e) = goxlr.reset_command_index() {
+        report("Reset Command Index", Err(e));
+        return;
+    }
+
+    let mut first_error = None;
+    for _ in 0..TIMING_REQUESTS {
+        if let Err(e) = goxlr.get_button_states() {
+            first_error.get_or_insert(e);
+        }
+    }
+
+    let metrics = goxlr.request_metrics().clone();
+    report(
+        "Requests",
+        Ok(format!(
+            "{} sent, {} failed",
+            metrics.count(),
+            metrics.errors()
+        )),
+    );
+    report(
+        "Request Timings",
+        Ok(format!(
+            "p50 {}, p95 {}, p99 {}, max {}",
+            millis(metrics.percentile(50.)),
+            millis(metrics.percentile(95.)),
+            millis(metrics.percentile(99.)),
+            millis(metrics.max())
+        )),
+    );
+    report(
+        "Command Index Mismatches",
+        Ok(format!("{}", metrics.mismatches())),
+    );
+
+    if let Some(e) = first_error {
+        report("First Request Error", Err(e));
+    }
+}
+
+fn report(name: &str, result: Result<String>) {
+    match result {
+        Ok(value) => println!("  {}: {}", name, value),
+        Err(e) => println!("  {}: FAILED ({})", name, e),
+    }
+}
+
+fn describe(value: Option<bool>) -> String {
+    match value {
+        Some(true) => String::from("Yes"),
+        Some(false) => String::from("No"),
+        None => String::from("Not Applicable"),
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2}ms", duration.as_secs_f64() * 1000.)
+}
@@ -1,3 +1,4 @@
 mod cli;
+mod doctor;
 mod microphone;
 pub mod runner;
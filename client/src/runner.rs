@@ -1,11 +1,13 @@
 use crate::cli::{
     AnimationCommands, ButtonGroupLightingCommands, ButtonLightingCommands, CompressorCommands,
-    CoughButtonBehaviours, Echo, EffectsCommands, EqualiserCommands, EqualiserMiniCommands,
-    FaderCommands, FaderLightingCommands, FadersAllLightingCommands, Gender, HardTune,
-    LightingCommands, Megaphone, MicrophoneCommands, NoiseGateCommands, Pitch, ProfileAction,
-    ProfileType, Reverb, Robot, SamplerCommands, Scribbles, SubCommands, SubmixCommands,
+    CoughButtonBehaviours, Echo, EffectLevelUnit, EffectsCommands, EqualiserCommands,
+    EqualiserMiniCommands, FaderCommands, FaderLightingCommands, FaderPages,
+    FadersAllLightingCommands, Gender, HardTune, LightingCommands, Megaphone, MicrophoneCommands,
+    NoiseGateCommands, Pitch, ProfileAction, ProfileType, Reverb, Robot, SamplerCommands,
+    Scribbles, SubCommands, SubmixCommands, VoiceMeterCommands, VolumePresetCommands,
 };
-use crate::cli::{Cli, DeviceSettings};
+use crate::cli::{ApiTokenCommands, ApiTokenScope, Cli, DeviceSettings, InteractionLockMode};
+use crate::doctor::run_doctor;
 use crate::microphone::apply_microphone_controls;
 use anyhow::{anyhow, bail, Context, Result};
 use clap::Parser;
@@ -13,13 +15,17 @@ use goxlr_ipc::client::Client;
 use goxlr_ipc::clients::ipc::ipc_client::IPCClient;
 use goxlr_ipc::clients::ipc::ipc_socket::Socket;
 use goxlr_ipc::clients::web::web_client::WebClient;
-use goxlr_ipc::GoXLRCommand;
-use goxlr_ipc::{DaemonRequest, DaemonResponse, MixerStatus, UsbProductInformation};
+use goxlr_ipc::{
+    AuditQuery, DaemonCommand, DaemonRequest, DaemonResponse, MixerStatus, TokenScope,
+    UsbProductInformation,
+};
+use goxlr_ipc::{Ducking, EffectLevelValue, GoXLRCommand, InteractionLock};
 use goxlr_types::{ChannelName, DeviceType, FaderName, InputDevice, MicrophoneType, OutputDevice};
 
 use interprocess::local_socket::tokio::prelude::LocalSocketStream;
 use interprocess::local_socket::traits::tokio::Stream;
 use interprocess::local_socket::{GenericFilePath, GenericNamespaced, ToFsName, ToNsName};
+use std::fs;
 use strum::IntoEnumIterator;
 
 static SOCKET_PATH: &str = "/tmp/goxlr.socket";
@@ -28,10 +34,15 @@ static NAMED_PIPE: &str = "@goxlr.socket";
 pub async fn run_cli() -> Result<()> {
     let cli: Cli = Cli::parse();
 
+    // The doctor needs the device to itself, so doesn't go near the daemon..
+    if let Some(SubCommands::Doctor) = &cli.subcommands {
+        return run_doctor().await;
+    }
+
     let mut client: Box<dyn Client>;
 
     if let Some(url) = cli.use_http {
-        client = Box::new(WebClient::new(format!("{}/api/command", url)));
+        client = Box::new(WebClient::new(format!("{}/api/command", url), cli.token));
     } else {
         // Windows supports unix sockets now, but we want to maintain the historic behaviour
         // so we'll force it to a NameSpace here..
@@ -56,19 +67,54 @@ pub async fn run_cli() -> Result<()> {
         client = Box::new(IPCClient::new(socket));
     }
 
+    client.handshake().await?;
     client.poll_status().await?;
 
-    let serial = if let Some(serial) = &cli.device {
-        serial.to_owned()
+    // Tokens belong to the daemon rather than a device, so we don't need a serial..
+    if let Some(SubCommands::ApiToken { command }) = &cli.subcommands {
+        return run_api_token_command(command, &mut client).await;
+    }
+
+    // The audit log covers every device, so this doesn't need one either..
+    if let Some(SubCommands::Audit { serial, limit }) = &cli.subcommands {
+        let query = AuditQuery {
+            serial: serial.clone(),
+            since: None,
+            limit: Some(*limit),
+        };
+        let entries = client
+            .get_audit_log(query)
+            .await
+            .context("Unable to fetch the Audit Log")?;
+        for entry in entries {
+            println!("{}", serde_json::to_string(&entry)?);
+        }
+        return Ok(());
+    }
+
+    for (serial, message) in &client.status().errored_devices {
+        eprintln!("{}: {}", serial, message);
+    }
+
+    let serial = if let Some(device) = &cli.device {
+        match client.status().find_serial(device) {
+            Some(serial) => serial.to_owned(),
+            None => device.to_owned(),
+        }
     } else if client.status().mixers.is_empty() {
         return Err(anyhow!("No GoXLR Devices are Connected."));
     } else if client.status().mixers.len() == 1 {
         client.status().mixers.keys().next().unwrap().to_owned()
     } else {
         for mixer in client.status().mixers.values() {
+            let label = match &mixer.settings.label {
+                Some(label) => format!(" ({})", label),
+                None => String::new(),
+            };
             println!(
-                "{} - {} on bus {}, address {}",
+                "{}{} - {} on bus {}, address {}",
                 mixer.hardware.serial_number,
+                label,
                 match mixer.hardware.device_type {
                     DeviceType::Unknown => "Unknown device",
                     DeviceType::Full => "Regular GoXLR",
@@ -226,6 +272,18 @@ pub async fn run_cli() -> Result<()> {
                                 )
                                 .await?;
                         }
+                        Scribbles::Display { fader, lines } => {
+                            let lines = match lines.is_empty() {
+                                true => None,
+                                false => Some(lines.clone()),
+                            };
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::SetScribbleDisplayText(*fader, lines),
+                                )
+                                .await?;
+                        }
                         Scribbles::Number { fader, text } => {
                             client
                                 .command(
@@ -242,6 +300,51 @@ pub async fn run_cli() -> Result<()> {
                                 )
                                 .await?;
                         }
+                        Scribbles::LeftBar { fader, enabled } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::SetScribbleLeftBar(*fader, *enabled),
+                                )
+                                .await?;
+                        }
+                        Scribbles::Image { fader, path } => {
+                            let png = fs::read(path)
+                                .with_context(|| format!("Unable to read {}", path.display()))?;
+                            client
+                                .command(&serial, GoXLRCommand::SetScribbleImage(*fader, png))
+                                .await?;
+                        }
+                    },
+                    FaderCommands::Pages { command } => match command {
+                        FaderPages::Add { name } => {
+                            client
+                                .command(&serial, GoXLRCommand::AddFaderPage(name.clone()))
+                                .await?;
+                        }
+                        FaderPages::Remove { page } => {
+                            client
+                                .command(&serial, GoXLRCommand::RemoveFaderPage(*page))
+                                .await?;
+                        }
+                        FaderPages::Rename { page, name } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::RenameFaderPage(*page, name.clone()),
+                                )
+                                .await?;
+                        }
+                        FaderPages::Load { page } => {
+                            client
+                                .command(&serial, GoXLRCommand::SetFaderPage(*page))
+                                .await?;
+                        }
+                        FaderPages::Next => {
+                            client
+                                .command(&serial, GoXLRCommand::NextFaderPage())
+                                .await?;
+                        }
                     },
                 },
                 SubCommands::Router {
@@ -253,6 +356,28 @@ pub async fn run_cli() -> Result<()> {
                         .command(&serial, GoXLRCommand::SetRouter(*input, *output, *enabled))
                         .await?;
                 }
+                SubCommands::Solo { input } => {
+                    client
+                        .command(&serial, GoXLRCommand::SoloChannel(*input))
+                        .await?;
+                }
+                SubCommands::Panic { enabled } => {
+                    let command = match enabled {
+                        true => GoXLRCommand::PanicMute(),
+                        false => GoXLRCommand::PanicRestore(),
+                    };
+                    client.command(&serial, command).await?;
+                }
+                SubCommands::Lock { enabled } => {
+                    client
+                        .command(&serial, GoXLRCommand::SetInteractionLock(*enabled))
+                        .await?;
+                }
+                SubCommands::MuteOutput { output, muted } => {
+                    client
+                        .command(&serial, GoXLRCommand::SetOutputMuted(*output, *muted))
+                        .await?;
+                }
                 SubCommands::Volume {
                     channel,
                     volume_percent,
@@ -290,6 +415,27 @@ pub async fn run_cli() -> Result<()> {
                         )
                         .await?;
                 }
+                SubCommands::Undo => {
+                    client
+                        .command(&serial, GoXLRCommand::Undo())
+                        .await
+                        .context("Unable to Undo")?;
+                }
+                SubCommands::Redo => {
+                    client
+                        .command(&serial, GoXLRCommand::Redo())
+                        .await
+                        .context("Unable to Redo")?;
+                }
+                SubCommands::Doctor | SubCommands::ApiToken { .. } | SubCommands::Audit { .. } => {
+                    // Handled before finding the device
+                }
+                SubCommands::ResetDevice => {
+                    client
+                        .command(&serial, GoXLRCommand::ResetDevice())
+                        .await
+                        .context("Unable to Reset the Device")?;
+                }
 
                 SubCommands::Lighting { command } => match command {
                     LightingCommands::Animation { command } => match command {
@@ -315,6 +461,48 @@ pub async fn run_cli() -> Result<()> {
                         }
                     },
 
+                    LightingCommands::Brightness { brightness } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetLightingBrightness(*brightness))
+                            .await?;
+                    }
+                    LightingCommands::GlobalBrightness { brightness } => {
+                        client
+                            .command(
+                                &serial,
+                                GoXLRCommand::SetGlobalLightingBrightness(*brightness),
+                            )
+                            .await?;
+                    }
+                    LightingCommands::Crossfade { duration } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetLightingCrossfade(*duration))
+                            .await?;
+                    }
+                    LightingCommands::VoiceMeter { command } => match command {
+                        VoiceMeterCommands::Enabled { enabled } => {
+                            client
+                                .command(&serial, GoXLRCommand::SetVoiceMeterEnabled(*enabled))
+                                .await?;
+                        }
+                        VoiceMeterCommands::Sensitivity { sensitivity } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::SetVoiceMeterSensitivity(*sensitivity),
+                                )
+                                .await?;
+                        }
+                        VoiceMeterCommands::Colours { low, high } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::SetVoiceMeterColours(low.clone(), high.clone()),
+                                )
+                                .await?;
+                        }
+                    },
+
                     LightingCommands::Global { colour } => {
                         client
                             .command(&serial, GoXLRCommand::SetGlobalColour(colour.to_string()))
@@ -390,6 +578,17 @@ pub async fn run_cli() -> Result<()> {
                                 )
                                 .await?;
                         }
+                        ButtonLightingCommands::MutedStyle {
+                            button,
+                            muted_style,
+                        } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::SetButtonMutedStyle(*button, *muted_style),
+                                )
+                                .await?;
+                        }
                     },
                     LightingCommands::ButtonGroup { command } => match command {
                         ButtonGroupLightingCommands::Colour {
@@ -448,6 +647,23 @@ pub async fn run_cli() -> Result<()> {
                             )
                             .await?;
                     }
+                    LightingCommands::Export { path } => {
+                        let Some(mixer) = client.status().mixers.get(&serial) else {
+                            bail!("Device {} not found", serial);
+                        };
+                        let json = serde_json::to_string_pretty(&mixer.lighting)?;
+                        fs::write(path, json)
+                            .with_context(|| format!("Unable to write {}", path.display()))?;
+                    }
+                    LightingCommands::Import { path } => {
+                        let json = fs::read_to_string(path)
+                            .with_context(|| format!("Unable to read {}", path.display()))?;
+                        let lighting = serde_json::from_str(&json)
+                            .with_context(|| format!("{} is not valid lighting", path.display()))?;
+                        client
+                            .command(&serial, GoXLRCommand::SetLighting(lighting))
+                            .await?;
+                    }
                 },
 
                 SubCommands::Profiles { command } => match command {
@@ -485,6 +701,21 @@ pub async fn run_cli() -> Result<()> {
                                 .await
                                 .context("Unable to load Profile Colours")?;
                         }
+                        ProfileAction::Merge {
+                            profile_name,
+                            sections,
+                        } => {
+                            client
+                                .command(
+                                    &serial,
+                                    GoXLRCommand::MergeProfile(
+                                        profile_name.to_string(),
+                                        sections.clone(),
+                                    ),
+                                )
+                                .await
+                                .context("Unable to Merge Profile")?;
+                        }
                         ProfileAction::Save {} => {
                             client
                                 .command(&serial, GoXLRCommand::SaveProfile())
@@ -500,6 +731,24 @@ pub async fn run_cli() -> Result<()> {
                                 .await
                                 .context("Unable to Save Profile")?;
                         }
+                        ProfileAction::Discard => {
+                            client
+                                .command(&serial, GoXLRCommand::DiscardProfileChanges())
+                                .await
+                                .context("Unable to Discard Profile Changes")?;
+                        }
+                        ProfileAction::Reset => {
+                            client
+                                .command(&serial, GoXLRCommand::ResetToDefaultProfile())
+                                .await
+                                .context("Unable to Reset to the Default Profile")?;
+                        }
+                        ProfileAction::ResolveConflict { resolution } => {
+                            client
+                                .command(&serial, GoXLRCommand::ResolveProfileConflict(*resolution))
+                                .await
+                                .context("Unable to Resolve Profile Conflict")?;
+                        }
                     },
                     ProfileType::Microphone { command } => match command {
                         ProfileAction::New { profile_name } => {
@@ -529,6 +778,9 @@ pub async fn run_cli() -> Result<()> {
                         ProfileAction::LoadColours { .. } => {
                             return Err(anyhow!("Not supported for Microphone"));
                         }
+                        ProfileAction::Merge { .. } => {
+                            return Err(anyhow!("Not supported for Microphone"));
+                        }
                         ProfileAction::Save {} => {
                             client
                                 .command(&serial, GoXLRCommand::SaveMicProfile())
@@ -544,6 +796,15 @@ pub async fn run_cli() -> Result<()> {
                                 .await
                                 .context("Unable to Save Microphone Profile")?;
                         }
+                        ProfileAction::Discard => {
+                            return Err(anyhow!("Not supported for Microphone"));
+                        }
+                        ProfileAction::Reset => {
+                            return Err(anyhow!("Not supported for Microphone"));
+                        }
+                        ProfileAction::ResolveConflict { .. } => {
+                            return Err(anyhow!("Not supported for Microphone"));
+                        }
                     },
                 },
                 SubCommands::Effects { command } => match command {
@@ -855,6 +1116,31 @@ pub async fn run_cli() -> Result<()> {
                             .command(&serial, GoXLRCommand::SetFXEnabled(*enabled))
                             .await?;
                     }
+                    EffectsCommands::Level {
+                        encoder,
+                        unit,
+                        value,
+                    } => {
+                        let level = match unit {
+                            EffectLevelUnit::Raw => EffectLevelValue::Raw(i8::try_from(*value)?),
+                            EffectLevelUnit::Percent => {
+                                EffectLevelValue::Percent(u8::try_from(*value)?)
+                            }
+                            EffectLevelUnit::Value => EffectLevelValue::Value(*value),
+                        };
+                        client
+                            .command(&serial, GoXLRCommand::SetEffectLevel(*encoder, level))
+                            .await
+                            .context("Unable to set the Effect Level")?;
+                    }
+                    EffectsCommands::Sensitivity { encoder, steps } => {
+                        client
+                            .command(
+                                &serial,
+                                GoXLRCommand::SetEncoderSensitivity(*encoder, *steps),
+                            )
+                            .await?;
+                    }
                 },
                 SubCommands::Sampler { command } => match command {
                     SamplerCommands::Add { bank, button, file } => {
@@ -963,6 +1249,27 @@ pub async fn run_cli() -> Result<()> {
                             .await
                             .context("Unable to set Stop Percent")?;
                     }
+                    SamplerCommands::Trim {
+                        bank,
+                        button,
+                        sample_id,
+                        start_position,
+                        stop_position,
+                    } => {
+                        client
+                            .command(
+                                &serial,
+                                GoXLRCommand::SetSampleTrim(
+                                    *bank,
+                                    *button,
+                                    *sample_id,
+                                    *start_position,
+                                    *stop_position,
+                                ),
+                            )
+                            .await
+                            .context("Unable to set Sample Trim")?;
+                    }
                 },
                 SubCommands::Submix { command } => match command {
                     SubmixCommands::Enabled { enabled } => {
@@ -998,7 +1305,32 @@ pub async fn run_cli() -> Result<()> {
                             .await?;
                     }
                 },
+                SubCommands::VolumePresets { command } => match command {
+                    VolumePresetCommands::Save { name } => {
+                        client
+                            .command(&serial, GoXLRCommand::SaveVolumePreset(name.clone()))
+                            .await?;
+                    }
+                    VolumePresetCommands::Delete { name } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetVolumePreset(name.clone(), None))
+                            .await?;
+                    }
+                    VolumePresetCommands::Apply { name, fade } => {
+                        client
+                            .command(
+                                &serial,
+                                GoXLRCommand::ApplyVolumePreset(name.clone(), *fade),
+                            )
+                            .await?;
+                    }
+                },
                 SubCommands::Settings { command } => match command {
+                    DeviceSettings::Label { label } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetDeviceLabel(label.clone()))
+                            .await?;
+                    }
                     DeviceSettings::MuteHoldDuration { duration } => {
                         client
                             .command(&serial, GoXLRCommand::SetMuteHoldDuration(*duration))
@@ -1012,6 +1344,11 @@ pub async fn run_cli() -> Result<()> {
                             )
                             .await?;
                     }
+                    DeviceSettings::SampleNormalisation { enabled } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetSamplerNormalisation(*enabled))
+                            .await?;
+                    }
                     DeviceSettings::MonitorWithFx { enabled } => {
                         client
                             .command(&serial, GoXLRCommand::SetMonitorWithFx(*enabled))
@@ -1027,6 +1364,89 @@ pub async fn run_cli() -> Result<()> {
                             .command(&serial, GoXLRCommand::SetLockFaders(*enabled))
                             .await?;
                     }
+                    DeviceSettings::AttachStatePolicy { policy } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetAttachStatePolicy(*policy))
+                            .await?;
+                    }
+                    DeviceSettings::ErrorFeedback { enabled } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetErrorFeedback(*enabled))
+                            .await?;
+                    }
+                    DeviceSettings::ProfileFallbacks { profiles } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetProfileFallbacks(profiles.clone()))
+                            .await?;
+                    }
+                    DeviceSettings::VolumeLimit { channel, limit } => {
+                        let limit = limit.map(|limit| ((255 * limit as u16) / 100) as u8);
+                        client
+                            .command(&serial, GoXLRCommand::SetVolumeLimit(*channel, limit))
+                            .await?;
+                    }
+                    DeviceSettings::OutputTrim { channel, trim } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetOutputTrim(*channel, *trim))
+                            .await?;
+                    }
+                    DeviceSettings::OutputTone { output, band, gain } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetOutputTone(*output, *band, *gain))
+                            .await?;
+                    }
+                    DeviceSettings::Ducking {
+                        enabled,
+                        channels,
+                        threshold,
+                        attenuation,
+                        attack,
+                        release,
+                    } => {
+                        let mut ducking = Ducking {
+                            enabled: *enabled,
+                            threshold: *threshold,
+                            attenuation: *attenuation,
+                            attack: *attack,
+                            release: *release,
+                            ..Default::default()
+                        };
+                        if !channels.is_empty() {
+                            ducking.channels = channels.clone();
+                        }
+                        client
+                            .command(&serial, GoXLRCommand::SetDucking(ducking))
+                            .await?;
+                    }
+                    DeviceSettings::InteractionLock {
+                        mode,
+                        buttons,
+                        faders,
+                    } => {
+                        let mut lock = InteractionLock {
+                            mode: match mode {
+                                InteractionLockMode::Ignore => {
+                                    goxlr_ipc::InteractionLockMode::Ignore
+                                }
+                                InteractionLockMode::Hold => goxlr_ipc::InteractionLockMode::Hold,
+                            },
+                            ..Default::default()
+                        };
+                        if !buttons.is_empty() {
+                            lock.buttons = buttons.clone();
+                        }
+                        if !faders.is_empty() {
+                            lock.faders = faders.clone();
+                        }
+                        client
+                            .command(&serial, GoXLRCommand::SetInteractionLockControls(lock))
+                            .await?;
+                    }
+                    DeviceSettings::NowPlaying { fader } => {
+                        client
+                            .command(&serial, GoXLRCommand::SetNowPlayingFader(*fader))
+                            .await?;
+                    }
                 },
             }
         }
@@ -1063,6 +1483,39 @@ pub async fn run_cli() -> Result<()> {
     Ok(())
 }
 
+async fn run_api_token_command(
+    command: &ApiTokenCommands,
+    client: &mut Box<dyn Client>,
+) -> Result<()> {
+    match command {
+        ApiTokenCommands::List => {
+            for (name, scope) in &client.status().config.api_tokens {
+                println!("{}: {:?}", name, scope);
+            }
+        }
+        ApiTokenCommands::Create { name, scope } => {
+            let scope = match scope {
+                ApiTokenScope::ReadOnly => TokenScope::ReadOnly,
+                ApiTokenScope::Control => TokenScope::Control,
+            };
+            let token = client
+                .create_api_token(name, scope)
+                .await
+                .context("Unable to Create the API Token")?;
+            println!("{}", token);
+        }
+        ApiTokenCommands::Remove { name } => {
+            client
+                .send(DaemonRequest::Daemon(DaemonCommand::RemoveApiToken(
+                    name.clone(),
+                )))
+                .await
+                .context("Unable to Remove the API Token")?;
+        }
+    }
+    Ok(())
+}
+
 fn print_device(device: &MixerStatus) {
     println!(
         "Device type: {}",
@@ -1096,6 +1549,9 @@ fn print_mixer_info(mixer: &MixerStatus) {
     println!("Mixer dice: {}", mixer.hardware.versions.dice);
     println!("Mixer FPGA count: {}", mixer.hardware.versions.fpga_count);
     println!("Mixer serial number: {}", mixer.hardware.serial_number);
+    if let Some(label) = &mixer.settings.label {
+        println!("Mixer label: {}", label);
+    }
     println!(
         "Mixer manufacture date: {}",
         mixer.hardware.manufactured_date
@@ -0,0 +1,211 @@
+/*
+   A record of every command run against a device or the daemon, who sent it, and what it
+   changed, so when several people are controlling the same GoXLR, it's possible to work out who
+   did what.
+
+   Entries are appended to a JSON Lines file in the logs directory, once that gets too big it's
+   rotated out (goxlr-audit.jsonl -> goxlr-audit.1.jsonl -> goxlr-audit.2.jsonl..), and the
+   oldest is dropped. It can be turned off with SetAuditLogEnabled, at which point nothing is
+   recorded.
+*/
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use goxlr_ipc::{
+    AuditChange, AuditCommand, AuditEntry, AuditQuery, CommandSource, DaemonCommand, DaemonConfig,
+    GoXLRCommand, MixerStatus,
+};
+use log::warn;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::rotating_log::{get_path, RotatingLog};
+
+const AUDIT_FILE_NAME: &str = "goxlr-audit.jsonl";
+const MAX_AUDIT_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_AUDIT_FILES: usize = 5;
+
+/// Records are handed off to a writer thread, so working out what changed and writing it to disk
+/// never holds up the device loop.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    path: PathBuf,
+    sender: Sender<AuditRecord>,
+}
+
+struct AuditRecord {
+    entry: AuditEntry,
+    old: AuditState,
+    new: AuditState,
+}
+
+// The state before and after a command, these are only turned into JSON on the writer thread..
+#[derive(Serialize)]
+#[serde(untagged)]
+enum AuditState {
+    Mixer(Box<MixerStatus>),
+    Daemon(Box<DaemonConfig>),
+}
+
+impl AuditLog {
+    pub fn new(directory: PathBuf) -> Self {
+        let path = directory.join(AUDIT_FILE_NAME);
+        let sender =
+            RotatingLog::new(path.clone(), MAX_AUDIT_SIZE, MAX_AUDIT_FILES).spawn(format_record);
+        Self { path, sender }
+    }
+
+    pub fn record_device(
+        &self,
+        serial: &str,
+        source: CommandSource,
+        command: GoXLRCommand,
+        old: MixerStatus,
+        new: MixerStatus,
+    ) {
+        self.record(
+            Some(serial.to_string()),
+            source,
+            AuditCommand::Device(command),
+            AuditState::Mixer(Box::new(old)),
+            AuditState::Mixer(Box::new(new)),
+        );
+    }
+
+    pub fn record_daemon(
+        &self,
+        source: CommandSource,
+        command: DaemonCommand,
+        old: DaemonConfig,
+        new: DaemonConfig,
+    ) {
+        // The icon itself isn't worth keeping, the name is enough to know what happened..
+        let command = match command {
+            DaemonCommand::UploadIcon(name, _) => DaemonCommand::UploadIcon(name, vec![]),
+            command => command,
+        };
+
+        self.record(
+            None,
+            source,
+            AuditCommand::Daemon(command),
+            AuditState::Daemon(Box::new(old)),
+            AuditState::Daemon(Box::new(new)),
+        );
+    }
+
+    fn record(
+        &self,
+        serial: Option<String>,
+        source: CommandSource,
+        command: AuditCommand,
+        old: AuditState,
+        new: AuditState,
+    ) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let entry = AuditEntry {
+            timestamp,
+            source,
+            serial,
+            command,
+            changes: vec![],
+        };
+
+        if self.sender.send(AuditRecord { entry, old, new }).is_err() {
+            warn!("Audit Log writer has stopped, unable to record command");
+        }
+    }
+
+    pub fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>> {
+        let mut entries = VecDeque::new();
+
+        // Go from the oldest file to the newest, so the entries come out in order..
+        for index in (0..=MAX_AUDIT_FILES).rev() {
+            let path = get_path(&self.path, index);
+            if !path.exists() {
+                continue;
+            }
+
+            for line in BufReader::new(File::open(path)?).lines() {
+                // A line may have been cut short if we didn't shut down cleanly, skip it..
+                let Ok(entry) = serde_json::from_str::<AuditEntry>(&line?) else {
+                    continue;
+                };
+
+                if query.serial.is_some() && query.serial != entry.serial {
+                    continue;
+                }
+                if query.since.is_some_and(|since| entry.timestamp <= since) {
+                    continue;
+                }
+
+                entries.push_back(entry);
+                if query.limit.is_some_and(|limit| entries.len() > limit) {
+                    entries.pop_front();
+                }
+            }
+        }
+        Ok(entries.into())
+    }
+}
+
+// Commands which don't change the configuration (playing samples, saving, or runtime scribble text
+// which is replaced as often as the track changes) aren't worth recording..
+pub fn is_audited(command: &GoXLRCommand) -> bool {
+    !matches!(
+        command,
+        GoXLRCommand::ClearSampleProcessError()
+            | GoXLRCommand::PlaySampleByIndex(_, _, _)
+            | GoXLRCommand::PlayNextSample(_, _)
+            | GoXLRCommand::StopSamplePlayback(_, _)
+            | GoXLRCommand::SetScribbleDisplayText(_, _)
+            | GoXLRCommand::SaveProfile()
+            | GoXLRCommand::SaveMicProfile()
+    )
+}
+
+fn format_record(record: AuditRecord) -> Result<String> {
+    let mut entry = record.entry;
+    let old = serde_json::to_value(record.old)?;
+    let new = serde_json::to_value(record.new)?;
+    get_changes("", &old, &new, &mut entry.changes);
+    Ok(serde_json::to_string(&entry)?)
+}
+
+// Walks both values, noting every field which has been added, removed or changed. Arrays are
+// treated as a single value, as an index into them doesn't mean much on its own..
+fn get_changes(path: &str, old: &Value, new: &Value, changes: &mut Vec<AuditChange>) {
+    match (old, new) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let added = new_map.keys().filter(|key| !old_map.contains_key(*key));
+            for key in old_map.keys().chain(added) {
+                // Keys are escaped as per the JSON Pointer spec..
+                let path = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+                match (old_map.get(key), new_map.get(key)) {
+                    (Some(old), Some(new)) => get_changes(&path, old, new, changes),
+                    (old, new) => changes.push(AuditChange {
+                        path,
+                        old: old.cloned(),
+                        new: new.cloned(),
+                    }),
+                }
+            }
+        }
+        (old, new) if old != new => changes.push(AuditChange {
+            path: path.to_string(),
+            old: Some(old.clone()),
+            new: Some(new.clone()),
+        }),
+        _ => {}
+    }
+}
@@ -0,0 +1,243 @@
+/*
+   Works out what a device is able to do from its type and firmware version.
+
+   The Mini has no Effects, Sampler, Scribbles or Encoders, and older firmware lacks features
+   which were added later (Animations and Submixes). Output Tone controls aren't available on any
+   firmware so far. Every command is checked here before it's applied, so rather than sending the
+   device something it doesn't understand (or quietly changing a profile setting which will never
+   do anything), the caller gets told why.
+*/
+
+use goxlr_ipc::{DaemonError, GoXLRCommand, HardwareStatus};
+use goxlr_types::{
+    AnimationMode, Button, ButtonColourGroups, DeviceType, SimpleColourTargets, VersionNumber,
+};
+
+use goxlr_usb::colouring::ColourMapFormat;
+
+use crate::profile::version_newer_or_equal_to;
+
+pub fn is_mini(hardware: &HardwareStatus) -> bool {
+    hardware.device_type == DeviceType::Mini
+}
+
+pub fn supports_submixes(hardware: &HardwareStatus) -> bool {
+    let support_full = VersionNumber(1, 4, Some(2), Some(107));
+    let support_mini = VersionNumber(1, 2, Some(0), Some(46));
+
+    let current = &hardware.versions.firmware;
+
+    match hardware.device_type {
+        DeviceType::Unknown => false,
+        DeviceType::Full => version_newer_or_equal_to(current, support_full),
+        DeviceType::Mini => version_newer_or_equal_to(current, support_mini),
+    }
+}
+
+pub fn supports_animations(hardware: &HardwareStatus) -> bool {
+    let support_full = VersionNumber(1, 3, Some(40), Some(0));
+    let support_mini = VersionNumber(1, 1, Some(8), Some(0));
+
+    let current = &hardware.versions.firmware;
+
+    match hardware.device_type {
+        DeviceType::Unknown => true,
+        DeviceType::Full => version_newer_or_equal_to(current, support_full),
+        DeviceType::Mini => version_newer_or_equal_to(current, support_mini),
+    }
+}
+
+pub fn supports_output_tone(_hardware: &HardwareStatus) -> bool {
+    // Every effect key found so far belongs to the mic chain, there's nothing to EQ the outputs
+    // with. Once firmware turns up which has them, the check for that version goes here..
+    false
+}
+
+pub fn colour_map_format(hardware: &HardwareStatus) -> ColourMapFormat {
+    // The larger colour map arrived alongside animations..
+    if supports_animations(hardware) {
+        ColourMapFormat::Animated
+    } else {
+        ColourMapFormat::Legacy
+    }
+}
+
+/// Returns every reason this device can't run the command, an empty list means it can
+pub fn check_command(hardware: &HardwareStatus, command: &GoXLRCommand) -> Vec<DaemonError> {
+    let mut errors = vec![];
+
+    if is_mini(hardware) {
+        if let Some(feature) = get_full_only_feature(command) {
+            errors.push(DaemonError::UnsupportedOnMini {
+                feature: String::from(feature),
+            });
+        }
+    }
+
+    if let Some((feature, false)) = get_firmware_support(hardware, command) {
+        errors.push(DaemonError::UnsupportedFirmware {
+            feature: String::from(feature),
+        });
+    }
+
+    errors
+}
+
+// Returns the name of the feature a command needs which only exists on the full device..
+fn get_full_only_feature(command: &GoXLRCommand) -> Option<&'static str> {
+    match command {
+        GoXLRCommand::SetAnimationMode(AnimationMode::Ripple) => Some("Ripple Mode"),
+        GoXLRCommand::SetLighting(lighting)
+            if matches!(lighting.animation.mode, AnimationMode::Ripple) =>
+        {
+            Some("Ripple Mode")
+        }
+
+        GoXLRCommand::SetScribbleIcon(_, _)
+        | GoXLRCommand::SetScribbleText(_, _)
+        | GoXLRCommand::SetScribbleDisplayText(_, _)
+        | GoXLRCommand::SetNowPlayingFader(Some(_))
+        | GoXLRCommand::SetScribbleNumber(_, _)
+        | GoXLRCommand::SetScribbleInvert(_, _)
+        | GoXLRCommand::SetScribbleLeftBar(_, _)
+        | GoXLRCommand::SetScribbleImage(_, _) => Some("Scribbles"),
+        GoXLRCommand::SetSimpleColour(target, _) if is_scribble_target(*target) => {
+            Some("Scribbles")
+        }
+
+        GoXLRCommand::SetEncoderColour(_, _, _, _) => Some("Encoders"),
+
+        GoXLRCommand::SetButtonColours(button, _, _)
+        | GoXLRCommand::SetButtonOffStyle(button, _)
+            if !is_mini_button(*button) =>
+        {
+            Some("Effect and Sampler Buttons")
+        }
+        GoXLRCommand::SetButtonGroupColours(group, _, _)
+        | GoXLRCommand::SetButtonGroupOffStyle(group, _)
+            if *group != ButtonColourGroups::FaderMute =>
+        {
+            Some("Effect Buttons")
+        }
+
+        GoXLRCommand::ClearSampleProcessError()
+        | GoXLRCommand::SetSamplerFunction(_, _, _)
+        | GoXLRCommand::SetSamplerOrder(_, _, _)
+        | GoXLRCommand::AddSample(_, _, _)
+        | GoXLRCommand::SetSampleStartPercent(_, _, _, _)
+        | GoXLRCommand::SetSampleStopPercent(_, _, _, _)
+        | GoXLRCommand::SetSampleTrim(_, _, _, _, _)
+        | GoXLRCommand::RemoveSampleByIndex(_, _, _)
+        | GoXLRCommand::PlaySampleByIndex(_, _, _)
+        | GoXLRCommand::PlayNextSample(_, _)
+        | GoXLRCommand::StopSamplePlayback(_, _)
+        | GoXLRCommand::SetActiveSamplerBank(_)
+        | GoXLRCommand::SetSamplerPreBufferDuration(_)
+        | GoXLRCommand::SetSamplerResetOnClear(_)
+        | GoXLRCommand::SetSamplerNormalisation(_)
+        | GoXLRCommand::SetSampleColour(_, _, _, _)
+        | GoXLRCommand::SetSampleOffStyle(_, _) => Some("Sampler"),
+
+        GoXLRCommand::LoadEffectPreset(_)
+        | GoXLRCommand::RenameActivePreset(_)
+        | GoXLRCommand::SaveActivePreset()
+        | GoXLRCommand::SetEffectLevel(_, _)
+        | GoXLRCommand::SetEncoderSensitivity(_, _)
+        | GoXLRCommand::SetReverbStyle(_)
+        | GoXLRCommand::SetReverbAmount(_)
+        | GoXLRCommand::SetReverbDecay(_)
+        | GoXLRCommand::SetReverbEarlyLevel(_)
+        | GoXLRCommand::SetReverbTailLevel(_)
+        | GoXLRCommand::SetReverbPreDelay(_)
+        | GoXLRCommand::SetReverbLowColour(_)
+        | GoXLRCommand::SetReverbHighColour(_)
+        | GoXLRCommand::SetReverbHighFactor(_)
+        | GoXLRCommand::SetReverbDiffuse(_)
+        | GoXLRCommand::SetReverbModSpeed(_)
+        | GoXLRCommand::SetReverbModDepth(_)
+        | GoXLRCommand::SetEchoStyle(_)
+        | GoXLRCommand::SetEchoAmount(_)
+        | GoXLRCommand::SetEchoFeedback(_)
+        | GoXLRCommand::SetEchoTempo(_)
+        | GoXLRCommand::SetEchoDelayLeft(_)
+        | GoXLRCommand::SetEchoDelayRight(_)
+        | GoXLRCommand::SetEchoFeedbackLeft(_)
+        | GoXLRCommand::SetEchoFeedbackRight(_)
+        | GoXLRCommand::SetEchoFeedbackXFBLtoR(_)
+        | GoXLRCommand::SetEchoFeedbackXFBRtoL(_)
+        | GoXLRCommand::SetPitchStyle(_)
+        | GoXLRCommand::SetPitchAmount(_)
+        | GoXLRCommand::SetPitchCharacter(_)
+        | GoXLRCommand::SetGenderStyle(_)
+        | GoXLRCommand::SetGenderAmount(_)
+        | GoXLRCommand::SetMegaphoneStyle(_)
+        | GoXLRCommand::SetMegaphoneAmount(_)
+        | GoXLRCommand::SetMegaphonePostGain(_)
+        | GoXLRCommand::SetRobotStyle(_)
+        | GoXLRCommand::SetRobotGain(_, _)
+        | GoXLRCommand::SetRobotFreq(_, _)
+        | GoXLRCommand::SetRobotWidth(_, _)
+        | GoXLRCommand::SetRobotWaveform(_)
+        | GoXLRCommand::SetRobotPulseWidth(_)
+        | GoXLRCommand::SetRobotThreshold(_)
+        | GoXLRCommand::SetRobotDryMix(_)
+        | GoXLRCommand::SetHardTuneStyle(_)
+        | GoXLRCommand::SetHardTuneAmount(_)
+        | GoXLRCommand::SetHardTuneRate(_)
+        | GoXLRCommand::SetHardTuneWindow(_)
+        | GoXLRCommand::SetHardTuneSource(_)
+        | GoXLRCommand::SetActiveEffectPreset(_)
+        | GoXLRCommand::SetMegaphoneEnabled(_)
+        | GoXLRCommand::SetRobotEnabled(_)
+        | GoXLRCommand::SetHardTuneEnabled(_)
+        | GoXLRCommand::SetFXEnabled(_) => Some("Voice Effects"),
+
+        _ => None,
+    }
+}
+
+// Returns the feature a command needs from newer firmware, and whether this device has it..
+fn get_firmware_support(
+    hardware: &HardwareStatus,
+    command: &GoXLRCommand,
+) -> Option<(&'static str, bool)> {
+    match command {
+        GoXLRCommand::SetAnimationMode(_)
+        | GoXLRCommand::SetAnimationMod1(_)
+        | GoXLRCommand::SetAnimationMod2(_)
+        | GoXLRCommand::SetAnimationWaterfall(_) => {
+            Some(("Animations", supports_animations(hardware)))
+        }
+
+        GoXLRCommand::SetSubMixEnabled(_)
+        | GoXLRCommand::SetSubMixVolume(_, _)
+        | GoXLRCommand::SetSubMixLinked(_, _)
+        | GoXLRCommand::SetSubMixOutputMix(_, _)
+        | GoXLRCommand::SetMonitorMix(_) => Some(("Submixes", supports_submixes(hardware))),
+
+        GoXLRCommand::SetOutputTone(_, _, _) => {
+            Some(("Output Tone", supports_output_tone(hardware)))
+        }
+
+        _ => None,
+    }
+}
+
+fn is_mini_button(button: Button) -> bool {
+    matches!(
+        button,
+        Button::Fader1Mute
+            | Button::Fader2Mute
+            | Button::Fader3Mute
+            | Button::Fader4Mute
+            | Button::Bleep
+            | Button::Cough
+    )
+}
+
+fn is_scribble_target(target: SimpleColourTargets) -> bool {
+    !matches!(
+        target,
+        SimpleColourTargets::Global | SimpleColourTargets::Accent
+    )
+}
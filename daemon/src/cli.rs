@@ -37,10 +37,22 @@ pub struct Cli {
     #[arg(long)]
     pub force_root: bool,
 
+    /// If a daemon is already running, ask it to shut down and take over from it
+    #[arg(long)]
+    pub takeover: bool,
+
+    /// Install the udev rules needed to access the GoXLR without root (Linux only), then exit
+    #[arg(long)]
+    pub install_udev_rules: bool,
+
     /// Automatically Launch the UI on Start..
     #[arg(long)]
     pub start_ui: bool,
 
+    /// Write the daemon event log to the logs directory
+    #[arg(long)]
+    pub log_events: bool,
+
     /// Force regular expression to use when finding the Sampler Input
     #[arg(long)]
     pub override_sample_input_device: Option<String>,
@@ -0,0 +1,154 @@
+/*
+   When a UI is dragging a slider (or running an animation), it's possible for commands to
+   arrive far quicker than the GoXLR can handle them, which in turn can delay more important
+   commands (like a mute) from being processed.
+
+   This queue collects the commands as they come in, replacing any pending value updates with
+   newer ones, and allows 'urgent' commands to skip ahead of those updates. Anyone waiting on a
+   replaced update gets the result of the command which replaced it.
+*/
+
+use anyhow::{anyhow, Result};
+use goxlr_ipc::{CommandSource, DaemonError, GoXLRCommand};
+use std::collections::VecDeque;
+use tokio::sync::oneshot;
+
+pub struct QueuedCommand {
+    pub serial: String,
+    pub command: GoXLRCommand,
+    pub source: CommandSource,
+    pub senders: QueuedSenders,
+
+    key: Option<String>,
+    urgent: bool,
+}
+
+// Everyone waiting on a command, including those whose commands were coalesced into it..
+pub struct QueuedSenders(Vec<oneshot::Sender<Result<()>>>);
+
+impl QueuedSenders {
+    pub fn send(self, result: Result<()>) {
+        let mut senders = self.0;
+        let last = senders.pop();
+
+        // anyhow errors can't be cloned, so the earlier senders get a copy..
+        for sender in senders {
+            let copy = match &result {
+                Ok(()) => Ok(()),
+                Err(error) => match error.downcast_ref::<DaemonError>() {
+                    Some(error) => Err(error.clone().into()),
+                    None => Err(anyhow!("{}", error)),
+                },
+            };
+            let _ = sender.send(copy);
+        }
+
+        if let Some(sender) = last {
+            let _ = sender.send(result);
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CommandQueue {
+    queue: VecDeque<QueuedCommand>,
+    peak: usize,
+}
+
+impl CommandQueue {
+    pub fn push(
+        &mut self,
+        serial: String,
+        command: GoXLRCommand,
+        source: CommandSource,
+        sender: oneshot::Sender<Result<()>>,
+    ) {
+        let key = get_coalesce_key(&command).map(|key| format!("{}:{}", serial, key));
+        let urgent = is_urgent(&command);
+
+        let mut senders = vec![];
+        if let Some(key) = &key {
+            // Look back through the pending value updates, if one matches, it's superseded by
+            // this one. We stop at anything else, as the value may be needed before that command
+            // runs. The replacement goes to the back, so it still runs after anything which
+            // arrived before it..
+            let mut position = None;
+            for (index, queued) in self.queue.iter().enumerate().rev() {
+                if queued.key.is_none() {
+                    break;
+                }
+
+                if queued.key.as_ref() == Some(key) {
+                    position = Some(index);
+                    break;
+                }
+            }
+
+            if let Some(old) = position.and_then(|index| self.queue.remove(index)) {
+                senders = old.senders.0;
+            }
+        }
+        senders.push(sender);
+
+        self.queue.push_back(QueuedCommand {
+            serial,
+            command,
+            source,
+            senders: QueuedSenders(senders),
+            key,
+            urgent,
+        });
+        self.peak = self.peak.max(self.queue.len());
+    }
+
+    pub fn pop(&mut self) -> Option<QueuedCommand> {
+        // Urgent commands can skip past any pending value updates, but nothing else, as the
+        // order of those may matter (for example, a mute followed by a profile load)..
+        let position = self
+            .queue
+            .iter()
+            .take_while(|queued| queued.key.is_some() || queued.urgent)
+            .position(|queued| queued.urgent)
+            .unwrap_or(0);
+
+        self.queue.remove(position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn peak(&self) -> usize {
+        self.peak
+    }
+}
+
+fn is_urgent(command: &GoXLRCommand) -> bool {
+    matches!(
+        command,
+        GoXLRCommand::SetFaderMuteState(_, _) | GoXLRCommand::SetCoughMuteState(_)
+    )
+}
+
+// Commands which simply set a value, where only the latest value matters..
+fn get_coalesce_key(command: &GoXLRCommand) -> Option<String> {
+    match command {
+        GoXLRCommand::SetVolume(channel, _) => Some(format!("Volume:{:?}", channel)),
+        GoXLRCommand::SetSubMixVolume(channel, _) => Some(format!("SubMixVolume:{:?}", channel)),
+        GoXLRCommand::SetMicrophoneGain(mic_type, _) => Some(format!("MicGain:{:?}", mic_type)),
+        GoXLRCommand::SetFaderColours(fader, _, _) => Some(format!("FaderColours:{:?}", fader)),
+        GoXLRCommand::SetButtonColours(button, _, _) => Some(format!("ButtonColours:{:?}", button)),
+        GoXLRCommand::SetSimpleColour(target, _) => Some(format!("SimpleColour:{:?}", target)),
+        GoXLRCommand::SetLightingBrightness(_) => Some(String::from("LightingBrightness")),
+        GoXLRCommand::SetGlobalLightingBrightness(_) => {
+            Some(String::from("GlobalLightingBrightness"))
+        }
+        GoXLRCommand::SetEncoderColour(target, _, _, _) => {
+            Some(format!("EncoderColour:{:?}", target))
+        }
+        GoXLRCommand::SetSampleColour(target, _, _, _) => {
+            Some(format!("SampleColour:{:?}", target))
+        }
+        _ => None,
+    }
+}
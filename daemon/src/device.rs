@@ -1,8 +1,10 @@
-use std::collections::HashSet;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
 use anyhow::{anyhow, bail, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use chrono::Local;
 use enum_map::EnumMap;
 use enumset::EnumSet;
@@ -13,39 +15,77 @@ use tokio::sync::mpsc::Sender;
 use tokio::time::Instant;
 
 use goxlr_ipc::{
-    Display, FaderStatus, GoXLRCommand, HardwareStatus, Levels, MicSettings, MixerStatus,
-    SampleProcessState, Settings,
+    ButtonBinding, DaemonError, DaemonEventType, DeviceMetrics, Display, Ducking, EffectLevelValue,
+    EventHook, FaderStatus, GateLevel, GateState, GoXLRCommand, HardwareStatus, InteractionLock,
+    InteractionLockMode, Levels, Lighting, MicSettings, MixerStatus, ResetStage,
+    SampleProcessState, Settings, TimingMetrics, VolumePreset,
 };
+use goxlr_profile_loader::components::encoder_sensitivity::MAX_ENCODER_STEPS;
 use goxlr_profile_loader::components::mute::MuteFunction;
+use goxlr_profile_loader::components::sequence::LightingSequence;
+use goxlr_scribbles::{
+    get_scribble_from_png, get_text_scribble, is_text_scrolling, prepare_scribble,
+};
 use goxlr_types::{
-    Button, ChannelName, DeviceType, DisplayModeComponents, EffectBankPresets, EffectKey,
-    EncoderName, FaderName, HardTuneSource, InputDevice as BasicInputDevice, MicrophoneParamKey,
-    Mix, MuteState, OutputDevice as BasicOutputDevice, RobotRange, SampleBank, SampleButtons,
-    SamplePlaybackMode, VersionNumber, VodMode, WaterfallDirection,
+    AttachStatePolicy, Button, ChannelName, DeviceType, DisplayModeComponents, EchoAmount,
+    EchoDelayMs, EffectBankPresets, EffectKey, EncoderName, FaderName, HardTuneSource,
+    InputDevice as BasicInputDevice, MicrophoneParamKey, Mix, MuteState,
+    OutputDevice as BasicOutputDevice, ProfileConflictResolution, ReverbAmount, RobotRange,
+    SampleBank, SampleButtons, SamplePlaybackMode, VersionNumber, VodMode, WaterfallDirection,
 };
 use goxlr_usb::animation::{AnimationMode, WaterFallDir};
 use goxlr_usb::buttonstate::{ButtonStates, Buttons};
 use goxlr_usb::channelstate::ChannelState;
 use goxlr_usb::channelstate::ChannelState::{Muted, Unmuted};
-use goxlr_usb::device::base::FullGoXLRDevice;
+use goxlr_usb::colouring::ColourMapFormat;
+use goxlr_usb::device::base::{FullGoXLRDevice, RequestMetrics};
 use goxlr_usb::routing::{InputDevice, OutputDevice};
 
 use crate::audio::{AudioFile, AudioHandler};
+use crate::capabilities;
+use crate::event_log::EventLogHandle;
 use crate::events::EventTriggers;
-use crate::events::EventTriggers::TTSMessage;
+use crate::events::EventTriggers::{Execute, Hook, Media, TTSMessage};
 use crate::files::find_file_in_path;
+use crate::hotkeys::validate_hotkey;
+use crate::locale::translate;
 use crate::mic_profile::{MicProfileAdapter, DEFAULT_MIC_PROFILE_NAME};
 use crate::profile::{
     usb_to_standard_button, version_newer_or_equal_to, ProfileAdapter, DEFAULT_PROFILE_NAME,
 };
+use crate::scheduler;
+use crate::undo::{clears_history, get_inverse, is_undoable, UndoHistory};
 use crate::SettingsHandle;
 
+// The minimum time between sending colour maps to the device
+const COLOUR_MAP_INTERVAL: Duration = Duration::from_millis(50);
+
+// How long a button flashes for after its action has failed
+const ERROR_FLASH_DURATION: Duration = Duration::from_secs(2);
+
+// The longest a profile switch may take to fade the lighting over, in milliseconds
+const MAX_CROSSFADE: u16 = 5000;
+
+// Output tone is kept gentle, it's only meant for taking the edge off a pair of headphones..
+const MAX_OUTPUT_TONE_GAIN: i8 = 6;
+
+// How often, and how far (in pixels), scribble text too wide for the display scrolls along
+const SCRIBBLE_SCROLL_INTERVAL: Duration = Duration::from_millis(100);
+const SCRIBBLE_SCROLL_STEP: u32 = 4;
+
+// How often the mic level is checked while ducking, each check is a request to the device
+const DUCK_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct Device<'a> {
     goxlr: Box<dyn FullGoXLRDevice>,
     hardware: HardwareStatus,
     last_buttons: EnumSet<Buttons>,
     button_states: EnumMap<Buttons, ButtonState>,
     encoder_states: EnumMap<EncoderName, i8>,
+
+    // Movement left over from an encoder with a custom sensitivity, which hasn't yet added up to
+    // a full position
+    encoder_remainders: EnumMap<EncoderName, f64>,
     fader_last_seen: EnumMap<FaderName, u8>,
     fader_pause_until: EnumMap<FaderName, PauseUntil>,
     profile: ProfileAdapter,
@@ -53,10 +93,110 @@ pub struct Device<'a> {
     audio_handler: Option<AudioHandler>,
     hold_time: Duration,
     vc_mute_also_mute_cm: bool,
+    volume_limits: HashMap<ChannelName, u8>,
+    output_trims: HashMap<ChannelName, u8>,
+    ducking: Ducking,
+    interaction_lock: InteractionLock,
     settings: &'a SettingsHandle,
     global_events: Sender<EventTriggers>,
+    event_log: EventLogHandle,
 
     last_sample_error: Option<String>,
+
+    // Used to limit how often the colour map is sent to the device
+    colour_map_sent: Option<Instant>,
+    colour_map_pending: bool,
+    voice_meter_lit: Option<u8>,
+    error_flash: Option<(Buttons, Instant)>,
+
+    // A startup or shutdown lighting sequence which is currently playing, and when it started
+    lighting_sequence: Option<(LightingSequence, Instant)>,
+
+    // The previous profile's colours, which are being faded out after a profile switch
+    colour_fade: Option<ColourFade>,
+
+    // Text shown on a scribble in place of the profile's, this is never saved
+    scribble_text: EnumMap<FaderName, Option<ScribbleText>>,
+
+    // The media currently playing, and which fader's scribble (if any) it's shown on
+    now_playing: Option<Vec<String>>,
+    now_playing_fader: Option<FaderName>,
+
+    // The last colour map sent, so we don't resend it if nothing has changed
+    colour_map_last: Option<Vec<u8>>,
+
+    // When we last checked whether the profile needed autosaving
+    last_autosave: Instant,
+
+    // Changes which can be undone / redone
+    undo_history: UndoHistory,
+
+    // How long each check of the buttons, faders and encoders takes
+    poll_metrics: RequestMetrics,
+
+    // Used to slow down input polling while the device isn't being used
+    last_activity: Instant,
+    poll_interval: Duration,
+
+    // A volume preset which is still being faded in
+    volume_fade: Option<VolumeFade>,
+
+    // How far into ducking we are (0 to 1), when it was last moved, and the attenuation (as a
+    // percentage) currently applied to the ducked channels
+    duck_level: f64,
+    duck_updated: Instant,
+    duck_applied: u8,
+
+    // Whether the mic was above the ducking threshold when its level was last checked
+    duck_speaking: bool,
+    duck_checked: Instant,
+
+    // The only input currently being sent to the headphones, this never touches the profile
+    solo_input: Option<BasicInputDevice>,
+
+    // Whether everything except the Headphones has been cut off, also never touches the profile
+    panic_muted: bool,
+
+    // Whether the controls listed in interaction_lock are being ignored
+    interaction_locked: bool,
+    muted_outputs: EnumMap<BasicOutputDevice, bool>,
+
+    // What was last sent to the device for each input
+    active_router: EnumMap<BasicInputDevice, EnumMap<BasicOutputDevice, bool>>,
+
+    // When the mic was last seen above the gate threshold, used to infer the gate's state
+    gate_last_open: Option<Instant>,
+}
+
+struct VolumeFade {
+    from: HashMap<ChannelName, u8>,
+    to: HashMap<ChannelName, u8>,
+    start: Instant,
+    duration: Duration,
+}
+
+struct ColourFade {
+    from: Vec<u8>,
+    start: Instant,
+    duration: Duration,
+}
+
+struct ScribbleText {
+    lines: Vec<String>,
+    scrolling: bool,
+    start: Instant,
+    scroll: u32,
+}
+
+impl ScribbleText {
+    fn new(lines: Vec<String>) -> Self {
+        Self {
+            scrolling: is_text_scrolling(&lines),
+            lines,
+            start: Instant::now(),
+            scroll: 0,
+        }
+    }
 }
 
 #[derive(Debug, Default, Copy, Clone)]
@@ -69,6 +209,9 @@ struct PauseUntil {
 struct ButtonState {
     press_time: Option<Instant>,
     hold_handled: bool,
+
+    // Pressed while the interaction lock was on, so the normal handlers are skipped
+    locked: bool,
 }
 
 // Used when loading profiles to provide the previous
@@ -86,6 +229,7 @@ impl<'a> Device<'a> {
         hardware: HardwareStatus,
         settings_handle: &'a SettingsHandle,
         global_events: Sender<EventTriggers>,
+        event_log: EventLogHandle,
     ) -> Result<Device<'a>> {
         debug!("New Device Loading..");
 
@@ -108,39 +252,30 @@ impl<'a> Device<'a> {
 
         let profile_path = settings_handle.get_profile_directory().await;
         let backup_path = settings_handle.get_backup_directory().await;
-        let profile = ProfileAdapter::from_named(profile_name.clone(), &profile_path);
-
-        // Check load situation..
-        let profile = match profile {
-            Ok(mut profile) => {
-                debug!("Profile Successfully Loaded, Performing Backup..");
-                profile.save(&backup_path, true).unwrap_or_else(|e| {
-                    warn!("Unable to Backup Profile: {}", e);
-                });
-                debug!("Main Profile Backup Complete");
-                profile
-            }
-            Err(e) => {
-                warn!("Failed to Load Profile: {}, checking for backup..", e);
-                match ProfileAdapter::from_named(profile_name, &backup_path) {
-                    Ok(mut profile) => {
-                        info!("Successfully Loaded backup profile");
-
-                        debug!("Overwriting existing corrupt / missing profile..");
-                        profile.save(&profile_path, true).unwrap_or_else(|e| {
-                            warn!("Unable to replace existing profile: {}", e);
-                        });
-
-                        // Return the new profile..
-                        profile
-                    }
-                    Err(e) => {
-                        warn!("Unable to Load Backup: {}, loading default", e);
-                        ProfileAdapter::default()
-                    }
+        let fallbacks = settings_handle.get_device_profile_fallbacks(&serial).await;
+
+        // Work through the configured profile and any fallbacks before settling on the default..
+        let mut profile = None;
+        for name in std::iter::once(profile_name).chain(fallbacks) {
+            match load_profile_or_backup(&name, &profile_path, &backup_path) {
+                Ok(loaded) => {
+                    profile.replace(loaded);
+                    break;
+                }
+                Err(e) => {
+                    let message = format!("Unable to load profile {}: {}", name, e);
+                    warn!("{}", message);
+                    event_log.push(Some(&serial), DaemonEventType::Warning(message));
                 }
             }
-        };
+        }
+
+        let profile = profile.unwrap_or_else(|| {
+            let message = String::from("No usable profiles found, loading the default profile");
+            warn!("{}", message);
+            event_log.push(Some(&serial), DaemonEventType::Warning(message));
+            ProfileAdapter::default()
+        });
 
         let mic_path = settings_handle.get_mic_profile_directory().await;
         let mic_profile = MicProfileAdapter::from_named(mic_name.clone(), &mic_path);
@@ -197,6 +332,11 @@ impl<'a> Device<'a> {
         let vc_mute_also_mute_cm = settings_handle
             .get_device_chat_mute_mutes_mic_to_chat(&serial)
             .await;
+        let volume_limits = settings_handle.get_device_volume_limits(&serial).await;
+        let output_trims = settings_handle.get_device_output_trims(&serial).await;
+        let ducking = settings_handle.get_device_ducking(&serial).await;
+        let interaction_lock = settings_handle.get_device_interaction_lock(&serial).await;
+        let now_playing_fader = settings_handle.get_device_now_playing_fader(&serial).await;
 
         debug!("--- DEVICE INFO ---");
         debug!("Serial: {:?}", &serial);
@@ -212,24 +352,99 @@ impl<'a> Device<'a> {
             hardware,
             hold_time: Duration::from_millis(hold_time.into()),
             vc_mute_also_mute_cm,
+            volume_limits,
+            output_trims,
+            ducking,
+            interaction_lock,
             last_buttons: EnumSet::empty(),
             button_states: EnumMap::default(),
             encoder_states: EnumMap::default(),
+            encoder_remainders: EnumMap::default(),
             fader_last_seen: EnumMap::default(),
             fader_pause_until: EnumMap::default(),
             audio_handler,
             settings: settings_handle,
             global_events,
+            event_log,
 
             last_sample_error: None,
+
+            colour_map_sent: None,
+            colour_map_pending: false,
+            voice_meter_lit: None,
+            error_flash: None,
+            lighting_sequence: None,
+            scribble_text: Default::default(),
+            now_playing: None,
+            now_playing_fader,
+            colour_fade: None,
+            colour_map_last: None,
+
+            last_autosave: Instant::now(),
+            undo_history: UndoHistory::default(),
+            poll_metrics: RequestMetrics::default(),
+            last_activity: Instant::now(),
+            poll_interval: Duration::from_millis(20),
+            volume_fade: None,
+            duck_level: 0.,
+            duck_updated: Instant::now(),
+            duck_applied: 0,
+            duck_speaking: false,
+            duck_checked: Instant::now(),
+            solo_input: None,
+            panic_muted: false,
+            interaction_locked: false,
+            muted_outputs: Default::default(),
+            active_router: Default::default(),
+            gate_last_open: None,
         };
 
+        device.reconcile_attach_state().await;
         device.apply_profile(None).await?;
         device.apply_mic_profile().await?;
 
+        // Play the profile's startup sequence, update_state will carry it along from here..
+        if let Some(sequence) = device.profile.get_startup_sequence() {
+            debug!("Playing Startup Lighting Sequence..");
+            device.lighting_sequence = Some((sequence, Instant::now()));
+            device.load_colour_map().await?;
+        }
+
         Ok(device)
     }
 
+    /// Called on attach, before the profile is applied. If the device is configured to 'win'
+    /// we pull the current fader positions from the GoXLR into the profile, so applying it
+    /// doesn't move anything.
+    async fn reconcile_attach_state(&mut self) {
+        let policy = self
+            .settings
+            .get_device_attach_state_policy(self.serial())
+            .await;
+        if policy == AttachStatePolicy::Profile {
+            return;
+        }
+
+        // The GoXLR doesn't expose its current mute states, only the fader positions, so mutes
+        // will always come from the profile..
+        let state = match self.goxlr.get_button_states() {
+            Ok(state) => state,
+            Err(e) => {
+                warn!("Unable to read device state, using profile: {}", e);
+                return;
+            }
+        };
+
+        for fader in FaderName::iter() {
+            let channel = self.profile.get_fader_assignment(fader);
+            let volume = state.volumes[fader as usize];
+            if let Err(e) = self.profile.set_channel_volume(channel, volume) {
+                warn!("Unable to set {} volume from device: {}", channel, e);
+            }
+            self.fader_last_seen[fader] = volume;
+        }
+    }
+
     pub fn serial(&self) -> &str {
         &self.hardware.serial_number
     }
@@ -275,9 +490,40 @@ impl<'a> Device<'a> {
             .settings
             .get_sampler_reset_on_clear(self.serial())
             .await;
+        let sampler_normalisation = self.settings.get_sampler_normalisation(self.serial()).await;
 
         let locked_faders = self.settings.get_device_lock_faders(self.serial()).await;
+        let lighting_brightness = self
+            .settings
+            .get_device_lighting_brightness(self.serial())
+            .await;
+        let lighting_crossfade = self
+            .settings
+            .get_device_lighting_crossfade(self.serial())
+            .await;
         let vod_mode = self.settings.get_device_vod_mode(self.serial()).await;
+        let attach_state_policy = self
+            .settings
+            .get_device_attach_state_policy(self.serial())
+            .await;
+        let error_feedback = self.settings.get_device_error_feedback(self.serial()).await;
+        let profile_fallbacks = self
+            .settings
+            .get_device_profile_fallbacks(self.serial())
+            .await;
+        let volume_limits = self.volume_limits.clone();
+        let output_trims = self.output_trims.clone();
+        let ducking = self.ducking.clone();
+        let interaction_lock = self.interaction_lock.clone();
+        let now_playing_fader = self.now_playing_fader;
+        let button_bindings = self
+            .settings
+            .get_device_button_bindings(self.serial())
+            .await;
+        let hotkeys = self.settings.get_device_hotkeys(self.serial()).await;
+        let schedule = self.settings.get_device_schedule(self.serial()).await;
+        let label = self.settings.get_device_label(self.serial()).await;
+        let volume_presets = self.settings.get_device_volume_presets(self.serial()).await;
 
         let submix_supported = self.device_supports_submixes();
 
@@ -304,9 +550,11 @@ impl<'a> Device<'a> {
             sleep_commands,
             wake_commands,
             fader_status: fader_map,
+            fader_pages: self.profile.get_fader_pages_ipc(),
             cough_button: self.profile.get_cough_status(),
             levels: Levels {
                 submix_supported: self.device_supports_submixes(),
+                output_tone_supported: capabilities::supports_output_tone(&self.hardware),
                 output_monitor: self.profile.get_monitoring_mix(),
                 volumes,
                 submix: self.profile.get_submixes_ipc(submix_supported),
@@ -314,6 +562,11 @@ impl<'a> Device<'a> {
                 deess: self.mic_profile.get_deesser(),
             },
             router: self.profile.create_router(),
+            solo_input: self.solo_input,
+            panic_muted: self.panic_muted,
+            interaction_locked: self.interaction_locked,
+            muted_outputs: self.muted_outputs,
+            active_router: self.active_router,
             mic_status: MicSettings {
                 mic_type: self.mic_profile.mic_type(),
                 mic_gains: self.mic_profile.mic_gains(),
@@ -322,9 +575,7 @@ impl<'a> Device<'a> {
                 equaliser_mini: self.mic_profile.equalizer_mini_ipc(),
                 compressor: self.mic_profile.compressor_ipc(),
             },
-            lighting: self
-                .profile
-                .get_lighting_ipc(is_mini, self.device_supports_animations()),
+            lighting: self.get_lighting(),
             effects: self.profile.get_effects_ipc(is_mini, self.encoder_states),
             sampler: self.profile.get_sampler_ipc(
                 is_mini,
@@ -336,6 +587,7 @@ impl<'a> Device<'a> {
                 },
             ),
             settings: Settings {
+                label,
                 display: Display {
                     gate: self.mic_profile.get_gate_display_mode(),
                     compressor: self.mic_profile.get_compressor_display_mode(),
@@ -346,18 +598,38 @@ impl<'a> Device<'a> {
                 vc_mute_also_mute_cm: self.vc_mute_also_mute_cm,
                 enable_monitor_with_fx: monitor_with_fx,
                 reset_sampler_on_clear: sampler_reset_on_clear,
+                sampler_normalisation,
                 lock_faders: locked_faders,
+                lighting_brightness,
+                lighting_crossfade,
                 vod_mode,
+                attach_state_policy,
+                error_feedback,
+                profile_fallbacks,
+                volume_limits,
+                output_trims,
+                button_bindings,
+                hotkeys,
+                schedule,
+                volume_presets,
+                ducking,
+                interaction_lock,
+                now_playing_fader,
             },
             button_down: button_states,
             profile_name: self.profile.name().to_owned(),
             mic_profile_name: self.mic_profile.name().to_owned(),
+            profile_conflict: self.profile.conflict(),
         }
     }
 
     pub async fn shutdown(&mut self, avoid_save: bool) {
         debug!("Shutting Down Device: {}", self.hardware.serial_number);
 
+        if let Some(sequence) = self.profile.get_shutdown_sequence() {
+            self.play_lighting_sequence(sequence).await;
+        }
+
         let commands = self
             .settings
             .get_device_shutdown_commands(&self.hardware.serial_number)
@@ -366,6 +638,46 @@ impl<'a> Device<'a> {
         self.execute_command_list(commands, avoid_save).await;
     }
 
+    // Plays a sequence through to the end before returning, the length is capped when the
+    // profile is loaded, so this can't hold up the shutdown for long..
+    async fn play_lighting_sequence(&mut self, sequence: LightingSequence) {
+        debug!("Playing Shutdown Lighting Sequence..");
+        let duration = Duration::from_millis(sequence.duration().into());
+        let start = Instant::now();
+        self.lighting_sequence = Some((sequence, start));
+
+        while start.elapsed() < duration {
+            self.colour_map_sent = None;
+            if let Err(e) = self.load_colour_map().await {
+                warn!("Unable to play Lighting Sequence: {}", e);
+                break;
+            }
+            tokio::time::sleep(COLOUR_MAP_INTERVAL).await;
+        }
+
+        // Put the profile's colours back, in case the shutdown commands don't touch them..
+        self.lighting_sequence = None;
+        self.colour_map_sent = None;
+        let _ = self.load_colour_map().await;
+    }
+
+    pub async fn set_now_playing(&mut self, lines: Option<Vec<String>>) -> Result<()> {
+        self.now_playing = lines;
+        self.apply_now_playing().await
+    }
+
+    async fn apply_now_playing(&mut self) -> Result<()> {
+        if self.is_device_mini() {
+            return Ok(());
+        }
+
+        if let Some(fader) = self.now_playing_fader {
+            self.scribble_text[fader] = self.now_playing.clone().map(ScribbleText::new);
+            self.apply_scribble(fader).await?;
+        }
+        Ok(())
+    }
+
     pub async fn sleep(&mut self) {
         debug!("Sleeping...");
 
@@ -377,9 +689,67 @@ impl<'a> Device<'a> {
         self.execute_command_list(commands, false).await;
     }
 
+    async fn reset_device(&mut self) -> Result<()> {
+        info!("Resetting Device {}", self.serial());
+        self.push_reset_stage(ResetStage::Started);
+        self.stop_all_samples(true, true).await?;
+
+        // Start the command count again, in case the device and us have lost track..
+        self.goxlr.reset_command_index()?;
+        self.push_reset_stage(ResetStage::Reinitialised);
+
+        // Drop everything which isn't stored in the profile..
+        self.solo_input = None;
+        self.panic_muted = false;
+        self.muted_outputs = Default::default();
+        self.volume_fade = None;
+        self.duck_level = 0.;
+        self.duck_applied = 0;
+        self.duck_speaking = false;
+        self.error_flash = None;
+        self.lighting_sequence = None;
+        self.colour_fade = None;
+        self.scribble_text = Default::default();
+        if let Some(fader) = self.now_playing_fader {
+            self.scribble_text[fader] = self.now_playing.clone().map(ScribbleText::new);
+        }
+        self.fader_pause_until = Default::default();
+        self.colour_map_last = None;
+
+        // Like ResetToDefaultProfile nothing is written to disk, but with no current state
+        // passed in, every fader, mute, route and colour is sent to the device..
+        self.profile = ProfileAdapter::default();
+        self.apply_profile(None).await?;
+        self.push_reset_stage(ResetStage::ProfileApplied);
+
+        self.mic_profile = MicProfileAdapter::default();
+        self.apply_mic_profile().await?;
+        self.push_reset_stage(ResetStage::MicProfileApplied);
+
+        self.event_log.push(
+            Some(self.serial()),
+            DaemonEventType::ProfileLoaded(self.profile.name().to_owned()),
+        );
+        self.event_log.push(
+            Some(self.serial()),
+            DaemonEventType::MicProfileLoaded(self.mic_profile.name().to_owned()),
+        );
+        self.push_reset_stage(ResetStage::Complete);
+        Ok(())
+    }
+
+    fn push_reset_stage(&self, stage: ResetStage) {
+        debug!("Device Reset: {:?}", stage);
+        self.event_log
+            .push(Some(self.serial()), DaemonEventType::DeviceReset(stage));
+    }
+
     pub async fn wake(&mut self) {
         debug!("Waking...");
 
+        // We can't be sure the device kept its state while we were asleep..
+        self.colour_map_last = None;
+
         let commands = self
             .settings
             .get_device_wake_commands(&self.hardware.serial_number)
@@ -392,43 +762,17 @@ impl<'a> Device<'a> {
         for command in commands {
             debug!("{:?}", command);
 
-            // Below is a list of all commands which will write to a disk, if any of them are
-            // in our command list, we do nothing.
-            match command {
-                // Shutdown / Sleep / Wake Commandsets
-                GoXLRCommand::SetShutdownCommands(_)
-                | GoXLRCommand::SetSleepCommands(_)
-                | GoXLRCommand::SetWakeCommands(_)
-                // Presets
-                | GoXLRCommand::SaveActivePreset()
-                // Profile Related Commands
-                | GoXLRCommand::NewProfile(_)
-                | GoXLRCommand::LoadProfile(_, true)
-                | GoXLRCommand::SaveProfile()
-                | GoXLRCommand::SaveProfileAs(_)
-                // Mic Profile Related Commands
-                | GoXLRCommand::NewMicProfile(_)
-                | GoXLRCommand::LoadMicProfile(_, true)
-                | GoXLRCommand::SaveMicProfile()
-                | GoXLRCommand::SaveMicProfileAs(_)
-                // settings.json variables
-                | GoXLRCommand::SetSamplerPreBufferDuration(_)
-                | GoXLRCommand::SetVCMuteAlsoMuteCM(_)
-                | GoXLRCommand::SetMonitorWithFx(_)
-                | GoXLRCommand::SetSamplerResetOnClear(_)
-                | GoXLRCommand::SetLockFaders(_)
-                => {
-                    if !avoid_write {
-                        let _ = self.perform_command(command).await;
-                    } else {
-                        warn!("Unable to Execute, command writes to the disk.");
-                    }
-                }
-
-                _ => {
-                    let _ = self.perform_command(command).await;
-                }
+            if avoid_write && writes_to_disk(&command) {
+                warn!("Unable to Execute, command writes to the disk.");
+                continue;
             }
+            let _ = self.perform_command(command).await;
+        }
+
+        // These may be run just before we stop or sleep, so don't hold back any colour changes..
+        if self.colour_map_pending {
+            self.colour_map_sent = None;
+            let _ = self.load_colour_map().await;
         }
     }
 
@@ -436,6 +780,37 @@ impl<'a> Device<'a> {
         &self.profile
     }
 
+    async fn autosave_profile(&mut self) {
+        let interval = self.settings.get_profile_autosave_interval().await;
+        if interval == 0 || self.last_autosave.elapsed() < Duration::from_secs(interval.into()) {
+            return;
+        }
+        self.last_autosave = Instant::now();
+
+        // Don't overwrite the file until the user has decided what to do with it..
+        if self.profile.conflict().is_some() {
+            return;
+        }
+
+        // After a reset, only an explicit save should replace the user's Default profile..
+        if self.profile.is_embedded() {
+            return;
+        }
+
+        if self.profile.has_unsaved_changes() {
+            debug!("Profile has changed, autosaving..");
+            let profile_directory = self.settings.get_profile_directory().await;
+            if let Err(e) = self.profile.save(&profile_directory, true) {
+                warn!("Unable to Autosave Profile: {}", e);
+            }
+        }
+    }
+
+    pub async fn check_profile_conflict(&mut self) {
+        let profile_directory = self.settings.get_profile_directory().await;
+        self.profile.check_for_conflict(&profile_directory);
+    }
+
     pub fn mic_profile(&self) -> &MicProfileAdapter {
         &self.mic_profile
     }
@@ -492,13 +867,122 @@ impl<'a> Device<'a> {
             }
         }
 
+        // Move any lighting sequence along, and put the profile's colours back once it's done..
+        if let Some((sequence, start)) = &self.lighting_sequence {
+            if start.elapsed() >= Duration::from_millis(sequence.duration().into()) {
+                self.lighting_sequence = None;
+            }
+            self.load_colour_map().await?;
+        }
+
+        // Scroll along any scribble text which doesn't fit..
+        for fader in FaderName::iter() {
+            if let Some(text) = &mut self.scribble_text[fader] {
+                let steps = text.start.elapsed().as_millis() / SCRIBBLE_SCROLL_INTERVAL.as_millis();
+                let scroll = steps as u32 * SCRIBBLE_SCROLL_STEP;
+                if text.scrolling && text.scroll != scroll {
+                    text.scroll = scroll;
+                    self.apply_scribble(fader).await?;
+                }
+            }
+        }
+
+        // Likewise for any fade between profile colours..
+        if let Some(fade) = &self.colour_fade {
+            if fade.start.elapsed() >= fade.duration {
+                self.colour_fade = None;
+            }
+            self.load_colour_map().await?;
+        }
+
+        // Drive the Voice Meter from the current mic level..
+        if self.profile.is_voice_meter_enabled() {
+            let lit = self.get_voice_meter_lit().await?;
+            if self.voice_meter_lit != Some(lit) {
+                self.voice_meter_lit = Some(lit);
+                self.load_colour_map().await?;
+            }
+        } else if self.voice_meter_lit.take().is_some() {
+            self.load_colour_map().await?;
+        }
+
+        // Clear any error indicators which have run their course..
+        if let Some((_, since)) = self.error_flash {
+            if since.elapsed() > ERROR_FLASH_DURATION {
+                self.error_flash = None;
+                self.update_button_states()?;
+            }
+        }
+
+        // Move any fading volumes along towards their preset..
+        if let Some(fade) = self.volume_fade.take() {
+            let progress = fade.start.elapsed().as_secs_f64() / fade.duration.as_secs_f64();
+            let progress = progress.min(1.0);
+
+            for (&channel, &target) in &fade.to {
+                let from = fade.from.get(&channel).copied().unwrap_or(target) as f64;
+                let volume = (from + (target as f64 - from) * progress).round() as u8;
+                if volume != self.profile.get_channel_volume(channel) {
+                    self.set_channel_volume(channel, volume)?;
+                }
+            }
+
+            if progress < 1.0 {
+                self.volume_fade = Some(fade);
+            }
+            state_updated = true;
+        }
+
+        // Pull the ducked channels down while the mic is in use, and back up when it isn't..
+        if self.ducking.enabled && self.duck_checked.elapsed() >= DUCK_CHECK_INTERVAL {
+            self.duck_checked = Instant::now();
+            self.duck_speaking = self.get_mic_level().await? >= self.ducking.threshold as f64;
+        }
+
+        let elapsed = self.duck_updated.elapsed().as_secs_f64();
+        self.duck_updated = Instant::now();
+        if self.ducking.enabled || self.duck_level > 0. {
+            let speaking = self.ducking.enabled && self.duck_speaking;
+
+            let (target, time) = match speaking {
+                true => (1., self.ducking.attack),
+                false => (0., self.ducking.release),
+            };
+            let step = match time {
+                0 => 1.,
+                _ => elapsed / (time as f64 / 1000.),
+            };
+            self.duck_level = match target > self.duck_level {
+                true => (self.duck_level + step).min(target),
+                false => (self.duck_level - step).max(target),
+            };
+
+            let applied = (self.ducking.attenuation as f64 * self.duck_level).round() as u8;
+            if applied != self.duck_applied {
+                self.duck_applied = applied;
+                self.apply_ducking()?;
+            }
+        }
+
+        // Send any colour map changes which were held back..
+        if self.colour_map_pending {
+            self.load_colour_map().await?;
+        }
+
+        self.autosave_profile().await;
+
         // Find any buttons that have been held, and action if needed.
         for button in self.last_buttons {
             if !self.button_states[button].hold_handled {
                 if let Some(time) = self.button_states[button].press_time {
                     if time.elapsed() > self.hold_time {
-                        if let Err(error) = self.on_button_hold(button).await {
+                        let result = match self.button_states[button].locked {
+                            true => self.on_locked_button_hold(button).await,
+                            false => self.on_button_hold(button).await,
+                        };
+                        if let Err(error) = result {
                             error!("{}", error);
+                            self.flag_button_error(button).await;
                         }
                         self.button_states[button].hold_handled = true;
                     }
@@ -510,6 +994,35 @@ impl<'a> Device<'a> {
     }
 
     pub async fn monitor_inputs(&mut self) -> Result<bool> {
+        let start = Instant::now();
+        let result = self.read_inputs().await;
+        self.poll_metrics.record(start.elapsed(), result.is_ok());
+
+        // Held buttons count as activity, otherwise we'd slow down before a hold registers..
+        if matches!(result, Ok(true)) || !self.last_buttons.is_empty() {
+            self.last_activity = Instant::now();
+        }
+        result
+    }
+
+    pub async fn update_poll_rate(&mut self, clients_connected: bool) {
+        let polling = self.settings.get_input_polling().await;
+        let idle_timeout = Duration::from_secs(polling.idle_timeout.into());
+
+        let idle = !clients_connected && self.last_activity.elapsed() >= idle_timeout;
+        let interval = Duration::from_millis(match idle {
+            true => polling.idle_interval.into(),
+            false => polling.active_interval.into(),
+        });
+
+        if interval != self.poll_interval {
+            debug!("Changing Input Poll Interval to {:?}", interval);
+            self.goxlr.set_poll_interval(interval);
+            self.poll_interval = interval;
+        }
+    }
+
+    async fn read_inputs(&mut self) -> Result<bool> {
         let state = self.goxlr.get_button_states()?;
         let mut changed = self.update_volumes_to(state.volumes).await?;
         let result = self.update_encoders_to(state.encoders).await?;
@@ -521,13 +1034,18 @@ impl<'a> Device<'a> {
         let pressed_buttons = state.pressed.difference(self.last_buttons);
         for button in pressed_buttons {
             // This is a new press, store it in the states..
+            let locked = self.is_button_locked(button);
             self.button_states[button] = ButtonState {
                 press_time: Some(Instant::now()),
                 hold_handled: false,
+                locked,
             };
 
-            if let Err(error) = self.on_button_down(button).await {
+            if locked {
+                debug!("Ignoring Button Down: {:?}, Interaction is Locked", button);
+            } else if let Err(error) = self.on_button_down(button).await {
                 error!("{}", error);
+                self.flag_button_error(button).await;
             }
 
             changed = true;
@@ -538,14 +1056,17 @@ impl<'a> Device<'a> {
             let button_state = self.button_states[button];
 
             // Output errors, but don't throw them up the stack!
-            if let Err(error) = self.on_button_up(button, &button_state).await {
+            if button_state.locked {
+                debug!(
+                    "Ignoring Button Release: {:?}, Interaction is Locked",
+                    button
+                );
+            } else if let Err(error) = self.on_button_up(button, &button_state).await {
                 error!("{}", error);
+                self.flag_button_error(button).await;
             }
 
-            self.button_states[button] = ButtonState {
-                press_time: None,
-                hold_handled: false,
-            };
+            self.button_states[button] = ButtonState::default();
 
             changed = true;
         }
@@ -554,8 +1075,75 @@ impl<'a> Device<'a> {
         Ok(changed)
     }
 
+    fn is_button_locked(&self, button: Buttons) -> bool {
+        self.interaction_locked
+            && self
+                .interaction_lock
+                .buttons
+                .contains(&usb_to_standard_button(button))
+    }
+
+    // A locked button which has been held long enough in Hold mode, this is treated as a single
+    // short press, as the hold itself was just there to confirm it..
+    async fn on_locked_button_hold(&mut self, button: Buttons) -> Result<()> {
+        if self.interaction_lock.mode != InteractionLockMode::Hold {
+            return Ok(());
+        }
+
+        debug!("Locked Button {:?} Held, Handling as a Press", button);
+        self.on_button_down(button).await?;
+
+        let state = ButtonState {
+            press_time: self.button_states[button].press_time,
+            hold_handled: false,
+            locked: false,
+        };
+        self.on_button_up(button, &state).await
+    }
+
+    async fn get_button_binding(&self, button: Buttons) -> Option<ButtonBinding> {
+        self.settings
+            .get_device_button_bindings(self.serial())
+            .await
+            .remove(&usb_to_standard_button(button))
+    }
+
+    // Flashes a button when its action fails, so it's noticed without having to check the logs
+    async fn flag_button_error(&mut self, button: Buttons) {
+        if !self.settings.get_device_error_feedback(self.serial()).await {
+            return;
+        }
+
+        self.error_flash = Some((button, Instant::now()));
+        if let Err(error) = self.update_button_states() {
+            warn!("Unable to flag error on button {:?}: {}", button, error);
+        }
+    }
+
     async fn on_button_down(&mut self, button: Buttons) -> Result<()> {
         debug!("Handling Button Down: {:?}", button);
+        self.event_log.push(
+            Some(self.serial()),
+            DaemonEventType::ButtonPressed(usb_to_standard_button(button)),
+        );
+
+        // If the user has bound this button to something else, that replaces the default behaviour..
+        if let Some(binding) = self.get_button_binding(button).await {
+            match binding {
+                ButtonBinding::Commands(commands) => {
+                    // Bindings can't be used to write settings, to prevent them changing themselves..
+                    self.execute_command_list(commands, true).await;
+                }
+                ButtonBinding::Execute(command) => {
+                    let _ = self.global_events.send(Execute(command)).await;
+                }
+                ButtonBinding::Media(action) => {
+                    let _ = self.global_events.send(Media(action)).await;
+                }
+            }
+            self.update_button_states()?;
+            return Ok(());
+        }
 
         match button {
             Buttons::MicrophoneMute => {
@@ -588,6 +1176,14 @@ impl<'a> Device<'a> {
 
     async fn on_button_hold(&mut self, button: Buttons) -> Result<()> {
         debug!("Handling Button Hold: {:?}", button);
+        self.event_log.push(
+            Some(self.serial()),
+            DaemonEventType::ButtonHeld(usb_to_standard_button(button)),
+        );
+
+        if self.get_button_binding(button).await.is_some() {
+            return Ok(());
+        }
 
         // Fader mute buttons maintain their own state check, so it can be programmatically called.
         match button {
@@ -621,6 +1217,11 @@ impl<'a> Device<'a> {
             "Handling Button Release: {:?}, Has Long Press Handled: {:?}",
             button, state.hold_handled
         );
+        if self.get_button_binding(button).await.is_some() {
+            self.update_button_states()?;
+            return Ok(());
+        }
+
         match button {
             Buttons::Fader1Mute => {
                 if !state.hold_handled {
@@ -751,6 +1352,11 @@ impl<'a> Device<'a> {
         Ok(())
     }
 
+    async fn send_event_hook(&self, hook: EventHook, mut env: Vec<(String, String)>) {
+        env.push((String::from("GOXLR_SERIAL"), self.serial().to_owned()));
+        let _ = self.global_events.send(Hook(hook, env)).await;
+    }
+
     async fn unmute_chat_if_muted(&mut self) -> Result<()> {
         let (_mute_toggle, muted_to_x, muted_to_all, _mute_function) =
             self.profile.get_mute_chat_button_state();
@@ -793,8 +1399,9 @@ impl<'a> Device<'a> {
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::MicInputMute]))?;
             }
 
-            let message = format!("Mic Muted{}", target);
+            let message = translate("tts.mic_muted", &[("target", &target)]);
             let _ = self.global_events.send(TTSMessage(message)).await;
+            self.send_event_hook(EventHook::MicMuted, vec![]).await;
 
             self.apply_routing(BasicInputDevice::Microphone).await?;
             return Ok(());
@@ -811,8 +1418,9 @@ impl<'a> Device<'a> {
             self.profile.set_mute_chat_button_on(true);
             self.profile.set_mute_chat_button_blink(true);
 
-            let message = "Mic Muted".to_string();
+            let message = translate("tts.mic_muted", &[("target", "")]);
             let _ = self.global_events.send(TTSMessage(message)).await;
+            self.send_event_hook(EventHook::MicMuted, vec![]).await;
 
             self.goxlr.set_channel_state(ChannelName::Mic, Muted)?;
             self.apply_effects(LinkedHashSet::from_iter([EffectKey::MicInputMute]))?;
@@ -838,8 +1446,9 @@ impl<'a> Device<'a> {
                         self.apply_effects(LinkedHashSet::from_iter([EffectKey::MicInputMute]))?;
                     }
 
-                    let message = "Mic Unmuted".to_string();
+                    let message = translate("tts.mic_unmuted", &[]);
                     let _ = self.global_events.send(TTSMessage(message)).await;
+                    self.send_event_hook(EventHook::MicUnmuted, vec![]).await;
                     self.apply_routing(BasicInputDevice::Microphone).await?;
                     return Ok(());
                 }
@@ -852,8 +1461,9 @@ impl<'a> Device<'a> {
                     self.apply_effects(LinkedHashSet::from_iter([EffectKey::MicInputMute]))?;
                 }
 
-                let message = format!("Mic Muted{}", target);
+                let message = translate("tts.mic_muted", &[("target", &target)]);
                 let _ = self.global_events.send(TTSMessage(message)).await;
+                self.send_event_hook(EventHook::MicMuted, vec![]).await;
 
                 // Update the transient routing..
                 self.apply_routing(BasicInputDevice::Microphone).await?;
@@ -866,8 +1476,9 @@ impl<'a> Device<'a> {
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::MicInputMute]))?;
             }
 
-            let message = "Mic Unmuted".to_string();
+            let message = translate("tts.mic_unmuted", &[]);
             let _ = self.global_events.send(TTSMessage(message)).await;
+            self.send_event_hook(EventHook::MicUnmuted, vec![]).await;
 
             // Disable button and refresh transient routing
             self.apply_routing(BasicInputDevice::Microphone).await?;
@@ -897,7 +1508,10 @@ impl<'a> Device<'a> {
 
         // Ok, we need to announce where we're muted to..
         let name = self.profile.get_fader_assignment(fader);
-        let message = format!("{} Muted{}", name, target);
+        let message = translate(
+            "tts.channel_muted",
+            &[("channel", &name.to_string()), ("target", &target)],
+        );
         let _ = self.global_events.send(TTSMessage(message)).await;
 
         let input = self.get_basic_input_from_channel(channel);
@@ -931,7 +1545,7 @@ impl<'a> Device<'a> {
 
                 if !lock_faders {
                     // User has asked us not to move the volume,
-                    self.goxlr.set_volume(channel, 0)?;
+                    self.set_device_volume(channel, 0)?;
                 }
             }
             self.goxlr.set_channel_state(channel, Muted)?;
@@ -939,7 +1553,10 @@ impl<'a> Device<'a> {
         }
 
         let name = self.profile.get_fader_assignment(fader);
-        let message = format!("{} Muted", name);
+        let message = translate(
+            "tts.channel_muted",
+            &[("channel", &name.to_string()), ("target", "")],
+        );
         let _ = self.global_events.send(TTSMessage(message)).await;
 
         if blink {
@@ -994,7 +1611,7 @@ impl<'a> Device<'a> {
 
             // As with mute, the mini doesn't modify volumes on mute / unmute
             if !self.is_device_mini() && !lock_faders {
-                self.goxlr.set_volume(channel, previous_volume)?;
+                self.set_device_volume(channel, previous_volume)?;
                 self.profile.set_channel_volume(channel, previous_volume)?;
             } else {
                 if self.needs_submix_correction(channel) {
@@ -1002,7 +1619,7 @@ impl<'a> Device<'a> {
                     // and Headphones don't set correctly, so we need to forcibly restore the
                     // volume. This does mean unlatching though :(
                     let current_volume = self.profile.get_channel_volume(channel);
-                    self.goxlr.set_volume(channel, current_volume)?;
+                    self.set_device_volume(channel, current_volume)?;
                 }
 
                 // Reload the Minis colour Map to re-establish colours.
@@ -1026,7 +1643,7 @@ impl<'a> Device<'a> {
         }
 
         let name = self.profile.get_fader_assignment(fader);
-        let message = format!("{} unmuted", name);
+        let message = translate("tts.channel_unmuted", &[("channel", &name.to_string())]);
         let _ = self.global_events.send(TTSMessage(message)).await;
 
         self.update_button_states()?;
@@ -1045,7 +1662,7 @@ impl<'a> Device<'a> {
                 let channel = self.profile.get_fader_assignment(fader);
 
                 // Set the volume of the channel back to where it should be
-                self.goxlr.set_volume(channel, volume)?;
+                self.set_device_volume(channel, volume)?;
             }
         }
         Ok(())
@@ -1067,7 +1684,7 @@ impl<'a> Device<'a> {
                 self.profile.set_mute_previous_volume(fader, volume)?;
 
                 // Set the volume of the channel to 0
-                self.goxlr.set_volume(channel, 0)?;
+                self.set_device_volume(channel, 0)?;
             }
         }
 
@@ -1096,7 +1713,7 @@ impl<'a> Device<'a> {
 
     async fn load_sample_bank(&mut self, bank: SampleBank) -> Result<()> {
         // Send the TTS Message..
-        let tts_message = format!("Sample {}", bank);
+        let tts_message = translate("tts.sample_bank", &[("bank", &bank.to_string())]);
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.load_sample_bank(bank)?;
@@ -1234,7 +1851,8 @@ impl<'a> Device<'a> {
         if let Some(audio) = &self.audio_handler {
             let state = self.profile.is_sample_clear_active();
             if !audio.is_sample_recording() {
-                let message = format!("Sample Clear {}", tts_bool_to_state(!state));
+                let message =
+                    translate("tts.sample_clear", &[("state", &tts_bool_to_state(!state))]);
                 self.global_events.send(TTSMessage(message)).await?;
 
                 self.profile.set_sample_clear_active(!state);
@@ -1340,6 +1958,11 @@ impl<'a> Device<'a> {
         let sample_path = self.get_path_for_sample(audio.file).await?;
         audio.file = sample_path;
 
+        // The calculated gain is only used if normalisation is enabled..
+        if !self.settings.get_sampler_normalisation(self.serial()).await {
+            audio.gain = None;
+        }
+
         // Calculate the Gain from the settings..
         let name = audio.name.clone();
         let percent = self.settings.get_sample_gain_percent(name).await;
@@ -1443,7 +2066,11 @@ impl<'a> Device<'a> {
     async fn load_effect_bank(&mut self, preset: EffectBankPresets) -> Result<()> {
         // Send the TTS Message..
         let preset_name = self.profile.get_effect_name(preset);
-        let tts_message = format!("Effects {}, {}", preset as u8 + 1, preset_name);
+        let number = (preset as u8 + 1).to_string();
+        let tts_message = translate(
+            "tts.effect_preset",
+            &[("preset", &number), ("name", &preset_name)],
+        );
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.load_effect_bank(preset)?;
@@ -1457,7 +2084,7 @@ impl<'a> Device<'a> {
 
     async fn set_megaphone(&mut self, enabled: bool) -> Result<()> {
         // Send the TTS Message..
-        let tts_message = format!("Megaphone {}", tts_bool_to_state(enabled));
+        let tts_message = translate("tts.megaphone", &[("state", &tts_bool_to_state(enabled))]);
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.set_megaphone(enabled);
@@ -1467,7 +2094,7 @@ impl<'a> Device<'a> {
 
     async fn set_robot(&mut self, enabled: bool) -> Result<()> {
         // Send the TTS Message..
-        let tts_message = format!("Robot {}", tts_bool_to_state(enabled));
+        let tts_message = translate("tts.robot", &[("state", &tts_bool_to_state(enabled))]);
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.set_robot(enabled);
@@ -1477,7 +2104,7 @@ impl<'a> Device<'a> {
 
     async fn set_hardtune(&mut self, enabled: bool) -> Result<()> {
         // Send the TTS Message..
-        let tts_message = format!("Hard tune {}", tts_bool_to_state(enabled));
+        let tts_message = translate("tts.hard_tune", &[("state", &tts_bool_to_state(enabled))]);
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.set_hardtune(enabled);
@@ -1494,7 +2121,7 @@ impl<'a> Device<'a> {
 
     async fn set_effects(&mut self, enabled: bool) -> Result<()> {
         // Send the TTS Message..
-        let tts_message = format!("Effects {}", tts_bool_to_state(enabled));
+        let tts_message = translate("tts.effects", &[("state", &tts_bool_to_state(enabled))]);
         let _ = self.global_events.send(TTSMessage(tts_message)).await;
 
         self.profile.set_effects(enabled);
@@ -1508,6 +2135,37 @@ impl<'a> Device<'a> {
         Ok(())
     }
 
+    fn set_effect_level(&mut self, encoder: EncoderName, level: EffectLevelValue) -> Result<()> {
+        match level {
+            EffectLevelValue::Raw(position) => {
+                self.profile.set_encoder_position(encoder, position)?;
+            }
+            EffectLevelValue::Percent(percent) => {
+                if percent > 100 {
+                    bail!("Value must be a percentage");
+                }
+                let (min, max) = self.profile.get_encoder_range(encoder);
+                let position = min as i32 + ((max as i32 - min as i32) * percent as i32) / 100;
+                self.profile.set_encoder_position(encoder, position as i8)?;
+            }
+            EffectLevelValue::Value(value) => {
+                self.profile.set_encoder_effect_value(encoder, value)?;
+            }
+        }
+
+        // Move the knob to match, and send the new value..
+        let position = self.profile.get_encoder_position(encoder);
+        self.goxlr.set_encoder_value(encoder, position)?;
+
+        let key = match encoder {
+            EncoderName::Pitch => EffectKey::PitchAmount,
+            EncoderName::Gender => EffectKey::GenderAmount,
+            EncoderName::Reverb => EffectKey::ReverbAmount,
+            EncoderName::Echo => EffectKey::EchoAmount,
+        };
+        self.apply_effects(LinkedHashSet::from_iter([key]))
+    }
+
     fn mic_muted_by_fader(&self) -> bool {
         // Is the mute button even assigned to a fader?
         if self.profile.is_mic_on_fader() {
@@ -1557,11 +2215,36 @@ impl<'a> Device<'a> {
                     self.fader_pause_until[fader].paused = false;
                 }
             }
+            let last_seen = self.fader_last_seen[fader];
             self.fader_last_seen[fader] = new_volume;
 
             let channel = self.profile.get_fader_assignment(fader);
             let old_volume = self.profile.get_channel_volume(channel);
 
+            // While locked, put the volume back to where it was and ignore the move..
+            if self.interaction_locked && self.interaction_lock.faders.contains(&fader) {
+                if new_volume != old_volume && new_volume != last_seen {
+                    debug!("Ignoring {} Fader Move, Interaction is Locked", channel);
+                    self.set_device_volume(channel, old_volume)?;
+                    if !self.is_device_mini() {
+                        self.fader_pause_until[fader].paused = true;
+                        self.fader_pause_until[fader].until = old_volume;
+                    }
+                }
+                continue;
+            }
+
+            // If the fader's been pushed past the channel's limit, pull the volume back down..
+            let limited_volume = self.limit_volume(channel, new_volume);
+            if limited_volume != new_volume && new_volume != last_seen {
+                self.set_device_volume(channel, limited_volume)?;
+                if !self.is_device_mini() {
+                    self.fader_pause_until[fader].paused = true;
+                    self.fader_pause_until[fader].until = limited_volume;
+                }
+            }
+            let new_volume = limited_volume;
+
             if new_volume != old_volume {
                 debug!(
                     "Updating {} volume from {} to {} as a human moved the fader",
@@ -1578,13 +2261,73 @@ impl<'a> Device<'a> {
         Ok(value_changed)
     }
 
-    fn update_submix_for(&mut self, channel: ChannelName, volume: u8) -> Result<()> {
-        if self.device_supports_submixes() && self.profile.is_submix_enabled() {
-            if let Some(mix) = self.profile.get_submix_from_channel(channel) {
-                if !self.profile.submix_linked(mix) {
-                    return Ok(());
-                }
-
+    fn limit_volume(&self, channel: ChannelName, volume: u8) -> u8 {
+        match self.volume_limits.get(&channel) {
+            Some(limit) => volume.min(*limit),
+            None => volume,
+        }
+    }
+
+    // Sets a volume as if it came in over IPC, so limits, submixes and faders are all handled..
+    fn set_channel_volume(&mut self, channel: ChannelName, volume: u8) -> Result<()> {
+        let volume = self.limit_volume(channel, volume);
+        self.set_device_volume(channel, volume)?;
+        self.profile.set_channel_volume(channel, volume)?;
+        self.update_submix_for(channel, volume)?;
+
+        if let Some(fader) = self.profile.get_fader_from_channel(channel) {
+            self.fader_pause_until[fader].paused = true;
+            self.fader_pause_until[fader].until = volume;
+        }
+        Ok(())
+    }
+
+    fn set_device_volume(&mut self, channel: ChannelName, volume: u8) -> Result<()> {
+        let volume = self.duck_volume(channel, self.trim_volume(channel, volume));
+        self.goxlr.set_volume(channel, volume)
+    }
+
+    fn duck_volume(&self, channel: ChannelName, volume: u8) -> u8 {
+        if self.duck_applied == 0 || !self.ducking.channels.contains(&channel) {
+            return volume;
+        }
+
+        // As with trims, a channel on a fader is left alone, the fader would be dragged down
+        // and every position it passed through would be read back as a human moving it..
+        if self.profile.get_fader_from_channel(channel).is_some() {
+            return volume;
+        }
+        ((volume as u16 * (100 - self.duck_applied) as u16) / 100) as u8
+    }
+
+    fn apply_ducking(&mut self) -> Result<()> {
+        for channel in self.ducking.channels.clone() {
+            let volume = self.profile.get_channel_volume(channel);
+            self.set_device_volume(channel, volume)?;
+        }
+        Ok(())
+    }
+
+    fn trim_volume(&self, channel: ChannelName, volume: u8) -> u8 {
+        // When a channel is on a fader, the fader *is* the hardware volume, so trimming it
+        // would just drag the fader around. Trims only apply to channels off the faders..
+        if self.profile.get_fader_from_channel(channel).is_some() {
+            return volume;
+        }
+
+        match self.output_trims.get(&channel) {
+            Some(trim) => ((volume as u16 * *trim as u16) / 100) as u8,
+            None => volume,
+        }
+    }
+
+    fn update_submix_for(&mut self, channel: ChannelName, volume: u8) -> Result<()> {
+        if self.device_supports_submixes() && self.profile.is_submix_enabled() {
+            if let Some(mix) = self.profile.get_submix_from_channel(channel) {
+                if !self.profile.submix_linked(mix) {
+                    return Ok(());
+                }
+
                 let mix_current_volume = self.profile.get_submix_volume(mix);
                 let ratio = self.profile.get_submix_ratio(mix);
 
@@ -1602,6 +2345,8 @@ impl<'a> Device<'a> {
     }
 
     async fn update_encoders_to(&mut self, encoders: [i8; 4]) -> Result<bool> {
+        let encoders = self.apply_encoder_sensitivity(encoders)?;
+
         // Ok, this is funky, due to the way pitch works, the encoder 'value' doesn't match
         // the profile value if hardtune is enabled, so we'll pre-emptively calculate pitch here..
         let mut value_changed = false;
@@ -1632,10 +2377,10 @@ impl<'a> Device<'a> {
 
             let user_value = self
                 .mic_profile
-                .get_effect_value(EffectKey::PitchAmount, self.profile());
+                .get_effect_value(EffectKey::PitchAmount, self.profile())?;
 
             if !self.is_device_mini() {
-                let message = format!("Pitch {}", user_value);
+                let message = translate("tts.pitch", &[("value", &user_value.to_string())]);
                 let _ = self.global_events.send(TTSMessage(message)).await;
             }
         }
@@ -1649,20 +2394,20 @@ impl<'a> Device<'a> {
 
             let current_value = self
                 .mic_profile
-                .get_effect_value(EffectKey::GenderAmount, self.profile());
+                .get_effect_value(EffectKey::GenderAmount, self.profile())?;
 
             self.profile.set_gender_value(encoders[1])?;
             value_changed = true;
 
             let new_value = self
                 .mic_profile
-                .get_effect_value(EffectKey::GenderAmount, self.profile());
+                .get_effect_value(EffectKey::GenderAmount, self.profile())?;
 
             if new_value != current_value {
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::GenderAmount]))?;
 
                 if !self.is_device_mini() {
-                    let message = format!("Gender {}", new_value);
+                    let message = translate("tts.gender", &[("value", &new_value.to_string())]);
                     let _ = self.global_events.send(TTSMessage(message)).await;
                 }
             }
@@ -1678,16 +2423,12 @@ impl<'a> Device<'a> {
             value_changed = true;
             self.profile.set_reverb_value(encoders[2])?;
 
-            let new_value = self
-                .mic_profile
-                .get_effect_value(EffectKey::ReverbAmount, self.profile());
-
             self.apply_effects(LinkedHashSet::from_iter([EffectKey::ReverbAmount]))?;
 
-            let percent = 100 - ((new_value as f32 / -36.) * 100.) as i32;
+            let percent = ReverbAmount::from_knob_position(encoders[2])?.percent();
 
             if !self.is_device_mini() {
-                let message = format!("Reverb {} percent", percent);
+                let message = translate("tts.reverb", &[("percent", &percent.to_string())]);
                 let _ = self.global_events.send(TTSMessage(message)).await;
             }
         }
@@ -1702,13 +2443,10 @@ impl<'a> Device<'a> {
             self.profile.set_echo_value(encoders[3])?;
             self.apply_effects(LinkedHashSet::from_iter([EffectKey::EchoAmount]))?;
 
-            let mut user_value = self
-                .mic_profile
-                .get_effect_value(EffectKey::EchoAmount, self.profile());
-            user_value = 100 - ((user_value as f32 / -36.) * 100.) as i32;
+            let percent = EchoAmount::from_knob_position(encoders[3])?.percent();
 
             if !self.is_device_mini() {
-                let message = format!("Echo {} percent", user_value);
+                let message = translate("tts.echo", &[("percent", &percent.to_string())]);
                 let _ = self.global_events.send(TTSMessage(message)).await;
             }
         }
@@ -1716,6 +2454,47 @@ impl<'a> Device<'a> {
         Ok(value_changed)
     }
 
+    // The GoXLR moves an encoder one position per step, so for encoders with a custom sensitivity
+    // we scale how far it moved over the configured number of steps, and move the knob to match..
+    fn apply_encoder_sensitivity(&mut self, mut encoders: [i8; 4]) -> Result<[i8; 4]> {
+        for encoder in EncoderName::iter() {
+            let Some(steps) = self.profile.get_encoder_sensitivity(encoder) else {
+                continue;
+            };
+
+            let current = self.profile.get_encoder_position(encoder);
+            let moved = encoders[encoder as usize] as f64 - current as f64;
+            if moved == 0. {
+                continue;
+            }
+
+            let (min, max) = self.profile.get_encoder_range(encoder);
+            let per_step = (max as f64 - min as f64) / steps as f64;
+
+            let total = self.encoder_remainders[encoder] + moved * per_step;
+            let applied = total.trunc();
+            self.encoder_remainders[encoder] = total - applied;
+
+            let position = (current as f64 + applied).clamp(min as f64, max as f64) as i8;
+            if position == min || position == max {
+                self.encoder_remainders[encoder] = 0.;
+            }
+
+            if position != encoders[encoder as usize] {
+                self.goxlr.set_encoder_value(encoder, position)?;
+                encoders[encoder as usize] = position;
+            }
+        }
+        Ok(encoders)
+    }
+
+    pub fn get_metrics(&mut self) -> DeviceMetrics {
+        DeviceMetrics {
+            requests: get_timing_metrics(self.goxlr.request_metrics()),
+            poll_loop: get_timing_metrics(&self.poll_metrics),
+        }
+    }
+
     pub async fn get_mic_level(&mut self) -> Result<f64> {
         let level = self.goxlr.get_microphone_level()?;
 
@@ -1723,8 +2502,303 @@ impl<'a> Device<'a> {
         Ok(db)
     }
 
+    /// Only the mic has a meter on the device, the other channels are left empty until there's
+    /// a way to read them..
+    pub async fn get_channel_levels(&mut self) -> Result<EnumMap<ChannelName, Option<f64>>> {
+        let mut levels = EnumMap::default();
+        levels[ChannelName::Mic] = Some(self.get_mic_level().await?);
+        Ok(levels)
+    }
+
+    pub fn get_lighting(&self) -> Lighting {
+        self.profile
+            .get_lighting_ipc(self.is_device_mini(), self.device_supports_animations())
+    }
+
+    /// Returns the scribble image stored in the profile as a base64 encoded PNG
+    pub fn get_scribble_image(&self, fader: FaderName) -> Result<String> {
+        let png = self.profile.get_scribble_png(fader);
+        if png.is_empty() {
+            bail!("The profile has no image for Scribble {:?}", fader);
+        }
+        Ok(STANDARD.encode(png))
+    }
+
+    /// The gate's state isn't reported by the device, so it's inferred from the mic level, it's
+    /// only as accurate as the rate this is polled at.
+    pub async fn get_gate_level(&mut self) -> Result<GateLevel> {
+        let level = self.get_mic_level().await?;
+        let gate = self.mic_profile.noise_gate_ipc();
+
+        let state = if !gate.enabled || level >= gate.threshold as f64 {
+            self.gate_last_open = Some(Instant::now());
+            GateState::Open
+        } else {
+            // Once the level drops, the gate fades out over its release time before closing..
+            let release = Duration::from_millis(gate.release.as_millis().into());
+            match self.gate_last_open {
+                Some(open) if open.elapsed() < release => GateState::Attenuating,
+                _ => GateState::Closed,
+            }
+        };
+
+        Ok(GateLevel {
+            level,
+            threshold: gate.threshold,
+            attenuation: gate.attenuation,
+            state,
+        })
+    }
+
+    async fn get_voice_meter_lit(&mut self) -> Result<u8> {
+        // Sensitivity moves the bottom of the meter, from -10dB at 0 down to -70dB at 100..
+        let floor = -10. - (self.profile.get_voice_meter_sensitivity() as f64 * 0.6);
+        let level = self.get_mic_level().await?;
+
+        let fraction = ((level - floor) / -floor).clamp(0., 1.);
+        Ok((fraction * 4.).ceil() as u8)
+    }
+
     pub async fn perform_command(&mut self, command: GoXLRCommand) -> Result<()> {
         match command {
+            GoXLRCommand::Undo() => {
+                let Some(entry) = self.undo_history.pop_undo() else {
+                    bail!("Nothing to Undo");
+                };
+                // If it can't be undone right now, leave it where it was..
+                if let Err(error) = self.apply_history_command(&entry.inverse).await {
+                    self.undo_history.push_undo(entry);
+                    return Err(error);
+                }
+                self.undo_history.push_redo(entry);
+            }
+            GoXLRCommand::Redo() => {
+                let Some(entry) = self.undo_history.pop_redo() else {
+                    bail!("Nothing to Redo");
+                };
+                if let Err(error) = self.apply_history_command(&entry.command).await {
+                    self.undo_history.push_redo(entry);
+                    return Err(error);
+                }
+                self.undo_history.push_undo(entry);
+            }
+            GoXLRCommand::ExecuteBatch(commands) => {
+                let inverse = self.execute_batch(&commands).await?;
+                if commands.iter().any(clears_history) {
+                    self.undo_history.clear();
+                } else if let Some(inverse) = inverse {
+                    let command = GoXLRCommand::ExecuteBatch(commands);
+                    self.undo_history
+                        .record(command, GoXLRCommand::ExecuteBatch(inverse));
+                }
+            }
+            command => {
+                if clears_history(&command) {
+                    self.apply_command(command).await?;
+                    self.undo_history.clear();
+                    return Ok(());
+                }
+
+                let inverse = self.get_inverse(&command).await;
+                self.apply_command(command.clone()).await?;
+                if let Some(inverse) = inverse {
+                    self.undo_history.record(command, inverse);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Only commands which can be undone need the status building to work out their inverse..
+    async fn get_inverse(&self, command: &GoXLRCommand) -> Option<GoXLRCommand> {
+        if !is_undoable(command) {
+            return None;
+        }
+        get_inverse(command, &self.status().await)
+    }
+
+    async fn apply_history_command(&mut self, command: &GoXLRCommand) -> Result<()> {
+        if let GoXLRCommand::ExecuteBatch(commands) = command {
+            self.execute_batch(commands).await?;
+            return Ok(());
+        }
+        self.apply_command(command.clone()).await
+    }
+
+    // Runs a batch of commands, if one fails, everything before it is put back (as far as the
+    // undo history is able to). Returns the commands needed to undo the whole batch, if any..
+    async fn execute_batch(
+        &mut self,
+        commands: &[GoXLRCommand],
+    ) -> Result<Option<Vec<GoXLRCommand>>> {
+        for command in commands {
+            if matches!(
+                command,
+                GoXLRCommand::Undo() | GoXLRCommand::Redo() | GoXLRCommand::ExecuteBatch(_)
+            ) {
+                bail!("Batches can't contain Undo, Redo or other Batches");
+            }
+        }
+
+        let mut inverses = vec![];
+
+        // Commands (like loading a profile) which have no inverse, and so can't be rolled back..
+        let mut irreversible = vec![];
+        for (index, command) in commands.iter().enumerate() {
+            let inverse = self.get_inverse(command).await;
+
+            if let Err(error) = self.apply_command(command.clone()).await {
+                for inverse in inverses.into_iter().rev() {
+                    if let Err(error) = self.apply_command(inverse).await {
+                        warn!("Unable to roll back batch command: {}", error);
+                    }
+                }
+
+                let context = if irreversible.is_empty() {
+                    format!(
+                        "Batch command {} failed, earlier commands were rolled back",
+                        index + 1
+                    )
+                } else {
+                    format!(
+                        "Batch command {} failed, earlier commands were rolled back except {}",
+                        index + 1,
+                        irreversible.join(", ")
+                    )
+                };
+                return Err(error.context(context));
+            }
+
+            match inverse {
+                Some(inverse) => inverses.push(inverse),
+                None => irreversible.push(format!("{} ({:?})", index + 1, command)),
+            }
+        }
+
+        if !irreversible.is_empty() {
+            return Ok(None);
+        }
+        inverses.reverse();
+        Ok(Some(inverses))
+    }
+
+    /// Checks a command against this device's type, firmware and current state without running
+    /// it, returning every problem found. This only covers what can be known up front, so a
+    /// command which passes here may still fail when it's actually executed.
+    pub fn validate_command(&self, command: &GoXLRCommand) -> Vec<DaemonError> {
+        let mut errors = capabilities::check_command(&self.hardware, command);
+        errors.extend(self.check_command(command));
+
+        if let GoXLRCommand::ExecuteBatch(commands) = command {
+            for command in commands {
+                errors.extend(self.validate_command(command));
+            }
+        }
+        errors
+    }
+
+    // The argument and state checks shared by validate_command and apply_command, anything which
+    // can be known before the command is run belongs here so both give the same answer..
+    fn check_command(&self, command: &GoXLRCommand) -> Vec<DaemonError> {
+        let mut errors = vec![];
+
+        let invalid_state = |reason: &str| DaemonError::InvalidState {
+            reason: reason.to_string(),
+        };
+        let out_of_range = |field: &str, max: i64, value: i64| DaemonError::ValueOutOfRange {
+            field: field.to_string(),
+            min: 0,
+            max,
+            value,
+        };
+
+        match command {
+            GoXLRCommand::Undo() if !self.undo_history.can_undo() => {
+                errors.push(invalid_state("Nothing to Undo"));
+            }
+            GoXLRCommand::Redo() if !self.undo_history.can_redo() => {
+                errors.push(invalid_state("Nothing to Redo"));
+            }
+            GoXLRCommand::SetSamplerPreBufferDuration(duration) if *duration > 30000 => {
+                errors.push(out_of_range(
+                    "Sampler Pre-Buffer",
+                    30000,
+                    (*duration).into(),
+                ));
+            }
+            GoXLRCommand::SetFaderPage(page) | GoXLRCommand::RemoveFaderPage(page)
+                if *page >= self.profile.get_fader_page_count() =>
+            {
+                errors.push(invalid_state("Fader Page does not exist"));
+            }
+            GoXLRCommand::NextFaderPage() if self.profile.get_fader_page_count() == 0 => {
+                errors.push(invalid_state("No Fader Pages have been defined"));
+            }
+            GoXLRCommand::SetGlobalLightingBrightness(brightness) if *brightness > 100 => {
+                errors.push(out_of_range("Brightness", 100, (*brightness).into()));
+            }
+            GoXLRCommand::SetEncoderSensitivity(_, Some(steps))
+                if !(1..=MAX_ENCODER_STEPS).contains(steps) =>
+            {
+                errors.push(DaemonError::ValueOutOfRange {
+                    field: String::from("Encoder Sensitivity"),
+                    min: 1,
+                    max: MAX_ENCODER_STEPS.into(),
+                    value: (*steps).into(),
+                });
+            }
+            GoXLRCommand::SetLightingCrossfade(duration) if *duration > MAX_CROSSFADE => {
+                errors.push(out_of_range(
+                    "Lighting Crossfade",
+                    MAX_CROSSFADE.into(),
+                    (*duration).into(),
+                ));
+            }
+            GoXLRCommand::SetOutputTrim(channel, trim) => {
+                errors.extend(check_output_trim(*channel, *trim).err());
+            }
+            GoXLRCommand::SetVolumePreset(_, Some(preset)) => {
+                for (&channel, &trim) in &preset.output_trims {
+                    errors.extend(check_output_trim(channel, trim).err());
+                }
+            }
+            GoXLRCommand::SetOutputTone(output, _, gain) => {
+                errors.extend(check_output_tone(*output, *gain).err());
+            }
+            GoXLRCommand::SetDucking(ducking) => {
+                errors.extend(check_ducking(ducking).err());
+            }
+            GoXLRCommand::SetCoughMuteState(_) if !self.profile.is_mute_chat_button_toggle() => {
+                errors.push(invalid_state(
+                    "Cannot Set state when Mute button is in 'Hold' Mode",
+                ));
+            }
+            GoXLRCommand::ResolveProfileConflict(_) if self.profile.conflict().is_none() => {
+                errors.push(invalid_state("No Profile Conflict to Resolve"));
+            }
+            GoXLRCommand::DeleteProfile(name) if self.profile.name() == name.as_str() => {
+                errors.push(invalid_state("Unable to Remove Active Profile!"));
+            }
+            GoXLRCommand::DeleteMicProfile(name) if self.mic_profile.name() == name.as_str() => {
+                errors.push(invalid_state("Unable to Remove Active Profile!"));
+            }
+            _ => {}
+        }
+        errors
+    }
+
+    async fn apply_command(&mut self, command: GoXLRCommand) -> Result<()> {
+        if let Some(error) = capabilities::check_command(&self.hardware, &command).pop() {
+            bail!(error);
+        }
+        if let Some(error) = self.check_command(&command).pop() {
+            bail!(error);
+        }
+
+        match command {
+            GoXLRCommand::Undo() | GoXLRCommand::Redo() | GoXLRCommand::ExecuteBatch(_) => {
+                bail!("Undo, Redo and Batches can't be applied directly");
+            }
             GoXLRCommand::SetShutdownCommands(commands) => {
                 self.settings
                     .set_device_shutdown_commands(self.serial(), commands)
@@ -1743,11 +2817,63 @@ impl<'a> Device<'a> {
                     .await;
                 self.settings.save().await;
             }
-            GoXLRCommand::SetSamplerPreBufferDuration(duration) => {
-                if duration > 30000 {
-                    bail!("Buffer must be below 30seconds");
+            GoXLRCommand::SetButtonBinding(button, binding) => {
+                self.settings
+                    .set_device_button_binding(self.serial(), button, binding)
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SetHotkey(hotkey, commands) => {
+                validate_hotkey(&hotkey)?;
+                self.settings
+                    .set_device_hotkey(self.serial(), hotkey, commands)
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SetScheduledAction(name, action) => {
+                if let Some(action) = &action {
+                    scheduler::parse_time(&action.time)?;
                 }
+                self.settings
+                    .set_device_scheduled_action(self.serial(), name, action)
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SetDucking(ducking) => {
+                self.settings
+                    .set_device_ducking(self.serial(), ducking.clone())
+                    .await;
+                self.settings.save().await;
 
+                // Bring everything back up, the new settings will duck from scratch..
+                self.duck_level = 0.;
+                self.duck_applied = 0;
+                self.duck_speaking = false;
+                self.apply_ducking()?;
+                self.ducking = ducking;
+            }
+            GoXLRCommand::SetInteractionLockControls(interaction_lock) => {
+                self.settings
+                    .set_device_interaction_lock(self.serial(), interaction_lock.clone())
+                    .await;
+                self.settings.save().await;
+                self.interaction_lock = interaction_lock;
+            }
+            GoXLRCommand::SetNowPlayingFader(fader) => {
+                self.settings
+                    .set_device_now_playing_fader(self.serial(), fader)
+                    .await;
+                self.settings.save().await;
+
+                // Give the old fader its scribble back before moving across..
+                if let Some(old_fader) = self.now_playing_fader.take() {
+                    self.scribble_text[old_fader] = None;
+                    self.apply_scribble(old_fader).await?;
+                }
+                self.now_playing_fader = fader;
+                self.apply_now_playing().await?;
+            }
+            GoXLRCommand::SetSamplerPreBufferDuration(duration) => {
                 self.settings
                     .set_device_sampler_pre_buffer(self.serial(), duration)
                     .await;
@@ -1765,6 +2891,23 @@ impl<'a> Device<'a> {
             GoXLRCommand::SetFader(fader, channel) => {
                 self.set_fader(fader, channel).await?;
             }
+            GoXLRCommand::AddFaderPage(name) => {
+                self.profile.add_fader_page(name);
+            }
+            GoXLRCommand::RemoveFaderPage(page) => {
+                self.profile.remove_fader_page(page)?;
+            }
+            GoXLRCommand::RenameFaderPage(page, name) => {
+                self.profile.rename_fader_page(page, name)?;
+            }
+            GoXLRCommand::SetFaderPage(page) => {
+                self.load_fader_page(page).await?;
+            }
+            GoXLRCommand::NextFaderPage() => {
+                let count = self.profile.get_fader_page_count();
+                let page = (self.profile.get_active_fader_page() + 1) % count;
+                self.load_fader_page(page).await?;
+            }
             GoXLRCommand::SetFaderMuteFunction(fader, behaviour) => {
                 if self.profile.get_mute_button_behaviour(fader) == behaviour {
                     // Settings are the same..
@@ -1792,16 +2935,7 @@ impl<'a> Device<'a> {
 
             GoXLRCommand::SetVolume(channel, volume) => {
                 debug!("Setting Mix volume for {} to {}", channel, volume);
-                self.goxlr.set_volume(channel, volume)?;
-                self.profile.set_channel_volume(channel, volume)?;
-
-                // Update the Submix when volume changes via IPC
-                self.update_submix_for(channel, volume)?;
-
-                if let Some(fader) = self.profile.get_fader_from_channel(channel) {
-                    self.fader_pause_until[fader].paused = true;
-                    self.fader_pause_until[fader].until = volume;
-                }
+                self.set_channel_volume(channel, volume)?;
             }
 
             GoXLRCommand::SetCoughMuteFunction(mute_function) => {
@@ -1843,6 +2977,32 @@ impl<'a> Device<'a> {
                 // Apply the change..
                 self.apply_routing(input).await?;
             }
+            GoXLRCommand::SoloChannel(input) => {
+                if self.solo_input != input {
+                    // Solo is applied on top of the profile's routing, so reapplying it all is
+                    // enough to both solo the channel, and put everything back afterwards..
+                    self.solo_input = input;
+                    for input in BasicInputDevice::iter() {
+                        self.apply_routing(input).await?;
+                    }
+                }
+            }
+            GoXLRCommand::PanicMute() => self.set_panic_muted(true).await?,
+            GoXLRCommand::PanicRestore() => self.set_panic_muted(false).await?,
+            GoXLRCommand::SetInteractionLock(locked) => {
+                if self.interaction_locked != locked {
+                    self.interaction_locked = locked;
+                    self.load_colour_map().await?;
+                }
+            }
+            GoXLRCommand::SetOutputMuted(output, muted) => {
+                if self.muted_outputs[output] != muted {
+                    self.muted_outputs[output] = muted;
+                    for input in BasicInputDevice::iter() {
+                        self.apply_routing(input).await?;
+                    }
+                }
+            }
 
             GoXLRCommand::SetElementDisplayMode(element, display) => match element {
                 DisplayModeComponents::NoiseGate => {
@@ -1940,41 +3100,36 @@ impl<'a> Device<'a> {
 
             // Colouring..
             GoXLRCommand::SetAnimationMode(mode) => {
-                if !self.device_supports_animations() {
-                    bail!("Animations not supported on this firmware.");
-                }
-
-                if mode == goxlr_types::AnimationMode::Ripple && self.is_device_mini() {
-                    bail!("Ripple Mode not supported on the GoXLR Mini");
-                }
-
                 self.profile.set_animation_mode(mode)?;
                 self.load_animation(false).await?;
             }
             GoXLRCommand::SetAnimationMod1(value) => {
-                if !self.device_supports_animations() {
-                    bail!("Animations not supported on this firmware.");
-                }
-
                 self.profile.set_animation_mod1(value)?;
                 self.load_animation(false).await?;
             }
             GoXLRCommand::SetAnimationMod2(value) => {
-                if !self.device_supports_animations() {
-                    bail!("Animations not supported on this firmware.");
-                }
-
                 self.profile.set_animation_mod2(value)?;
                 self.load_animation(false).await?;
             }
             GoXLRCommand::SetAnimationWaterfall(direction) => {
-                if !self.device_supports_animations() {
-                    bail!("Animations not supported on this firmware.");
-                }
-
                 self.profile.set_animation_waterfall(direction)?;
                 self.load_animation(false).await?;
             }
+            GoXLRCommand::SetLightingBrightness(brightness) => {
+                self.profile.set_lighting_brightness(brightness)?;
+                self.load_colour_map().await?;
+            }
+            GoXLRCommand::SetVoiceMeterEnabled(enabled) => {
+                // The meter will pick this up on the next state update..
+                self.profile.set_voice_meter_enabled(enabled);
+            }
+            GoXLRCommand::SetVoiceMeterSensitivity(sensitivity) => {
+                self.profile.set_voice_meter_sensitivity(sensitivity)?;
+            }
+            GoXLRCommand::SetVoiceMeterColours(low, high) => {
+                self.profile.set_voice_meter_colours(low, high)?;
+                self.load_colour_map().await?;
+            }
 
             GoXLRCommand::SetGlobalColour(colour) => {
                 self.profile.set_global_colour(colour)?;
@@ -2021,6 +3176,12 @@ impl<'a> Device<'a> {
                 self.load_colour_map().await?;
                 self.update_button_states()?;
             }
+            GoXLRCommand::SetButtonMutedStyle(target, muted_style) => {
+                self.profile.set_button_muted_style(target, muted_style)?;
+
+                self.load_colour_map().await?;
+                self.update_button_states()?;
+            }
             GoXLRCommand::SetButtonGroupColours(target, colour, colour_2) => {
                 self.profile
                     .set_group_button_colours(target, colour, colour_2)?;
@@ -2054,6 +3215,22 @@ impl<'a> Device<'a> {
                 self.load_colour_map().await?;
                 self.update_button_states()?;
             }
+            GoXLRCommand::SetLighting(lighting) => {
+                // Grab everything (not just what this device shows) so a failure can be put back..
+                let previous = self.profile.get_lighting_ipc(false, true);
+                if let Err(error) = self.profile.set_lighting(lighting) {
+                    self.profile.set_lighting(previous)?;
+                    return Err(error);
+                }
+
+                if self.device_supports_animations() {
+                    self.load_animation(false).await?;
+                } else {
+                    self.load_colour_map().await?;
+                }
+                self.update_button_states()?;
+                self.set_all_fader_display_from_profile()?;
+            }
 
             // Effects
             GoXLRCommand::LoadEffectPreset(name) => {
@@ -2098,15 +3275,24 @@ impl<'a> Device<'a> {
                 self.profile.write_preset(name, &preset_directory)?;
             }
 
+            GoXLRCommand::SetEffectLevel(encoder, level) => {
+                self.set_effect_level(encoder, level)?;
+            }
+            GoXLRCommand::SetEncoderSensitivity(encoder, steps) => {
+                self.profile.set_encoder_sensitivity(encoder, steps);
+                self.encoder_remainders[encoder] = 0.;
+            }
+
             // Reverb
             GoXLRCommand::SetReverbStyle(style) => {
                 self.profile.set_reverb_style(style)?;
                 self.apply_effects(self.mic_profile.get_reverb_keyset())?;
             }
             GoXLRCommand::SetReverbAmount(amount) => {
+                let amount = ReverbAmount::from_percent(amount)?;
                 self.profile
                     .get_active_reverb_profile_mut()
-                    .set_percentage_amount(amount)?;
+                    .set_knob_position(amount.knob_position())?;
 
                 let encoder_value = self.profile.get_reverb_value();
                 self.goxlr
@@ -2183,9 +3369,10 @@ impl<'a> Device<'a> {
                 )?;
             }
             GoXLRCommand::SetEchoAmount(value) => {
+                let amount = EchoAmount::from_percent(value)?;
                 self.profile
                     .get_active_echo_profile_mut()
-                    .set_percentage_value(value)?;
+                    .set_knob_position(amount.knob_position())?;
 
                 let encoder_value = self.profile.get_echo_value();
                 self.goxlr
@@ -2206,15 +3393,17 @@ impl<'a> Device<'a> {
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::EchoTempo]))?;
             }
             GoXLRCommand::SetEchoDelayLeft(value) => {
+                let delay = EchoDelayMs::new(value)?;
                 self.profile
                     .get_active_echo_profile_mut()
-                    .set_time_left(value)?;
+                    .set_time_left(delay.millis())?;
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::EchoDelayL]))?;
             }
             GoXLRCommand::SetEchoDelayRight(value) => {
+                let delay = EchoDelayMs::new(value)?;
                 self.profile
                     .get_active_echo_profile_mut()
-                    .set_time_right(value)?;
+                    .set_time_right(delay.millis())?;
                 self.apply_effects(LinkedHashSet::from_iter([EffectKey::EchoDelayR]))?;
             }
             GoXLRCommand::SetEchoFeedbackLeft(value) => {
@@ -2474,6 +3663,10 @@ impl<'a> Device<'a> {
                 self.profile
                     .set_sample_stop_pct(bank, button, index, percent)?;
             }
+            GoXLRCommand::SetSampleTrim(bank, button, index, start, stop) => {
+                self.profile
+                    .set_sample_trim(bank, button, index, start, stop)?;
+            }
             GoXLRCommand::RemoveSampleByIndex(bank, button, index) => {
                 let remaining = self
                     .profile
@@ -2511,6 +3704,10 @@ impl<'a> Device<'a> {
                 self.profile.set_scribble_text(fader, text);
                 self.apply_scribble(fader).await?;
             }
+            GoXLRCommand::SetScribbleDisplayText(fader, lines) => {
+                self.scribble_text[fader] = lines.map(ScribbleText::new);
+                self.apply_scribble(fader).await?;
+            }
             GoXLRCommand::SetScribbleNumber(fader, number) => {
                 self.profile.set_scribble_number(fader, number);
                 self.apply_scribble(fader).await?;
@@ -2519,6 +3716,18 @@ impl<'a> Device<'a> {
                 self.profile.set_scribble_inverted(fader, inverted);
                 self.apply_scribble(fader).await?;
             }
+            GoXLRCommand::SetScribbleLeftBar(fader, left_bar) => {
+                self.profile.set_scribble_left_bar(fader, left_bar);
+                self.apply_scribble(fader).await?;
+            }
+            GoXLRCommand::SetScribbleImage(fader, png) => {
+                let png = prepare_scribble(&png)?;
+
+                // Make sure it's usable before it replaces anything..
+                get_scribble_from_png(&png, self.profile.is_scribble_inverted(fader))?;
+                self.profile.set_scribble_png(fader, png);
+                self.apply_scribble(fader).await?;
+            }
 
             // Profiles
             GoXLRCommand::NewProfile(profile_name) => {
@@ -2546,6 +3755,7 @@ impl<'a> Device<'a> {
             GoXLRCommand::LoadProfile(profile_name, save_change) => {
                 self.stop_all_samples(true, true).await?;
                 let volumes = self.profile.get_current_state();
+                let previous_colours = self.colour_map_last.clone();
 
                 // Grab the needed Paths..
                 let profile_path = self.settings.get_profile_directory().await;
@@ -2573,7 +3783,7 @@ impl<'a> Device<'a> {
                             return Err(e);
                         }
                         warn!("Failed to Load Profile: {}, checking for backup..", e);
-                        match ProfileAdapter::from_named(profile_name, &backup_path) {
+                        match ProfileAdapter::from_named(profile_name.clone(), &backup_path) {
                             Ok(profile) => {
                                 info!("Backup Profile Loaded");
                                 self.profile = profile;
@@ -2582,13 +3792,26 @@ impl<'a> Device<'a> {
                                 self.profile.save(&profile_path, true)?;
                             }
                             Err(e) => {
-                                bail!("Failed to Load backup profile: {}", e);
+                                bail!(DaemonError::ProfileParse {
+                                    name: profile_name,
+                                    message: e.to_string(),
+                                });
                             }
                         }
                     }
                 };
 
+                self.start_colour_fade(previous_colours).await;
                 self.apply_profile(Some(volumes)).await?;
+                self.event_log.push(
+                    Some(self.serial()),
+                    DaemonEventType::ProfileLoaded(self.profile.name().to_owned()),
+                );
+                let env = vec![(
+                    String::from("GOXLR_PROFILE"),
+                    self.profile.name().to_owned(),
+                )];
+                self.send_event_hook(EventHook::ProfileLoaded, env).await;
                 if save_change {
                     self.settings
                         .set_device_profile_name(self.serial(), self.profile.name())
@@ -2610,10 +3833,75 @@ impl<'a> Device<'a> {
                 }
                 self.update_button_states()?;
             }
+            GoXLRCommand::MergeProfile(profile_name, sections) => {
+                self.stop_all_samples(true, true).await?;
+                let volumes = self.profile.get_current_state();
+
+                debug!("Merging {:?} From Profile: {}", sections, profile_name);
+                let profile_path = self.settings.get_profile_directory().await;
+                let profile = ProfileAdapter::from_named(profile_name, &profile_path)?;
+                self.profile.merge_profile(profile, &sections);
+                self.apply_profile(Some(volumes)).await?;
+            }
             GoXLRCommand::SaveProfile() => {
                 let profile_directory = self.settings.get_profile_directory().await;
                 self.profile.save(&profile_directory, true)?;
             }
+            GoXLRCommand::DiscardProfileChanges() => {
+                self.stop_all_samples(true, true).await?;
+                let volumes = self.profile.get_current_state();
+
+                // Simply reload the profile from disk..
+                let profile_path = self.settings.get_profile_directory().await;
+                let name = self.profile.name().to_owned();
+                self.profile = ProfileAdapter::from_named(name, &profile_path)?;
+                self.apply_profile(Some(volumes)).await?;
+            }
+            GoXLRCommand::ResetToDefaultProfile() => {
+                self.stop_all_samples(true, true).await?;
+                let volumes = self.profile.get_current_state();
+                let previous_colours = self.colour_map_last.clone();
+
+                // Swap both profiles for the embedded defaults, nothing is written to disk here,
+                // so the user's profiles stay intact until they choose to save..
+                self.profile = ProfileAdapter::default();
+                self.mic_profile = MicProfileAdapter::default();
+                self.start_colour_fade(previous_colours).await;
+                self.apply_profile(Some(volumes)).await?;
+                self.apply_mic_profile().await?;
+
+                self.event_log.push(
+                    Some(self.serial()),
+                    DaemonEventType::ProfileLoaded(self.profile.name().to_owned()),
+                );
+                self.event_log.push(
+                    Some(self.serial()),
+                    DaemonEventType::MicProfileLoaded(self.mic_profile.name().to_owned()),
+                );
+            }
+            GoXLRCommand::ResetDevice() => {
+                self.reset_device().await?;
+            }
+            GoXLRCommand::ResolveProfileConflict(resolution) => {
+                let profile_path = self.settings.get_profile_directory().await;
+                match resolution {
+                    ProfileConflictResolution::Keep => {
+                        self.profile.keep_conflict(&profile_path)?;
+                    }
+                    ProfileConflictResolution::Reload | ProfileConflictResolution::Merge => {
+                        self.stop_all_samples(true, true).await?;
+                        let volumes = self.profile.get_current_state();
+
+                        if resolution == ProfileConflictResolution::Reload {
+                            let name = self.profile.name().to_owned();
+                            self.profile = ProfileAdapter::from_named(name, &profile_path)?;
+                        } else {
+                            self.profile.merge_conflict(&profile_path)?;
+                        }
+                        self.apply_profile(Some(volumes)).await?;
+                    }
+                }
+            }
             GoXLRCommand::SaveProfileAs(profile_name) => {
                 let path = self.settings.get_profile_directory().await;
 
@@ -2629,10 +3917,6 @@ impl<'a> Device<'a> {
                 self.settings.save().await;
             }
             GoXLRCommand::DeleteProfile(name) => {
-                if self.profile.name() == name {
-                    bail!("Unable to Remove Active Profile!");
-                }
-
                 let profiles = self.settings.get_profile_directory().await;
                 let backups = self.settings.get_backup_directory().await;
                 self.profile.delete_profile(name.clone(), &profiles)?;
@@ -2693,7 +3977,7 @@ impl<'a> Device<'a> {
                             return Err(e);
                         }
                         warn!("Failed to Load Profile: {}, checking for backup..", e);
-                        match MicProfileAdapter::from_named(name, &backup) {
+                        match MicProfileAdapter::from_named(name.clone(), &backup) {
                             Ok(profile) => {
                                 info!("Backup Mic Profile Loaded");
                                 self.mic_profile = profile;
@@ -2702,12 +3986,22 @@ impl<'a> Device<'a> {
                                 self.profile.save(&path, true)?;
                             }
                             Err(e) => {
-                                bail!("Failed to Load backup profile: {}", e);
+                                bail!(DaemonError::ProfileParse {
+                                    name,
+                                    message: e.to_string(),
+                                });
                             }
                         }
                     }
                 };
                 self.apply_mic_profile().await?;
+                self.event_log.push(
+                    Some(self.serial()),
+                    DaemonEventType::MicProfileLoaded(self.mic_profile.name().to_owned()),
+                );
+                let name = self.mic_profile.name().to_owned();
+                let env = vec![(String::from("GOXLR_MIC_PROFILE"), name)];
+                self.send_event_hook(EventHook::MicProfileLoaded, env).await;
 
                 if persist {
                     self.settings
@@ -2734,10 +4028,6 @@ impl<'a> Device<'a> {
                 self.settings.save().await;
             }
             GoXLRCommand::DeleteMicProfile(profile_name) => {
-                if self.mic_profile.name() == profile_name {
-                    bail!("Unable to Remove Active Profile!");
-                }
-
                 let profile_directory = self.settings.get_mic_profile_directory().await;
                 self.mic_profile
                     .delete_profile(profile_name.clone(), &profile_directory)?;
@@ -2777,6 +4067,13 @@ impl<'a> Device<'a> {
                 self.settings.save().await;
             }
 
+            GoXLRCommand::SetSamplerNormalisation(value) => {
+                self.settings
+                    .set_sampler_normalisation(self.serial(), value)
+                    .await;
+                self.settings.save().await;
+            }
+
             GoXLRCommand::SetLockFaders(value) => {
                 let current = self.settings.get_device_lock_faders(self.serial()).await;
 
@@ -2795,6 +4092,20 @@ impl<'a> Device<'a> {
                     self.load_colour_map().await?;
                 }
             }
+            GoXLRCommand::SetGlobalLightingBrightness(brightness) => {
+                self.settings
+                    .set_device_lighting_brightness(self.serial(), brightness)
+                    .await;
+                self.settings.save().await;
+                self.load_colour_map().await?;
+            }
+
+            GoXLRCommand::SetLightingCrossfade(duration) => {
+                self.settings
+                    .set_device_lighting_crossfade(self.serial(), duration)
+                    .await;
+                self.settings.save().await;
+            }
 
             GoXLRCommand::SetVodMode(value) => {
                 let serial = self.serial();
@@ -2811,6 +4122,131 @@ impl<'a> Device<'a> {
                 }
             }
 
+            GoXLRCommand::SetVolumeLimit(channel, limit) => {
+                self.settings
+                    .set_device_volume_limit(self.serial(), channel, limit)
+                    .await;
+                self.settings.save().await;
+                self.volume_limits = self.settings.get_device_volume_limits(self.serial()).await;
+
+                // Bring the channel down if it's currently above the new limit..
+                let volume = self.profile.get_channel_volume(channel);
+                let limited = self.limit_volume(channel, volume);
+                if limited != volume {
+                    self.set_device_volume(channel, limited)?;
+                    self.profile.set_channel_volume(channel, limited)?;
+                    self.update_submix_for(channel, limited)?;
+
+                    if let Some(fader) = self.profile.get_fader_from_channel(channel) {
+                        self.fader_pause_until[fader].paused = true;
+                        self.fader_pause_until[fader].until = limited;
+                    }
+                }
+            }
+
+            GoXLRCommand::SetOutputTone(output, band, _) => {
+                // The capability check should stop us getting this far, there's nothing to send..
+                bail!("The {} {} can't be changed on this device", output, band);
+            }
+            GoXLRCommand::SetOutputTrim(channel, trim) => {
+                self.settings
+                    .set_device_output_trim(self.serial(), channel, trim)
+                    .await;
+                self.settings.save().await;
+                self.output_trims = self.settings.get_device_output_trims(self.serial()).await;
+
+                // Reapply the channel volume so the new trim takes effect..
+                let volume = self.profile.get_channel_volume(channel);
+                self.set_device_volume(channel, volume)?;
+            }
+
+            GoXLRCommand::SetVolumePreset(name, preset) => {
+                self.settings
+                    .set_device_volume_preset(self.serial(), name, preset)
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SaveVolumePreset(name) => {
+                let preset = VolumePreset {
+                    volumes: ChannelName::iter()
+                        .map(|channel| (channel, self.profile.get_channel_volume(channel)))
+                        .collect(),
+                    output_trims: self.output_trims.clone(),
+                };
+                self.settings
+                    .set_device_volume_preset(self.serial(), name, Some(preset))
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::ApplyVolumePreset(name, fade) => {
+                let presets = self.settings.get_device_volume_presets(self.serial()).await;
+                let Some(preset) = presets.get(&name) else {
+                    bail!("Volume Preset {} does not exist", name);
+                };
+
+                // Trims from a preset are only held until the daemon restarts, the trims in the
+                // settings stay as the baseline that presets are switched away from..
+                for (&channel, &trim) in &preset.output_trims {
+                    match trim {
+                        100.. => self.output_trims.remove(&channel),
+                        _ => self.output_trims.insert(channel, trim),
+                    };
+                    let volume = self.profile.get_channel_volume(channel);
+                    self.set_device_volume(channel, volume)?;
+                }
+
+                if fade == 0 {
+                    self.volume_fade = None;
+                    for (&channel, &volume) in &preset.volumes {
+                        self.set_channel_volume(channel, volume)?;
+                    }
+                } else {
+                    let from = preset
+                        .volumes
+                        .keys()
+                        .map(|&channel| (channel, self.profile.get_channel_volume(channel)))
+                        .collect();
+
+                    self.volume_fade = Some(VolumeFade {
+                        from,
+                        to: preset.volumes.clone(),
+                        start: Instant::now(),
+                        duration: Duration::from_millis(fade.into()),
+                    });
+                }
+            }
+
+            GoXLRCommand::SetAttachStatePolicy(value) => {
+                self.settings
+                    .set_device_attach_state_policy(self.serial(), value)
+                    .await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SetErrorFeedback(value) => {
+                self.settings
+                    .set_device_error_feedback(self.serial(), value)
+                    .await;
+                self.settings.save().await;
+
+                if !value && self.error_flash.take().is_some() {
+                    self.update_button_states()?;
+                }
+            }
+            GoXLRCommand::SetDeviceLabel(label) => {
+                let label = label
+                    .map(|label| label.trim().to_string())
+                    .filter(|label| !label.is_empty());
+
+                self.settings.set_device_label(self.serial(), label).await;
+                self.settings.save().await;
+            }
+            GoXLRCommand::SetProfileFallbacks(fallbacks) => {
+                self.settings
+                    .set_device_profile_fallbacks(self.serial(), fallbacks)
+                    .await;
+                self.settings.save().await;
+            }
+
             GoXLRCommand::SetActiveEffectPreset(preset) => {
                 self.load_effect_bank(preset).await?;
                 self.update_button_states()?;
@@ -2844,9 +4280,6 @@ impl<'a> Device<'a> {
                 // This is more complicated because the 'state' of the mute can come from
                 // various different locations, so what we're going to do is simply update
                 // the profile, and re-apply the Mute settings from there.
-                if !self.profile.is_mute_chat_button_toggle() {
-                    bail!("Cannot Set state when Mute button is in 'Hold' Mode");
-                }
                 match state {
                     MuteState::Unmuted => {
                         self.profile.set_mute_chat_button_on(false);
@@ -2909,6 +4342,12 @@ impl<'a> Device<'a> {
     fn update_button_states(&mut self) -> Result<()> {
         let button_states = self.create_button_states();
         self.goxlr.set_button_states(button_states)?;
+
+        // Mute buttons which turn 'Off' are handled in the colour map, so let update_state send
+        // a new one if it's changed.
+        if self.profile.has_muted_light_off() {
+            self.colour_map_pending = true;
+        }
         Ok(())
     }
 
@@ -2921,6 +4360,11 @@ impl<'a> Device<'a> {
 
         // Replace the Cough Button button data with correct data.
         result[Buttons::MicrophoneMute as usize] = self.profile.get_mute_chat_button_colour_state();
+
+        // A button whose action just failed flashes until the error clears..
+        if let Some((button, _)) = self.error_flash {
+            result[button as usize] = ButtonStates::Flashing;
+        }
         result
     }
 
@@ -2930,6 +4374,8 @@ impl<'a> Device<'a> {
         input: BasicInputDevice,
         router: EnumMap<BasicOutputDevice, bool>,
     ) -> Result<()> {
+        self.active_router[input] = router;
+
         let (left_input, right_input) = InputDevice::from_basic(&input);
         let mut left = [0; 22];
         let mut right = [0; 22];
@@ -3102,12 +4548,25 @@ impl<'a> Device<'a> {
         Ok(())
     }
 
+    async fn set_panic_muted(&mut self, panic_muted: bool) -> Result<()> {
+        if self.panic_muted != panic_muted {
+            // As with solo, reapplying the routing both applies and removes this..
+            self.panic_muted = panic_muted;
+            for input in BasicInputDevice::iter() {
+                self.apply_routing(input).await?;
+            }
+        }
+        Ok(())
+    }
+
     async fn apply_routing(&mut self, input: BasicInputDevice) -> Result<()> {
         // Load the routing for this channel from the profile..
         let mut router = self.profile.get_router(input);
 
         // Before we apply transient routing (especially because mic), check whether we should
-        // be forcing Mic -> Headphones to 'On' due to settings..
+        // be forcing Mic -> Headphones to 'On' due to settings. As the profile's router is left
+        // alone, turning FX off again simply puts the monitoring back to how it was..
+        let mut monitor_with_fx = false;
         if input == BasicInputDevice::Microphone {
             // If the mic is muted, transient routing will forcefully disable this, so we should
             // be safe to simply set it true here, and hope for the best :D
@@ -3116,6 +4575,7 @@ impl<'a> Device<'a> {
                 // We need to adjust this based on the FX state..
                 if self.profile.is_fx_enabled() {
                     router[BasicOutputDevice::Headphones] = true;
+                    monitor_with_fx = true;
                 }
             }
         }
@@ -3135,9 +4595,26 @@ impl<'a> Device<'a> {
         debug!("Applying Routing to {:?}:", input);
         debug!("{:?}", router);
 
+        // If a mute has dropped the Headphones, the mic shouldn't be monitored either..
+        let monitor_with_fx = monitor_with_fx && router[BasicOutputDevice::Headphones];
+
+        // When monitoring another mix the Headphones follow that, but the mic should still be
+        // heard while FX are on..
         let monitor = self.profile.get_monitoring_mix();
         if monitor != BasicOutputDevice::Headphones {
-            router[BasicOutputDevice::Headphones] = router[monitor];
+            router[BasicOutputDevice::Headphones] = router[monitor] || monitor_with_fx;
+        }
+
+        if let Some(solo) = self.solo_input {
+            router[BasicOutputDevice::Headphones] = input == solo;
+        }
+
+        for output in BasicOutputDevice::iter() {
+            if self.muted_outputs[output]
+                || (self.panic_muted && output != BasicOutputDevice::Headphones)
+            {
+                router[output] = false;
+            }
         }
 
         self.apply_channel_routing(input, router)?;
@@ -3222,6 +4699,63 @@ impl<'a> Device<'a> {
         Ok(())
     }
 
+    async fn load_fader_page(&mut self, page: usize) -> Result<()> {
+        let channels = self.profile.get_fader_page_channels(page)?;
+        if page == self.profile.get_active_fader_page() {
+            return Ok(());
+        }
+
+        // Keep hold of the current layout, then release anything that's about to lose its
+        // fader, channels not on a fader can't be muted..
+        self.profile.store_fader_page();
+        for fader in FaderName::iter() {
+            if !channels.contains(&self.profile.get_fader_assignment(fader)) {
+                self.unmute_fader(fader).await?;
+            }
+        }
+
+        self.profile.load_fader_page(page)?;
+
+        let mut mic_assigned_to_fader = false;
+        for fader in FaderName::iter() {
+            let channel = self.profile.get_fader_assignment(fader);
+            self.goxlr.set_fader(fader, channel)?;
+
+            if channel == ChannelName::Mic {
+                mic_assigned_to_fader = true;
+                self.profile.set_mic_fader(fader)?;
+            }
+        }
+        if !mic_assigned_to_fader {
+            self.profile.clear_mic_fader();
+        }
+
+        for fader in FaderName::iter() {
+            let channel = self.profile.get_fader_assignment(fader);
+            if channel != ChannelName::Mic {
+                self.apply_mute_from_profile(fader, None)?;
+            }
+
+            // Bring the (potentially motorised) fader to the channel's volume..
+            let volume = self.profile.get_channel_volume(channel);
+            self.set_device_volume(channel, volume)?;
+        }
+        self.apply_cough_from_profile()?;
+
+        if !self.is_device_mini() {
+            for fader in FaderName::iter() {
+                self.apply_scribble(fader).await?;
+            }
+        }
+
+        self.update_button_states()?;
+        for input in BasicInputDevice::iter() {
+            self.apply_routing(input).await?;
+        }
+
+        Ok(())
+    }
+
     async fn set_fader(&mut self, fader: FaderName, new_channel: ChannelName) -> Result<()> {
         // A couple of things need to happen when a fader change occurs depending on scenario..
         if new_channel == self.profile.get_fader_assignment(fader) {
@@ -3260,13 +4794,18 @@ impl<'a> Device<'a> {
                 self.profile.clear_mic_fader();
             }
 
+            // Likewise, if the mic is arriving from outside the faders, it needs tracking..
+            if new_channel == ChannelName::Mic {
+                self.profile.set_mic_fader(fader)?;
+            }
+
             // Now set the new fader..
             self.profile.set_fader_assignment(fader, new_channel);
             self.goxlr.set_fader(fader, new_channel)?;
 
             // Due to motorised faders, the internal 'old' channel may be incorrectly set,
             // despite our config here being valid. So we'll force update the old channel.
-            self.goxlr.set_volume(
+            self.set_device_volume(
                 existing_channel,
                 self.profile.get_channel_volume(existing_channel),
             )?;
@@ -3274,7 +4813,7 @@ impl<'a> Device<'a> {
             // Make sure the new channel comes in with the correct volume..
             if self.needs_submix_correction(new_channel) {
                 let volume = self.profile.get_channel_volume(new_channel);
-                self.goxlr.set_volume(new_channel, volume)?;
+                self.set_device_volume(new_channel, volume)?;
             }
 
             // Remember to update the button states after change..
@@ -3307,11 +4846,11 @@ impl<'a> Device<'a> {
         // If the channel being moved is either Headphone or Line Out, reset the volume..
         if self.needs_submix_correction(new_channel) {
             let volume = self.profile.get_channel_volume(new_channel);
-            self.goxlr.set_volume(new_channel, volume)?;
+            self.set_device_volume(new_channel, volume)?;
         }
         if self.needs_submix_correction(existing_channel) {
             let volume = self.profile.get_channel_volume(existing_channel);
-            self.goxlr.set_volume(existing_channel, volume)?;
+            self.set_device_volume(existing_channel, volume)?;
         }
 
         if !self.is_device_mini() {
@@ -3331,7 +4870,12 @@ impl<'a> Device<'a> {
             mute_type: self.profile().get_mute_button_behaviour(fader),
             scribble: self
                 .profile()
-                .get_scribble_ipc(fader, self.is_device_mini()),
+                .get_scribble_ipc(fader, self.is_device_mini())
+                .map(|mut scribble| {
+                    let text = self.scribble_text[fader].as_ref();
+                    scribble.display_text = text.map(|text| text.lines.clone());
+                    scribble
+                }),
             mute_state: self.profile.get_ipc_mute_state(fader),
         }
     }
@@ -3353,26 +4897,105 @@ impl<'a> Device<'a> {
     }
 
     async fn load_colour_map(&mut self) -> Result<()> {
+        // The colour map is fairly large, so if we've only just sent one, hold this change back
+        // and let update_state send it shortly, any other changes in the meantime are merged.
+        if let Some(sent) = self.colour_map_sent {
+            if sent.elapsed() < COLOUR_MAP_INTERVAL {
+                self.colour_map_pending = true;
+                return Ok(());
+            }
+        }
+        self.colour_map_pending = false;
+        self.colour_map_sent = Some(Instant::now());
+
         // The new colour format occurred on different firmware versions depending on device,
         // so do the check here.
         let lock_faders = self.settings.get_device_lock_faders(self.serial()).await;
 
         let blank_mute = self.is_device_mini() || lock_faders;
 
-        let use_1_3_40_format = self.device_supports_animations();
-        let colour_map = self.profile.get_colour_map(use_1_3_40_format, blank_mute);
+        let brightness = self
+            .settings
+            .get_device_lighting_brightness(self.serial())
+            .await;
 
-        if use_1_3_40_format {
-            self.goxlr.set_button_colours_1_3_40(colour_map)?;
-        } else {
-            let mut map: [u8; 328] = [0; 328];
-            map.copy_from_slice(&colour_map[0..328]);
-            self.goxlr.set_button_colours(map)?;
+        // Dim everything while locked, so it's obvious why nothing's responding..
+        let brightness = match self.interaction_locked {
+            true => brightness / 2,
+            false => brightness,
+        };
+
+        let format = capabilities::colour_map_format(&self.hardware);
+        let colour_map = match &self.lighting_sequence {
+            Some((sequence, start)) => {
+                self.profile
+                    .get_sequence_colour_map(format, sequence, start.elapsed(), brightness)
+            }
+            None => {
+                self.profile
+                    .get_colour_map(format, blank_mute, brightness, self.voice_meter_lit)
+            }
+        };
+
+        // Partway through a profile switch, so blend the old colours into the new..
+        let colour_map = match &self.colour_fade {
+            Some(fade) if fade.from.len() == colour_map.len() => {
+                let progress = fade.start.elapsed().as_secs_f64() / fade.duration.as_secs_f64();
+                let progress = progress.min(1.0);
+
+                fade.from
+                    .iter()
+                    .zip(&colour_map)
+                    .map(|(&from, &to)| {
+                        let from = from as f64;
+                        (from + (to as f64 - from) * progress).round() as u8
+                    })
+                    .collect()
+            }
+            _ => colour_map,
+        };
+
+        // The GoXLR doesn't have a (known) way to update the colour of a single button, so the
+        // whole map needs to go every time. The best we can do is not send it if nothing changed.
+        if self.colour_map_last.as_ref() == Some(&colour_map) {
+            return Ok(());
         }
 
+        match format {
+            ColourMapFormat::Legacy => {
+                self.goxlr
+                    .set_button_colours(colour_map.as_slice().try_into()?)?;
+            }
+            ColourMapFormat::Animated => {
+                self.goxlr
+                    .set_button_colours_1_3_40(colour_map.as_slice().try_into()?)?;
+            }
+        }
+        self.colour_map_last = Some(colour_map);
+
         Ok(())
     }
 
+    async fn start_colour_fade(&mut self, from: Option<Vec<u8>>) {
+        let duration = self
+            .settings
+            .get_device_lighting_crossfade(self.serial())
+            .await;
+
+        // With animations turned off in the profile the lighting should stay still, so the colours
+        // just snap over..
+        if duration == 0 || self.profile.get_animation_mode() == goxlr_types::AnimationMode::None {
+            self.colour_fade = None;
+            return;
+        }
+
+        self.colour_fade = from.map(|from| ColourFade {
+            from,
+            start: Instant::now(),
+            duration: Duration::from_millis(duration.into()),
+        });
+    }
+
     async fn load_animation(&mut self, map_set: bool) -> Result<()> {
         let enabled = self.profile.get_animation_mode() != goxlr_types::AnimationMode::None;
 
@@ -3397,6 +5020,9 @@ impl<'a> Device<'a> {
         self.goxlr
             .set_animation_mode(enabled, mode, mod1, mod2, waterfall)?;
 
+        // Changing the animation mode may require the colour map to be resent..
+        self.colour_map_last = None;
+
         if !map_set
             && (mode == AnimationMode::None
                 || mode == AnimationMode::Ripple
@@ -3472,10 +5098,14 @@ impl<'a> Device<'a> {
         };
 
         for channel in volumes {
-            let channel_volume = self.profile.get_channel_volume(channel);
+            let profile_volume = self.profile.get_channel_volume(channel);
+            let channel_volume = self.limit_volume(channel, profile_volume);
+            if channel_volume != profile_volume {
+                self.profile.set_channel_volume(channel, channel_volume)?;
+            }
 
             debug!("Setting volume for {} to {}", channel, channel_volume);
-            self.goxlr.set_volume(channel, channel_volume)?;
+            self.set_device_volume(channel, channel_volume)?;
         }
 
         debug!("Applying Submixing Settings..");
@@ -3585,7 +5215,7 @@ impl<'a> Device<'a> {
         for effect in params {
             vec.push((
                 effect,
-                self.mic_profile.get_effect_value(effect, self.profile()),
+                self.mic_profile.get_effect_value(effect, self.profile())?,
             ));
         }
 
@@ -3670,6 +5300,26 @@ impl<'a> Device<'a> {
     }
 
     async fn apply_scribble(&mut self, fader: FaderName) -> Result<()> {
+        if let Some(text) = &self.scribble_text[fader] {
+            let inverted = self.profile.is_scribble_inverted(fader);
+            let scribble = get_text_scribble(&text.lines, text.scroll, inverted);
+            self.goxlr.set_fader_scribble(fader, scribble)?;
+            return Ok(());
+        }
+
+        // An image stored in the profile takes priority over building one from the settings..
+        let png = self.profile.get_scribble_png(fader);
+        if !png.is_empty() {
+            let inverted = self.profile.is_scribble_inverted(fader);
+            match get_scribble_from_png(png, inverted) {
+                Ok(scribble) => {
+                    self.goxlr.set_fader_scribble(fader, scribble)?;
+                    return Ok(());
+                }
+                Err(e) => warn!("Unable to use the Image for Scribble {:?}: {}", fader, e),
+            }
+        }
+
         let icon_path = self.settings.get_icons_directory().await;
 
         let scribble = self.profile.get_scribble_image(fader, &icon_path);
@@ -3745,10 +5395,10 @@ impl<'a> Device<'a> {
         // If submixes are enabled, the Mic Monitor should be at 100% as monitoring
         // is supposed to be handled by the mix.
         if submix_enabled {
-            self.goxlr.set_volume(ChannelName::MicMonitor, 255)?;
+            self.set_device_volume(ChannelName::MicMonitor, 255)?;
         } else {
             let volume = self.profile.get_channel_volume(ChannelName::MicMonitor);
-            self.goxlr.set_volume(ChannelName::MicMonitor, volume)?;
+            self.set_device_volume(ChannelName::MicMonitor, volume)?;
         }
 
         Ok(())
@@ -3784,7 +5434,7 @@ impl<'a> Device<'a> {
                 // We need to calculate the new value for the main channel..
                 let ratio = self.profile.get_submix_ratio(mix);
 
-                let linked_volume = (volume as f64 / ratio) as u8;
+                let linked_volume = self.limit_volume(channel, (volume as f64 / ratio) as u8);
                 if self.profile.get_channel_volume(channel) != linked_volume {
                     // Setup the latch..
                     if let Some(fader) = self.profile.get_fader_from_channel(channel) {
@@ -3792,7 +5442,7 @@ impl<'a> Device<'a> {
                         self.fader_pause_until[fader].until = linked_volume;
                     }
                     self.profile.set_channel_volume(channel, linked_volume)?;
-                    self.goxlr.set_volume(channel, linked_volume)?;
+                    self.set_device_volume(channel, linked_volume)?;
                 }
             }
 
@@ -3830,7 +5480,7 @@ impl<'a> Device<'a> {
     }
 
     fn is_device_mini(&self) -> bool {
-        self.hardware.device_type == DeviceType::Mini
+        capabilities::is_mini(&self.hardware)
     }
 
     fn needs_submix_correction(&self, channel: ChannelName) -> bool {
@@ -3859,29 +5509,11 @@ impl<'a> Device<'a> {
     }
 
     fn device_supports_submixes(&self) -> bool {
-        let support_full = VersionNumber(1, 4, Some(2), Some(107));
-        let support_mini = VersionNumber(1, 2, Some(0), Some(46));
-
-        let current = &self.hardware.versions.firmware;
-
-        match self.hardware.device_type {
-            DeviceType::Unknown => false,
-            DeviceType::Full => version_newer_or_equal_to(current, support_full),
-            DeviceType::Mini => version_newer_or_equal_to(current, support_mini),
-        }
+        capabilities::supports_submixes(&self.hardware)
     }
 
     fn device_supports_animations(&self) -> bool {
-        let support_full = VersionNumber(1, 3, Some(40), Some(0));
-        let support_mini = VersionNumber(1, 1, Some(8), Some(0));
-
-        let current = &self.hardware.versions.firmware;
-
-        match self.hardware.device_type {
-            DeviceType::Unknown => true,
-            DeviceType::Full => version_newer_or_equal_to(current, support_full),
-            DeviceType::Mini => version_newer_or_equal_to(current, support_mini),
-        }
+        capabilities::supports_animations(&self.hardware)
     }
 
     async fn is_steam_no_music(&self) -> bool {
@@ -3890,19 +5522,177 @@ impl<'a> Device<'a> {
     }
 }
 
+// Commands which write to the disk, these are skipped when running commands just before a
+// shutdown or sleep, as the disk may not be around to write to..
+fn writes_to_disk(command: &GoXLRCommand) -> bool {
+    match command {
+        // Shutdown / Sleep / Wake Commandsets
+        GoXLRCommand::SetShutdownCommands(_)
+        | GoXLRCommand::SetSleepCommands(_)
+        | GoXLRCommand::SetWakeCommands(_)
+        | GoXLRCommand::SetButtonBinding(_, _)
+        | GoXLRCommand::SetHotkey(_, _)
+        | GoXLRCommand::SetScheduledAction(_, _)
+        | GoXLRCommand::SetDucking(_)
+        | GoXLRCommand::SetInteractionLockControls(_)
+        | GoXLRCommand::SetNowPlayingFader(_)
+        // Presets
+        | GoXLRCommand::SaveActivePreset()
+        // Profile Related Commands
+        | GoXLRCommand::NewProfile(_)
+        | GoXLRCommand::LoadProfile(_, true)
+        | GoXLRCommand::SaveProfile()
+        | GoXLRCommand::SaveProfileAs(_)
+        // Mic Profile Related Commands
+        | GoXLRCommand::NewMicProfile(_)
+        | GoXLRCommand::LoadMicProfile(_, true)
+        | GoXLRCommand::SaveMicProfile()
+        | GoXLRCommand::SaveMicProfileAs(_)
+        // settings.json variables
+        | GoXLRCommand::SetDeviceLabel(_)
+        | GoXLRCommand::SetSamplerPreBufferDuration(_)
+        | GoXLRCommand::SetVCMuteAlsoMuteCM(_)
+        | GoXLRCommand::SetMonitorWithFx(_)
+        | GoXLRCommand::SetSamplerResetOnClear(_)
+        | GoXLRCommand::SetSamplerNormalisation(_)
+        | GoXLRCommand::SetLockFaders(_)
+        | GoXLRCommand::SetGlobalLightingBrightness(_)
+        | GoXLRCommand::SetLightingCrossfade(_)
+        | GoXLRCommand::SetAttachStatePolicy(_)
+        | GoXLRCommand::SetErrorFeedback(_)
+        | GoXLRCommand::SetProfileFallbacks(_)
+        | GoXLRCommand::SetVolumeLimit(_, _)
+        | GoXLRCommand::SetOutputTrim(_, _)
+        | GoXLRCommand::SetVolumePreset(_, _)
+        | GoXLRCommand::SaveVolumePreset(_)
+        => true,
+
+        GoXLRCommand::ExecuteBatch(commands) => commands.iter().any(writes_to_disk),
+        _ => false,
+    }
+}
+
+fn check_ducking(ducking: &Ducking) -> Result<(), DaemonError> {
+    if ducking.channels.contains(&ChannelName::Mic) {
+        return Err(DaemonError::InvalidState {
+            reason: String::from("The Mic can't duck itself"),
+        });
+    }
+    if !(-72..=0).contains(&ducking.threshold) {
+        return Err(DaemonError::ValueOutOfRange {
+            field: String::from("Ducking Threshold"),
+            min: -72,
+            max: 0,
+            value: ducking.threshold.into(),
+        });
+    }
+    if ducking.attenuation > 100 {
+        return Err(DaemonError::ValueOutOfRange {
+            field: String::from("Ducking Attenuation"),
+            min: 0,
+            max: 100,
+            value: ducking.attenuation.into(),
+        });
+    }
+    Ok(())
+}
+
+fn check_output_trim(channel: ChannelName, trim: u8) -> Result<(), DaemonError> {
+    if channel != ChannelName::Headphones && channel != ChannelName::LineOut {
+        return Err(DaemonError::InvalidState {
+            reason: String::from("Output Trims are only available for Headphones and Line Out"),
+        });
+    }
+    if trim > 100 {
+        return Err(DaemonError::ValueOutOfRange {
+            field: String::from("Trim"),
+            min: 0,
+            max: 100,
+            value: trim.into(),
+        });
+    }
+    Ok(())
+}
+
+fn is_tone_output(output: BasicOutputDevice) -> bool {
+    matches!(
+        output,
+        BasicOutputDevice::Headphones | BasicOutputDevice::BroadcastMix
+    )
+}
+
+fn check_output_tone(output: BasicOutputDevice, gain: i8) -> Result<(), DaemonError> {
+    if !is_tone_output(output) {
+        return Err(DaemonError::InvalidState {
+            reason: String::from(
+                "Tone controls are only available for Headphones and the Stream Mix",
+            ),
+        });
+    }
+    if !(-MAX_OUTPUT_TONE_GAIN..=MAX_OUTPUT_TONE_GAIN).contains(&gain) {
+        return Err(DaemonError::ValueOutOfRange {
+            field: String::from("Output Tone"),
+            min: (-MAX_OUTPUT_TONE_GAIN).into(),
+            max: MAX_OUTPUT_TONE_GAIN.into(),
+            value: gain.into(),
+        });
+    }
+    Ok(())
+}
+
 fn tts_bool_to_state(bool: bool) -> String {
     match bool {
-        true => "On".to_string(),
-        false => "Off".to_string(),
+        true => translate("tts.on", &[]),
+        false => translate("tts.off", &[]),
     }
 }
 
 fn tts_target(target: MuteFunction) -> String {
     match target {
         MuteFunction::All => "".to_string(),
-        MuteFunction::ToStream => " to Stream".to_string(),
-        MuteFunction::ToVoiceChat => " to Voice Chat".to_string(),
-        MuteFunction::ToPhones => " to Headphones".to_string(),
-        MuteFunction::ToLineOut => " to Line Out".to_string(),
+        MuteFunction::ToStream => translate("tts.target.stream", &[]),
+        MuteFunction::ToVoiceChat => translate("tts.target.voice_chat", &[]),
+        MuteFunction::ToPhones => translate("tts.target.headphones", &[]),
+        MuteFunction::ToLineOut => translate("tts.target.line_out", &[]),
+    }
+}
+
+fn get_timing_metrics(metrics: &RequestMetrics) -> TimingMetrics {
+    TimingMetrics {
+        count: metrics.count(),
+        errors: metrics.errors(),
+        p50: metrics.percentile(50.).as_micros() as u64,
+        p90: metrics.percentile(90.).as_micros() as u64,
+        p99: metrics.percentile(99.).as_micros() as u64,
+        max: metrics.max().as_micros() as u64,
+    }
+}
+
+// Loads a profile, falling back to (and restoring from) its backup if it's missing or corrupt..
+fn load_profile_or_backup(
+    name: &str,
+    profile_path: &Path,
+    backup_path: &Path,
+) -> Result<ProfileAdapter> {
+    match ProfileAdapter::from_named(name.to_owned(), profile_path) {
+        Ok(mut profile) => {
+            debug!("Profile Successfully Loaded, Performing Backup..");
+            profile.save(backup_path, true).unwrap_or_else(|e| {
+                warn!("Unable to Backup Profile: {}", e);
+            });
+            debug!("Main Profile Backup Complete");
+            Ok(profile)
+        }
+        Err(e) => {
+            warn!("Failed to Load Profile: {}, checking for backup..", e);
+            let mut profile = ProfileAdapter::from_named(name.to_owned(), backup_path)?;
+            info!("Successfully Loaded backup profile");
+
+            debug!("Overwriting existing corrupt / missing profile..");
+            profile.save(profile_path, true).unwrap_or_else(|e| {
+                warn!("Unable to replace existing profile: {}", e);
+            });
+            Ok(profile)
+        }
     }
 }
@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use goxlr_ipc::{DaemonEvent, DaemonEventType};
+use log::warn;
+
+use crate::rotating_log::RotatingLog;
+
+// How many events we keep in memory before dropping the oldest..
+const MAX_EVENTS: usize = 500;
+
+const MAX_EVENT_LOG_SIZE: u64 = 5 * 1024 * 1024;
+const MAX_EVENT_LOG_FILES: usize = 5;
+
+// If there's a log file, events are handed off to a writer thread so pushing one never waits on
+// the disk..
+#[derive(Debug, Clone)]
+pub struct EventLogHandle {
+    sender: Option<Sender<DaemonEvent>>,
+    log: Arc<Mutex<EventLog>>,
+}
+
+#[derive(Debug, Default)]
+struct EventLog {
+    next_id: u64,
+    events: VecDeque<DaemonEvent>,
+}
+
+impl EventLogHandle {
+    pub fn new(log_file: Option<PathBuf>) -> Self {
+        let sender = log_file.map(|path| {
+            RotatingLog::new(path, MAX_EVENT_LOG_SIZE, MAX_EVENT_LOG_FILES)
+                .spawn(|event: DaemonEvent| Ok(serde_json::to_string(&event)?))
+        });
+
+        Self {
+            sender,
+            log: Arc::new(Mutex::new(EventLog {
+                next_id: 1,
+                events: VecDeque::with_capacity(MAX_EVENTS),
+            })),
+        }
+    }
+
+    pub fn push(&self, serial: Option<&str>, event: DaemonEventType) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        let mut log = self.log.lock().unwrap();
+        let event = DaemonEvent {
+            id: log.next_id,
+            timestamp,
+            serial: serial.map(String::from),
+            event,
+        };
+        log.next_id += 1;
+
+        if let Some(sender) = &self.sender {
+            if sender.send(event.clone()).is_err() {
+                warn!("Event Log writer has stopped, unable to write event to log file");
+            }
+        }
+
+        if log.events.len() >= MAX_EVENTS {
+            log.events.pop_front();
+        }
+        log.events.push_back(event);
+    }
+
+    pub fn get_since(&self, since: u64) -> Vec<DaemonEvent> {
+        let log = self.log.lock().unwrap();
+        log.events
+            .iter()
+            .filter(|event| event.id > since)
+            .cloned()
+            .collect()
+    }
+}
@@ -1,9 +1,11 @@
 // This file primarily handles 'global' events which may occur inside the daemon from a potential
 // variety of sources, which affect other parts of the daemon.
 
+use crate::platform::perform_media_action;
 use crate::primary_worker::DeviceStateChange;
 use crate::{SettingsHandle, Shutdown};
-use goxlr_ipc::{HttpSettings, PathTypes};
+use anyhow::{bail, Result};
+use goxlr_ipc::{EventHook, HttpSettings, MediaAction, PathTypes};
 use log::{debug, warn};
 use std::process::{Command, Stdio};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -25,6 +27,10 @@ pub enum EventTriggers {
     Activate,
     OpenUi,
     DevicesStopped,
+    Execute(String),
+    Hook(EventHook, Vec<(String, String)>),
+    NowPlaying(Option<Vec<String>>),
+    Media(MediaAction),
 }
 
 #[derive(Clone)]
@@ -93,6 +99,14 @@ pub async fn spawn_event_handler(
                     EventTriggers::Wake(sender) => {
                         let _ = device_state_tx.send(DeviceStateChange::Wake(sender)).await;
                     }
+                    EventTriggers::NowPlaying(lines) => {
+                        let _ = device_state_tx.send(DeviceStateChange::NowPlaying(lines)).await;
+                    }
+                    EventTriggers::Media(action) => {
+                        if let Err(error) = perform_media_action(action).await {
+                            warn!("Error Performing Media Action {:?}: {}", action, error);
+                        }
+                    }
                     EventTriggers::Lock => {
                         debug!("Received Screen Lock Event..");
                     }
@@ -118,6 +132,20 @@ pub async fn spawn_event_handler(
                             warn!("Error Opening URL: {:?}", error);
                         }
                     },
+                    EventTriggers::Execute(command) => {
+                        if let Err(error) = execute_command(&command, vec![]) {
+                            warn!("Error Executing command: {:?}, {}", command, error);
+                        }
+                    }
+                    EventTriggers::Hook(hook, mut env) => {
+                        if let Some(command) = state.settings_handle.get_event_hook(hook).await {
+                            debug!("Running {:?} hook", hook);
+                            env.push((String::from("GOXLR_EVENT"), format!("{:?}", hook)));
+                            if let Err(error) = execute_command(&command, env) {
+                                warn!("Error Executing {:?} hook: {:?}, {}", hook, command, error);
+                            }
+                        }
+                    }
                     EventTriggers::Activate => {
                         let activate = state.settings_handle.get_activate().await;
                         let url = get_util_url(&state);
@@ -201,6 +229,32 @@ pub async fn spawn_event_handler(
     }
 }
 
+// Executes an external command in the background, without waiting for it to complete.
+fn execute_command(exec: &str, env: Vec<(String, String)>) -> Result<()> {
+    let tmp_dir = std::env::temp_dir();
+
+    #[cfg(not(unix))]
+    let params: Vec<String> = windows_args::Args::parse_cmd(exec).collect();
+
+    #[cfg(unix)]
+    let params = shell_words::split(exec)?;
+
+    if params.is_empty() {
+        bail!("No command provided");
+    }
+
+    debug!("Attempting to Execute: {:?}", params);
+    Command::new(&params[0])
+        .current_dir(tmp_dir)
+        .args(&params[1..])
+        .envs(env)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    Ok(())
+}
+
 fn get_util_url(state: &DaemonState) -> String {
     let mut host = String::from("localhost");
     if state.http_settings.bind_address != "localhost"
@@ -7,12 +7,13 @@ This has been created as a separate mod primarily because profile.rs is big enou
 secondly because it's managing different types of files
  */
 
-use std::collections::BTreeMap;
-use std::ffi::OsString;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::fs::{create_dir_all, File};
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::{Command, Stdio};
+use std::time::SystemTime;
 
 use anyhow::{anyhow, bail, Context, Result};
 // use futures::channel::mpsc::{channel, Receiver};
@@ -20,7 +21,9 @@ use anyhow::{anyhow, bail, Context, Result};
 use log::{debug, info, warn};
 
 use glob::glob;
-use goxlr_ipc::PathTypes;
+use goxlr_audio::metadata::get_sample_info;
+use goxlr_ipc::{PathTypes, SampleFile, SampleMetadata};
+use goxlr_scribbles::prepare_icon;
 use notify::event::{CreateKind, ModifyKind, RemoveKind, RenameMode};
 use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use tokio::sync::mpsc;
@@ -42,6 +45,9 @@ pub struct FilePaths {
 #[derive(Debug)]
 pub struct FileManager {
     paths: FilePaths,
+
+    // Reading sample metadata means opening every file, so only do it when they change..
+    sample_metadata: HashMap<PathBuf, (Option<SystemTime>, Option<SampleMetadata>)>,
 }
 
 impl FileManager {
@@ -49,7 +55,10 @@ impl FileManager {
         let paths = FileManager::get_file_paths_from_settings(settings).await;
         FileManager::create_paths(&paths);
 
-        Self { paths }
+        Self {
+            paths,
+            sample_metadata: HashMap::new(),
+        }
     }
 
     pub async fn get_file_paths_from_settings(settings: &SettingsHandle) -> FilePaths {
@@ -116,8 +125,7 @@ impl FileManager {
 
     pub fn get_profiles(&mut self) -> Vec<String> {
         let path = self.paths.profiles.clone();
-        let extension = ["goxlr"].to_vec();
-        self.get_files_from_path(path, extension, false)
+        self.get_recursive_names(path, "goxlr")
     }
 
     pub fn get_mic_profiles(&mut self) -> Vec<String> {
@@ -129,16 +137,61 @@ impl FileManager {
 
     pub fn get_presets(&mut self) -> Vec<String> {
         let path = self.paths.presets.clone();
-        let extension = ["preset"].to_vec();
-
-        self.get_files_from_path(path, extension, false)
+        self.get_recursive_names(path, "preset")
     }
 
-    pub fn get_samples(&mut self) -> BTreeMap<String, String> {
+    pub fn get_samples(&mut self) -> BTreeMap<String, SampleFile> {
         let base_path = self.paths.samples.clone();
         let extensions = ["wav", "mp3"].to_vec();
 
-        self.get_recursive_file_list(base_path, extensions)
+        let files = self.get_recursive_file_list(base_path.clone(), extensions);
+
+        // Drop anything from the cache which no longer exists..
+        self.sample_metadata
+            .retain(|path, _| match path.strip_prefix(&base_path) {
+                Ok(relative) => files.contains_key(&*relative.to_string_lossy()),
+                Err(_) => false,
+            });
+
+        let mut samples = BTreeMap::new();
+        for (relative, name) in files {
+            let path = base_path.join(&relative);
+            let metadata = self.get_sample_metadata(&path);
+            samples.insert(
+                relative,
+                SampleFile {
+                    name,
+                    gain_pct: 100,
+                    metadata,
+                },
+            );
+        }
+        samples
+    }
+
+    fn get_sample_metadata(&mut self, path: &Path) -> Option<SampleMetadata> {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        if let Some((cached_modified, metadata)) = self.sample_metadata.get(path) {
+            if *cached_modified == modified {
+                return *metadata;
+            }
+        }
+
+        let metadata = match get_sample_info(path) {
+            Ok(info) => Some(SampleMetadata {
+                duration_ms: info.duration_ms,
+                sample_rate: info.sample_rate,
+                channels: info.channels,
+            }),
+            Err(e) => {
+                debug!("Unable to read metadata for {:?}: {}", path, e);
+                None
+            }
+        };
+
+        self.sample_metadata
+            .insert(path.to_path_buf(), (modified, metadata));
+        metadata
     }
 
     pub fn get_icons(&mut self) -> Vec<String> {
@@ -174,6 +227,30 @@ impl FileManager {
         map
     }
 
+    // Returns the names of files (without the extension) relative to the path, using '/' to
+    // separate any sub directories, so 'Streaming/Main' would be Streaming/Main.goxlr
+    fn get_recursive_names(&self, path: PathBuf, extension: &str) -> Vec<String> {
+        let format = format!("{}/**/*.{}", path.to_string_lossy(), extension);
+        let mut result: Vec<String> = match glob(format.as_str()) {
+            Ok(files) => files
+                .filter_map(|f| f.ok())
+                .filter_map(|f| {
+                    let relative = f.strip_prefix(&path).ok()?.with_extension("");
+                    let parts: Vec<String> = relative
+                        .components()
+                        .map(|c| c.as_os_str().to_string_lossy().to_string())
+                        .collect();
+                    Some(parts.join("/"))
+                })
+                .collect(),
+            Err(_) => vec![],
+        };
+
+        result.sort_by_key(|a| a.to_lowercase());
+        result.dedup();
+        result
+    }
+
     fn get_files_from_path(
         &self,
         path: PathBuf,
@@ -250,13 +327,13 @@ pub async fn spawn_file_notification_service(
     let (mut watcher, mut rx) = watcher.unwrap();
 
     // Add the Paths to the Watcher..
-    if let Err(error) = watcher.watch(&paths.profiles, RecursiveMode::NonRecursive) {
+    if let Err(error) = watcher.watch(&paths.profiles, RecursiveMode::Recursive) {
         warn!("Unable to Monitor Profiles Path: {:?}", error);
     }
     if let Err(error) = watcher.watch(&paths.mic_profiles, RecursiveMode::NonRecursive) {
         warn!("Unable to Monitor the Microphone Profile Path {:?}", error);
     }
-    if let Err(error) = watcher.watch(&paths.presets, RecursiveMode::NonRecursive) {
+    if let Err(error) = watcher.watch(&paths.presets, RecursiveMode::Recursive) {
         warn!("Unable to Monitor the Presets Path: {:?}", error)
     }
     if let Err(error) = watcher.watch(&paths.icons, RecursiveMode::NonRecursive) {
@@ -320,6 +397,14 @@ pub async fn spawn_file_notification_service(
                                     }
                                 },
 
+                                // Triggered when a file is written in place, we only care about this
+                                // for profiles, which may be synced from somewhere else..
+                                EventKind::Modify(ModifyKind::Data(_)) |
+                                EventKind::Modify(ModifyKind::Any)
+                                    if event.paths[0].starts_with(&paths.profiles) => {
+                                    let _ = sender.send(PathTypes::Profiles).await;
+                                },
+
                                 _ => {
                                     // Do nothing, not our kind of event!
                                 }
@@ -361,6 +446,27 @@ pub fn find_file_in_path(path: PathBuf, file: PathBuf) -> Option<PathBuf> {
     None
 }
 
+/// Resolves a (potentially nested) file name inside a directory, making sure it can't escape it.
+pub fn get_named_path(directory: &Path, name: &str, extension: &str) -> Result<PathBuf> {
+    let relative = Path::new(name);
+    if name.is_empty()
+        || !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        bail!("Invalid file name: {}", name);
+    }
+    Ok(directory.join(format!("{name}.{extension}")))
+}
+
+// Profiles and Presets can be saved into sub directories which may not exist yet..
+pub fn create_parent_path(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        create_path(parent)?;
+    }
+    Ok(())
+}
+
 pub fn create_path(path: &Path) -> Result<()> {
     if !path.exists() {
         // Attempt to create the profile directory..
@@ -393,6 +499,40 @@ pub fn can_create_new_file(path: PathBuf) -> Result<()> {
     Ok(())
 }
 
+// Icons are always stored as PNGs once processed, whatever format they were uploaded in..
+pub fn save_icon(path: &Path, name: &str, data: &[u8]) -> Result<String> {
+    let stem = match Path::new(name).file_stem() {
+        Some(stem) if Path::new(name).file_name() == Some(OsStr::new(name)) => stem,
+        _ => bail!("Invalid Icon Name: {}", name),
+    };
+
+    let png = prepare_icon(data)?;
+    let file_name = format!("{}.png", stem.to_string_lossy());
+
+    create_path(path)?;
+    fs::write(path.join(&file_name), png)?;
+    Ok(file_name)
+}
+
+pub fn delete_icon(path: &Path, name: &str) -> Result<()> {
+    let file = Path::new(name);
+    if file.file_name() != Some(OsStr::new(name)) {
+        bail!("Invalid Icon Name: {}", name);
+    }
+
+    let extension = file.extension().map(|e| e.to_string_lossy().to_lowercase());
+    if !matches!(extension.as_deref(), Some("gif" | "jpg" | "png")) {
+        bail!("{} is not an Icon", name);
+    }
+
+    let file = path.join(file);
+    if !file.exists() {
+        bail!("Icon {} does not exist", name);
+    }
+    fs::remove_file(file)?;
+    Ok(())
+}
+
 const DEFAULTS_BINARY: &str = "goxlr-defaults";
 pub fn extract_defaults(file_type: PathTypes, path: &Path) -> Result<()> {
     let binary_name = if cfg!(target_os = "windows") {
@@ -0,0 +1,205 @@
+use crate::primary_worker::{DeviceCommand, DeviceSender};
+use crate::settings::SettingsHandle;
+use crate::shutdown::Shutdown;
+use anyhow::Result;
+use goxlr_ipc::{CommandSource, GoXLRCommand};
+use log::{debug, info, warn};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+use tokio::time;
+
+#[cfg(feature = "hotkeys")]
+use global_hotkey::hotkey::HotKey;
+#[cfg(feature = "hotkeys")]
+use global_hotkey::{GlobalHotKeyEvent, GlobalHotKeyManager, HotKeyState};
+#[cfg(feature = "hotkeys")]
+use std::str::FromStr;
+
+// Key Combination -> [(Serial, Commands)]
+pub type Bindings = HashMap<String, Vec<(String, Vec<GoXLRCommand>)>>;
+
+/// Whether global hotkeys can work on this platform / build.
+pub fn hotkeys_supported() -> bool {
+    if !cfg!(feature = "hotkeys") {
+        return false;
+    }
+
+    if cfg!(target_os = "windows") {
+        return true;
+    }
+
+    // Under Linux, we can only grab keys via X11, Wayland doesn't permit global grabs..
+    if cfg!(target_os = "linux") {
+        return std::env::var("DISPLAY").is_ok() && std::env::var("WAYLAND_DISPLAY").is_err();
+    }
+
+    // MacOS requires the hotkey manager to live on the main thread, which the tray owns..
+    false
+}
+
+/// Checks that a key combination (eg. 'ctrl+shift+KeyM') can be understood.
+pub fn validate_hotkey(hotkey: &str) -> Result<()> {
+    #[cfg(feature = "hotkeys")]
+    if let Err(e) = HotKey::from_str(hotkey) {
+        anyhow::bail!("Invalid Hotkey {}: {}", hotkey, e);
+    }
+
+    #[cfg(not(feature = "hotkeys"))]
+    let _ = hotkey;
+
+    Ok(())
+}
+
+pub async fn spawn_hotkey_service(
+    settings: SettingsHandle,
+    usb_tx: DeviceSender,
+    mut shutdown: Shutdown,
+) {
+    if !hotkeys_supported() {
+        info!("Global Hotkeys are not supported on this platform, not starting.");
+        return;
+    }
+
+    info!("Starting Hotkey Service..");
+    let (bindings_tx, bindings_rx) = std::sync::mpsc::channel();
+    let (event_tx, mut event_rx) = mpsc::channel(32);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let thread_stop = stop.clone();
+    std::thread::spawn(move || run_hotkey_thread(bindings_rx, event_tx, thread_stop));
+
+    let mut bindings: Bindings = Default::default();
+    let mut keys: Vec<String> = vec![];
+    let mut ticker = time::interval(Duration::from_secs(1));
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                // Pick up any changes to the bindings..
+                bindings = settings.get_hotkey_bindings().await;
+
+                let mut current: Vec<String> = bindings.keys().cloned().collect();
+                current.sort();
+                if current != keys {
+                    if bindings_tx.send(current.clone()).is_err() {
+                        warn!("Hotkey thread has stopped, Hotkeys will no longer work");
+                        return;
+                    }
+                    keys = current;
+                }
+            },
+            Some(hotkey) = event_rx.recv() => {
+                debug!("Hotkey Pressed: {}", hotkey);
+                if let Some(targets) = bindings.get(&hotkey) {
+                    for (serial, commands) in targets {
+                        run_commands(&usb_tx, serial, commands).await;
+                    }
+                }
+            },
+            () = shutdown.recv() => {
+                info!("Shutting down Hotkey Service");
+                stop.store(true, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+}
+
+async fn run_commands(usb_tx: &DeviceSender, serial: &str, commands: &[GoXLRCommand]) {
+    for command in commands {
+        let (tx, rx) = oneshot::channel();
+        let message = DeviceCommand::RunDeviceCommand(
+            serial.to_string(),
+            command.clone(),
+            CommandSource::Hotkey,
+            tx,
+        );
+        if usb_tx.send(message).await.is_err() {
+            return;
+        }
+        if let Ok(Err(e)) = rx.await {
+            warn!("Error running Hotkey command {:?}: {}", command, e);
+        }
+    }
+}
+
+#[cfg(feature = "hotkeys")]
+fn run_hotkey_thread(
+    bindings_rx: std::sync::mpsc::Receiver<Vec<String>>,
+    event_tx: mpsc::Sender<String>,
+    stop: Arc<AtomicBool>,
+) {
+    let manager = match GlobalHotKeyManager::new() {
+        Ok(manager) => manager,
+        Err(e) => {
+            warn!("Unable to create Hotkey Manager: {}", e);
+            return;
+        }
+    };
+
+    let mut registered: HashMap<u32, (HotKey, String)> = HashMap::new();
+    while !stop.load(Ordering::Relaxed) {
+        if let Ok(keys) = bindings_rx.try_recv() {
+            for (hotkey, _) in registered.values() {
+                let _ = manager.unregister(*hotkey);
+            }
+            registered.clear();
+
+            for key in keys {
+                match HotKey::from_str(&key) {
+                    Ok(hotkey) => {
+                        if let Err(e) = manager.register(hotkey) {
+                            warn!("Unable to register Hotkey {}: {}", key, e);
+                            continue;
+                        }
+                        registered.insert(hotkey.id(), (hotkey, key));
+                    }
+                    Err(e) => warn!("Unable to parse Hotkey {}: {}", key, e),
+                }
+            }
+        }
+
+        #[cfg(target_os = "windows")]
+        pump_messages();
+
+        while let Ok(event) = GlobalHotKeyEvent::receiver().try_recv() {
+            if event.state != HotKeyState::Pressed {
+                continue;
+            }
+            if let Some((_, key)) = registered.get(&event.id) {
+                let _ = event_tx.blocking_send(key.clone());
+            }
+        }
+
+        std::thread::sleep(Duration::from_millis(20));
+    }
+}
+
+#[cfg(not(feature = "hotkeys"))]
+fn run_hotkey_thread(
+    _: std::sync::mpsc::Receiver<Vec<String>>,
+    _: mpsc::Sender<String>,
+    _: Arc<AtomicBool>,
+) {
+    warn!("Hotkey Feature is not enabled in build, Hotkeys will not work.");
+}
+
+// Under Windows, the hotkey manager delivers via window messages, so this thread needs to pump them..
+#[cfg(all(feature = "hotkeys", target_os = "windows"))]
+fn pump_messages() {
+    use windows::Win32::Foundation::HWND;
+    use windows::Win32::UI::WindowsAndMessaging::{
+        DispatchMessageW, PeekMessageW, TranslateMessage, MSG, PM_REMOVE,
+    };
+
+    let mut msg = MSG::default();
+    unsafe {
+        while PeekMessageW(&mut msg, HWND::default(), 0, 0, PM_REMOVE).as_bool() {
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+    }
+}
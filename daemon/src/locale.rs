@@ -0,0 +1,111 @@
+/*
+   Translations for the messages the daemon produces itself (TTS, and errors sent to clients).
+
+   Each catalogue maps a message id to a template, with {name} placeholders for any values. The
+   locale is picked from the user's setting (or the system locale if they've not set one), if
+   a message isn't in that locale's catalogue, we fall back to the language without a region
+   (so en_GB will use en), then to English, and finally to the id itself.
+*/
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use goxlr_ipc::{DaemonError, LocalisedError, LocalisedMessage};
+use lazy_static::lazy_static;
+use log::{debug, warn};
+
+const CATALOGUES: [(&str, &str); 3] = [
+    ("en", include_str!("../locales/en.json")),
+    ("de", include_str!("../locales/de.json")),
+    ("fr", include_str!("../locales/fr.json")),
+];
+
+const FALLBACK_LOCALE: &str = "en";
+
+lazy_static! {
+    static ref MESSAGES: HashMap<&'static str, HashMap<String, String>> = load_catalogues();
+    static ref ACTIVE_LOCALE: RwLock<String> = RwLock::new(String::from(FALLBACK_LOCALE));
+}
+
+fn load_catalogues() -> HashMap<&'static str, HashMap<String, String>> {
+    let mut catalogues = HashMap::new();
+    for (locale, json) in CATALOGUES {
+        match serde_json::from_str(json) {
+            Ok(messages) => {
+                catalogues.insert(locale, messages);
+            }
+            Err(e) => warn!("Unable to load the {} message catalogue: {}", locale, e),
+        }
+    }
+    catalogues
+}
+
+/// Sets the locale messages are translated into, accepts both 'en_GB' and 'en-GB' forms
+pub fn set_locale(locale: &str) {
+    let locale = locale.replace('-', "_").to_lowercase();
+    debug!("Setting Message Locale to {}", locale);
+    *ACTIVE_LOCALE.write().unwrap() = locale;
+}
+
+/// Translates a message into the active locale, replacing any {name} placeholders
+pub fn translate(id: &str, args: &[(&str, &str)]) -> String {
+    let locale = ACTIVE_LOCALE.read().unwrap().clone();
+    let language = locale.split('_').next().unwrap_or_default();
+
+    let template = [locale.as_str(), language, FALLBACK_LOCALE]
+        .iter()
+        .find_map(|locale| MESSAGES.get(locale).and_then(|m| m.get(id)))
+        .map(String::as_str)
+        .unwrap_or(id);
+
+    let mut message = template.to_string();
+    for (name, value) in args {
+        message = message.replace(&format!("{{{}}}", name), value);
+    }
+    message
+}
+
+pub fn localise_error(error: DaemonError) -> LocalisedError {
+    let id = error.message_id();
+    let text = match &error {
+        DaemonError::DeviceNotFound { serial } => translate(id, &[("serial", serial)]),
+        DaemonError::ProfileParse { name, message } => {
+            translate(id, &[("name", name), ("message", message)])
+        }
+        DaemonError::ValueOutOfRange {
+            field,
+            min,
+            max,
+            value,
+        } => translate(
+            id,
+            &[
+                ("field", field),
+                ("min", &min.to_string()),
+                ("max", &max.to_string()),
+                ("value", &value.to_string()),
+            ],
+        ),
+        DaemonError::UnsupportedOnMini { feature }
+        | DaemonError::UnsupportedFirmware { feature } => translate(id, &[("feature", feature)]),
+        DaemonError::UsbFailure { message } => translate(id, &[("message", message)]),
+        DaemonError::ProtocolMismatch { daemon, client } => translate(
+            id,
+            &[
+                ("daemon", &daemon.to_string()),
+                ("client", &client.to_string()),
+            ],
+        ),
+        DaemonError::InvalidState { reason } => translate(id, &[("reason", reason)]),
+        DaemonError::RateLimited { limit } => translate(id, &[("limit", &limit.to_string())]),
+    };
+
+    LocalisedError {
+        code: error.code(),
+        error,
+        message: LocalisedMessage {
+            id: id.to_string(),
+            text,
+        },
+    }
+}
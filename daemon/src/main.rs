@@ -11,7 +11,7 @@ use anyhow::{bail, Context, Result};
 use clap::Parser;
 use file_rotate::compression::Compression;
 use file_rotate::suffix::AppendCount;
-use file_rotate::{ContentLimit, FileRotate};
+use file_rotate::{ContentLimit, FileRotate, TimeFrequency};
 use json_patch::Patch;
 use lazy_static::lazy_static;
 use log::{debug, error, info, warn};
@@ -23,38 +23,55 @@ use sys_locale::get_locale;
 use tokio::join;
 use tokio::sync::{broadcast, mpsc};
 
-use goxlr_ipc::{HttpSettings, LogLevel};
+use goxlr_ipc::{HttpSettings, LogLevel, LogRotation};
 
 use crate::cli::{Cli, LevelFilter};
+use crate::event_log::EventLogHandle;
 use crate::events::{spawn_event_handler, DaemonState, EventTriggers};
 use crate::files::{spawn_file_notification_service, FileManager};
+use crate::hotkeys::spawn_hotkey_service;
+use crate::platform::install_udev_rules;
 use crate::platform::perform_preflight;
 use crate::platform::spawn_runtime;
 use crate::primary_worker::spawn_usb_handler;
+use crate::scheduler::spawn_scheduler;
 use crate::servers::http_server::spawn_http_server;
-use crate::servers::ipc_server::{bind_socket, spawn_ipc_server};
+use crate::servers::ipc_server::{bind_socket, spawn_ipc_server, DaemonAlreadyRunning};
 use crate::settings::SettingsHandle;
 use crate::shutdown::Shutdown;
 use crate::tts::spawn_tts_service;
 
 mod audio;
+mod audit_log;
+mod capabilities;
 mod cli;
+mod command_queue;
 mod device;
+mod event_log;
 mod events;
 mod files;
+mod hotkeys;
+mod locale;
 mod mic_profile;
 mod platform;
+mod preview;
 mod primary_worker;
 mod profile;
+mod rotating_log;
+mod scheduler;
 mod servers;
 mod settings;
 mod shutdown;
 mod tray;
 mod tts;
+mod undo;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 const ICON: &[u8] = include_bytes!("../resources/goxlr-utility-large.png");
 
+// Exit code used when another instance of the daemon is already running
+const EXIT_ALREADY_RUNNING: i32 = 2;
+
 /**
 This is ugly, and I know it's ugly. I need to rework how the Primary Worker is constructed
 so that various variables can be easily passed through it down to the device level via a struct
@@ -90,6 +107,14 @@ async fn main() -> Result<()> {
     // If running the utility has an error, make sure log level is debug, and propagate the
     // error up to the user on Windows.
     if let Err(e) = run_utility().await {
+        // Another daemon already owns the device, this isn't a failure of this instance so
+        // there's no need to change the log level, just let the user know and exit.
+        if e.downcast_ref::<DaemonAlreadyRunning>().is_some() {
+            error!("{}", e);
+            platform::display_error(e.to_string());
+            std::process::exit(EXIT_ALREADY_RUNNING);
+        }
+
         let args: Cli = Cli::parse();
         let settings = SettingsHandle::load(args.config).await?;
 
@@ -128,6 +153,13 @@ async fn run_utility() -> Result<()> {
     }
     let log_file = log_path.join("goxlr-daemon.log");
 
+    // Create the Event Log, optionally persisting it alongside the main log..
+    let event_log = if args.log_events {
+        EventLogHandle::new(Some(log_path.join("goxlr-events.log")))
+    } else {
+        EventLogHandle::new(None)
+    };
+
     // We need to ignore a couple of packages log output so create a builder.
     let mut config = ConfigBuilder::new();
 
@@ -147,11 +179,19 @@ async fn run_utility() -> Result<()> {
 
     let timezone_calculated = config.set_time_offset_to_local().is_ok();
 
-    // Create a file rotator, that will compress and rotate files after 5Mb
+    // Create a file rotator, that will compress and rotate files based on the user's settings
+    let content_limit = match settings.get_log_rotation().await {
+        LogRotation::Size(megabytes) => {
+            ContentLimit::Bytes(1024 * 1024 * megabytes.max(1) as usize)
+        }
+        LogRotation::Hourly => ContentLimit::Time(TimeFrequency::Hourly),
+        LogRotation::Daily => ContentLimit::Time(TimeFrequency::Daily),
+        LogRotation::Weekly => ContentLimit::Time(TimeFrequency::Weekly),
+    };
     let file_rotator = FileRotate::new(
         log_file,
-        AppendCount::new(5),
-        ContentLimit::Bytes(1024 * 1024 * 2),
+        AppendCount::new(settings.get_log_retention().await.max(1) as usize),
+        content_limit,
         Compression::OnRotate(1),
         #[cfg(unix)]
         None,
@@ -168,27 +208,23 @@ async fn run_utility() -> Result<()> {
             LevelFilter::Trace => log::LevelFilter::Trace,
         }
     } else {
-        match settings.get_log_level().await {
-            LogLevel::Off => log::LevelFilter::Off,
-            LogLevel::Error => log::LevelFilter::Error,
-            LogLevel::Warn => log::LevelFilter::Warn,
-            LogLevel::Info => log::LevelFilter::Info,
-            LogLevel::Debug => log::LevelFilter::Debug,
-            LogLevel::Trace => log::LevelFilter::Trace,
-        }
+        get_level_filter(&settings.get_log_level().await)
     };
 
     // Create the loggers :)
+    // The loggers themselves accept everything, the level is instead enforced through the log
+    // crate's max level, so it can be changed at runtime without rebuilding them.
     CombinedLogger::init(vec![
         TermLogger::new(
-            log_level,
+            log::LevelFilter::Trace,
             config.build(),
             TerminalMode::Mixed,
             ColorChoice::Auto,
         ),
-        WriteLogger::new(log_level, config.build(), file_rotator),
+        WriteLogger::new(log::LevelFilter::Trace, config.build(), file_rotator),
     ])
     .context("Could not configure the logger")?;
+    log::set_max_level(log_level);
 
     // Enable the PANIC logger..
     log_panics::init();
@@ -197,6 +233,11 @@ async fn run_utility() -> Result<()> {
         warn!("Unable to calculate timezone, using UTC for log timestamps");
     }
 
+    // This needs root, so has to happen before we complain about running as it..
+    if args.install_udev_rules {
+        return install_udev_rules();
+    }
+
     if cfg!(target_os = "macos") {
         debug!(
             "Configure MacOS Aggregates: {:?}",
@@ -237,11 +278,19 @@ async fn run_utility() -> Result<()> {
     info!("Starting GoXLR Daemon v{}", VERSION);
     info!("System Locale: {}", *SYSTEM_LOCALE);
 
+    let user_locale = settings.get_selected_locale().await;
+    locale::set_locale(user_locale.as_ref().unwrap_or(&SYSTEM_LOCALE));
+
     // Before we do anything, perform platform pre-flight to make
     // sure we're allowed to start.
     info!("Performing Platform Preflight...");
     perform_preflight()?;
 
+    if !platform::has_udev_rules() {
+        warn!("No udev rules for the GoXLR were found, it may not be accessible without them.");
+        warn!("If your GoXLR isn't detected, run 'goxlr-daemon --install-udev-rules'");
+    }
+
     let bind_address = if let Some(address) = args.http_bind_address {
         debug!("Command Line Override, binding to: {}", address);
         address
@@ -252,6 +301,9 @@ async fn run_utility() -> Result<()> {
     };
 
     debug!("HTTP Bind Address: {}", bind_address);
+    if bind_address != "localhost" {
+        info!("Remote HTTP clients will need an API token, see 'goxlr-client api-token create'");
+    }
     let http_settings = HttpSettings {
         enabled: !args.http_disable,
         bind_address,
@@ -300,10 +352,10 @@ async fn run_utility() -> Result<()> {
     ));
 
     // Spawn the IPC Socket..
-    let ipc_socket = bind_socket().await;
+    let ipc_socket = bind_socket(args.takeover).await;
     if let Err(e) = ipc_socket {
         error!("Error Binding IPC Socket: {}", e);
-        bail!("{}", e);
+        return Err(e);
     }
 
     // Start the USB Device Handler
@@ -317,6 +369,7 @@ async fn run_utility() -> Result<()> {
         settings.clone(),
         http_settings.clone(),
         file_manager,
+        event_log,
     ));
 
     // Launch the IPC Server..
@@ -324,6 +377,7 @@ async fn run_utility() -> Result<()> {
     let communications_handle = tokio::spawn(spawn_ipc_server(
         ipc_socket,
         usb_tx.clone(),
+        broadcast_tx.clone(),
         shutdown.clone(),
     ));
 
@@ -341,6 +395,7 @@ async fn run_utility() -> Result<()> {
             broadcast_tx.clone(),
             http_settings.clone(),
             file_paths.clone(),
+            settings.clone(),
         ));
         http_server = httpd_rx.await?;
         if let Err(e) = http_server {
@@ -357,6 +412,20 @@ async fn run_utility() -> Result<()> {
         shutdown.clone(),
     ));
 
+    // Start the Hotkey Service..
+    let hotkey_handle = tokio::spawn(spawn_hotkey_service(
+        settings.clone(),
+        usb_tx.clone(),
+        shutdown.clone(),
+    ));
+
+    // Start the Scheduler..
+    let scheduler_handle = tokio::spawn(spawn_scheduler(
+        settings.clone(),
+        usb_tx.clone(),
+        shutdown.clone(),
+    ));
+
     let mut local_shutdown = shutdown.clone();
     let state = DaemonState {
         tts_sender,
@@ -399,6 +468,8 @@ async fn run_utility() -> Result<()> {
             server.stop(false),
             file_handle,
             tts_handle,
+            hotkey_handle,
+            scheduler_handle,
             event_handle,
             platform_handle
         );
@@ -408,6 +479,8 @@ async fn run_utility() -> Result<()> {
             communications_handle,
             file_handle,
             tts_handle,
+            hotkey_handle,
+            scheduler_handle,
             event_handle,
             platform_handle
         );
@@ -415,6 +488,17 @@ async fn run_utility() -> Result<()> {
     Ok(())
 }
 
+pub fn get_level_filter(level: &LogLevel) -> log::LevelFilter {
+    match level {
+        LogLevel::Off => log::LevelFilter::Off,
+        LogLevel::Error => log::LevelFilter::Error,
+        LogLevel::Warn => log::LevelFilter::Warn,
+        LogLevel::Info => log::LevelFilter::Info,
+        LogLevel::Debug => log::LevelFilter::Debug,
+        LogLevel::Trace => log::LevelFilter::Trace,
+    }
+}
+
 #[cfg(target_family = "unix")]
 fn is_root() -> bool {
     nix::unistd::Uid::effective().is_root()
@@ -7,8 +7,9 @@ use goxlr_ipc::{Compressor, Equaliser, EqualiserMini, NoiseGate};
 use goxlr_profile_loader::components::mute::MuteFunction;
 use goxlr_profile_loader::mic_profile::MicProfileSettings;
 use goxlr_types::{
-    CompressorAttackTime, CompressorRatio, CompressorReleaseTime, DisplayMode, EffectKey,
-    EqFrequencies, GateTimes, MicrophoneParamKey, MicrophoneType, MiniEqFrequencies,
+    CompressorAttackTime, CompressorRatio, CompressorReleaseTime, DisplayMode, EchoAmount,
+    EchoDelayMs, EffectKey, EqFrequencies, GateTimes, MicrophoneParamKey, MicrophoneType,
+    MiniEqFrequencies, PitchSemitones, ReverbAmount,
 };
 use log::warn;
 use ritelinked::LinkedHashSet;
@@ -786,8 +787,12 @@ impl MicProfileAdapter {
     }
 
     /// This is going to require a CRAPLOAD of work to sort..
-    pub fn get_effect_value(&self, effect: EffectKey, main_profile: &ProfileAdapter) -> i32 {
-        match effect {
+    pub fn get_effect_value(
+        &self,
+        effect: EffectKey,
+        main_profile: &ProfileAdapter,
+    ) -> Result<i32> {
+        let value = match effect {
             EffectKey::MicInputMute => self.get_mic_mute(main_profile),
             EffectKey::BleepLevel => self.profile.bleep_level().into(),
             EffectKey::GateMode => self.profile.gate_mode().into(),
@@ -830,7 +835,9 @@ impl MicProfileAdapter {
 
             EffectKey::DeEsser => self.profile.deess() as i32,
 
-            EffectKey::ReverbAmount => main_profile.get_active_reverb_profile().amount().into(),
+            EffectKey::ReverbAmount => {
+                ReverbAmount::from_knob_position(main_profile.get_reverb_value())?.into()
+            }
             EffectKey::ReverbDecay => main_profile.get_active_reverb_profile().decay().into(),
             EffectKey::ReverbEarlyLevel => main_profile
                 .get_active_reverb_profile()
@@ -859,14 +866,20 @@ impl MicProfileAdapter {
                 .reverb_type()
                 .into(),
 
-            EffectKey::EchoAmount => main_profile.get_active_echo_profile().amount().into(),
+            EffectKey::EchoAmount => {
+                EchoAmount::from_knob_position(main_profile.get_echo_value())?.into()
+            }
             EffectKey::EchoFeedback => main_profile
                 .get_active_echo_profile()
                 .feedback_control()
                 .into(),
             EffectKey::EchoTempo => main_profile.get_active_echo_profile().tempo().into(),
-            EffectKey::EchoDelayL => main_profile.get_active_echo_profile().time_left().into(),
-            EffectKey::EchoDelayR => main_profile.get_active_echo_profile().time_right().into(),
+            EffectKey::EchoDelayL => {
+                EchoDelayMs::new(main_profile.get_active_echo_profile().time_left())?.into()
+            }
+            EffectKey::EchoDelayR => {
+                EchoDelayMs::new(main_profile.get_active_echo_profile().time_right())?.into()
+            }
             EffectKey::EchoFeedbackL => main_profile
                 .get_active_echo_profile()
                 .feedback_left()
@@ -884,10 +897,10 @@ impl MicProfileAdapter {
                 main_profile.get_active_echo_profile().filter_style().into()
             }
 
-            EffectKey::PitchAmount => main_profile
-                .get_active_pitch_profile()
-                .get_pitch_value()
-                .into(),
+            EffectKey::PitchAmount => {
+                PitchSemitones::new(main_profile.get_active_pitch_profile().get_pitch_value())?
+                    .into()
+            }
             EffectKey::PitchThreshold => main_profile.get_active_pitch_profile().threshold().into(),
             EffectKey::PitchCharacter => main_profile
                 .get_active_pitch_profile()
@@ -1026,7 +1039,8 @@ impl MicProfileAdapter {
             EffectKey::Encoder2Enabled => main_profile.is_fx_enabled().into(),
             EffectKey::Encoder3Enabled => main_profile.is_fx_enabled().into(),
             EffectKey::Encoder4Enabled => main_profile.is_fx_enabled().into(),
-        }
+        };
+        Ok(value)
     }
 
     fn u8_to_f32(&self, value: u8) -> [u8; 4] {
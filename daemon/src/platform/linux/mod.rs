@@ -1,5 +1,8 @@
 pub mod autostart;
+pub mod mpris;
+pub mod pipewire;
 pub mod sleep;
+pub mod udev;
 
 pub fn display_error(message: String) {
     use std::process::Command;
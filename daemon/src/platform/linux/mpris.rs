@@ -0,0 +1,174 @@
+/* MPRIS is how media players under Linux let other apps know what they're playing, and control
+   them, over the session dbus. Each player registers its own 'org.mpris.MediaPlayer2.<name>' bus
+   name, and as they come and go at any time, rather than tracking signals from all of them we
+   simply check once a second for whichever player is active.
+
+   What's found is sent across as 'NowPlaying', so it can be shown on a scribble. If no device has
+   a 'Now Playing' fader, there's nobody to show it to, so the players are left alone.
+
+   Refs:
+   https://specifications.freedesktop.org/mpris-spec/latest/
+*/
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use goxlr_ipc::MediaAction;
+use log::debug;
+use tokio::select;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+use zbus::fdo::DBusProxy;
+use zbus::proxy::CacheProperties;
+use zbus::zvariant::{OwnedValue, Value};
+use zbus::{proxy, Connection};
+
+use crate::events::EventTriggers;
+use crate::settings::SettingsHandle;
+use crate::shutdown::Shutdown;
+
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[proxy(
+    interface = "org.mpris.MediaPlayer2.Player",
+    default_path = "/org/mpris/MediaPlayer2"
+)]
+trait Player {
+    fn play_pause(&self) -> zbus::Result<()>;
+    fn next(&self) -> zbus::Result<()>;
+    fn previous(&self) -> zbus::Result<()>;
+
+    /// One of 'Playing', 'Paused' or 'Stopped'
+    #[zbus(property)]
+    fn playback_status(&self) -> zbus::Result<String>;
+
+    #[zbus(property)]
+    fn metadata(&self) -> zbus::Result<HashMap<String, OwnedValue>>;
+}
+
+pub async fn run(
+    tx: mpsc::Sender<EventTriggers>,
+    settings: SettingsHandle,
+    mut stop: Shutdown,
+) -> Result<()> {
+    debug!("Spawning MPRIS Handler..");
+
+    // If there's no session bus (for example, we're running as a service), there's nothing to do..
+    let conn = Connection::session().await?;
+    let mut now_playing = None;
+
+    loop {
+        select! {
+            () = sleep(POLL_INTERVAL) => {
+                if !settings.has_now_playing_fader().await {
+                    // Forget what we last saw, so it's sent again if a fader is assigned..
+                    now_playing = None;
+                    continue;
+                }
+
+                let lines = match get_now_playing(&conn).await {
+                    Ok(lines) => lines,
+                    Err(error) => {
+                        debug!("Unable to fetch MPRIS Metadata: {}", error);
+                        None
+                    }
+                };
+
+                if lines != now_playing {
+                    now_playing.clone_from(&lines);
+                    let _ = tx.send(EventTriggers::NowPlaying(lines)).await;
+                }
+            }
+            () = stop.recv() => {
+                break;
+            }
+        }
+    }
+
+    debug!("End of MPRIS Run");
+    Ok(())
+}
+
+pub async fn perform(action: MediaAction) -> Result<()> {
+    let conn = Connection::session().await?;
+    let player = match get_active_player(&conn).await? {
+        Some((player, _)) => player,
+        None => bail!("No Media Players Found"),
+    };
+
+    match action {
+        MediaAction::PlayPause => player.play_pause().await?,
+        MediaAction::Next => player.next().await?,
+        MediaAction::Previous => player.previous().await?,
+    }
+    Ok(())
+}
+
+async fn get_now_playing(conn: &Connection) -> Result<Option<Vec<String>>> {
+    let player = match get_active_player(conn).await? {
+        Some((player, status)) if status != "Stopped" => player,
+        _ => return Ok(None),
+    };
+
+    let metadata = player.metadata().await?;
+    let mut lines = vec![];
+    if let Some(title) = metadata.get("xesam:title") {
+        lines.push(get_strings(title).join(", "));
+    }
+    if let Some(artist) = metadata.get("xesam:artist") {
+        lines.push(get_strings(artist).join(", "));
+    }
+    lines.retain(|line| !line.is_empty());
+
+    if lines.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(lines))
+}
+
+// Finds the player which is playing, falling back to one that's paused, then to anything..
+async fn get_active_player(conn: &Connection) -> Result<Option<(PlayerProxy<'static>, String)>> {
+    let dbus = DBusProxy::new(conn).await?;
+
+    let mut found: Option<(PlayerProxy<'static>, String)> = None;
+    for name in dbus.list_names().await? {
+        if !name.as_str().starts_with(MPRIS_PREFIX) {
+            continue;
+        }
+
+        let player = PlayerProxy::builder(conn)
+            .destination(name.to_string())?
+            .cache_properties(CacheProperties::No)
+            .build()
+            .await?;
+
+        let Ok(status) = player.playback_status().await else {
+            continue;
+        };
+
+        if status == "Playing" {
+            return Ok(Some((player, status)));
+        }
+
+        let replace = match &found {
+            Some((_, found_status)) => found_status != "Paused" && status == "Paused",
+            None => true,
+        };
+        if replace {
+            found = Some((player, status));
+        }
+    }
+    Ok(found)
+}
+
+// The spec has the artist as a list of strings, but not every player follows it..
+fn get_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::Str(string) => vec![string.to_string()],
+        Value::Array(array) => array.iter().flat_map(get_strings).collect(),
+        Value::Value(inner) => get_strings(inner),
+        _ => vec![],
+    }
+}
@@ -0,0 +1,186 @@
+/*
+   Modern versions of alsa-ucm-conf split the GoXLR's multichannel interface into individual
+   named channels (System, Game, Chat, etc), which PipeWire then exposes as separate nodes.
+
+   This locates those nodes so their IDs can be handed to clients, and warns if the device
+   is present but the channels haven't been split (normally an outdated alsa-ucm-conf).
+
+   It can also move application streams onto those nodes (Discord to Chat, Spotify to Music),
+   each new stream is moved once, so if the user moves it somewhere else afterwards it stays.
+*/
+
+use anyhow::{bail, Result};
+use goxlr_ipc::{AudioChannel, AudioNode};
+use log::{debug, warn};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use strum::IntoEnumIterator;
+use which::which;
+
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+// The streams which have already been moved to a GoXLR channel..
+static ROUTED: Mutex<BTreeSet<u64>> = Mutex::new(BTreeSet::new());
+
+pub fn get_goxlr_nodes() -> Result<Vec<AudioNode>> {
+    if which("pw-dump").is_err() {
+        // PipeWire isn't available, nothing to do here..
+        return Ok(vec![]);
+    }
+
+    Ok(find_goxlr_nodes(&pw_dump()?))
+}
+
+pub fn route_applications(routes: &HashMap<String, Vec<AudioChannel>>) -> Result<()> {
+    if which("pw-dump").is_err() || which("pw-metadata").is_err() {
+        return Ok(());
+    }
+
+    let objects = pw_dump()?;
+    let nodes = find_goxlr_nodes(&objects);
+
+    let mut routed = ROUTED.lock().unwrap();
+    let mut streams = BTreeSet::new();
+    for object in &objects {
+        if object["type"] != "PipeWire:Interface:Node" {
+            continue;
+        }
+
+        let props = &object["info"]["props"];
+        let capture = match props["media.class"].as_str() {
+            Some("Stream/Output/Audio") => false,
+            Some("Stream/Input/Audio") => true,
+            _ => continue,
+        };
+        let Some(id) = object["id"].as_u64() else {
+            continue;
+        };
+
+        streams.insert(id);
+        if routed.contains(&id) {
+            continue;
+        }
+
+        // Playback streams go to a playback channel, and recording streams to a capture one..
+        let Some(channel) = get_route(routes, props)
+            .and_then(|channels| channels.iter().find(|c| c.is_capture() == capture))
+        else {
+            continue;
+        };
+
+        // If the channel hasn't shown up yet, we'll try again next time round..
+        let Some(node) = nodes.iter().find(|node| node.channel == *channel) else {
+            continue;
+        };
+
+        debug!("Moving stream {} to GoXLR {:?}", id, channel);
+        let output = Command::new("pw-metadata")
+            .arg(id.to_string())
+            .arg("target.object")
+            .arg(&node.node_name)
+            .output()?;
+
+        if output.status.success() {
+            routed.insert(id);
+        } else {
+            warn!(
+                "Unable to move stream {}, pw-metadata exited with {}",
+                id, output.status
+            );
+        }
+    }
+
+    // Forget about any streams which have gone, PipeWire can reuse their IDs..
+    routed.retain(|id| streams.contains(id));
+    Ok(())
+}
+
+fn pw_dump() -> Result<Vec<Value>> {
+    let output = Command::new("pw-dump").output()?;
+    if !output.status.success() {
+        bail!("pw-dump exited with {}", output.status);
+    }
+
+    match serde_json::from_slice(&output.stdout)? {
+        Value::Array(objects) => Ok(objects),
+        _ => bail!("Unexpected output from pw-dump"),
+    }
+}
+
+fn find_goxlr_nodes(objects: &[Value]) -> Vec<AudioNode> {
+    let mut found_device = false;
+    let mut nodes = vec![];
+    for object in objects {
+        if object["type"] != "PipeWire:Interface:Node" {
+            continue;
+        }
+
+        let props = &object["info"]["props"];
+        let node_name = props["node.name"].as_str().unwrap_or_default();
+        if !node_name.to_lowercase().contains("goxlr") {
+            continue;
+        }
+        found_device = true;
+
+        let description = props["node.description"].as_str().unwrap_or_default();
+        let capture = props["media.class"] == "Audio/Source";
+        let Some(id) = object["id"].as_u64() else {
+            continue;
+        };
+
+        if let Some(channel) = get_channel(description, capture) {
+            debug!("Found GoXLR {:?} channel as node {}", channel, id);
+            nodes.push(AudioNode {
+                channel,
+                node_id: id as u32,
+                node_name: node_name.to_string(),
+                description: description.to_string(),
+            });
+        }
+    }
+
+    // This is checked periodically, so only complain about it once..
+    if found_device && nodes.is_empty() && !WARNED.swap(true, Ordering::Relaxed) {
+        warn!(
+            "GoXLR found in PipeWire, but channels are not split, alsa-ucm-conf may need updating"
+        );
+    }
+
+    nodes
+}
+
+fn get_route<'a>(
+    routes: &'a HashMap<String, Vec<AudioChannel>>,
+    props: &Value,
+) -> Option<&'a Vec<AudioChannel>> {
+    // Applications are matched by either their name, or the name of their binary..
+    let names = [
+        props["application.name"].as_str(),
+        props["application.process.binary"].as_str(),
+    ];
+
+    routes
+        .iter()
+        .find(|(application, _)| {
+            names
+                .iter()
+                .flatten()
+                .any(|name| name.eq_ignore_ascii_case(application))
+        })
+        .map(|(_, channels)| channels)
+}
+
+fn get_channel(description: &str, capture: bool) -> Option<AudioChannel> {
+    // Go through the channels longest name first, so 'Chat Mic' isn't matched as 'Chat'
+    let mut channels: Vec<AudioChannel> = AudioChannel::iter()
+        .filter(|channel| channel.is_capture() == capture)
+        .collect();
+    channels.sort_by_key(|channel| std::cmp::Reverse(channel.get_name().len()));
+
+    channels
+        .into_iter()
+        .find(|channel| description.contains(channel.get_name()))
+}
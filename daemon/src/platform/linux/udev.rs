@@ -0,0 +1,71 @@
+use anyhow::{bail, Result};
+use log::{debug, info};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use which::which;
+
+const RULES: &str = include_str!("../../../../50-goxlr.rules");
+const RULES_PATH: &str = "/etc/udev/rules.d/50-goxlr.rules";
+
+// Distributions (and users) don't always agree on where the rules go, or what they're called, so
+// we look for anything which mentions the GoXLR's vendor..
+const RULES_DIRECTORIES: [&str; 4] = [
+    "/etc/udev/rules.d",
+    "/run/udev/rules.d",
+    "/usr/lib/udev/rules.d",
+    "/lib/udev/rules.d",
+];
+const VENDOR_MATCH: &str = "ATTR{idVendor}==\"1220\"";
+
+pub fn has_rules() -> bool {
+    RULES_DIRECTORIES
+        .iter()
+        .filter_map(|dir| fs::read_dir(Path::new(dir)).ok())
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "rules"))
+        .any(|entry| {
+            fs::read_to_string(entry.path()).is_ok_and(|content| content.contains(VENDOR_MATCH))
+        })
+}
+
+pub fn install_rules() -> Result<()> {
+    // The rules are passed in as an argument, so nothing needs escaping..
+    let script = format!(
+        "printf '%s\\n' \"$1\" > {path} && chmod 644 {path} && udevadm control --reload-rules \
+         && udevadm trigger --subsystem-match=usb --attr-match=idVendor=1220",
+        path = RULES_PATH
+    );
+
+    // We need root to write them, prefer polkit as it'll prompt graphically if it can..
+    let mut command = if nix::unistd::Uid::effective().is_root() {
+        Command::new("sh")
+    } else if which("pkexec").is_ok() {
+        let mut command = Command::new("pkexec");
+        command.arg("sh");
+        command
+    } else if which("sudo").is_ok() {
+        let mut command = Command::new("sudo");
+        command.arg("sh");
+        command
+    } else {
+        bail!("Unable to find pkexec or sudo, please copy 50-goxlr.rules to /etc/udev/rules.d/");
+    };
+
+    debug!("Installing udev Rules to {}", RULES_PATH);
+    let status = command
+        .arg("-c")
+        .arg(script)
+        .arg("sh")
+        .arg(RULES.trim_end())
+        .status()?;
+
+    if !status.success() {
+        bail!("Unable to install the udev rules ({})", status);
+    }
+
+    info!("udev Rules installed to {}", RULES_PATH);
+    info!("If your GoXLR still isn't found, unplug it and plug it back in.");
+    Ok(())
+}
@@ -2,6 +2,8 @@ use crate::events::EventTriggers;
 use crate::DaemonState;
 use anyhow::Result;
 use cfg_if::cfg_if;
+use goxlr_ipc::{AudioChannel, AudioNode, MediaAction};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use tokio::sync::mpsc;
 use which::which;
@@ -32,6 +34,26 @@ cfg_if! {
         pub fn display_error(message: String) {
             windows::display_error(message);
         }
+
+        pub fn get_audio_nodes() -> Result<Vec<AudioNode>> {
+            Ok(vec![])
+        }
+
+        pub fn route_applications(_routes: &HashMap<String, Vec<AudioChannel>>) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn has_udev_rules() -> bool {
+            true
+        }
+
+        pub fn install_udev_rules() -> Result<()> {
+            anyhow::bail!("udev rules are only needed under Linux");
+        }
+
+        pub async fn perform_media_action(_action: MediaAction) -> Result<()> {
+            anyhow::bail!("Media controls are only supported under Linux");
+        }
     } else if #[cfg(target_os = "linux")] {
         mod linux;
         mod unix;
@@ -43,6 +65,11 @@ cfg_if! {
 
         pub async fn spawn_runtime(state: DaemonState, tx: mpsc::Sender<EventTriggers>) -> Result<()> {
             tokio::spawn(linux::sleep::run(tx.clone(), state.shutdown.clone()));
+            tokio::spawn(linux::mpris::run(
+                tx.clone(),
+                state.settings_handle.clone(),
+                state.shutdown.clone(),
+            ));
             unix::spawn_platform_runtime(state, tx).await
         }
 
@@ -60,6 +87,26 @@ cfg_if! {
         pub fn display_error(message: String) {
             linux::display_error(message);
         }
+
+        pub fn get_audio_nodes() -> Result<Vec<AudioNode>> {
+            linux::pipewire::get_goxlr_nodes()
+        }
+
+        pub fn route_applications(routes: &HashMap<String, Vec<AudioChannel>>) -> Result<()> {
+            linux::pipewire::route_applications(routes)
+        }
+
+        pub fn has_udev_rules() -> bool {
+            linux::udev::has_rules()
+        }
+
+        pub fn install_udev_rules() -> Result<()> {
+            linux::udev::install_rules()
+        }
+
+        pub async fn perform_media_action(action: MediaAction) -> Result<()> {
+            linux::mpris::perform(action).await
+        }
     } else if #[cfg(target_os = "macos")] {
         mod macos;
 
@@ -82,6 +129,26 @@ cfg_if! {
          pub fn display_error(message: String) {
             macos::display_error(message);
          }
+
+        pub fn get_audio_nodes() -> Result<Vec<AudioNode>> {
+            Ok(vec![])
+        }
+
+        pub fn route_applications(_routes: &HashMap<String, Vec<AudioChannel>>) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn has_udev_rules() -> bool {
+            true
+        }
+
+        pub fn install_udev_rules() -> Result<()> {
+            anyhow::bail!("udev rules are only needed under Linux");
+        }
+
+        pub async fn perform_media_action(_action: MediaAction) -> Result<()> {
+            anyhow::bail!("Media controls are only supported under Linux");
+        }
     } else {
         use anyhow::bail;
 
@@ -102,6 +169,26 @@ cfg_if! {
         }
 
         pub fn display_error(message: String) {}
+
+        pub fn get_audio_nodes() -> Result<Vec<AudioNode>> {
+            Ok(vec![])
+        }
+
+        pub fn route_applications(_routes: &HashMap<String, Vec<AudioChannel>>) -> Result<()> {
+            Ok(())
+        }
+
+        pub fn has_udev_rules() -> bool {
+            true
+        }
+
+        pub fn install_udev_rules() -> Result<()> {
+            bail!("udev rules are only needed under Linux");
+        }
+
+        pub async fn perform_media_action(_action: MediaAction) -> Result<()> {
+            bail!("Media controls are only supported under Linux");
+        }
     }
 }
 
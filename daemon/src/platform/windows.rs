@@ -2,7 +2,7 @@ use crate::events::EventTriggers;
 use crate::DaemonState;
 use anyhow::{bail, Result};
 use lazy_static::lazy_static;
-use log::{debug, error};
+use log::{debug, error, warn};
 use mslnk::ShellLink;
 use std::path::PathBuf;
 use std::{env, fs};
@@ -27,8 +27,8 @@ lazy_static! {
 
 pub fn perform_platform_preflight() -> Result<()> {
     if !locate_goxlr_driver() {
-        error!("Driver not found, Failing Preflight.");
-        bail!("The GoXLR Driver was not found, please install it and try again.");
+        // We can still work if the GoXLR has been bound to WinUSB, so let libUSB have a go..
+        warn!("GoXLR Driver not found, devices will only be found if bound to WinUSB.");
     }
 
     let count = get_official_app_count();
@@ -0,0 +1,141 @@
+use crate::files::get_named_path;
+use crate::profile::ProfileAdapter;
+use anyhow::{bail, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use goxlr_usb::colouring::ColourTargets;
+use image::{ImageFormat, Rgb, RgbImage};
+use std::collections::HashMap;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+const PREVIEW_WIDTH: u32 = 240;
+const PREVIEW_HEIGHT: u32 = 100;
+const BACKGROUND: Rgb<u8> = Rgb([24, 24, 24]);
+
+// The rough position of each lit element on a full sized GoXLR, as (x, y, width, height)..
+const BUTTONS: [(ColourTargets, u32, u32, u32, u32); 28] = [
+    (ColourTargets::Scribble1, 8, 8, 20, 12),
+    (ColourTargets::Scribble2, 36, 8, 20, 12),
+    (ColourTargets::Scribble3, 64, 8, 20, 12),
+    (ColourTargets::Scribble4, 92, 8, 20, 12),
+    (ColourTargets::Fader1Mute, 8, 80, 20, 10),
+    (ColourTargets::Fader2Mute, 36, 80, 20, 10),
+    (ColourTargets::Fader3Mute, 64, 80, 20, 10),
+    (ColourTargets::Fader4Mute, 92, 80, 20, 10),
+    (ColourTargets::PitchEncoder, 124, 8, 16, 12),
+    (ColourTargets::GenderEncoder, 144, 8, 16, 12),
+    (ColourTargets::ReverbEncoder, 164, 8, 16, 12),
+    (ColourTargets::EchoEncoder, 184, 8, 16, 12),
+    (ColourTargets::EffectSelect1, 124, 26, 12, 8),
+    (ColourTargets::EffectSelect2, 138, 26, 12, 8),
+    (ColourTargets::EffectSelect3, 152, 26, 12, 8),
+    (ColourTargets::EffectSelect4, 166, 26, 12, 8),
+    (ColourTargets::EffectSelect5, 180, 26, 12, 8),
+    (ColourTargets::EffectSelect6, 194, 26, 12, 8),
+    (ColourTargets::EffectMegaphone, 124, 40, 16, 8),
+    (ColourTargets::EffectRobot, 144, 40, 16, 8),
+    (ColourTargets::EffectHardTune, 164, 40, 16, 8),
+    (ColourTargets::EffectFx, 184, 40, 16, 8),
+    (ColourTargets::SamplerSelectA, 124, 54, 16, 8),
+    (ColourTargets::SamplerSelectB, 144, 54, 16, 8),
+    (ColourTargets::SamplerSelectC, 164, 54, 16, 8),
+    (ColourTargets::MicrophoneMute, 212, 8, 20, 12),
+    (ColourTargets::Bleep, 212, 26, 20, 12),
+    (ColourTargets::LogoX, 212, 80, 20, 10),
+];
+
+const SAMPLERS: [(ColourTargets, u32, u32, u32, u32); 5] = [
+    (ColourTargets::SamplerTopLeft, 124, 66, 18, 10),
+    (ColourTargets::SamplerTopRight, 146, 66, 18, 10),
+    (ColourTargets::SamplerBottomLeft, 124, 80, 18, 10),
+    (ColourTargets::SamplerBottomRight, 146, 80, 18, 10),
+    (ColourTargets::SamplerClear, 168, 66, 14, 24),
+];
+
+const FADERS: [(ColourTargets, u32); 4] = [
+    (ColourTargets::FadeMeter1, 15),
+    (ColourTargets::FadeMeter2, 43),
+    (ColourTargets::FadeMeter3, 71),
+    (ColourTargets::FadeMeter4, 99),
+];
+
+/// Keeps rendered previews around until the profile on disk changes, they're requested in
+/// bulk when a UI draws its profile list, so there's no point redrawing them each time.
+///
+/// Clones share the same cache, so previews can be rendered away from the device loop.
+#[derive(Default, Clone)]
+pub struct PreviewCache {
+    previews: Arc<Mutex<HashMap<String, (SystemTime, String)>>>,
+}
+
+impl PreviewCache {
+    /// Returns a base64 encoded PNG of the named profile's lighting, this loads and renders the
+    /// profile if it's not cached, so shouldn't be called from an async context.
+    pub fn get_preview(&self, directory: &Path, name: &str) -> Result<String> {
+        let path = get_named_path(directory, name, "goxlr")?;
+        if !path.is_file() {
+            bail!("Profile {} does not exist", name);
+        }
+        let modified = path.metadata()?.modified()?;
+
+        if let Some((cached, preview)) = self.previews.lock().unwrap().get(name) {
+            if *cached == modified {
+                return Ok(preview.clone());
+            }
+        }
+
+        let profile = ProfileAdapter::from_named(name.to_string(), directory)?;
+        let preview = STANDARD.encode(render_preview(&profile)?);
+        self.previews
+            .lock()
+            .unwrap()
+            .insert(name.to_string(), (modified, preview.clone()));
+        Ok(preview)
+    }
+}
+
+fn render_preview(profile: &ProfileAdapter) -> Result<Vec<u8>> {
+    let mut image = RgbImage::from_pixel(PREVIEW_WIDTH, PREVIEW_HEIGHT, BACKGROUND);
+
+    for (target, x, y, width, height) in BUTTONS.iter().chain(SAMPLERS.iter()) {
+        let colour = Rgb(profile.get_preview_colour(*target, 0));
+        fill(&mut image, *x, *y, *width, *height, |_| colour);
+    }
+
+    // Faders are drawn as a strip fading from the top colour down to the bottom one..
+    for (target, x) in FADERS {
+        let top = profile.get_preview_colour(target, 1);
+        let bottom = profile.get_preview_colour(target, 0);
+        fill(&mut image, x, 26, 6, 48, |offset| {
+            blend(top, bottom, offset, 48)
+        });
+    }
+
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
+
+fn fill<F>(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, colour: F)
+where
+    F: Fn(u32) -> Rgb<u8>,
+{
+    for row in 0..height {
+        let pixel = colour(row);
+        for column in 0..width {
+            image.put_pixel(x + column, y + row, pixel);
+        }
+    }
+}
+
+fn blend(from: [u8; 3], to: [u8; 3], step: u32, steps: u32) -> Rgb<u8> {
+    let mix = |a: u8, b: u8| (a as u32 * (steps - step) + b as u32 * step) / steps;
+    Rgb([
+        mix(from[0], to[0]) as u8,
+        mix(from[1], to[1]) as u8,
+        mix(from[2], to[2]) as u8,
+    ])
+}
@@ -1,23 +1,38 @@
+use crate::audit_log::{is_audited, AuditLog};
+use crate::command_queue::CommandQueue;
 use crate::device::Device;
+use crate::event_log::EventLogHandle;
 use crate::events::EventTriggers;
-use crate::files::extract_defaults;
-use crate::platform::{get_ui_app_path, has_autostart, set_autostart};
-use crate::{FileManager, PatchEvent, SettingsHandle, Shutdown, SYSTEM_LOCALE, VERSION};
-use anyhow::{anyhow, Result};
+use crate::files::{delete_icon, extract_defaults, save_icon};
+use crate::hotkeys::hotkeys_supported;
+use crate::locale;
+use crate::platform::{
+    get_audio_nodes, get_ui_app_path, has_autostart, route_applications, set_autostart,
+};
+use crate::preview::PreviewCache;
+use crate::servers::rate_limit::get_rate_limit_metrics;
+use crate::{
+    get_level_filter, FileManager, PatchEvent, SettingsHandle, Shutdown, SYSTEM_LOCALE, VERSION,
+};
+use anyhow::{anyhow, bail, Result};
 use enum_map::EnumMap;
+use goxlr_audio::metadata::get_waveform;
 use goxlr_ipc::{
-    Activation, ColourWay, DaemonCommand, DaemonConfig, DaemonStatus, DriverDetails, Files,
-    GoXLRCommand, HardwareStatus, HttpSettings, Locale, PathTypes, Paths, SampleFile,
-    UsbProductInformation,
+    Activation, AudioNode, AuditEntry, AuditQuery, ColourWay, CommandSource, DaemonCommand,
+    DaemonConfig, DaemonError, DaemonEvent, DaemonEventType, DaemonMetrics, DaemonStatus,
+    DriverDetails, EventHook, Files, GateLevel, GoXLRCommand, HardwareStatus, HttpSettings,
+    Lighting, Locale, PathTypes, Paths, SampleFile, TokenScope, UsbProductInformation,
 };
-use goxlr_types::{DeviceType, VersionNumber};
+use goxlr_types::{ChannelName, DeviceType, FaderName, VersionNumber};
 use goxlr_usb::device::base::GoXLRDevice;
 use goxlr_usb::device::{find_devices, from_device, get_version};
+use goxlr_usb::error::ConnectError;
 use goxlr_usb::{PID_GOXLR_FULL, PID_GOXLR_MINI};
 use json_patch::diff;
 use log::{debug, error, info, warn};
 use std::collections::{BTreeMap, HashMap};
 use std::env;
+use std::path::Path;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast::Sender as BroadcastSender;
 use tokio::sync::mpsc::{Receiver, Sender};
@@ -26,16 +41,52 @@ use tokio::time::sleep;
 use xmltree::Element;
 
 const IGNORE_DEVICE_DURATION: Duration = Duration::from_secs(10);
+
+// Shown when a device is still attached after disconnecting, but can no longer be loaded..
+const HUNG_DEVICE_MESSAGE: &str = "The GoXLR has stopped responding and couldn't be reset, \
+    unplug it (or switch it off) and reconnect it to recover";
+
+// Shown when the device can't be opened, which under Linux is almost always missing udev rules..
+const PERMISSION_DENIED_MESSAGE: &str = "Permission was denied while accessing the GoXLR, this \
+    usually means the udev rules which allow access to it are missing, they can be installed by \
+    running 'goxlr-daemon --install-udev-rules'";
 const APP_CHECK_INTERVAL: Duration = Duration::from_secs(30);
 
+// New application streams are checked for more often, so they don't play on the wrong channel
+const APP_ROUTING_INTERVAL: Duration = Duration::from_secs(2);
+
 // Adding a third entry has tripped enum_variant_names, I'll probably need to rename
 // RunDeviceCommand, but that'll need to be in a separate commit, for now, suppress.
 #[allow(clippy::enum_variant_names)]
 pub enum DeviceCommand {
     SendDaemonStatus(oneshot::Sender<DaemonStatus>),
-    RunDaemonCommand(DaemonCommand, oneshot::Sender<Result<()>>),
-    RunDeviceCommand(String, GoXLRCommand, oneshot::Sender<Result<()>>),
+    RunDaemonCommand(DaemonCommand, CommandSource, oneshot::Sender<Result<()>>),
+    RunDeviceCommand(
+        String,
+        GoXLRCommand,
+        CommandSource,
+        oneshot::Sender<Result<()>>,
+    ),
+    ValidateCommand(
+        String,
+        GoXLRCommand,
+        oneshot::Sender<Result<Vec<DaemonError>>>,
+    ),
     GetDeviceMicLevel(String, oneshot::Sender<Result<f64>>),
+    GetDeviceGateLevel(String, oneshot::Sender<Result<GateLevel>>),
+    GetDeviceChannelLevels(
+        String,
+        oneshot::Sender<Result<EnumMap<ChannelName, Option<f64>>>>,
+    ),
+    GetEvents(u64, oneshot::Sender<Vec<DaemonEvent>>),
+    GetSampleWaveform(String, usize, oneshot::Sender<Result<Vec<f32>>>),
+    GetProfilePreview(String, oneshot::Sender<Result<String>>),
+    GetScribbleImage(String, FaderName, oneshot::Sender<Result<String>>),
+    GetLighting(String, oneshot::Sender<Result<Lighting>>),
+    GetFiles(oneshot::Sender<Files>),
+    GetMetrics(oneshot::Sender<DaemonMetrics>),
+    CreateApiToken(String, TokenScope, oneshot::Sender<String>),
+    GetAuditLog(AuditQuery, oneshot::Sender<Result<Vec<AuditEntry>>>),
 }
 
 #[allow(dead_code)]
@@ -43,6 +94,7 @@ pub enum DeviceStateChange {
     Shutdown(bool),
     Sleep(oneshot::Sender<()>),
     Wake(oneshot::Sender<()>),
+    NowPlaying(Option<Vec<String>>),
 }
 
 pub type DeviceSender = Sender<DeviceCommand>;
@@ -60,9 +112,13 @@ pub async fn spawn_usb_handler(
     settings: SettingsHandle,
     http_settings: HttpSettings,
     mut file_manager: FileManager,
+    event_log: EventLogHandle,
 ) {
     let mut firmware_version = None;
 
+    // What the active media player is playing, so newly attached devices can show it..
+    let mut now_playing: Option<Vec<String>> = None;
+
     // We can probably either merge these, or struct them..
     let (disconnect_sender, mut disconnect_receiver) = mpsc::channel(16);
     let (event_sender, mut event_receiver) = mpsc::channel(16);
@@ -89,6 +145,9 @@ pub async fn spawn_usb_handler(
     let app_sleep = sleep(app_duration);
     tokio::pin!(app_sleep);
 
+    let app_routing_sleep = sleep(APP_ROUTING_INTERVAL);
+    tokio::pin!(app_routing_sleep);
+
     // Get the Driver Type and Details..
     let (interface, version) = get_version();
     let driver_interface = DriverDetails { interface, version };
@@ -97,7 +156,17 @@ pub async fn spawn_usb_handler(
     let mut devices: HashMap<String, Device> = HashMap::new();
     let mut ignore_list = HashMap::new();
 
+    // Devices which have disconnected, so we can tell if a device failing to load has hung..
+    let mut detached: HashMap<String, UsbProductInformation> = HashMap::new();
+    let mut errored_devices: HashMap<String, String> = HashMap::new();
+    let mut command_queue = CommandQueue::default();
+    let audit_log = AuditLog::new(settings.get_log_directory().await);
+    let previews = PreviewCache::default();
+
     let mut files = get_files(&mut file_manager, &settings).await;
+    let mut audio_nodes = vec![];
+    update_audio_nodes(&mut audio_nodes).await;
+
     let mut daemon_status = get_daemon_status(
         &devices,
         &settings,
@@ -108,9 +177,14 @@ pub async fn spawn_usb_handler(
         &app_check,
     )
     .await;
+    daemon_status.audio_nodes = audio_nodes.clone();
+    daemon_status.errored_devices = errored_devices.clone();
 
     let mut shutdown_triggered = false;
 
+    // Device commands which have run, waiting on the device's new status to be audited..
+    let mut pending_audits = HashMap::new();
+
     loop {
         let mut change_found = false;
         tokio::select! {
@@ -143,8 +217,16 @@ pub async fn spawn_usb_handler(
                         device_identifier = Some(identifier.clone());
                     }
 
-                    match load_device(device, existing_serials, disconnect_sender.clone(), event_sender.clone(), global_tx.clone(), &settings).await {
-                        Ok(device) => {
+                    match load_device(device, existing_serials, disconnect_sender.clone(), event_sender.clone(), global_tx.clone(), &settings, event_log.clone()).await {
+                        Ok(mut device) => {
+                            if now_playing.is_some() {
+                                let _ = device.set_now_playing(now_playing.clone()).await;
+                            }
+                            detached.remove(device.serial());
+                            errored_devices.remove(device.serial());
+                            event_log.push(Some(device.serial()), DaemonEventType::DeviceAttached);
+                            let env = vec![(String::from("GOXLR_SERIAL"), device.serial().to_owned())];
+                            let _ = global_tx.send(EventTriggers::Hook(EventHook::DeviceConnected, env)).await;
                             devices.insert(device.serial().to_owned(), device);
                             change_found = true;
                         }
@@ -153,6 +235,24 @@ pub async fn spawn_usb_handler(
                                 "Couldn't load potential GoXLR on bus {} address {}: {}",
                                 bus_number, address, e
                             );
+                            event_log.push(None, DaemonEventType::Error(format!("Unable to load device: {}", e)));
+
+                            if matches!(e.downcast_ref(), Some(ConnectError::PermissionDenied)) {
+                                error!("{}", PERMISSION_DENIED_MESSAGE);
+                                event_log.push(None, DaemonEventType::Error(PERMISSION_DENIED_MESSAGE.to_string()));
+                            }
+
+                            // If this is a device we just lost, it's still attached but not responding..
+                            let hung = detached.iter().find(|(_, usb)| match (&usb.identifier, &device_identifier) {
+                                (Some(known), Some(identifier)) => known == identifier,
+                                _ => usb.bus_number == bus_number && usb.address == address,
+                            });
+                            if let Some((serial, _)) = hung {
+                                error!("[{}] {}", serial, HUNG_DEVICE_MESSAGE);
+                                event_log.push(Some(serial), DaemonEventType::Error(HUNG_DEVICE_MESSAGE.to_string()));
+                                errored_devices.insert(serial.clone(), HUNG_DEVICE_MESSAGE.to_string());
+                                change_found = true;
+                            }
                             ignore_list
                                 .insert((bus_number, address, device_identifier), Instant::now() + IGNORE_DEVICE_DURATION);
                         }
@@ -178,10 +278,32 @@ pub async fn spawn_usb_handler(
                 if get_app_path(&mut app_check) {
                     change_found = true;
                 }
+
+                // The audio nodes can take a moment to appear after a device is attached, so
+                // rather than tracking that, we simply check them periodically..
+                if update_audio_nodes(&mut audio_nodes).await {
+                    change_found = true;
+                }
                 app_sleep.as_mut().reset(tokio::time::Instant::now() + APP_CHECK_INTERVAL);
             },
+            () = &mut app_routing_sleep => {
+                let routes = settings.get_app_routes().await;
+                if !routes.is_empty() {
+                    let result = tokio::task::spawn_blocking(move || route_applications(&routes)).await;
+                    if let Ok(Err(error)) = result {
+                        debug!("Unable to route applications: {}", error);
+                    }
+                }
+                app_routing_sleep.as_mut().reset(tokio::time::Instant::now() + APP_ROUTING_INTERVAL);
+            },
             Some(serial) = disconnect_receiver.recv() => {
                 info!("[{}] Device Disconnected", serial);
+                event_log.push(Some(&serial), DaemonEventType::DeviceDetached);
+                let env = vec![(String::from("GOXLR_SERIAL"), serial.clone())];
+                let _ = global_tx.send(EventTriggers::Hook(EventHook::DeviceDisconnected, env)).await;
+                if let Some(mixer) = daemon_status.mixers.get(&serial) {
+                    detached.insert(serial.clone(), mixer.hardware.usb_device.clone());
+                }
                 devices.remove(&serial);
                 change_found = true;
             },
@@ -192,6 +314,10 @@ pub async fn spawn_usb_handler(
                         change_found = changed;
                     }
 
+                    // Keep polling quickly while the device is in use, or someone's watching..
+                    let clients_connected = broadcast_tx.receiver_count() > 0;
+                    device.update_poll_rate(clients_connected).await;
+
                     if let Err(error) = result {
                         warn!("Error Received from {}: {}", device.serial(), error);
                     }
@@ -234,6 +360,14 @@ pub async fn spawn_usb_handler(
                         // allows the UI to update when waking up.
                         change_found = true;
                     }
+                    DeviceStateChange::NowPlaying(lines) => {
+                        for device in devices.values_mut() {
+                            if let Err(error) = device.set_now_playing(lines.clone()).await {
+                                warn!("Unable to show Now Playing on {}: {}", device.serial(), error);
+                            }
+                        }
+                        now_playing = lines;
+                    }
                 }
 
 
@@ -243,146 +377,374 @@ pub async fn spawn_usb_handler(
                 return;
             },
             Some(command) = command_rx.recv() => {
-                match command {
-                    DeviceCommand::SendDaemonStatus(sender) => {
-                        let _ = sender.send(daemon_status.clone());
-                    }
+                // Grab anything else that's waiting, so device commands can be queued up and
+                // coalesced before any of them are sent..
+                let mut commands = vec![command];
+                while let Ok(command) = command_rx.try_recv() {
+                    commands.push(command);
+                }
 
-                    DeviceCommand::RunDaemonCommand(command, sender) => {
-                        match command {
-                            DaemonCommand::StopDaemon => {
-                                // These should probably be moved upstream somewhere, they're not
-                                // device specific!
-                                let _ = global_tx.send(EventTriggers::Stop(false)).await;
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::OpenUi => {
-                                let _ = global_tx.send(EventTriggers::OpenUi).await;
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::Activate => {
-                                let _ = global_tx.send(EventTriggers::Activate).await;
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::RecoverDefaults(path_type) => {
-                                let path = match path_type {
-                                    PathTypes::Profiles => settings.get_profile_directory().await,
-                                    PathTypes::Presets => settings.get_presets_directory().await,
-                                    PathTypes::Icons => settings.get_icons_directory().await,
-                                    PathTypes::MicProfiles => settings.get_mic_profile_directory().await,
-                                    _ => {
-                                        let _ = sender.send(Err(anyhow!("Invalid Path type Sent")));
-                                        return;
+                // What the daemon's config looked like before each daemon command, for the audit log..
+                let auditing = settings.get_audit_log_enabled().await;
+                let mut config = auditing.then(|| daemon_status.config.clone());
+
+                for command in commands {
+                    match command {
+                        DeviceCommand::SendDaemonStatus(sender) => {
+                            let _ = sender.send(daemon_status.clone());
+                        }
+
+                        DeviceCommand::RunDaemonCommand(command, source, reply) => {
+                            // Catch the result on its way out, so we know whether to audit it..
+                            let (sender, mut result) = oneshot::channel();
+                            let audited = auditing.then(|| command.clone());
+
+                            match command {
+                                DaemonCommand::StopDaemon => {
+                                    // These should probably be moved upstream somewhere, they're not
+                                    // device specific!
+                                    let _ = global_tx.send(EventTriggers::Stop(false)).await;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::OpenUi => {
+                                    let _ = global_tx.send(EventTriggers::OpenUi).await;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::Activate => {
+                                    let _ = global_tx.send(EventTriggers::Activate).await;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::RecoverDefaults(path_type) => {
+                                    let path = match path_type {
+                                        PathTypes::Profiles => settings.get_profile_directory().await,
+                                        PathTypes::Presets => settings.get_presets_directory().await,
+                                        PathTypes::Icons => settings.get_icons_directory().await,
+                                        PathTypes::MicProfiles => settings.get_mic_profile_directory().await,
+                                        _ => {
+                                            let _ = sender.send(Err(anyhow!("Invalid Path type Sent")));
+                                            return;
+                                        }
+                                    };
+                                    let _ = sender.send(extract_defaults(path_type, &path));
+                                }
+                                DaemonCommand::UploadIcon(name, data) => {
+                                    let path = settings.get_icons_directory().await;
+                                    let result = save_icon(&path, &name, &data);
+                                    if let Ok(file_name) = &result {
+                                        debug!("Saved Icon {} as {}", name, file_name);
                                     }
-                                };
-                                let _ = sender.send(extract_defaults(path_type, &path));
-                            }
-                            DaemonCommand::SetAutoStartEnabled(enabled) => {
-                                let _ = sender.send(set_autostart(enabled));
-                                change_found = true;
-                            }
-                            DaemonCommand::SetLogLevel(level) => {
-                                settings.set_log_level(level).await;
-                                settings.save().await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::SetLocale(language) => {
-                                settings.set_selected_locale(language).await;
-                                settings.save().await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::SetUiLaunchOnLoad(value) => {
-                                settings.set_open_ui_on_launch(value).await;
-                                settings.save().await;
-                                change_found = true;
+                                    let _ = sender.send(result.map(|_| ()));
+                                }
+                                DaemonCommand::DeleteIcon(name) => {
+                                    let path = settings.get_icons_directory().await;
+                                    let _ = sender.send(delete_icon(&path, &name));
+                                }
+                                DaemonCommand::RemoveApiToken(name) => {
+                                    if settings.remove_api_token(&name).await {
+                                        settings.save().await;
+                                        change_found = true;
+                                        let _ = sender.send(Ok(()));
+                                    } else {
+                                        let _ = sender.send(Err(anyhow!("API Token {} not found", name)));
+                                    }
+                                }
+                                DaemonCommand::SetAutoStartEnabled(enabled) => {
+                                    let _ = sender.send(set_autostart(enabled));
+                                    change_found = true;
+                                }
+                                DaemonCommand::SetLogLevel(level) => {
+                                    // Applied straight away, rather than on the next start..
+                                    log::set_max_level(get_level_filter(&level));
+                                    settings.set_log_level(level).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetLogRotation(rotation) => {
+                                    settings.set_log_rotation(rotation).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetLogRetention(count) => {
+                                    if count == 0 {
+                                        let _ = sender.send(Err(anyhow!("At least one log file must be retained")));
+                                    } else {
+                                        settings.set_log_retention(count).await;
+                                        settings.save().await;
+                                        change_found = true;
+                                        let _ = sender.send(Ok(()));
+                                    }
+                                }
+                                DaemonCommand::SetAuditLogEnabled(enabled) => {
+                                    settings.set_audit_log_enabled(enabled).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetLocale(language) => {
+                                    locale::set_locale(language.as_ref().unwrap_or(&SYSTEM_LOCALE));
+                                    settings.set_selected_locale(language).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetUiLaunchOnLoad(value) => {
+                                    settings.set_open_ui_on_launch(value).await;
+                                    settings.save().await;
+                                    change_found = true;
 
-                                let _ = sender.send(Ok(()));
-                            }
-                            DaemonCommand::SetShowTrayIcon(enabled) => {
-                                settings.set_show_tray_icon(enabled).await;
-                                settings.save().await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetShowTrayIcon(enabled) => {
+                                    settings.set_show_tray_icon(enabled).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetTTSEnabled(enabled) => {
+                                    settings.set_tts_enabled(enabled).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetAllowNetworkAccess(enabled) => {
+                                    settings.set_allow_network_access(enabled).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetProfileAutosaveInterval(interval) => {
+                                    settings.set_profile_autosave_interval(interval).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetInputPolling(polling) => {
+                                    if polling.active_interval == 0 || polling.idle_interval == 0 {
+                                        let _ = sender.send(Err(anyhow!("Poll Intervals must be greater than 0")));
+                                    } else {
+                                        settings.set_input_polling(polling).await;
+                                        settings.save().await;
+                                        change_found = true;
+                                        let _ = sender.send(Ok(()));
+                                    }
+                                }
+                                DaemonCommand::OpenPath(path_type) => {
+                                    // There's nothing we can really do if this errors..
+                                    let _ = global_tx.send(EventTriggers::Open(path_type)).await;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetSampleGainPct(sample, gain) => {
+                                    settings.set_sample_gain_percent(sample, gain).await;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::ApplySampleChange => {
+                                    // Change is committed, save it..
+                                    settings.save().await;
+
+                                    // Resend the value.
+                                    files = update_files(files, PathTypes::Samples, &mut file_manager, &settings).await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetEventHook(hook, command) => {
+                                    settings.set_event_hook(hook, command).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetApplicationRoute(application, channels) => {
+                                    settings.set_app_route(application, channels).await;
+                                    settings.save().await;
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::SetActivatorPath(path) => {
+                                    if let Some(path) = path {
+                                        settings.set_activate(Some(path.to_string_lossy().to_string())).await;
+                                        settings.save().await;
+                                    } else {
+                                        settings.set_activate(None).await;
+                                        settings.save().await;
+                                    }
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
+                                DaemonCommand::HandleMacOSAggregates(value) => {
+                                    settings.set_macos_handle_aggregates(value).await;
+                                    settings.save().await;
+
+                                    change_found = true;
+                                    let _ = sender.send(Ok(()));
+                                }
                             }
-                            DaemonCommand::SetTTSEnabled(enabled) => {
-                                settings.set_tts_enabled(enabled).await;
-                                settings.save().await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+
+                            let result = result.try_recv().unwrap_or_else(|_| Err(anyhow!("Daemon Command didn't complete")));
+                            if let (Some(audited), Some(config), Ok(_)) = (audited, &mut config, &result) {
+                                let new_config = get_daemon_config(&settings, &http_settings, &driver_interface, &firmware_version, &app_check).await;
+                                let old_config = std::mem::replace(config, new_config.clone());
+                                audit_log.record_daemon(source, audited, old_config, new_config);
                             }
-                            DaemonCommand::SetAllowNetworkAccess(enabled) => {
-                                settings.set_allow_network_access(enabled).await;
-                                settings.save().await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+                            let _ = reply.send(result);
+                        },
+
+                        DeviceCommand::RunDeviceCommand(serial, command, source, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            command_queue.push(serial, command, source, sender);
+                        },
+
+                        DeviceCommand::ValidateCommand(serial, command, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get(&serial) {
+                                let _ = sender.send(Ok(device.validate_command(&command)));
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
-                            DaemonCommand::OpenPath(path_type) => {
-                                // There's nothing we can really do if this errors..
-                                let _ = global_tx.send(EventTriggers::Open(path_type)).await;
-                                let _ = sender.send(Ok(()));
+                        }
+
+                        DeviceCommand::GetDeviceMicLevel(serial, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get_mut(&serial) {
+                                let _ = sender.send(device.get_mic_level().await);
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
-                            DaemonCommand::SetSampleGainPct(sample, gain) => {
-                                settings.set_sample_gain_percent(sample, gain).await;
-                                let _ = sender.send(Ok(()));
+                        }
+
+                        DeviceCommand::GetDeviceGateLevel(serial, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get_mut(&serial) {
+                                let _ = sender.send(device.get_gate_level().await);
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
-                            DaemonCommand::ApplySampleChange => {
-                                // Change is committed, save it..
-                                settings.save().await;
+                        }
 
-                                // Resend the value.
-                                files = update_files(files, PathTypes::Samples, &mut file_manager, &settings).await;
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+                        DeviceCommand::GetDeviceChannelLevels(serial, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get_mut(&serial) {
+                                let _ = sender.send(device.get_channel_levels().await);
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
-                            DaemonCommand::SetActivatorPath(path) => {
-                                if let Some(path) = path {
-                                    settings.set_activate(Some(path.to_string_lossy().to_string())).await;
-                                    settings.save().await;
-                                } else {
-                                    settings.set_activate(None).await;
-                                    settings.save().await;
-                                }
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+                        }
+
+                        DeviceCommand::GetScribbleImage(serial, fader, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get(&serial) {
+                                let _ = sender.send(device.get_scribble_image(fader));
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
-                            DaemonCommand::HandleMacOSAggregates(value) => {
-                                settings.set_macos_handle_aggregates(value).await;
-                                settings.save().await;
+                        }
 
-                                change_found = true;
-                                let _ = sender.send(Ok(()));
+                        DeviceCommand::GetLighting(serial, sender) => {
+                            let serial = resolve_serial(&daemon_status, serial);
+                            if let Some(device) = devices.get(&serial) {
+                                let _ = sender.send(Ok(device.get_lighting()));
+                            } else {
+                                let _ = sender.send(Err(DaemonError::DeviceNotFound { serial }.into()));
                             }
                         }
-                    },
 
-                    DeviceCommand::RunDeviceCommand(serial, command, sender) => {
-                        if let Some(device) = devices.get_mut(&serial) {
-                            let result = match device.perform_command(command.clone()).await {
-                                Ok(result) => {
-                                    Ok(result)
-                                }
-                                Err(error) => {
-                                    warn!("Error Executing: {:?}, {}", command, error);
-                                    Err(error)
-                                }
+                        DeviceCommand::GetEvents(since, sender) => {
+                            let _ = sender.send(event_log.get_since(since));
+                        }
+                        DeviceCommand::GetFiles(sender) => {
+                            // These are only refreshed when the file watcher tells us something has
+                            // changed, so are cheap to hand out..
+                            let _ = sender.send(files.clone());
+                        }
+                        DeviceCommand::GetMetrics(sender) => {
+                            let metrics = DaemonMetrics {
+                                queue_depth: command_queue.len(),
+                                peak_queue_depth: command_queue.peak(),
+                                devices: devices
+                                    .iter_mut()
+                                    .map(|(serial, device)| (serial.clone(), device.get_metrics()))
+                                    .collect(),
+                                rate_limits: get_rate_limit_metrics(),
                             };
-                            let _ = sender.send(result);
+                            let _ = sender.send(metrics);
+                        }
+                        DeviceCommand::CreateApiToken(name, scope, sender) => {
+                            info!("Creating {:?} API Token {}", scope, name);
+                            let token = settings.create_api_token(name, scope).await;
+                            settings.save().await;
                             change_found = true;
-                        } else {
-                            let _ = sender.send(Err(anyhow!("Device {} is not connected", serial)));
+                            let _ = sender.send(token);
                         }
-                    },
+                        DeviceCommand::GetSampleWaveform(path, points, sender) => {
+                            let samples = settings.get_samples_directory().await;
 
-                    DeviceCommand::GetDeviceMicLevel(serial, sender) => {
-                        if let Some(device) = devices.get_mut(&serial) {
-                            let _ = sender.send(device.get_mic_level().await);
-                        } else {
-                            let _ = sender.send(Err(anyhow!("Device {} is not connected", serial)));
+                            // Decoding the sample can take a moment, so don't hold up the worker..
+                            tokio::task::spawn_blocking(move || {
+                                let _ = sender.send(get_sample_waveform(&samples, &path, points));
+                            });
+                        }
+                        DeviceCommand::GetProfilePreview(name, sender) => {
+                            let profiles = settings.get_profile_directory().await;
+
+                            // Loading and drawing the profile can take a moment..
+                            let previews = previews.clone();
+                            tokio::task::spawn_blocking(move || {
+                                let _ = sender.send(previews.get_preview(&profiles, &name));
+                            });
+                        }
+                        DeviceCommand::GetAuditLog(query, sender) => {
+                            // Reading back through the files may take a moment..
+                            let audit_log = audit_log.clone();
+                            tokio::task::spawn_blocking(move || {
+                                let _ = sender.send(audit_log.query(&query));
+                            });
                         }
                     }
                 }
+
+                // Now run the queued device commands, the status sent out at the end of the last pass
+                // is what the device looked like before the first of them. The status after each one
+                // is normally the one built for the patch below, so audits wait for that unless
+                // another command for the same device needs it first..
+                while let Some(queued) = command_queue.pop() {
+                    let serial = queued.serial;
+                    let command = queued.command;
+                    let senders = queued.senders;
+
+                    if let Some(device) = devices.get_mut(&serial) {
+                        let mut old_status = None;
+                        if let Some((source, audited, old)) = pending_audits.remove(&serial) {
+                            let status = device.status().await;
+                            audit_log.record_device(&serial, source, audited, old, status.clone());
+                            old_status = Some(status);
+                        }
+
+                        let old_status = match auditing && is_audited(&command) {
+                            true => match old_status.or_else(|| daemon_status.mixers.get(&serial).cloned()) {
+                                Some(status) => Some(status),
+                                None => Some(device.status().await),
+                            },
+                            false => None,
+                        };
+                        let result = match device.perform_command(command.clone()).await {
+                            Ok(result) => {
+                                if let Some(old_status) = old_status {
+                                    pending_audits.insert(serial.clone(), (queued.source, command, old_status));
+                                }
+                                Ok(result)
+                            }
+                            Err(error) => {
+                                warn!("Error Executing: {:?}, {}", command, error);
+                                event_log.push(Some(&serial), DaemonEventType::Error(error.to_string()));
+                                Err(map_usb_error(error))
+                            }
+                        };
+                        senders.send(result);
+                        change_found = true;
+                    } else {
+                        senders.send(Err(DaemonError::DeviceNotFound { serial }.into()));
+                    }
+                }
             },
             Some(path) = file_rx.recv() => {
                 // Notify devices if Samples have changed..
@@ -392,13 +754,20 @@ pub async fn spawn_usb_handler(
                     }
                 }
 
+                // Check whether the active profiles have been changed by something else..
+                if path == PathTypes::Profiles {
+                    for device in devices.values_mut() {
+                        device.check_profile_conflict().await;
+                    }
+                }
+
                 files = update_files(files, path, &mut file_manager, &settings).await;
                 change_found = true;
             }
         }
 
         if change_found {
-            let new_status = get_daemon_status(
+            let mut new_status = get_daemon_status(
                 &devices,
                 &settings,
                 &http_settings,
@@ -408,6 +777,14 @@ pub async fn spawn_usb_handler(
                 &app_check,
             )
             .await;
+            new_status.audio_nodes = audio_nodes.clone();
+            new_status.errored_devices = errored_devices.clone();
+
+            for (serial, (source, command, old_status)) in pending_audits.drain() {
+                if let Some(status) = new_status.mixers.get(&serial) {
+                    audit_log.record_device(&serial, source, command, old_status, status.clone());
+                }
+            }
 
             // Convert them to JSON..
             let json_old = serde_json::to_value(&daemon_status).unwrap();
@@ -436,28 +813,14 @@ async fn get_daemon_status(
     app_check: &Option<String>,
 ) -> DaemonStatus {
     let mut status = DaemonStatus {
-        config: DaemonConfig {
-            http_settings: http_settings.clone(),
-            daemon_version: String::from(VERSION),
-            driver_interface: driver_details.clone(),
-            latest_firmware: firmware_versions.clone(),
-            locale: Locale {
-                user_locale: settings.get_selected_locale().await,
-                system_locale: SYSTEM_LOCALE.clone(),
-            },
-            autostart_enabled: has_autostart(),
-            show_tray_icon: settings.get_show_tray_icon().await,
-            tts_enabled: settings.get_tts_enabled().await,
-            allow_network_access: settings.get_allow_network_access().await,
-            log_level: settings.get_log_level().await,
-            open_ui_on_launch: settings.get_open_ui_on_launch().await,
-            activation: Activation {
-                active_path: settings.get_activate().await,
-                app_path: app_check.clone(),
-            },
-            platform: env::consts::OS.to_string(),
-            handle_macos_aggregates: settings.get_macos_handle_aggregates().await,
-        },
+        config: get_daemon_config(
+            settings,
+            http_settings,
+            driver_details,
+            firmware_versions,
+            app_check,
+        )
+        .await,
         paths: Paths {
             profile_directory: settings.get_profile_directory().await,
             mic_profile_directory: settings.get_mic_profile_directory().await,
@@ -479,6 +842,66 @@ async fn get_daemon_status(
     status
 }
 
+async fn get_daemon_config(
+    settings: &SettingsHandle,
+    http_settings: &HttpSettings,
+    driver_details: &DriverDetails,
+    firmware_versions: &Option<EnumMap<DeviceType, Option<VersionNumber>>>,
+    app_check: &Option<String>,
+) -> DaemonConfig {
+    DaemonConfig {
+        http_settings: http_settings.clone(),
+        daemon_version: String::from(VERSION),
+        driver_interface: driver_details.clone(),
+        latest_firmware: firmware_versions.clone(),
+        locale: Locale {
+            user_locale: settings.get_selected_locale().await,
+            system_locale: SYSTEM_LOCALE.clone(),
+        },
+        autostart_enabled: has_autostart(),
+        show_tray_icon: settings.get_show_tray_icon().await,
+        tts_enabled: settings.get_tts_enabled().await,
+        hotkeys_supported: hotkeys_supported(),
+        allow_network_access: settings.get_allow_network_access().await,
+        profile_autosave_interval: settings.get_profile_autosave_interval().await,
+        input_polling: settings.get_input_polling().await,
+        log_level: settings.get_log_level().await,
+        log_rotation: settings.get_log_rotation().await,
+        log_retention: settings.get_log_retention().await,
+        audit_log_enabled: settings.get_audit_log_enabled().await,
+        open_ui_on_launch: settings.get_open_ui_on_launch().await,
+        activation: Activation {
+            active_path: settings.get_activate().await,
+            app_path: app_check.clone(),
+        },
+        platform: env::consts::OS.to_string(),
+        handle_macos_aggregates: settings.get_macos_handle_aggregates().await,
+        event_hooks: settings.get_event_hooks().await,
+        app_routes: settings.get_app_routes().await,
+        api_tokens: settings.get_api_token_scopes().await,
+    }
+}
+
+// Devices can be referred to by their label (or part of it) rather than the full serial..
+fn resolve_serial(status: &DaemonStatus, name: String) -> String {
+    status.find_serial(&name).map(str::to_owned).unwrap_or(name)
+}
+
+// If a command failed due to a USB problem, let the client know about it specifically..
+fn map_usb_error(error: anyhow::Error) -> anyhow::Error {
+    let usb_error = error
+        .chain()
+        .find_map(|e| e.downcast_ref::<goxlr_usb::rusb::Error>());
+
+    if let Some(usb_error) = usb_error {
+        return DaemonError::UsbFailure {
+            message: usb_error.to_string(),
+        }
+        .into();
+    }
+    error
+}
+
 #[allow(const_item_mutation)]
 fn get_app_path(app_check: &mut Option<String>) -> bool {
     if let Some(path) = get_ui_app_path() {
@@ -509,6 +932,32 @@ fn get_app_path(app_check: &mut Option<String>) -> bool {
     }
 }
 
+async fn update_audio_nodes(audio_nodes: &mut Vec<AudioNode>) -> bool {
+    let nodes = match tokio::task::spawn_blocking(get_audio_nodes).await {
+        Ok(Ok(nodes)) => nodes,
+        Ok(Err(error)) => {
+            debug!("Unable to locate Audio Nodes: {}", error);
+            vec![]
+        }
+        Err(_) => return false,
+    };
+
+    if *audio_nodes != nodes {
+        *audio_nodes = nodes;
+        return true;
+    }
+    false
+}
+
+fn get_sample_waveform(samples: &Path, path: &str, points: usize) -> Result<Vec<f32>> {
+    // Make sure nobody's trying to read something outside the samples directory..
+    let file = samples.join(path).canonicalize()?;
+    if !file.starts_with(samples.canonicalize()?) {
+        bail!("Sample is not in the Samples Directory");
+    }
+    get_waveform(&file, points)
+}
+
 async fn get_sample_files(
     file_manager: &mut FileManager,
     settings: &SettingsHandle,
@@ -521,15 +970,15 @@ async fn get_sample_files(
     for (key, value) in file_samples {
         let mut gain = 100;
 
-        if let Some(config_gain) = config_samples.get(&*value) {
+        if let Some(config_gain) = config_samples.get(&*value.name) {
             gain = *config_gain;
         }
 
         samples.insert(
             key,
             SampleFile {
-                name: value,
                 gain_pct: gain,
+                ..value
             },
         );
     }
@@ -633,6 +1082,7 @@ async fn load_device(
     event_sender: Sender<String>,
     global_events: Sender<EventTriggers>,
     settings: &SettingsHandle,
+    event_log: EventLogHandle,
 ) -> Result<Device<'_>> {
     let device_copy = device.clone();
 
@@ -684,7 +1134,7 @@ async fn load_device(
         colour_way,
         usb_device,
     };
-    let device = Device::new(handled_device, hardware, settings, global_events).await?;
+    let device = Device::new(handled_device, hardware, settings, global_events, event_log).await?;
     settings
         .set_device_profile_name(&serial_number, device.profile().name())
         .await;
@@ -4,6 +4,7 @@ use std::default::Default;
 use std::fs::{remove_file, File};
 use std::io::{Cursor, Read, Seek};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{anyhow, bail, Context, Result};
 use enum_map::EnumMap;
@@ -11,46 +12,51 @@ use log::{debug, warn};
 use strum::IntoEnumIterator;
 
 use goxlr_ipc::{
-    ActiveEffects, AnimationLighting, ButtonLighting, CoughButton, Echo, Effects, FaderLighting,
-    Gender, HardTune, Lighting, Megaphone, OneColour, Pitch, Reverb, Robot, Sample,
-    SampleProcessState, Sampler, SamplerButton, SamplerLighting, Scribble, Submix, Submixes,
-    ThreeColours, TwoColours,
+    ActiveEffects, AnimationLighting, ButtonLighting, CoughButton, Echo, EffectLevel, EffectLevels,
+    Effects, FaderLighting, FaderPages, Gender, HardTune, Lighting, Megaphone, OneColour, Pitch,
+    ProfileConflict, Reverb, Robot, Sample, SampleProcessState, Sampler, SamplerButton,
+    SamplerLighting, Scribble, Submix, Submixes, ThreeColours, TwoColours, VoiceMeter,
 };
 use goxlr_profile_loader::components::animation::{AnimationMode, WaterfallDirection};
 use goxlr_profile_loader::components::colours::{
     Colour, ColourDisplay, ColourMap, ColourOffStyle, ColourState,
 };
 use goxlr_profile_loader::components::echo::{EchoEncoder, EchoStyle};
+use goxlr_profile_loader::components::encoder_sensitivity::Encoders;
+use goxlr_profile_loader::components::fader_pages::FaderPage;
 use goxlr_profile_loader::components::gender::{GenderEncoder, GenderStyle};
 use goxlr_profile_loader::components::hardtune::{HardTuneEffect, HardTuneSource, HardTuneStyle};
 use goxlr_profile_loader::components::megaphone::{MegaphoneEffect, MegaphoneStyle};
 use goxlr_profile_loader::components::mixer::{FullChannelList, InputChannels, OutputChannels};
-use goxlr_profile_loader::components::mute::{MuteButton, MuteFunction};
+use goxlr_profile_loader::components::mute::{MuteButton, MuteFunction, MuteLightingStyle};
 use goxlr_profile_loader::components::mute_chat::{CoughToggle, MuteChat};
 use goxlr_profile_loader::components::pitch::{PitchEncoder, PitchStyle};
 use goxlr_profile_loader::components::reverb::{ReverbEncoder, ReverbStyle};
 use goxlr_profile_loader::components::robot::{RobotEffect, RobotStyle};
 use goxlr_profile_loader::components::sample::{PlayOrder, PlaybackMode, SampleBank, Track};
+use goxlr_profile_loader::components::sequence::{LightingSequence, SequenceStyle};
 use goxlr_profile_loader::components::simple::SimpleElements;
 use goxlr_profile_loader::components::submix::mix_routing_tree::Mix;
-use goxlr_profile_loader::profile::{Profile, ProfileSettings};
+use goxlr_profile_loader::profile::{MergeSelection, Profile, ProfileSettings};
+use goxlr_profile_loader::structure::{apply_changes, ProfileStructure};
 use goxlr_profile_loader::SampleButtons::{BottomLeft, BottomRight, Clear, TopLeft, TopRight};
 use goxlr_profile_loader::{Faders, Preset, SampleButtons};
 use goxlr_scribbles::get_scribble;
 use goxlr_types::{
     Button, ButtonColourGroups, ButtonColourOffStyle as BasicColourOffStyle, ChannelName,
-    EffectBankPresets, EncoderColourTargets, EncoderName, FaderDisplayStyle as BasicColourDisplay,
-    FaderDisplayStyle, FaderName, InputDevice, MuteFunction as BasicMuteFunction, MuteState,
-    OutputDevice, SamplePlayOrder, SamplePlaybackMode, SamplerColourTargets, SimpleColourTargets,
-    SubMixChannelName, VersionNumber,
+    EchoAmount, EffectBankPresets, EncoderColourTargets, EncoderName,
+    FaderDisplayStyle as BasicColourDisplay, FaderDisplayStyle, FaderName, InputDevice,
+    MuteFunction as BasicMuteFunction, MuteLightingStyle as BasicMuteLightingStyle, MuteState,
+    OutputDevice, ProfileMergeSection, ReverbAmount, SamplePlayOrder, SamplePlaybackMode,
+    SamplerColourTargets, SimpleColourTargets, SubMixChannelName, VersionNumber,
 };
 use goxlr_usb::buttonstate::{ButtonStates, Buttons};
 use goxlr_usb::channelstate::ChannelState;
-use goxlr_usb::colouring::ColourTargets;
+use goxlr_usb::colouring::{ColourMapFormat, ColourScheme, ColourTargets};
 
 use crate::audio::{AudioFile, AudioHandler};
 use crate::device::CurrentState;
-use crate::files::can_create_new_file;
+use crate::files::{can_create_new_file, create_parent_path, get_named_path};
 
 pub const DEFAULT_PROFILE_NAME: &str = "Default";
 const DEFAULT_PROFILE: &[u8] = include_bytes!("../profiles/Default.goxlr");
@@ -59,63 +65,178 @@ const DEFAULT_PROFILE: &[u8] = include_bytes!("../profiles/Default.goxlr");
 pub struct ProfileAdapter {
     name: String,
     profile: Profile,
+
+    // The profile as it was when last loaded or saved, used to check for unsaved changes
+    saved_state: Vec<u8>,
+
+    // The modified time of the file when last loaded or saved, so we can tell if something
+    // else (Dropbox, git, etc) has changed it under us
+    modified: Option<SystemTime>,
+    conflict: Option<ProfileConflict>,
+
+    // Set while this is the embedded default rather than something loaded from (or saved to)
+    // the profile directory, it shares its name with the user's Default profile, so autosave
+    // mustn't write it over that file..
+    embedded: bool,
 }
 
 impl ProfileAdapter {
     pub fn from_named(name: String, directory: &Path) -> Result<Self> {
-        let path = directory.join(format!("{}.goxlr", name));
+        let path = get_named_path(directory, &name, "goxlr")?;
 
         if path.is_file() {
             debug!("Loading Profile From {}", path.to_string_lossy());
-            let file = File::open(path).context("Couldn't open profile for reading")?;
-            return ProfileAdapter::from_reader(name, file);
+            let file = File::open(&path).context("Couldn't open profile for reading")?;
+            let mut adapter = ProfileAdapter::from_reader(name, file)?;
+            adapter.modified = get_modified(&path);
+            return Ok(adapter);
         }
 
         bail!("Profile {} does not exist inside {:?}", name, directory);
     }
 
     pub fn default() -> Self {
-        ProfileAdapter::from_reader(
+        let mut adapter = ProfileAdapter::from_reader(
             DEFAULT_PROFILE_NAME.to_string(),
             Cursor::new(DEFAULT_PROFILE),
         )
-        .expect("Default profile isn't available")
+        .expect("Default profile isn't available");
+        adapter.embedded = true;
+        adapter
     }
 
     pub fn from_reader<R: Read + Seek>(name: String, reader: R) -> Result<Self> {
         let profile = Profile::load(reader)?;
-        Ok(Self { name, profile })
+        let mut adapter = Self {
+            name,
+            profile,
+            saved_state: vec![],
+            modified: None,
+            conflict: None,
+            embedded: false,
+        };
+        adapter.saved_state = adapter.get_saved_state()?;
+        Ok(adapter)
     }
 
     pub fn can_create_new_file(name: String, directory: &Path) -> Result<()> {
-        let path = directory.join(format!("{name}.goxlr"));
+        let path = get_named_path(directory, &name, "goxlr")?;
+        create_parent_path(&path)?;
         can_create_new_file(path)
     }
 
     pub fn save_as(&mut self, name: String, directory: &Path, overwrite: bool) -> Result<()> {
+        // Make sure the name is valid before we adopt it..
+        get_named_path(directory, &name, "goxlr")?;
         self.name = name;
         self.save(directory, overwrite)
     }
 
     pub fn save(&mut self, directory: &Path, overwrite: bool) -> Result<()> {
-        let name = &self.name;
-        let path = directory.join(format!("{name}.goxlr"));
+        let path = get_named_path(directory, &self.name, "goxlr")?;
         if !overwrite && path.is_file() {
             return Err(anyhow!("Profile exists, will not overwrite"));
         }
 
-        self.profile.save(path)?;
+        create_parent_path(&path)?;
+        self.profile.save(&path)?;
+        self.saved_state = self.get_saved_state()?;
+        self.modified = get_modified(&path);
+        self.conflict = None;
+        self.embedded = false;
+        Ok(())
+    }
+
+    /// Whether this is the embedded default profile, which hasn't been saved anywhere yet
+    pub fn is_embedded(&self) -> bool {
+        self.embedded
+    }
+
+    fn get_saved_state(&mut self) -> Result<Vec<u8>> {
+        // Profiles are written deterministically, so we can simply compare the output..
+        let mut state = vec![];
+        self.profile.settings_mut().write_to(&mut state)?;
+        Ok(state)
+    }
+
+    pub fn has_unsaved_changes(&mut self) -> bool {
+        match self.get_saved_state() {
+            Ok(state) => state != self.saved_state,
+            Err(_) => true,
+        }
+    }
+
+    /// Called when the profile directory changes, flags a conflict if our profile's file has
+    /// been modified by something other than us since we last loaded or saved it.
+    pub fn check_for_conflict(&mut self, directory: &Path) {
+        if self.conflict.is_some() {
+            return;
+        }
+
+        let Ok(path) = get_named_path(directory, &self.name, "goxlr") else {
+            return;
+        };
+        if let (Some(ours), Some(theirs)) = (self.modified, get_modified(&path)) {
+            if ours != theirs {
+                warn!("Profile {} has been changed on disk", self.name);
+                self.conflict = Some(ProfileConflict {
+                    name: self.name.clone(),
+                    unsaved_changes: self.has_unsaved_changes(),
+                });
+            }
+        }
+    }
+
+    pub fn conflict(&self) -> Option<ProfileConflict> {
+        self.conflict.clone()
+    }
+
+    /// Accepts the current file on disk as 'seen' without loading it, our version will replace
+    /// it on the next save.
+    pub fn keep_conflict(&mut self, directory: &Path) -> Result<()> {
+        let path = get_named_path(directory, &self.name, "goxlr")?;
+        self.modified = get_modified(&path);
+        self.conflict = None;
+        Ok(())
+    }
+
+    /// Loads the version of the profile on disk, and reapplies any changes we've made since the
+    /// last load or save on top of it. Where both sides changed the same value, ours wins.
+    pub fn merge_conflict(&mut self, directory: &Path) -> Result<()> {
+        let path = get_named_path(directory, &self.name, "goxlr")?;
+        let modified = get_modified(&path);
+        let file = File::open(&path).context("Couldn't open profile for reading")?;
+        let mut disk = Profile::load(file)?;
+
+        // Write the disk version back out, so it's laid out the same way as our states..
+        let mut theirs = vec![];
+        disk.settings_mut().write_to(&mut theirs)?;
+
+        let ours = ProfileStructure::from_xml(self.get_saved_state()?.as_slice())?;
+        let base = ProfileStructure::from_xml(self.saved_state.as_slice())?;
+        let changes = ours.changes_from(&base);
+        debug!("Merging {} local changes into {}", changes.len(), self.name);
+
+        let mut merged = vec![];
+        apply_changes(theirs.as_slice(), &mut merged, &changes)?;
+        *disk.settings_mut() = ProfileSettings::load(merged.as_slice())?;
+
+        self.profile = disk;
+        self.saved_state = theirs;
+        self.modified = modified;
+        self.conflict = None;
         Ok(())
     }
 
     pub fn write_preset(&mut self, name: String, directory: &Path) -> Result<()> {
-        let path = directory.join(format!("{name}.preset"));
+        let path = get_named_path(directory, &name, "preset")?;
+        create_parent_path(&path)?;
         self.profile.save_preset(path)?;
         Ok(())
     }
 
     pub fn delete_profile(&mut self, name: String, directory: &Path) -> Result<()> {
-        let path = directory.join(format!("{name}.goxlr"));
+        let path = get_named_path(directory, &name, "goxlr")?;
         if path.is_file() {
             remove_file(path)?;
         }
@@ -142,12 +263,23 @@ impl ProfileAdapter {
             .replace(new_tree);
     }
 
+    pub fn merge_profile(&mut self, other: ProfileAdapter, sections: &[ProfileMergeSection]) {
+        let selection = MergeSelection {
+            lighting: sections.contains(&ProfileMergeSection::Lighting),
+            mixer: sections.contains(&ProfileMergeSection::Mixer),
+            effects: sections.contains(&ProfileMergeSection::Effects),
+            sampler: sections.contains(&ProfileMergeSection::Sampler),
+        };
+        let settings = other.profile.into_settings();
+        self.profile.settings_mut().merge(settings, selection);
+    }
+
     pub fn load_preset(&mut self, name: String, directories: Vec<&Path>) -> Result<()> {
         let mut dir_list = "".to_string();
 
         // Loop through the provided directories, and try to find the preset..
         for directory in directories {
-            let path = directory.join(format!("{name}.preset"));
+            let path = get_named_path(directory, &name, "preset")?;
 
             if path.is_file() {
                 debug!("Loading Preset From {}", path.to_string_lossy());
@@ -332,6 +464,102 @@ impl ProfileAdapter {
             .swap(profile_fader_one, profile_fader_two);
     }
 
+    // Fader Pages
+    pub fn get_fader_pages_ipc(&self) -> FaderPages {
+        let pages = self.profile.settings().fader_pages();
+        FaderPages {
+            active: pages.active(),
+            pages: pages.pages().iter().map(|p| p.name().to_string()).collect(),
+        }
+    }
+
+    pub fn get_fader_page_count(&self) -> usize {
+        self.profile.settings().fader_pages().pages().len()
+    }
+
+    pub fn get_active_fader_page(&self) -> usize {
+        self.profile.settings().fader_pages().active()
+    }
+
+    pub fn get_fader_page_channels(&self, index: usize) -> Result<Vec<ChannelName>> {
+        let page = self.get_fader_page(index)?;
+        Ok(Faders::iter()
+            .map(|fader| profile_to_standard_channel(page.slot(fader).channel))
+            .collect())
+    }
+
+    fn get_fader_page(&self, index: usize) -> Result<&FaderPage> {
+        match self.profile.settings().fader_pages().page(index) {
+            Some(page) => Ok(page),
+            None => bail!("Fader Page {} does not exist", index),
+        }
+    }
+
+    pub fn add_fader_page(&mut self, name: String) {
+        let settings = self.profile.settings_mut();
+
+        // If this is the first page, the current layout needs to become a page itself..
+        if settings.fader_pages().pages().is_empty() {
+            let page = settings.capture_fader_page(String::from("Page 1"));
+            settings.fader_pages_mut().add_page(page);
+        }
+
+        let page = settings.capture_fader_page(name);
+        settings.fader_pages_mut().add_page(page);
+    }
+
+    pub fn remove_fader_page(&mut self, index: usize) -> Result<()> {
+        self.get_fader_page(index)?;
+        if index == self.get_active_fader_page() {
+            bail!("Unable to remove the active Fader Page");
+        }
+
+        self.profile
+            .settings_mut()
+            .fader_pages_mut()
+            .remove_page(index);
+        Ok(())
+    }
+
+    pub fn rename_fader_page(&mut self, index: usize, name: String) -> Result<()> {
+        match self
+            .profile
+            .settings_mut()
+            .fader_pages_mut()
+            .page_mut(index)
+        {
+            Some(page) => page.set_name(name),
+            None => bail!("Fader Page {} does not exist", index),
+        }
+        Ok(())
+    }
+
+    /// Stores the current fader layout into the active page
+    pub fn store_fader_page(&mut self) {
+        let settings = self.profile.settings_mut();
+        let active = settings.fader_pages().active();
+
+        if let Some(name) = settings
+            .fader_pages()
+            .page(active)
+            .map(|p| p.name().to_string())
+        {
+            let page = settings.capture_fader_page(name);
+            if let Some(existing) = settings.fader_pages_mut().page_mut(active) {
+                *existing = page;
+            }
+        }
+    }
+
+    pub fn load_fader_page(&mut self, index: usize) -> Result<()> {
+        let page = self.get_fader_page(index)?.clone();
+
+        let settings = self.profile.settings_mut();
+        settings.apply_fader_page(&page);
+        settings.fader_pages_mut().set_active(index);
+        Ok(())
+    }
+
     // Animation Settings
     pub fn set_animation_mode(&mut self, mode: goxlr_types::AnimationMode) -> Result<()> {
         self.profile
@@ -370,6 +598,52 @@ impl ProfileAdapter {
             .set_waterfall(standard_to_profile_animation_waterfall(waterfall))
     }
 
+    pub fn get_lighting_brightness(&self) -> u8 {
+        self.profile.settings().animation().brightness()
+    }
+
+    pub fn set_lighting_brightness(&mut self, brightness: u8) -> Result<()> {
+        self.profile
+            .settings_mut()
+            .animation_mut()
+            .set_brightness(brightness)
+    }
+
+    pub fn is_voice_meter_enabled(&self) -> bool {
+        self.profile.settings().animation().voice_meter()
+    }
+
+    pub fn set_voice_meter_enabled(&mut self, enabled: bool) {
+        self.profile
+            .settings_mut()
+            .animation_mut()
+            .set_voice_meter(enabled);
+    }
+
+    pub fn get_voice_meter_sensitivity(&self) -> u8 {
+        self.profile
+            .settings()
+            .animation()
+            .voice_meter_sensitivity()
+    }
+
+    pub fn set_voice_meter_sensitivity(&mut self, sensitivity: u8) -> Result<()> {
+        self.profile
+            .settings_mut()
+            .animation_mut()
+            .set_voice_meter_sensitivity(sensitivity)
+    }
+
+    pub fn set_voice_meter_colours(&mut self, low: String, high: String) -> Result<()> {
+        let low = Colour::fromrgb(low.as_str())?;
+        let high = Colour::fromrgb(high.as_str())?;
+        self.profile
+            .settings_mut()
+            .animation_mut()
+            .set_voice_meter_colours(low, high);
+        Ok(())
+    }
+
     pub fn get_animation_waterfall(&self) -> goxlr_types::WaterfallDirection {
         profile_to_standard_animation_waterfall(self.profile.settings().animation().waterfall())
     }
@@ -416,9 +690,27 @@ impl ProfileAdapter {
             scribble.text_bottom_middle(),
             scribble.text_top_left(),
             scribble.is_style_invert(),
+            scribble.has_left_bar(),
         )
     }
 
+    pub fn get_scribble_png(&self, fader: FaderName) -> &Vec<u8> {
+        self.profile
+            .get_scribble(standard_to_profile_fader(fader) as usize)
+    }
+
+    pub fn set_scribble_png(&mut self, fader: FaderName, png: Vec<u8>) {
+        self.profile
+            .set_scribble(standard_to_profile_fader(fader) as usize, png);
+    }
+
+    pub fn is_scribble_inverted(&self, fader: FaderName) -> bool {
+        self.profile
+            .settings()
+            .scribble(standard_to_profile_fader(fader))
+            .is_style_invert()
+    }
+
     pub fn set_scribble_icon(&mut self, fader: FaderName, icon: Option<String>) {
         let scribble = self
             .profile
@@ -426,6 +718,9 @@ impl ProfileAdapter {
             .scribble_mut(standard_to_profile_fader(fader));
 
         scribble.set_icon_file(icon);
+
+        // The stored image was built from the old settings, so no longer matches..
+        self.set_scribble_png(fader, vec![]);
     }
 
     pub fn set_scribble_text(&mut self, fader: FaderName, text: String) {
@@ -435,6 +730,7 @@ impl ProfileAdapter {
             .scribble_mut(standard_to_profile_fader(fader));
 
         scribble.set_text_bottom_middle(text);
+        self.set_scribble_png(fader, vec![]);
     }
 
     pub fn set_scribble_number(&mut self, fader: FaderName, text: String) {
@@ -444,6 +740,7 @@ impl ProfileAdapter {
             .scribble_mut(standard_to_profile_fader(fader));
 
         scribble.set_text_top_left(text);
+        self.set_scribble_png(fader, vec![]);
     }
 
     pub fn set_scribble_inverted(&mut self, fader: FaderName, inverted: bool) {
@@ -455,6 +752,16 @@ impl ProfileAdapter {
         scribble.set_scribble_inverted(inverted);
     }
 
+    pub fn set_scribble_left_bar(&mut self, fader: FaderName, left_bar: bool) {
+        let scribble = self
+            .profile
+            .settings_mut()
+            .scribble_mut(standard_to_profile_fader(fader));
+
+        scribble.set_left_bar(left_bar);
+        self.set_scribble_png(fader, vec![]);
+    }
+
     pub fn get_channel_volume(&self, channel: ChannelName) -> u8 {
         self.profile
             .settings()
@@ -481,48 +788,190 @@ impl ProfileAdapter {
             .set_channel_volume(standard_to_profile_channel(channel), volume)
     }
 
-    pub fn get_colour_map(&self, use_format_1_3_40: bool, blank_mute: bool) -> [u8; 520] {
-        let mut colour_array = [0; 520];
+    /// Returns a colour from a target's map as RGB, ignoring any state, for drawing previews
+    pub fn get_preview_colour(&self, target: ColourTargets, index: u8) -> [u8; 3] {
+        let [blue, green, red, _] = get_profile_colour_map(self.profile.settings(), target)
+            .colour_or_default(index)
+            .to_reverse_bytes();
+        [red, green, blue]
+    }
 
-        for colour in ColourTargets::iter() {
+    pub fn get_colour_map(
+        &self,
+        format: ColourMapFormat,
+        blank_mute: bool,
+        global_brightness: u8,
+        voice_meter: Option<u8>,
+    ) -> Vec<u8> {
+        let mut scheme = ColourScheme::from_fn(format, |colour, i| {
             let colour_map = get_profile_colour_map(self.profile.settings(), colour);
 
-            for i in 0..colour.get_colour_count() {
-                let position = colour.position(i, use_format_1_3_40);
-
-                // Ok, previously this was based on 'is_blank_when_dimmed', but turns out I misinterpreted
-                // what was going on there, if a sample button has no samples assigned to it, it'll go
-                // dark, so we need to check for that here.
-                match colour {
-                    ColourTargets::SamplerBottomLeft
-                    | ColourTargets::SamplerBottomRight
-                    | ColourTargets::SamplerTopLeft
-                    | ColourTargets::SamplerTopRight => {
-                        colour_array[position..position + 4]
-                            .copy_from_slice(&self.get_sampler_lighting(colour, i));
-                    }
-                    ColourTargets::FadeMeter1
-                    | ColourTargets::FadeMeter2
-                    | ColourTargets::FadeMeter3
-                    | ColourTargets::FadeMeter4 => {
-                        let array = if blank_mute {
-                            self.get_fader_lighting(colour, i)
-                        } else {
-                            colour_map.colour(i).to_reverse_bytes()
-                        };
-                        colour_array[position..position + 4].copy_from_slice(&array);
+            // Ok, previously this was based on 'is_blank_when_dimmed', but turns out I misinterpreted
+            // what was going on there, if a sample button has no samples assigned to it, it'll go
+            // dark, so we need to check for that here.
+            match colour {
+                ColourTargets::SamplerBottomLeft
+                | ColourTargets::SamplerBottomRight
+                | ColourTargets::SamplerTopLeft
+                | ColourTargets::SamplerTopRight => self.get_sampler_lighting(colour, i),
+                ColourTargets::FadeMeter1
+                | ColourTargets::FadeMeter2
+                | ColourTargets::FadeMeter3
+                | ColourTargets::FadeMeter4 => {
+                    if blank_mute {
+                        self.get_fader_lighting(colour, i)
+                    } else {
+                        colour_map.colour(i).to_reverse_bytes()
                     }
+                }
 
-                    _ => {
-                        // Update the correct 4 bytes in the map..
-                        colour_array[position..position + 4]
-                            .copy_from_slice(&colour_map.colour(i).to_reverse_bytes());
+                _ => {
+                    // Mute buttons set to go 'Off' when muted lose their primary colour..
+                    if i == 0 {
+                        if let Some(button) = map_colour_target_to_mute_button(colour) {
+                            if self.is_muted_light_off(button) {
+                                return [00, 00, 00, 00];
+                            }
+                        }
                     }
+                    colour_map.colour(i).to_reverse_bytes()
                 }
             }
+        });
+
+        // When the Voice Meter is active, it takes over the fader colours..
+        if let Some(lit) = voice_meter {
+            let animation = self.profile.settings().animation();
+            let low = animation.voice_meter_low().to_reverse_bytes();
+            let high = animation.voice_meter_high().to_reverse_bytes();
+
+            let faders = [
+                ColourTargets::FadeMeter1,
+                ColourTargets::FadeMeter2,
+                ColourTargets::FadeMeter3,
+                ColourTargets::FadeMeter4,
+            ];
+            for (index, target) in faders.iter().enumerate() {
+                let colour = if index < lit as usize {
+                    let mut colour = [0; 4];
+                    for byte in 0..4 {
+                        let (low, high) = (low[byte] as usize, high[byte] as usize);
+                        colour[byte] = ((low * (3 - index) + high * index) / 3) as u8;
+                    }
+                    colour
+                } else {
+                    [0, 0, 0, 0]
+                };
+                scheme.set_all(*target, colour);
+            }
         }
 
-        colour_array
+        self.apply_brightness(scheme.as_bytes_mut(), global_brightness);
+        scheme.into_bytes()
+    }
+
+    pub fn get_startup_sequence(&self) -> Option<LightingSequence> {
+        self.profile.settings().startup_sequence().cloned()
+    }
+
+    pub fn get_shutdown_sequence(&self) -> Option<LightingSequence> {
+        self.profile.settings().shutdown_sequence().cloned()
+    }
+
+    /// Renders the frame of a lighting sequence `elapsed` into it, laid out like get_colour_map
+    pub fn get_sequence_colour_map(
+        &self,
+        format: ColourMapFormat,
+        sequence: &LightingSequence,
+        elapsed: Duration,
+        global_brightness: u8,
+    ) -> Vec<u8> {
+        let mut scheme = ColourScheme::new(format);
+
+        let colours = sequence.colours();
+        if colours.is_empty() {
+            return scheme.into_bytes();
+        }
+
+        let duration = Duration::from_millis(sequence.duration().into());
+        let progress = if duration.is_zero() {
+            1.
+        } else {
+            (elapsed.as_secs_f64() / duration.as_secs_f64()).min(1.)
+        };
+
+        match sequence.style() {
+            SequenceStyle::Sweep => {
+                // Each colour gets an equal slice of time, in which the faders light up in turn..
+                let position = progress * colours.len() as f64;
+                let index = (position as usize).min(colours.len() - 1);
+                let lit = ((position - index as f64) * 4.).ceil() as usize;
+
+                let colour = colours[index].to_reverse_bytes();
+                let columns = [
+                    [
+                        ColourTargets::Fader1Mute,
+                        ColourTargets::FadeMeter1,
+                        ColourTargets::Scribble1,
+                    ],
+                    [
+                        ColourTargets::Fader2Mute,
+                        ColourTargets::FadeMeter2,
+                        ColourTargets::Scribble2,
+                    ],
+                    [
+                        ColourTargets::Fader3Mute,
+                        ColourTargets::FadeMeter3,
+                        ColourTargets::Scribble3,
+                    ],
+                    [
+                        ColourTargets::Fader4Mute,
+                        ColourTargets::FadeMeter4,
+                        ColourTargets::Scribble4,
+                    ],
+                ];
+                for target in columns.iter().take(lit).flatten() {
+                    scheme.set_all(*target, colour);
+                }
+            }
+            SequenceStyle::Fade => {
+                // Blend between each pair of colours, ending on the last one..
+                let position = progress * (colours.len() - 1) as f64;
+                let index = (position as usize).min(colours.len() - 1);
+                let step = position - index as f64;
+
+                let from = colours[index].to_reverse_bytes();
+                let to = colours.get(index + 1).unwrap_or(&colours[index]);
+                let to = to.to_reverse_bytes();
+
+                let mut colour = [0; 4];
+                for byte in 0..4 {
+                    let (from, to) = (from[byte] as f64, to[byte] as f64);
+                    colour[byte] = (from + (to - from) * step).round() as u8;
+                }
+
+                for target in ColourTargets::iter() {
+                    scheme.set_all(target, colour);
+                }
+            }
+        }
+
+        self.apply_brightness(scheme.as_bytes_mut(), global_brightness);
+        scheme.into_bytes()
+    }
+
+    fn apply_brightness(&self, colour_array: &mut [u8], global_brightness: u8) {
+        // Scale the colours down by the brightness, this is only applied to what we send, the
+        // colours stored in the profile are left alone.
+        let brightness = global_brightness as u32 * self.get_lighting_brightness() as u32 / 100;
+        if brightness < 100 {
+            for colour in colour_array.chunks_exact_mut(4) {
+                // Colours are stored as BGRA, so leave the last byte alone..
+                for channel in colour.iter_mut().take(3) {
+                    *channel = (*channel as u32 * brightness / 100) as u8;
+                }
+            }
+        }
     }
 
     fn get_sampler_lighting(&self, target: ColourTargets, index: u8) -> [u8; 4] {
@@ -641,6 +1090,9 @@ impl ProfileAdapter {
                 button,
                 ButtonLighting {
                     off_style,
+                    muted_style: self
+                        .get_muted_style(button)
+                        .map(profile_to_standard_mute_lighting_style),
                     colours: TwoColours {
                         colour_one: colour_map.colour_or_default(0).to_rgb(),
                         colour_two: colour_map.colour_or_default(1).to_rgb(),
@@ -736,6 +1188,23 @@ impl ProfileAdapter {
             waterfall_direction: profile_to_standard_animation_waterfall(
                 self.profile.settings().animation().waterfall(),
             ),
+            brightness: self.get_lighting_brightness(),
+            voice_meter: VoiceMeter {
+                enabled: self.is_voice_meter_enabled(),
+                sensitivity: self.get_voice_meter_sensitivity(),
+                low_colour: self
+                    .profile
+                    .settings()
+                    .animation()
+                    .voice_meter_low()
+                    .to_rgb(),
+                high_colour: self
+                    .profile
+                    .settings()
+                    .animation()
+                    .voice_meter_high()
+                    .to_rgb(),
+            },
         };
 
         Lighting {
@@ -748,6 +1217,65 @@ impl ProfileAdapter {
         }
     }
 
+    /// Applies a full lighting state, as returned by get_lighting_ipc(). Anything missing from the
+    /// maps is left as it is, and the 'supported' flag on the animation is ignored.
+    pub fn set_lighting(&mut self, lighting: Lighting) -> Result<()> {
+        let animation = lighting.animation;
+        self.set_animation_mode(animation.mode)?;
+        self.set_animation_mod1(animation.mod1)?;
+        self.set_animation_mod2(animation.mod2)?;
+        self.set_animation_waterfall(animation.waterfall_direction)?;
+        self.set_lighting_brightness(animation.brightness)?;
+        self.set_voice_meter_enabled(animation.voice_meter.enabled);
+        self.set_voice_meter_sensitivity(animation.voice_meter.sensitivity)?;
+        self.set_voice_meter_colours(
+            animation.voice_meter.low_colour,
+            animation.voice_meter.high_colour,
+        )?;
+
+        for (fader, fader_lighting) in lighting.faders {
+            self.set_fader_display(fader, fader_lighting.style);
+            let colours = fader_lighting.colours;
+            self.set_fader_colours(fader, colours.colour_one, colours.colour_two)?;
+        }
+
+        for (button, button_lighting) in lighting.buttons {
+            let colours = button_lighting.colours;
+            self.set_button_colours(button, colours.colour_one, Some(&colours.colour_two))?;
+            self.set_button_off_style(button, button_lighting.off_style);
+            if let Some(muted_style) = button_lighting.muted_style {
+                self.set_button_muted_style(button, muted_style)?;
+            }
+        }
+
+        for (target, colour) in lighting.simple {
+            self.set_simple_colours(target, colour.colour_one)?;
+        }
+
+        for (target, sampler_lighting) in lighting.sampler {
+            let colours = sampler_lighting.colours;
+            self.set_sampler_colours(
+                target,
+                colours.colour_one,
+                colours.colour_two,
+                colours.colour_three,
+            )?;
+            self.set_sampler_off_style(target, sampler_lighting.off_style);
+            self.sync_sample_if_active(target)?;
+        }
+
+        for (target, colours) in lighting.encoders {
+            self.set_encoder_colours(
+                target,
+                colours.colour_one,
+                colours.colour_two,
+                colours.colour_three,
+            )?;
+        }
+
+        Ok(())
+    }
+
     pub fn get_effects_ipc(
         &self,
         is_device_mini: bool,
@@ -775,7 +1303,9 @@ impl ProfileAdapter {
 
         let reverb = Reverb {
             style: profile_to_standard_reverb_style(self.get_active_reverb_profile().style()),
-            amount: self.get_active_reverb_profile().get_percentage_amount(),
+            amount: ReverbAmount::from_knob_position(self.get_reverb_value())
+                .map(|amount| amount.percent())
+                .unwrap_or_default(),
             decay: self.get_active_reverb_profile().get_decay_millis(),
             early_level: self.get_active_reverb_profile().early_level(),
             tail_level: self.get_active_reverb_profile().tail_level(),
@@ -791,7 +1321,9 @@ impl ProfileAdapter {
 
         let echo = Echo {
             style: profile_to_standard_echo_style(self.get_active_echo_profile().style()),
-            amount: self.get_active_echo_profile().get_percentage_amount(),
+            amount: EchoAmount::from_knob_position(self.get_echo_value())
+                .map(|amount| amount.percent())
+                .unwrap_or_default(),
             feedback: self.get_active_echo_profile().feedback_control(),
             tempo: self.get_active_echo_profile().tempo(),
             delay_left: self.get_active_echo_profile().time_left(),
@@ -868,9 +1400,27 @@ impl ProfileAdapter {
                 robot,
                 hard_tune,
             },
+            levels: EffectLevels {
+                reverb: self.get_effect_level(EncoderName::Reverb),
+                echo: self.get_effect_level(EncoderName::Echo),
+                pitch: self.get_effect_level(EncoderName::Pitch),
+                gender: self.get_effect_level(EncoderName::Gender),
+            },
         })
     }
 
+    fn get_effect_level(&self, encoder: EncoderName) -> EffectLevel {
+        let raw = self.get_encoder_position(encoder);
+        let (min, max) = self.get_encoder_range(encoder);
+
+        EffectLevel {
+            raw,
+            percent: ((raw as i32 - min as i32) * 100 / (max as i32 - min as i32)) as u8,
+            value: self.get_encoder_effect_value(encoder).unwrap_or_default(),
+            sensitivity: self.get_encoder_sensitivity(encoder),
+        }
+    }
+
     pub fn get_sampler_ipc(
         &self,
         is_device_mini: bool,
@@ -952,6 +1502,8 @@ impl ProfileAdapter {
             bottom_text: scribble.text_bottom_middle(),
             left_text: scribble.text_top_left(),
             inverted: scribble.is_style_invert(),
+            left_bar: scribble.has_left_bar(),
+            display_text: None,
         })
     }
 
@@ -1092,7 +1644,7 @@ impl ProfileAdapter {
         }
 
         if self.get_mute_chat_button_on() {
-            return ButtonStates::Colour1;
+            return self.get_muted_colour_state(Button::Cough);
         }
 
         return match self
@@ -1108,6 +1660,80 @@ impl ProfileAdapter {
         };
     }
 
+    /** Muted Lighting Styles **/
+    fn get_muted_style(&self, button: Button) -> Option<MuteLightingStyle> {
+        match button {
+            Button::Fader1Mute => Some(self.get_mute_button(FaderName::A).muted_style()),
+            Button::Fader2Mute => Some(self.get_mute_button(FaderName::B).muted_style()),
+            Button::Fader3Mute => Some(self.get_mute_button(FaderName::C).muted_style()),
+            Button::Fader4Mute => Some(self.get_mute_button(FaderName::D).muted_style()),
+            Button::Cough => Some(self.get_chat_mute_button().muted_style()),
+            _ => None,
+        }
+    }
+
+    pub fn set_button_muted_style(
+        &mut self,
+        button: Button,
+        style: BasicMuteLightingStyle,
+    ) -> Result<()> {
+        let style = standard_to_profile_mute_lighting_style(style);
+        match button {
+            Button::Fader1Mute => self
+                .get_mute_button_mut(FaderName::A)
+                .set_muted_style(style),
+            Button::Fader2Mute => self
+                .get_mute_button_mut(FaderName::B)
+                .set_muted_style(style),
+            Button::Fader3Mute => self
+                .get_mute_button_mut(FaderName::C)
+                .set_muted_style(style),
+            Button::Fader4Mute => self
+                .get_mute_button_mut(FaderName::D)
+                .set_muted_style(style),
+            Button::Cough => self.get_chat_mute_button_mut().set_muted_style(style),
+            _ => bail!("Muted Style can only be set on Mute buttons"),
+        }
+        Ok(())
+    }
+
+    fn get_muted_colour_state(&self, button: Button) -> ButtonStates {
+        // 'Off' is handled by blanking the colour in the colour map..
+        match self.get_muted_style(button).unwrap_or_default() {
+            MuteLightingStyle::Colour1 | MuteLightingStyle::Off => ButtonStates::Colour1,
+            MuteLightingStyle::Dimmed => ButtonStates::DimmedColour1,
+            MuteLightingStyle::Colour2 => ButtonStates::Colour2,
+        }
+    }
+
+    fn is_muted_light_off(&self, button: Button) -> bool {
+        if self.get_muted_style(button) != Some(MuteLightingStyle::Off) {
+            return false;
+        }
+
+        // A flashing button (Muted to All) still needs its colour to flash..
+        if button == Button::Cough {
+            return self.get_mute_chat_button_on() && !self.get_mute_chat_button_blink();
+        }
+
+        let colour_map =
+            get_profile_colour_map(self.profile.settings(), standard_to_colour_target(button));
+        colour_map.state() == &Some(ColourState::On) && colour_map.blink() != &Some(ColourState::On)
+    }
+
+    // If any mute button goes 'Off', the colour map will need updating when mute states change
+    pub fn has_muted_light_off(&self) -> bool {
+        [
+            Button::Fader1Mute,
+            Button::Fader2Mute,
+            Button::Fader3Mute,
+            Button::Fader4Mute,
+            Button::Cough,
+        ]
+        .into_iter()
+        .any(|button| self.get_muted_style(button) == Some(MuteLightingStyle::Off))
+    }
+
     pub fn get_cough_status(&self) -> CoughButton {
         let (_, muted_to_x, muted_to_all, _) = self.get_mute_chat_button_state();
         let mic_state = if muted_to_all {
@@ -1533,6 +2159,87 @@ impl ProfileAdapter {
             .get_preset_mut(current)
     }
 
+    pub fn get_encoder_position(&self, encoder: EncoderName) -> i8 {
+        match encoder {
+            EncoderName::Pitch => self.get_pitch_encoder_position(),
+            EncoderName::Gender => self.get_gender_value(),
+            EncoderName::Reverb => self.get_reverb_value(),
+            EncoderName::Echo => self.get_echo_value(),
+        }
+    }
+
+    pub fn set_encoder_position(&mut self, encoder: EncoderName, position: i8) -> Result<()> {
+        match encoder {
+            EncoderName::Pitch => self.set_pitch_knob_position(position),
+            EncoderName::Gender => self.set_gender_value(position),
+            EncoderName::Reverb => self.set_reverb_value(position),
+            EncoderName::Echo => self.set_echo_value(position),
+        }
+    }
+
+    pub fn get_encoder_range(&self, encoder: EncoderName) -> (i8, i8) {
+        match encoder {
+            EncoderName::Pitch => {
+                if !self.is_hardtune_enabled(true) {
+                    return (-24, 24);
+                }
+
+                // With Hardtune, each step of the knob is a full octave..
+                match self.get_active_pitch_profile().style() {
+                    PitchStyle::Narrow => (-1, 1),
+                    PitchStyle::Wide => (-2, 2),
+                }
+            }
+            EncoderName::Gender => (-24, 24),
+            EncoderName::Reverb | EncoderName::Echo => (0, 24),
+        }
+    }
+
+    pub fn get_encoder_sensitivity(&self, encoder: EncoderName) -> Option<u8> {
+        self.profile
+            .settings()
+            .encoder_sensitivity()
+            .steps(standard_to_profile_encoder(encoder))
+    }
+
+    pub fn set_encoder_sensitivity(&mut self, encoder: EncoderName, steps: Option<u8>) {
+        self.profile
+            .settings_mut()
+            .encoder_sensitivity_mut()
+            .set_steps(standard_to_profile_encoder(encoder), steps);
+    }
+
+    // The value sent to the GoXLR for the encoder, in whatever unit that effect uses..
+    pub fn get_encoder_effect_value(&self, encoder: EncoderName) -> Result<i32> {
+        Ok(match encoder {
+            EncoderName::Pitch => self.get_active_pitch_profile().get_pitch_value().into(),
+            EncoderName::Gender => self.get_active_gender_profile().amount().into(),
+            EncoderName::Reverb => {
+                ReverbAmount::from_knob_position(self.get_reverb_value())?.into()
+            }
+            EncoderName::Echo => EchoAmount::from_knob_position(self.get_echo_value())?.into(),
+        })
+    }
+
+    pub fn set_encoder_effect_value(&mut self, encoder: EncoderName, value: i32) -> Result<()> {
+        match encoder {
+            EncoderName::Pitch => {
+                let hardtune_enabled = self.is_hardtune_enabled(true);
+                self.get_active_pitch_profile_mut()
+                    .set_pitch_value(i8::try_from(value)?, hardtune_enabled)
+            }
+            EncoderName::Gender => self
+                .get_active_gender_profile_mut()
+                .set_amount(i8::try_from(value)?),
+            EncoderName::Reverb => {
+                self.set_reverb_value(ReverbAmount::from_decibels(value)?.knob_position())
+            }
+            EncoderName::Echo => {
+                self.set_echo_value(EchoAmount::from_decibels(value)?.knob_position())
+            }
+        }
+    }
+
     pub fn set_megaphone_style(&mut self, style: goxlr_types::MegaphoneStyle) -> Result<()> {
         self.get_active_megaphone_profile_mut()
             .set_style(standard_to_profile_megaphone_style(style))?;
@@ -2082,6 +2789,25 @@ impl ProfileAdapter {
         Ok(())
     }
 
+    pub fn set_sample_trim(
+        &mut self,
+        bank: goxlr_types::SampleBank,
+        button: goxlr_types::SampleButtons,
+        index: usize,
+        start: f32,
+        stop: f32,
+    ) -> Result<()> {
+        let track = self
+            .profile
+            .settings_mut()
+            .sample_button_mut(standard_to_profile_sample_button(button))
+            .get_stack_mut(standard_to_profile_sample_bank(bank))
+            .get_track_by_index_mut(index)?;
+
+        track.set_positions(start, stop)?;
+        Ok(())
+    }
+
     pub fn remove_sample_file_by_index(
         &mut self,
         bank: goxlr_types::SampleBank,
@@ -2529,7 +3255,7 @@ impl ProfileAdapter {
 
         if let Some(state) = colour_map.state() {
             if state == &ColourState::On {
-                return ButtonStates::Colour1;
+                return self.get_muted_colour_state(usb_to_standard_button(button));
             }
         }
 
@@ -2643,6 +3369,24 @@ fn standard_to_profile_colour_off_style(value: BasicColourOffStyle) -> ColourOff
     }
 }
 
+fn standard_to_profile_mute_lighting_style(value: BasicMuteLightingStyle) -> MuteLightingStyle {
+    match value {
+        BasicMuteLightingStyle::Colour1 => MuteLightingStyle::Colour1,
+        BasicMuteLightingStyle::Dimmed => MuteLightingStyle::Dimmed,
+        BasicMuteLightingStyle::Colour2 => MuteLightingStyle::Colour2,
+        BasicMuteLightingStyle::Off => MuteLightingStyle::Off,
+    }
+}
+
+fn profile_to_standard_mute_lighting_style(value: MuteLightingStyle) -> BasicMuteLightingStyle {
+    match value {
+        MuteLightingStyle::Colour1 => BasicMuteLightingStyle::Colour1,
+        MuteLightingStyle::Dimmed => BasicMuteLightingStyle::Dimmed,
+        MuteLightingStyle::Colour2 => BasicMuteLightingStyle::Colour2,
+        MuteLightingStyle::Off => BasicMuteLightingStyle::Off,
+    }
+}
+
 fn profile_to_standard_colour_off_style(value: ColourOffStyle) -> BasicColourOffStyle {
     match value {
         ColourOffStyle::Dimmed => BasicColourOffStyle::Dimmed,
@@ -2787,6 +3531,15 @@ fn standard_to_profile_preset(value: EffectBankPresets) -> Preset {
     }
 }
 
+fn standard_to_profile_encoder(value: EncoderName) -> Encoders {
+    match value {
+        EncoderName::Pitch => Encoders::Pitch,
+        EncoderName::Gender => Encoders::Gender,
+        EncoderName::Reverb => Encoders::Reverb,
+        EncoderName::Echo => Encoders::Echo,
+    }
+}
+
 fn standard_to_profile_fader(value: FaderName) -> Faders {
     match value {
         FaderName::A => Faders::A,
@@ -2997,6 +3750,17 @@ pub fn standard_to_colour_target(target: Button) -> ColourTargets {
     }
 }
 
+fn map_colour_target_to_mute_button(target: ColourTargets) -> Option<Button> {
+    match target {
+        ColourTargets::Fader1Mute => Some(Button::Fader1Mute),
+        ColourTargets::Fader2Mute => Some(Button::Fader2Mute),
+        ColourTargets::Fader3Mute => Some(Button::Fader3Mute),
+        ColourTargets::Fader4Mute => Some(Button::Fader4Mute),
+        ColourTargets::MicrophoneMute => Some(Button::Cough),
+        _ => None,
+    }
+}
+
 pub fn get_mini_colour_targets() -> Vec<Button> {
     vec![
         Button::Fader1Mute,
@@ -3383,3 +4147,7 @@ pub fn version_newer_or_equal_to(version: &VersionNumber, comparison: VersionNum
 
     true
 }
+
+fn get_modified(path: &Path) -> Option<SystemTime> {
+    path.metadata().and_then(|m| m.modified()).ok()
+}
@@ -0,0 +1,143 @@
+/*
+   A line based log file which is written from its own thread, so nothing writing to it is held
+   up by the disk. Once the file gets too big it's rotated out (name.ext -> name.1.ext ->
+   name.2.ext..), and the oldest is dropped.
+*/
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use anyhow::Result;
+use log::warn;
+
+pub struct RotatingLog {
+    path: PathBuf,
+    max_size: u64,
+    max_files: usize,
+    writer: Option<(BufWriter<File>, u64)>,
+}
+
+impl RotatingLog {
+    pub fn new(path: PathBuf, max_size: u64, max_files: usize) -> Self {
+        Self {
+            path,
+            max_size,
+            max_files,
+            writer: None,
+        }
+    }
+
+    /// Starts a writer thread for this log, every item sent to it is turned into a line by
+    /// `format` on that thread, and appended to the file. The thread runs until every sender
+    /// has been dropped.
+    pub fn spawn<T, F>(self, format: F) -> Sender<T>
+    where
+        T: Send + 'static,
+        F: Fn(T) -> Result<String> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || self.run(receiver, format));
+        sender
+    }
+
+    // Everything waiting is written in one go, and only then flushed, so a burst of lines (like
+    // a fader being dragged) doesn't hit the disk for every single one..
+    fn run<T>(mut self, receiver: Receiver<T>, format: impl Fn(T) -> Result<String>) {
+        while let Ok(item) = receiver.recv() {
+            let mut next = Some(item);
+            while let Some(item) = next {
+                if let Err(e) = format(item).and_then(|line| self.write_line(&line)) {
+                    warn!("Unable to write to {}: {}", self.path.to_string_lossy(), e);
+                }
+                next = receiver.try_recv().ok();
+            }
+
+            if let Err(e) = self.flush() {
+                warn!("Unable to write to {}: {}", self.path.to_string_lossy(), e);
+            }
+        }
+    }
+
+    fn write_line(&mut self, line: &str) -> Result<()> {
+        let result = self.write_line_inner(line);
+        if result.is_err() {
+            // Start again with a fresh handle next time..
+            self.writer = None;
+        }
+        result
+    }
+
+    fn write_line_inner(&mut self, line: &str) -> Result<()> {
+        if self
+            .writer
+            .as_ref()
+            .is_some_and(|(_, size)| *size >= self.max_size)
+        {
+            if let Some((mut file, _)) = self.writer.take() {
+                file.flush()?;
+            }
+            self.rotate()?;
+        }
+
+        let (file, size) = match &mut self.writer {
+            Some(writer) => writer,
+            None => {
+                let file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                let size = file.metadata()?.len();
+                self.writer.insert((BufWriter::new(file), size))
+            }
+        };
+
+        writeln!(file, "{}", line)?;
+        *size += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        if let Some((file, _)) = &mut self.writer {
+            if let Err(e) = file.flush() {
+                self.writer = None;
+                return Err(e.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn rotate(&self) -> Result<()> {
+        // Windows won't rename over an existing file, so make space for the oldest first..
+        let oldest = get_path(&self.path, self.max_files);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (0..self.max_files).rev() {
+            let path = get_path(&self.path, index);
+            if path.exists() {
+                fs::rename(path, get_path(&self.path, index + 1))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Gets the path of a log file, 0 being the current file and anything above that a rotated one.
+pub fn get_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    match path.extension() {
+        Some(extension) => path.with_file_name(format!(
+            "{}.{}.{}",
+            stem,
+            index,
+            extension.to_string_lossy()
+        )),
+        None => path.with_file_name(format!("{}.{}", stem, index)),
+    }
+}
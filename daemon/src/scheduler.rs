@@ -0,0 +1,102 @@
+use crate::primary_worker::{DeviceCommand, DeviceSender};
+use crate::settings::SettingsHandle;
+use crate::shutdown::Shutdown;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Datelike, Local, NaiveTime, TimeZone};
+use goxlr_ipc::{CommandSource, GoXLRCommand, ScheduledAction};
+use log::{debug, info, warn};
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time;
+
+/// Parses the 'HH:MM' time of a scheduled action.
+pub fn parse_time(time: &str) -> Result<NaiveTime> {
+    NaiveTime::parse_from_str(time, "%H:%M").map_err(|e| anyhow!("Invalid Time {}: {}", time, e))
+}
+
+pub async fn spawn_scheduler(
+    settings: SettingsHandle,
+    usb_tx: DeviceSender,
+    mut shutdown: Shutdown,
+) {
+    info!("Starting Scheduler..");
+
+    // Anything due before the daemon started is ignored, we only catch up on time which passed
+    // while we were running (such as the machine being asleep)..
+    let mut last_check = Local::now();
+    let mut ticker = time::interval(Duration::from_secs(10));
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                let now = Local::now();
+
+                // The clock has gone backwards, so just carry on from here..
+                if now < last_check {
+                    last_check = now;
+                    continue;
+                }
+
+                for (serial, name, action) in settings.get_scheduled_actions().await {
+                    match is_due(&action, last_check, now) {
+                        Ok(true) => {
+                            debug!("Running Scheduled Action {} for {}", name, serial);
+                            run_commands(&usb_tx, &serial, &action.commands).await;
+                        }
+                        Ok(false) => {}
+                        Err(e) => warn!("Unable to check Scheduled Action {}: {}", name, e),
+                    }
+                }
+                last_check = now;
+            },
+            () = shutdown.recv() => {
+                info!("Shutting down Scheduler");
+                return;
+            }
+        }
+    }
+}
+
+// Whether the action was due at some point after `from`, up to and including `to`, however long
+// that gap is, an action will only be run once..
+fn is_due(action: &ScheduledAction, from: DateTime<Local>, to: DateTime<Local>) -> Result<bool> {
+    let time = parse_time(&action.time)?;
+
+    let mut date = from.date_naive();
+    while date <= to.date_naive() {
+        let day = date.weekday().num_days_from_monday();
+        let scheduled_today =
+            action.days.is_empty() || action.days.iter().any(|d| *d as u32 == day);
+
+        // If the clocks went forward over this time, it doesn't exist today, so is skipped..
+        if let Some(due) = Local.from_local_datetime(&date.and_time(time)).earliest() {
+            if scheduled_today && due > from && due <= to {
+                return Ok(true);
+            }
+        }
+
+        let Some(next) = date.succ_opt() else {
+            break;
+        };
+        date = next;
+    }
+    Ok(false)
+}
+
+async fn run_commands(usb_tx: &DeviceSender, serial: &str, commands: &[GoXLRCommand]) {
+    for command in commands {
+        let (tx, rx) = oneshot::channel();
+        let message = DeviceCommand::RunDeviceCommand(
+            serial.to_string(),
+            command.clone(),
+            CommandSource::Schedule,
+            tx,
+        );
+        if usb_tx.send(message).await.is_err() {
+            return;
+        }
+        if let Ok(Err(e)) = rx.await {
+            warn!("Error running Scheduled command {:?}: {}", command, e);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+/*
+   Works out what an HTTP (or WebSocket) client is allowed to do.
+
+   Anything connecting from this machine has full access, as it could just as easily use the IPC
+   socket. When network access is enabled, other machines need to present an API token, either
+   as an 'Authorization: Bearer' header, or as a 'token' query parameter (browsers can't set
+   headers on a WebSocket). ReadOnly tokens can only make requests which don't change anything,
+   and tokens can only be created or removed locally.
+
+   Anything which stores a program (or path) for the daemon to run later is also local only, as
+   otherwise a Control token would be enough to run anything on this machine. Commands can be
+   nested (batches, hotkeys, schedules, bindings..), so those are checked all the way down.
+*/
+
+use std::collections::HashMap;
+
+use actix_web::http::header::AUTHORIZATION;
+use actix_web::{web, HttpRequest};
+use goxlr_ipc::{
+    ButtonBinding, CommandSource, DaemonCommand, DaemonRequest, GoXLRCommand, TokenScope,
+};
+
+use crate::settings::SettingsHandle;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Access {
+    Local,
+
+    // The name of the token presented, and what it's allowed to do
+    Token(String, TokenScope),
+}
+
+impl Access {
+    pub fn source(&self) -> CommandSource {
+        match self {
+            Access::Local => CommandSource::Http,
+            Access::Token(name, _) => CommandSource::Token(name.clone()),
+        }
+    }
+}
+
+/// Returns the access a request has, or None if it's remote and hasn't presented a valid token
+pub async fn get_access(req: &HttpRequest, settings: &SettingsHandle) -> Option<Access> {
+    if req.peer_addr().is_some_and(|addr| addr.ip().is_loopback()) {
+        return Some(Access::Local);
+    }
+
+    let token = get_token(req)?;
+    settings
+        .get_api_token(&token)
+        .await
+        .map(|(name, scope)| Access::Token(name, scope))
+}
+
+pub fn is_permitted(access: &Access, request: &DaemonRequest) -> bool {
+    match access {
+        Access::Local => true,
+        Access::Token(_, TokenScope::Control) => !is_local_only(request),
+        Access::Token(_, TokenScope::ReadOnly) => matches!(
+            request,
+            DaemonRequest::Ping
+                | DaemonRequest::Handshake(_)
+                | DaemonRequest::Subscribe
+                | DaemonRequest::GetStatus
+                | DaemonRequest::GetMicLevel(_)
+                | DaemonRequest::GetGateLevel(_)
+                | DaemonRequest::GetChannelLevels(_)
+                | DaemonRequest::ValidateCommand(_, _)
+                | DaemonRequest::GetEvents(_)
+                | DaemonRequest::GetSampleWaveform(_, _)
+                | DaemonRequest::GetProfilePreview(_)
+                | DaemonRequest::GetScribbleImage(_, _)
+                | DaemonRequest::GetLighting(_)
+                | DaemonRequest::GetFiles
+                | DaemonRequest::GetDaemonMetrics
+                | DaemonRequest::GetAuditLog(_)
+        ),
+    }
+}
+
+fn is_local_only(request: &DaemonRequest) -> bool {
+    match request {
+        DaemonRequest::CreateApiToken(_, _) => true,
+        DaemonRequest::Daemon(command) => matches!(
+            command,
+            DaemonCommand::RemoveApiToken(_)
+                | DaemonCommand::SetEventHook(_, _)
+                | DaemonCommand::SetActivatorPath(_)
+        ),
+        DaemonRequest::Command(_, command) => is_local_only_command(command),
+        _ => false,
+    }
+}
+
+fn is_local_only_command(command: &GoXLRCommand) -> bool {
+    let any_local_only = |commands: &Vec<GoXLRCommand>| commands.iter().any(is_local_only_command);
+
+    match command {
+        GoXLRCommand::SetButtonBinding(_, Some(ButtonBinding::Execute(_))) => true,
+        GoXLRCommand::SetButtonBinding(_, Some(ButtonBinding::Commands(commands)))
+        | GoXLRCommand::SetHotkey(_, Some(commands))
+        | GoXLRCommand::SetShutdownCommands(commands)
+        | GoXLRCommand::SetSleepCommands(commands)
+        | GoXLRCommand::SetWakeCommands(commands)
+        | GoXLRCommand::ExecuteBatch(commands) => any_local_only(commands),
+        GoXLRCommand::SetScheduledAction(_, Some(action)) => any_local_only(&action.commands),
+        _ => false,
+    }
+}
+
+fn get_token(req: &HttpRequest) -> Option<String> {
+    let header = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    if let Some(token) = header {
+        return Some(token.trim().to_string());
+    }
+
+    web::Query::<HashMap<String, String>>::from_query(req.query_string())
+        .ok()
+        .and_then(|params| params.get("token").cloned())
+}
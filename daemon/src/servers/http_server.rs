@@ -29,19 +29,28 @@ use tokio::sync::Mutex;
 use crate::files::{find_file_in_path, FilePaths};
 use crate::PatchEvent;
 use goxlr_ipc::{
-    DaemonRequest, DaemonResponse, DaemonStatus, HttpSettings, WebsocketRequest, WebsocketResponse,
+    CommandSource, DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand, HttpSettings,
+    StreamDeckState, StreamDeckVolumeStep, WebsocketRequest, WebsocketResponse,
 };
 use goxlr_scribbles::get_scribble_png;
-use goxlr_types::FaderName;
+use goxlr_types::{FaderName, MuteState};
 
+use crate::locale::localise_error;
 use crate::primary_worker::DeviceSender;
-use crate::servers::server_packet::handle_packet;
+use crate::servers::auth::{get_access, is_permitted, Access};
+use crate::servers::rate_limit::{rate_limit_error, RateLimit, RateLimiter};
+use crate::servers::server_packet::{error_response, handle_packet};
+use crate::settings::SettingsHandle;
 
 const WEB_CONTENT: Dir = include_dir!("./daemon/web-content/");
+const MIN_STREAMDECK_SIZE: u32 = 16;
+const MAX_STREAMDECK_SIZE: u32 = 512;
 
 struct Websocket {
     usb_tx: DeviceSender,
     broadcast_tx: BroadcastSender<PatchEvent>,
+    access: Access,
+    rate_limiter: RateLimiter,
 }
 
 impl Actor for Websocket {
@@ -96,13 +105,42 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Websocket {
         match msg {
             Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
             Ok(ws::Message::Text(text)) => {
+                let limit = self.rate_limiter.check();
                 match serde_json::from_slice::<WebsocketRequest>(text.as_ref()) {
+                    Ok(request) if limit != RateLimit::Allowed => {
+                        let response = WebsocketResponse {
+                            id: request.id,
+                            data: DaemonResponse::DetailedError(localise_error(rate_limit_error())),
+                        };
+
+                        // Sent directly, rather than via the actor, so it goes out before we close..
+                        if let Ok(response) = serde_json::to_string(&response) {
+                            ctx.text(response);
+                        }
+                        if limit == RateLimit::Disconnect {
+                            warn!("Disconnecting WebSocket, too many requests");
+                            ctx.close(Some(CloseReason {
+                                code: CloseCode::Policy,
+                                description: Some(rate_limit_error().to_string()),
+                            }));
+                            ctx.stop();
+                        }
+                    }
+                    Ok(request) if !is_permitted(&self.access, &request.data) => {
+                        ctx.address().do_send(WsResponse(WebsocketResponse {
+                            id: request.id,
+                            data: DaemonResponse::Error(String::from(
+                                "This API Token does not permit this request",
+                            )),
+                        }));
+                    }
                     Ok(request) => {
                         let recipient = ctx.address().recipient();
                         let mut usb_tx = self.usb_tx.clone();
+                        let source = self.access.source();
                         let future = async move {
                             let request_id = request.id;
-                            let result = handle_packet(request.data, &mut usb_tx).await;
+                            let result = handle_packet(request.data, &mut usb_tx, source).await;
                             match result {
                                 Ok(resp) => match resp {
                                     DaemonResponse::Ok => {
@@ -117,6 +155,12 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Websocket {
                                             data: DaemonResponse::Error(error),
                                         }));
                                     }
+                                    DaemonResponse::DetailedError(error) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::DetailedError(error),
+                                        }));
+                                    }
                                     DaemonResponse::Status(status) => {
                                         recipient.do_send(WsResponse(WebsocketResponse {
                                             id: request_id,
@@ -129,12 +173,90 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for Websocket {
                                             data: DaemonResponse::MicLevel(level),
                                         }))
                                     }
+                                    DaemonResponse::GateLevel(gate) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::GateLevel(gate),
+                                        }))
+                                    }
+                                    DaemonResponse::ChannelLevels(levels) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::ChannelLevels(levels),
+                                        }))
+                                    }
+                                    DaemonResponse::Events(events) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Events(events),
+                                        }))
+                                    }
+                                    DaemonResponse::SampleWaveform(waveform) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::SampleWaveform(waveform),
+                                        }))
+                                    }
+                                    DaemonResponse::Handshake(version) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Handshake(version),
+                                        }))
+                                    }
+                                    DaemonResponse::ProfilePreview(png) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::ProfilePreview(png),
+                                        }))
+                                    }
+                                    DaemonResponse::ScribbleImage(image) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::ScribbleImage(image),
+                                        }))
+                                    }
+                                    DaemonResponse::Lighting(lighting) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Lighting(lighting),
+                                        }))
+                                    }
+                                    DaemonResponse::Files(files) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Files(files),
+                                        }))
+                                    }
+                                    DaemonResponse::Metrics(metrics) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Metrics(metrics),
+                                        }))
+                                    }
+                                    DaemonResponse::Validation(list) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::Validation(list),
+                                        }))
+                                    }
+                                    DaemonResponse::ApiToken(token) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::ApiToken(token),
+                                        }))
+                                    }
+                                    DaemonResponse::AuditLog(entries) => {
+                                        recipient.do_send(WsResponse(WebsocketResponse {
+                                            id: request_id,
+                                            data: DaemonResponse::AuditLog(entries),
+                                        }))
+                                    }
                                     _ => {}
                                 },
                                 Err(error) => {
                                     recipient.do_send(WsResponse(WebsocketResponse {
                                         id: request_id,
-                                        data: DaemonResponse::Error(error.to_string()),
+                                        data: error_response(error),
                                     }));
                                 }
                             }
@@ -200,6 +322,7 @@ struct AppData {
     usb_tx: DeviceSender,
     broadcast_tx: BroadcastSender<PatchEvent>,
     file_paths: FilePaths,
+    settings: SettingsHandle,
 }
 
 pub async fn spawn_http_server(
@@ -208,6 +331,7 @@ pub async fn spawn_http_server(
     broadcast_tx: tokio::sync::broadcast::Sender<PatchEvent>,
     settings: HttpSettings,
     file_paths: FilePaths,
+    settings_handle: SettingsHandle,
 ) {
     let server = HttpServer::new(move || {
         let cors = Cors::default()
@@ -224,12 +348,17 @@ pub async fn spawn_http_server(
                 broadcast_tx: broadcast_tx.clone(),
                 usb_tx: usb_tx.clone(),
                 file_paths: file_paths.clone(),
+                settings: settings_handle.clone(),
             })))
             .service(execute_command)
             .service(get_devices)
+            .service(get_metrics)
             .service(get_sample)
             .service(get_scribble)
             .service(get_path)
+            .service(get_streamdeck_state)
+            .service(get_streamdeck_fader)
+            .service(streamdeck_volume_step)
             .service(websocket)
             .default_service(web::to(default))
     })
@@ -273,12 +402,17 @@ async fn websocket(
     req: HttpRequest,
     stream: web::Payload,
 ) -> Result<HttpResponse, actix_web::Error> {
+    let Some(access) = check_access(&usb_mutex, &req).await else {
+        return Ok(HttpResponse::Unauthorized().finish());
+    };
     let data = usb_mutex.lock().await;
 
     ws::start(
         Websocket {
             usb_tx: data.usb_tx.clone(),
             broadcast_tx: data.broadcast_tx.clone(),
+            access,
+            rate_limiter: RateLimiter::default(),
         },
         &req,
         stream,
@@ -291,27 +425,59 @@ async fn websocket(
 async fn execute_command(
     request: web::Json<DaemonRequest>,
     app_data: Data<Mutex<AppData>>,
+    req: HttpRequest,
 ) -> HttpResponse {
+    let access = match check_access(&app_data, &req).await {
+        None => return HttpResponse::Unauthorized().finish(),
+        Some(access) if !is_permitted(&access, &request.0) => {
+            return HttpResponse::Forbidden().finish()
+        }
+        Some(access) => access,
+    };
+
     let mut guard = app_data.lock().await;
     let sender = guard.deref_mut();
 
     // Errors propagate weirdly in the javascript world, so send all as OK, and handle there.
-    match handle_packet(request.0, &mut sender.usb_tx).await {
+    match handle_packet(request.0, &mut sender.usb_tx, access.source()).await {
         Ok(result) => HttpResponse::Ok().json(result),
-        Err(error) => HttpResponse::Ok().json(DaemonResponse::Error(error.to_string())),
+        Err(error) => HttpResponse::Ok().json(error_response(error)),
     }
 }
 
 #[get("/api/get-devices")]
-async fn get_devices(app_data: Data<Mutex<AppData>>) -> HttpResponse {
+async fn get_devices(app_data: Data<Mutex<AppData>>, req: HttpRequest) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
     if let Ok(response) = get_status(app_data).await {
         return HttpResponse::Ok().json(&response);
     }
     HttpResponse::InternalServerError().finish()
 }
 
+#[get("/api/get-metrics")]
+async fn get_metrics(app_data: Data<Mutex<AppData>>, req: HttpRequest) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let mut guard = app_data.lock().await;
+    let sender = guard.deref_mut();
+
+    let request = DaemonRequest::GetDaemonMetrics;
+    match handle_packet(request, &mut sender.usb_tx, CommandSource::Http).await {
+        Ok(DaemonResponse::Metrics(metrics)) => HttpResponse::Ok().json(&metrics),
+        _ => HttpResponse::InternalServerError().finish(),
+    }
+}
+
 #[get("/api/path")]
 async fn get_path(app_data: Data<Mutex<AppData>>, req: HttpRequest) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
     let params = web::Query::<HashMap<String, String>>::from_query(req.query_string());
     if let Ok(params) = params {
         if let Some(path) = params.get("path") {
@@ -344,6 +510,10 @@ async fn get_scribble(
     app_data: Data<Mutex<AppData>>,
     req: HttpRequest,
 ) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
     let serial = &path.0;
     let fader = path.1;
 
@@ -370,7 +540,8 @@ async fn get_scribble(
     let sender = guard.deref_mut();
     let request = DaemonRequest::GetStatus;
 
-    if let Ok(DaemonResponse::Status(status)) = handle_packet(request, &mut sender.usb_tx).await {
+    let response = handle_packet(request, &mut sender.usb_tx, CommandSource::Http).await;
+    if let Ok(DaemonResponse::Status(status)) = response {
         let scribble_path = status.paths.icons_directory;
 
         if let Some(mixer) = status.mixers.get(serial) {
@@ -387,6 +558,7 @@ async fn get_scribble(
                     scribble.bottom_text.clone(),
                     scribble.left_text.clone(),
                     scribble.inverted,
+                    scribble.left_bar,
                     final_width,
                     final_height,
                 );
@@ -404,9 +576,136 @@ async fn get_scribble(
     HttpResponse::NotFound().finish()
 }
 
+// The Stream Deck endpoints provide a simpler view of a device, so plugin authors don't need to
+// walk the full DaemonStatus or deal with raw volume values..
+#[get("/api/streamdeck/{serial}")]
+async fn get_streamdeck_state(
+    serial: web::Path<String>,
+    app_data: Data<Mutex<AppData>>,
+    req: HttpRequest,
+) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    if let Ok(status) = get_status(app_data).await {
+        if let Some(mixer) = status.mixers.get(serial.as_str()) {
+            let state = StreamDeckState::from_status(&serial, mixer, &status.files);
+            return HttpResponse::Ok().json(state);
+        }
+    }
+    HttpResponse::NotFound().finish()
+}
+
+#[get("/api/streamdeck/{serial}/fader/{fader}.png")]
+async fn get_streamdeck_fader(
+    path: web::Path<(String, FaderName)>,
+    app_data: Data<Mutex<AppData>>,
+    req: HttpRequest,
+) -> HttpResponse {
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
+
+    let serial = &path.0;
+    let fader = path.1;
+
+    // Stream Deck keys are square, so only a single size is needed (?size=X), it's clamped to
+    // something sensible so a request can't have us render an enormous image..
+    let mut size = 72;
+    let params = web::Query::<HashMap<String, String>>::from_query(req.query_string());
+    if let Ok(params) = params {
+        if let Some(value) = params.get("size") {
+            if let Ok(value) = value.parse::<u32>() {
+                size = value.clamp(MIN_STREAMDECK_SIZE, MAX_STREAMDECK_SIZE);
+            }
+        }
+    }
+
+    if let Ok(status) = get_status(app_data).await {
+        if let Some(mixer) = status.mixers.get(serial) {
+            let state = StreamDeckState::from_status(serial, mixer, &status.files);
+            let fader_state = &state.faders[fader];
+
+            // Use the fader's scribble if it has one, otherwise the channel name..
+            let scribble = &mixer.fader_status[fader].scribble;
+            let text = scribble
+                .as_ref()
+                .and_then(|s| s.bottom_text.clone())
+                .unwrap_or(fader_state.channel.to_string());
+            let icon_path = scribble
+                .as_ref()
+                .and_then(|s| s.file_name.as_ref())
+                .map(|file| status.paths.icons_directory.join(file));
+
+            // A muted fader is drawn inverted, so it stands out on the deck..
+            let muted = fader_state.mute_state != MuteState::Unmuted;
+            let png = get_scribble_png(
+                icon_path,
+                Some(text),
+                Some(format!("{}%", fader_state.volume)),
+                muted,
+                false,
+                size,
+                size,
+            );
+
+            if let Ok(png) = png {
+                let mut builder = HttpResponse::Ok();
+                builder.insert_header(ContentType(IMAGE_PNG));
+                return builder.body(png);
+            }
+        }
+    }
+
+    debug!("Unable to Build Stream Deck Image: {} - {}", serial, fader);
+    HttpResponse::NotFound().finish()
+}
+
+#[post("/api/streamdeck/{serial}/volume")]
+async fn streamdeck_volume_step(
+    serial: web::Path<String>,
+    request: web::Json<StreamDeckVolumeStep>,
+    app_data: Data<Mutex<AppData>>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let Some(access) = check_access(&app_data, &req).await else {
+        return HttpResponse::Unauthorized().finish();
+    };
+
+    let volume = match get_status(app_data.clone()).await {
+        Ok(status) => match status.mixers.get(serial.as_str()) {
+            Some(mixer) => mixer.get_channel_volume(request.channel),
+            None => return HttpResponse::NotFound().finish(),
+        },
+        Err(_) => return HttpResponse::InternalServerError().finish(),
+    };
+
+    let volume = request.apply(volume);
+    let command = GoXLRCommand::SetVolume(request.channel, volume);
+    let request = DaemonRequest::Command(serial.into_inner(), command);
+    if !is_permitted(&access, &request) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let mut guard = app_data.lock().await;
+    let sender = guard.deref_mut();
+    match handle_packet(request, &mut sender.usb_tx, access.source()).await {
+        Ok(result) => HttpResponse::Ok().json(result),
+        Err(error) => HttpResponse::Ok().json(error_response(error)),
+    }
+}
+
 #[get("/files/samples/{sample}")]
-async fn get_sample(sample: web::Path<String>, app_data: Data<Mutex<AppData>>) -> HttpResponse {
+async fn get_sample(
+    sample: web::Path<String>,
+    app_data: Data<Mutex<AppData>>,
+    req: HttpRequest,
+) -> HttpResponse {
     debug!("Err?");
+    if check_access(&app_data, &req).await.is_none() {
+        return HttpResponse::Unauthorized().finish();
+    }
 
     // Get the Base Samples Path..
     let mut guard = app_data.lock().await;
@@ -453,6 +752,12 @@ async fn default(req: HttpRequest) -> HttpResponse {
     }
 }
 
+async fn check_access(app_data: &Data<Mutex<AppData>>, req: &HttpRequest) -> Option<Access> {
+    // Don't hold the lock while we're checking the token..
+    let settings = app_data.lock().await.settings.clone();
+    get_access(req, &settings).await
+}
+
 async fn get_status(app_data: Data<Mutex<AppData>>) -> Result<DaemonStatus> {
     // Unwrap the Mutex Guard..
     let mut guard = app_data.lock().await;
@@ -460,9 +765,9 @@ async fn get_status(app_data: Data<Mutex<AppData>>) -> Result<DaemonStatus> {
 
     let request = DaemonRequest::GetStatus;
 
-    let result = handle_packet(request, &mut sender.usb_tx).await?;
+    let result = handle_packet(request, &mut sender.usb_tx, CommandSource::Http).await?;
     match result {
-        DaemonResponse::Status(status) => Ok(status),
+        DaemonResponse::Status(status) => Ok(*status),
         _ => Err(anyhow!("Unexpected Daemon Status Result: {:?}", result)),
     }
 }
@@ -1,79 +1,129 @@
 use anyhow::{bail, Result};
 use goxlr_ipc::clients::ipc::ipc_socket::Socket;
-use goxlr_ipc::{DaemonRequest, DaemonResponse};
+use goxlr_ipc::{CommandSource, DaemonCommand, DaemonRequest, DaemonResponse};
 use interprocess::local_socket::tokio::prelude::{LocalSocketListener, LocalSocketStream};
 use interprocess::local_socket::traits::tokio::{Listener, Stream};
 use interprocess::local_socket::{
-    GenericFilePath, GenericNamespaced, ListenerOptions, ToFsName, ToNsName,
+    GenericFilePath, GenericNamespaced, ListenerOptions, Name, ToFsName, ToNsName,
 };
 use log::{debug, info, warn};
+use std::fmt::{Display, Formatter};
 use std::fs;
 use std::path::Path;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::{Receiver as BroadcastReceiver, Sender as BroadcastSender};
+use tokio::time::{sleep, timeout, Instant};
 
+use crate::locale::localise_error;
 use crate::primary_worker::DeviceSender;
-use crate::servers::server_packet::handle_packet;
-use crate::Shutdown;
+use crate::servers::rate_limit::{rate_limit_error, RateLimit, RateLimiter};
+use crate::servers::server_packet::{error_response, handle_packet};
+use crate::{PatchEvent, Shutdown};
 
 static SOCKET_PATH: &str = "/tmp/goxlr.socket";
 static NAMED_PIPE: &str = "@goxlr.socket";
 
-async fn ipc_tidy() -> Result<()> {
-    // We only need a possible cleanup if we're using file based sockets, this has changed
-    // substantially with the latest interprocess crate, so we're OS based now..
-    let socket_type = if cfg!(windows) {
+// How long we wait for an existing daemon to respond before assuming it's dead / hung
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+// How long we give an existing daemon to shut down when taking over
+const TAKEOVER_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug)]
+pub struct DaemonAlreadyRunning;
+
+impl Display for DaemonAlreadyRunning {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The GoXLR Daemon is already running.")
+    }
+}
+
+impl std::error::Error for DaemonAlreadyRunning {}
+
+fn get_socket_name() -> Result<Name<'static>> {
+    Ok(if cfg!(windows) {
         NAMED_PIPE.to_ns_name::<GenericNamespaced>()?
     } else {
-        if !Path::new(SOCKET_PATH).exists() {
-            return Ok(());
-        }
         SOCKET_PATH.to_fs_name::<GenericFilePath>()?
-    };
+    })
+}
 
-    let connection = LocalSocketStream::connect(socket_type).await;
-    if connection.is_err() {
+async fn connect_existing() -> Option<Socket<DaemonResponse, DaemonRequest>> {
+    if !cfg!(windows) && !Path::new(SOCKET_PATH).exists() {
+        return None;
+    }
+
+    let connection = LocalSocketStream::connect(get_socket_name().ok()?).await;
+    connection.ok().map(Socket::new)
+}
+
+async fn ipc_tidy(takeover: bool) -> Result<()> {
+    // We only need a possible cleanup if we're using file based sockets, this has changed
+    // substantially with the latest interprocess crate, so we're OS based now..
+    let Some(mut socket) = connect_existing().await else {
         match cfg!(windows) {
             true => {
                 debug!("Named Pipe not running, continuing..");
             }
             false => {
-                debug!("Connection Failed. Socket File is stale, removing..");
-                fs::remove_file(SOCKET_PATH)?;
+                if Path::new(SOCKET_PATH).exists() {
+                    debug!("Connection Failed. Socket File is stale, removing..");
+                    fs::remove_file(SOCKET_PATH)?;
+                }
             }
         }
         return Ok(());
-    }
+    };
 
     debug!("Connected to socket, seeing if there's a Daemon on the other side..");
-    let connection = connection.unwrap();
-
-    let mut socket: Socket<DaemonResponse, DaemonRequest> = Socket::new(connection);
-    if let Err(e) = socket.send(DaemonRequest::Ping).await {
-        match cfg!(windows) {
-            true => {
+    let response = match socket.send(DaemonRequest::Ping).await {
+        Ok(()) => timeout(PING_TIMEOUT, socket.read()).await,
+        Err(e) => {
+            if cfg!(windows) {
                 debug!("Our named pipe is broken, something is horribly wrong..");
                 bail!("Named Pipe Error: {}", e);
             }
-            false => {
-                debug!("Unable to send messages, removing socket..");
-                fs::remove_file(SOCKET_PATH)?;
-            }
+            debug!("Unable to send messages, removing socket..");
+            fs::remove_file(SOCKET_PATH)?;
+            return Ok(());
         }
-        return Ok(());
+    };
+
+    if !matches!(response, Ok(Some(Ok(DaemonResponse::Ok)))) {
+        // Something is still listening, it may just be busy, so the socket is left alone..
+        bail!("An existing GoXLR Daemon is not responding");
     }
 
     // If we get here, there's an active GoXLR Daemon running!
-    bail!("The GoXLR Daemon is already running.");
-}
+    if !takeover {
+        bail!(DaemonAlreadyRunning);
+    }
 
-pub async fn bind_socket() -> Result<LocalSocketListener> {
-    ipc_tidy().await?;
+    info!("Existing Daemon found, requesting it to shut down..");
+    let request = DaemonRequest::Daemon(DaemonCommand::StopDaemon);
+    socket.send(request).await?;
+    drop(socket);
 
-    let name = if cfg!(windows) {
-        NAMED_PIPE.to_ns_name::<GenericNamespaced>()?
-    } else {
-        SOCKET_PATH.to_fs_name::<GenericFilePath>()?
-    };
+    // Wait for the old daemon to go away..
+    let deadline = Instant::now() + TAKEOVER_TIMEOUT;
+    while Instant::now() < deadline {
+        sleep(Duration::from_millis(250)).await;
+        if connect_existing().await.is_none() {
+            info!("Existing Daemon has stopped, continuing startup..");
+            if !cfg!(windows) && Path::new(SOCKET_PATH).exists() {
+                fs::remove_file(SOCKET_PATH)?;
+            }
+            return Ok(());
+        }
+    }
+    bail!("Existing GoXLR Daemon did not shut down in time");
+}
 
+pub async fn bind_socket(takeover: bool) -> Result<LocalSocketListener> {
+    ipc_tidy(takeover).await?;
+
+    let name = get_socket_name()?;
     let opts = ListenerOptions::new().name(name.clone());
     let listener = opts.create_tokio()?;
 
@@ -84,6 +134,7 @@ pub async fn bind_socket() -> Result<LocalSocketListener> {
 pub async fn spawn_ipc_server(
     listener: LocalSocketListener,
     usb_tx: DeviceSender,
+    broadcast_tx: BroadcastSender<PatchEvent>,
     mut shutdown_signal: Shutdown,
 ) {
     debug!("Running IPC Server..");
@@ -92,8 +143,9 @@ pub async fn spawn_ipc_server(
             Ok(connection) = listener.accept() => {
                 let socket = Socket::new(connection);
                 let usb_tx = usb_tx.clone();
+                let broadcast_tx = broadcast_tx.clone();
                 tokio::spawn(async move {
-                    handle_connection(socket, usb_tx).await;
+                    handle_connection(socket, usb_tx, broadcast_tx).await;
                 });
             }
             () = shutdown_signal.recv() => {
@@ -109,10 +161,53 @@ pub async fn spawn_ipc_server(
 async fn handle_connection(
     mut socket: Socket<DaemonRequest, DaemonResponse>,
     mut usb_tx: DeviceSender,
+    broadcast_tx: BroadcastSender<PatchEvent>,
 ) {
-    while let Some(msg) = socket.read().await {
+    // Only populated once the client has sent a Subscribe request..
+    let mut patches: Option<BroadcastReceiver<PatchEvent>> = None;
+    let mut rate_limiter = RateLimiter::default();
+
+    loop {
+        let msg = tokio::select! {
+            msg = socket.read() => msg,
+            Some(patch) = next_patch(&mut patches) => {
+                if let Err(e) = socket.send(DaemonResponse::Patch(patch.data)).await {
+                    warn!("Couldn't send patch to {:?}: {}", socket.address(), e);
+                    return;
+                }
+                continue;
+            }
+        };
+
+        let Some(msg) = msg else {
+            break;
+        };
+
+        let limit = rate_limiter.check();
+        if limit != RateLimit::Allowed {
+            let error = DaemonResponse::DetailedError(localise_error(rate_limit_error()));
+            if let Err(e) = socket.send(error).await {
+                warn!("Couldn't reply to {:?}: {}", socket.address(), e);
+                return;
+            }
+            if limit == RateLimit::Disconnect {
+                warn!("Disconnecting {:?}, too many requests", socket.address());
+                return;
+            }
+            continue;
+        }
+
         match msg {
-            Ok(msg) => match handle_packet(msg, &mut usb_tx).await {
+            Ok(DaemonRequest::Subscribe) => {
+                if patches.is_none() {
+                    patches.replace(broadcast_tx.subscribe());
+                }
+                if let Err(e) = socket.send(DaemonResponse::Ok).await {
+                    warn!("Couldn't reply to {:?}: {}", socket.address(), e);
+                    return;
+                }
+            }
+            Ok(msg) => match handle_packet(msg, &mut usb_tx, CommandSource::Ipc).await {
                 Ok(response) => {
                     if let Err(e) = socket.send(response).await {
                         warn!("Couldn't reply to {:?}: {}", socket.address(), e);
@@ -120,7 +215,7 @@ async fn handle_connection(
                     }
                 }
                 Err(e) => {
-                    if let Err(e) = socket.send(DaemonResponse::Error(e.to_string())).await {
+                    if let Err(e) = socket.send(error_response(e)).await {
                         warn!("Couldn't reply to {:?}: {}", socket.address(), e);
                         return;
                     }
@@ -137,3 +232,22 @@ async fn handle_connection(
     }
     debug!("Disconnected {:?}", socket.address());
 }
+
+async fn next_patch(patches: &mut Option<BroadcastReceiver<PatchEvent>>) -> Option<PatchEvent> {
+    let Some(receiver) = patches else {
+        return std::future::pending().await;
+    };
+
+    loop {
+        match receiver.recv().await {
+            Ok(patch) => return Some(patch),
+            Err(RecvError::Lagged(count)) => {
+                warn!(
+                    "IPC Subscriber missed {} patches, status may be stale",
+                    count
+                );
+            }
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
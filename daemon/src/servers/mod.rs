@@ -1,3 +1,5 @@
+pub(crate) mod auth;
 pub(crate) mod http_server;
 pub(crate) mod ipc_server;
+pub(crate) mod rate_limit;
 pub(crate) mod server_packet;
@@ -0,0 +1,95 @@
+/*
+   Limits how quickly a single IPC or WebSocket client can send requests.
+
+   Every request a client sends (even one which only reads the status) passes through the device
+   worker, so an integration sending them as fast as it can will hold up everything else,
+   including commands from the user. Each connection gets a bucket which refills at a steady rate
+   and allows short bursts, requests which arrive when it's empty are rejected with a
+   RateLimited error rather than being run. If a client keeps going regardless, it's
+   disconnected.
+*/
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use goxlr_ipc::{DaemonError, RateLimitMetrics};
+
+// These are generous, the UI polls the mic level at around 20Hz, and a fader sweep from a
+// controller rarely goes above 60..
+const REQUESTS_PER_SECOND: u32 = 100;
+const BURST_SIZE: u32 = 200;
+
+// How many rejected requests in a second before we give up on the client
+const MAX_REJECTIONS_PER_SECOND: u32 = 100;
+
+static THROTTLED_REQUESTS: AtomicU64 = AtomicU64::new(0);
+static DISCONNECTED_CLIENTS: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RateLimit {
+    Allowed,
+    Throttled,
+    Disconnect,
+}
+
+#[derive(Debug)]
+pub struct RateLimiter {
+    tokens: f64,
+    last_refill: Instant,
+
+    window_start: Instant,
+    window_rejections: u32,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            tokens: BURST_SIZE as f64,
+            last_refill: Instant::now(),
+            window_start: Instant::now(),
+            window_rejections: 0,
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Called for each request a client sends, returning whether it should be run
+    pub fn check(&mut self) -> RateLimit {
+        let now = Instant::now();
+        let refill =
+            now.duration_since(self.last_refill).as_secs_f64() * REQUESTS_PER_SECOND as f64;
+        self.tokens = (self.tokens + refill).min(BURST_SIZE as f64);
+        self.last_refill = now;
+
+        if self.tokens >= 1. {
+            self.tokens -= 1.;
+            return RateLimit::Allowed;
+        }
+
+        THROTTLED_REQUESTS.fetch_add(1, Ordering::Relaxed);
+        if now.duration_since(self.window_start) >= Duration::from_secs(1) {
+            self.window_start = now;
+            self.window_rejections = 0;
+        }
+
+        self.window_rejections += 1;
+        if self.window_rejections > MAX_REJECTIONS_PER_SECOND {
+            DISCONNECTED_CLIENTS.fetch_add(1, Ordering::Relaxed);
+            return RateLimit::Disconnect;
+        }
+        RateLimit::Throttled
+    }
+}
+
+pub fn rate_limit_error() -> DaemonError {
+    DaemonError::RateLimited {
+        limit: REQUESTS_PER_SECOND,
+    }
+}
+
+pub fn get_rate_limit_metrics() -> RateLimitMetrics {
+    RateLimitMetrics {
+        throttled_requests: THROTTLED_REQUESTS.load(Ordering::Relaxed),
+        disconnected_clients: DISCONNECTED_CLIENTS.load(Ordering::Relaxed),
+    }
+}
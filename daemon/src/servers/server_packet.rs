@@ -1,14 +1,37 @@
+use crate::locale::localise_error;
 use crate::primary_worker::{DeviceCommand, DeviceSender};
 use anyhow::{anyhow, Context, Result};
-use goxlr_ipc::{DaemonRequest, DaemonResponse};
+use goxlr_ipc::{
+    CommandSource, DaemonError, DaemonRequest, DaemonResponse, MIN_PROTOCOL_VERSION,
+    PROTOCOL_VERSION,
+};
 use tokio::sync::oneshot;
 
+/// Handles a request from a client, the source is who sent it, and is recorded in the audit log
+/// if the request runs a command against a device or the daemon.
 pub async fn handle_packet(
     request: DaemonRequest,
     usb_tx: &mut DeviceSender,
+    source: CommandSource,
 ) -> Result<DaemonResponse> {
     match request {
         DaemonRequest::Ping => Ok(DaemonResponse::Ok),
+        DaemonRequest::Handshake(version) => {
+            // We can't help clients older than our minimum, newer clients are expected to fall
+            // back to our version (or refuse) based on the reply..
+            if version < MIN_PROTOCOL_VERSION {
+                return Ok(DaemonResponse::DetailedError(localise_error(
+                    DaemonError::ProtocolMismatch {
+                        daemon: PROTOCOL_VERSION,
+                        client: version,
+                    },
+                )));
+            }
+            Ok(DaemonResponse::Handshake(PROTOCOL_VERSION))
+        }
+
+        // The IPC server handles subscriptions itself, and WebSockets are always subscribed
+        DaemonRequest::Subscribe => Ok(DaemonResponse::Ok),
         DaemonRequest::GetStatus => {
             let (tx, rx) = oneshot::channel();
             usb_tx
@@ -16,14 +39,14 @@ pub async fn handle_packet(
                 .await
                 .map_err(|e| anyhow!(e.to_string()))
                 .context("Could not communicate with the device task")?;
-            Ok(DaemonResponse::Status(rx.await.context(
+            Ok(DaemonResponse::Status(Box::new(rx.await.context(
                 "Could not execute the command on the device task",
-            )?))
+            )?)))
         }
         DaemonRequest::Daemon(command) => {
             let (tx, rx) = oneshot::channel();
             usb_tx
-                .send(DeviceCommand::RunDaemonCommand(command, tx))
+                .send(DeviceCommand::RunDaemonCommand(command, source, tx))
                 .await
                 .map_err(|e| anyhow!(e.to_string()))
                 .context("Could not communicate with the GoXLR device")?;
@@ -45,14 +68,197 @@ pub async fn handle_packet(
 
             match result {
                 Ok(value) => Ok(DaemonResponse::MicLevel(value)),
-                Err(e) => Ok(DaemonResponse::Error(e.to_string())),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+        DaemonRequest::GetGateLevel(serial) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetDeviceGateLevel(serial, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the GoXLR device")?;
+            let result = rx
+                .await
+                .context("Could not execute the command on the GoXLR device")?;
+
+            match result {
+                Ok(value) => Ok(DaemonResponse::GateLevel(value)),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetChannelLevels(serial) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetDeviceChannelLevels(serial, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the GoXLR device")?;
+            let result = rx
+                .await
+                .context("Could not execute the command on the GoXLR device")?;
+
+            match result {
+                Ok(levels) => Ok(DaemonResponse::ChannelLevels(levels)),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetEvents(since) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetEvents(since, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            Ok(DaemonResponse::Events(rx.await.context(
+                "Could not retrieve the events from the device task",
+            )?))
+        }
+
+        DaemonRequest::GetFiles => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetFiles(tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            Ok(DaemonResponse::Files(rx.await.context(
+                "Could not retrieve the files from the device task",
+            )?))
+        }
+
+        DaemonRequest::GetDaemonMetrics => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetMetrics(tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            Ok(DaemonResponse::Metrics(rx.await.context(
+                "Could not retrieve the metrics from the device task",
+            )?))
+        }
+
+        DaemonRequest::CreateApiToken(name, scope) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::CreateApiToken(name, scope, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            Ok(DaemonResponse::ApiToken(
+                rx.await.context("Could not create the API Token")?,
+            ))
+        }
+
+        DaemonRequest::GetAuditLog(query) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetAuditLog(query, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            let result = rx.await.context("Could not read the audit log")?;
+
+            match result {
+                Ok(entries) => Ok(DaemonResponse::AuditLog(Box::new(entries))),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetSampleWaveform(path, points) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetSampleWaveform(path, points, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            let result = rx
+                .await
+                .context("Could not generate the waveform for the sample")?;
+
+            match result {
+                Ok(waveform) => Ok(DaemonResponse::SampleWaveform(waveform)),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetProfilePreview(name) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetProfilePreview(name, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the device task")?;
+            let result = rx
+                .await
+                .context("Could not generate the preview for the profile")?;
+
+            match result {
+                Ok(preview) => Ok(DaemonResponse::ProfilePreview(preview)),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetScribbleImage(serial, fader) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetScribbleImage(serial, fader, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the GoXLR device")?;
+            let result = rx
+                .await
+                .context("Could not execute the command on the GoXLR device")?;
+
+            match result {
+                Ok(image) => Ok(DaemonResponse::ScribbleImage(image)),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::GetLighting(serial) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::GetLighting(serial, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the GoXLR device")?;
+            let result = rx
+                .await
+                .context("Could not execute the command on the GoXLR device")?;
+
+            match result {
+                Ok(lighting) => Ok(DaemonResponse::Lighting(Box::new(lighting))),
+                Err(e) => Ok(error_response(e)),
+            }
+        }
+
+        DaemonRequest::ValidateCommand(serial, command) => {
+            let (tx, rx) = oneshot::channel();
+            usb_tx
+                .send(DeviceCommand::ValidateCommand(serial, command, tx))
+                .await
+                .map_err(|e| anyhow!(e.to_string()))
+                .context("Could not communicate with the GoXLR device")?;
+            let result = rx
+                .await
+                .context("Could not validate the command on the GoXLR device")?;
+
+            match result {
+                Ok(errors) => Ok(DaemonResponse::Validation(
+                    errors.into_iter().map(localise_error).collect(),
+                )),
+                Err(e) => Ok(error_response(e)),
             }
         }
 
         DaemonRequest::Command(serial, command) => {
             let (tx, rx) = oneshot::channel();
             usb_tx
-                .send(DeviceCommand::RunDeviceCommand(serial, command, tx))
+                .send(DeviceCommand::RunDeviceCommand(serial, command, source, tx))
                 .await
                 .map_err(|e| anyhow!(e.to_string()))
                 .context("Could not communicate with the GoXLR device")?;
@@ -62,3 +268,12 @@ pub async fn handle_packet(
         }
     }
 }
+
+/// Converts an error into a response, if the error is a known DaemonError we send it (with its
+/// translated message) so clients can handle it, otherwise we just send back the message.
+pub fn error_response(error: anyhow::Error) -> DaemonResponse {
+    match error.downcast::<DaemonError>() {
+        Ok(error) => DaemonResponse::DetailedError(localise_error(error)),
+        Err(error) => DaemonResponse::Error(error.to_string()),
+    }
+}
@@ -1,12 +1,20 @@
+use crate::hotkeys::Bindings;
 use crate::mic_profile::DEFAULT_MIC_PROFILE_NAME;
 use crate::profile::DEFAULT_PROFILE_NAME;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use directories::ProjectDirs;
-use goxlr_ipc::{GoXLRCommand, LogLevel};
-use goxlr_types::VodMode;
+use goxlr_ipc::{
+    AudioChannel, ButtonBinding, Ducking, EventHook, GoXLRCommand, InputPolling, InteractionLock,
+    LogLevel, LogRotation, ScheduledAction, TokenScope, VolumePreset,
+};
 use goxlr_types::VodMode::Routable;
+use goxlr_types::{AttachStatePolicy, Button, ChannelName, FaderName, VodMode};
 use log::{debug, error, info, warn};
+use rand::distributions::Alphanumeric;
+use rand::rngs::OsRng;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::{create_dir_all, File};
@@ -15,6 +23,17 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+// The current version of the settings schema, whenever a change is made which can't be handled
+// by serde defaults (renames, type changes, etc) this should be bumped, and a migration added.
+const SETTINGS_VERSION: u32 = 1;
+
+// Migrations, along with the version they migrate *from*. A version which only changed things
+// serde can handle (like v0, which just didn't carry a version number) doesn't need one.
+type Migration = fn(&mut Value) -> Result<()>;
+const MIGRATIONS: &[(u32, Migration)] = &[];
+
+const API_TOKEN_LENGTH: usize = 32;
+
 #[derive(Debug, Clone)]
 pub struct SettingsHandle {
     path: PathBuf,
@@ -57,10 +76,13 @@ impl SettingsHandle {
             error!("Unable to Load the Settings File, configuring default.");
 
             Settings {
+                version: SETTINGS_VERSION,
                 show_tray_icon: Some(true),
                 selected_locale: None,
                 tts_enabled: Some(false),
                 allow_network_access: Some(false),
+                profile_autosave_interval: None,
+                input_polling: None,
                 macos_handle_aggregates: None,
                 profile_directory: None,
                 mic_profile_directory: None,
@@ -70,10 +92,17 @@ impl SettingsHandle {
                 logs_directory: None,
                 backup_directory: None,
                 log_level: Some(LogLevel::Debug),
+                log_rotation: None,
+                log_retention: None,
+                audit_log_enabled: None,
                 open_ui_on_launch: None,
                 activate: None,
+                event_hooks: Some(Default::default()),
+                app_routes: Some(Default::default()),
+                api_tokens: Some(Default::default()),
                 devices: Some(Default::default()),
                 sample_gain: Some(Default::default()),
+                extra: Default::default(),
             }
         });
 
@@ -222,6 +251,31 @@ impl SettingsHandle {
         settings.allow_network_access = Some(enabled);
     }
 
+    // How often (in seconds) to save the profile if it has changed, 0 disables autosave
+    pub async fn get_profile_autosave_interval(&self) -> u16 {
+        let settings = self.settings.read().await;
+        settings.profile_autosave_interval.unwrap_or(0)
+    }
+
+    pub async fn set_profile_autosave_interval(&self, interval: u16) {
+        let mut settings = self.settings.write().await;
+        settings.profile_autosave_interval = Some(interval);
+    }
+
+    pub async fn get_input_polling(&self) -> InputPolling {
+        let settings = self.settings.read().await;
+        settings.input_polling.unwrap_or(InputPolling {
+            active_interval: 20,
+            idle_interval: 200,
+            idle_timeout: 10,
+        })
+    }
+
+    pub async fn set_input_polling(&self, polling: InputPolling) {
+        let mut settings = self.settings.write().await;
+        settings.input_polling = Some(polling);
+    }
+
     pub async fn set_macos_handle_aggregates(&self, enabled: bool) {
         let mut settings = self.settings.write().await;
         settings.macos_handle_aggregates = Some(enabled);
@@ -305,6 +359,36 @@ impl SettingsHandle {
         settings.log_level.clone().unwrap_or(LogLevel::Info)
     }
 
+    pub async fn set_log_rotation(&self, rotation: LogRotation) {
+        let mut settings = self.settings.write().await;
+        settings.log_rotation = Some(rotation);
+    }
+
+    pub async fn get_log_rotation(&self) -> LogRotation {
+        let settings = self.settings.read().await;
+        settings.log_rotation.unwrap_or_default()
+    }
+
+    pub async fn set_log_retention(&self, count: u8) {
+        let mut settings = self.settings.write().await;
+        settings.log_retention = Some(count);
+    }
+
+    pub async fn get_log_retention(&self) -> u8 {
+        let settings = self.settings.read().await;
+        settings.log_retention.unwrap_or(5)
+    }
+
+    pub async fn set_audit_log_enabled(&self, enabled: bool) {
+        let mut settings = self.settings.write().await;
+        settings.audit_log_enabled = Some(enabled);
+    }
+
+    pub async fn get_audit_log_enabled(&self) -> bool {
+        let settings = self.settings.read().await;
+        settings.audit_log_enabled.unwrap_or(true)
+    }
+
     pub async fn get_open_ui_on_launch(&self) -> bool {
         let settings = self.settings.read().await;
         settings.open_ui_on_launch.unwrap_or(false)
@@ -325,6 +409,104 @@ impl SettingsHandle {
         settings.activate = activate;
     }
 
+    pub async fn get_event_hooks(&self) -> HashMap<EventHook, String> {
+        let settings = self.settings.read().await;
+        settings.event_hooks.clone().unwrap_or_default()
+    }
+
+    pub async fn get_event_hook(&self, hook: EventHook) -> Option<String> {
+        let settings = self.settings.read().await;
+        settings
+            .event_hooks
+            .as_ref()
+            .and_then(|hooks| hooks.get(&hook).cloned())
+    }
+
+    pub async fn set_event_hook(&self, hook: EventHook, command: Option<String>) {
+        let mut settings = self.settings.write().await;
+        let hooks = settings.event_hooks.get_or_insert_with(HashMap::default);
+
+        if let Some(command) = command {
+            hooks.insert(hook, command);
+        } else {
+            hooks.remove(&hook);
+        }
+    }
+
+    pub async fn get_app_routes(&self) -> HashMap<String, Vec<AudioChannel>> {
+        let settings = self.settings.read().await;
+        settings.app_routes.clone().unwrap_or_default()
+    }
+
+    pub async fn set_app_route(&self, application: String, channels: Vec<AudioChannel>) {
+        let mut settings = self.settings.write().await;
+        let routes = settings.app_routes.get_or_insert_with(HashMap::default);
+
+        if channels.is_empty() {
+            routes.remove(&application);
+        } else {
+            routes.insert(application, channels);
+        }
+    }
+
+    pub async fn get_api_token_scopes(&self) -> HashMap<String, TokenScope> {
+        let settings = self.settings.read().await;
+        settings
+            .api_tokens
+            .iter()
+            .flatten()
+            .map(|(name, token)| (name.clone(), token.scope))
+            .collect()
+    }
+
+    // Creating a token with an existing name replaces it, so a leaked token can be rotated..
+    pub async fn create_api_token(&self, name: String, scope: TokenScope) -> String {
+        let token: String = OsRng
+            .sample_iter(&Alphanumeric)
+            .take(API_TOKEN_LENGTH)
+            .map(char::from)
+            .collect();
+
+        let mut settings = self.settings.write().await;
+        let tokens = settings.api_tokens.get_or_insert_with(HashMap::default);
+        tokens.insert(
+            name,
+            ApiToken {
+                token: token.clone(),
+                scope,
+            },
+        );
+        token
+    }
+
+    pub async fn remove_api_token(&self, name: &str) -> bool {
+        let mut settings = self.settings.write().await;
+        let tokens = settings.api_tokens.get_or_insert_with(HashMap::default);
+        tokens.remove(name).is_some()
+    }
+
+    /// Returns the name and scope of a token presented by a client, or None if it isn't one of ours
+    pub async fn get_api_token(&self, token: &str) -> Option<(String, TokenScope)> {
+        let settings = self.settings.read().await;
+
+        // Check every token, and don't bail early on a mismatch, so the time taken doesn't give
+        // away how much of a token was correct..
+        let mut found = None;
+        for (name, stored) in settings.api_tokens.iter().flatten() {
+            let matched = stored.token.len() == token.len()
+                && stored
+                    .token
+                    .bytes()
+                    .zip(token.bytes())
+                    .fold(0, |diff, (a, b)| diff | (a ^ b))
+                    == 0;
+            if matched {
+                found = Some((name.clone(), stored.scope));
+            }
+        }
+        found
+    }
+
     pub async fn get_device_profile_name(&self, device_serial: &str) -> Option<String> {
         let settings = self.settings.read().await;
         settings
@@ -390,6 +572,184 @@ impl SettingsHandle {
         vec![]
     }
 
+    pub async fn get_device_hotkeys(
+        &self,
+        device_serial: &str,
+    ) -> HashMap<String, Vec<GoXLRCommand>> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.hotkeys.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_hotkey_bindings(&self) -> Bindings {
+        let settings = self.settings.read().await;
+
+        let mut bindings: Bindings = HashMap::new();
+        for (serial, device) in settings.devices.as_ref().unwrap() {
+            for (hotkey, commands) in &device.hotkeys {
+                bindings
+                    .entry(hotkey.clone())
+                    .or_default()
+                    .push((serial.clone(), commands.clone()));
+            }
+        }
+        bindings
+    }
+
+    pub async fn get_device_schedule(
+        &self,
+        device_serial: &str,
+    ) -> HashMap<String, ScheduledAction> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.schedule.clone());
+
+        value.unwrap_or_default()
+    }
+
+    // Every scheduled action, across all devices, as (Serial, Name, Action)
+    pub async fn get_scheduled_actions(&self) -> Vec<(String, String, ScheduledAction)> {
+        let settings = self.settings.read().await;
+
+        let mut actions = vec![];
+        for (serial, device) in settings.devices.as_ref().unwrap() {
+            for (name, action) in &device.schedule {
+                actions.push((serial.clone(), name.clone(), action.clone()));
+            }
+        }
+        actions
+    }
+
+    pub async fn get_device_button_bindings(
+        &self,
+        device_serial: &str,
+    ) -> HashMap<Button, ButtonBinding> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.button_bindings.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_label(&self, device_serial: &str) -> Option<String> {
+        let settings = self.settings.read().await;
+        settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .and_then(|d| d.label.clone())
+    }
+
+    pub async fn get_device_profile_fallbacks(&self, device_serial: &str) -> Vec<String> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.profile_fallbacks.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_volume_limits(&self, device_serial: &str) -> HashMap<ChannelName, u8> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.volume_limits.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_output_trims(&self, device_serial: &str) -> HashMap<ChannelName, u8> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.output_trims.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_ducking(&self, device_serial: &str) -> Ducking {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.ducking.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_interaction_lock(&self, device_serial: &str) -> InteractionLock {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.interaction_lock.clone());
+
+        value.unwrap_or_default()
+    }
+
+    pub async fn get_device_now_playing_fader(&self, device_serial: &str) -> Option<FaderName> {
+        let settings = self.settings.read().await;
+        settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .and_then(|d| d.now_playing_fader)
+    }
+
+    pub async fn has_now_playing_fader(&self) -> bool {
+        let settings = self.settings.read().await;
+        settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .values()
+            .any(|d| d.now_playing_fader.is_some())
+    }
+
+    pub async fn get_device_volume_presets(
+        &self,
+        device_serial: &str,
+    ) -> HashMap<String, VolumePreset> {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.volume_presets.clone());
+
+        value.unwrap_or_default()
+    }
+
     pub async fn get_device_sampler_pre_buffer(&self, device_serial: &str) -> u16 {
         let settings = self.settings.read().await;
         let value = settings
@@ -450,6 +810,30 @@ impl SettingsHandle {
         true
     }
 
+    pub async fn get_device_lighting_brightness(&self, device_serial: &str) -> u8 {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .and_then(|d| d.lighting_brightness);
+
+        value.unwrap_or(100)
+    }
+
+    pub async fn get_device_lighting_crossfade(&self, device_serial: &str) -> u16 {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .and_then(|d| d.lighting_crossfade);
+
+        value.unwrap_or(0)
+    }
+
     pub async fn get_enable_monitor_with_fx(&self, device_serial: &str) -> bool {
         let settings = self.settings.read().await;
         let value = settings
@@ -479,6 +863,36 @@ impl SettingsHandle {
         Routable
     }
 
+    pub async fn get_device_error_feedback(&self, device_serial: &str) -> bool {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.error_feedback.unwrap_or(true));
+
+        if let Some(value) = value {
+            return value;
+        }
+        true
+    }
+
+    pub async fn get_device_attach_state_policy(&self, device_serial: &str) -> AttachStatePolicy {
+        let settings = self.settings.read().await;
+        let value = settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.attach_state_policy.unwrap_or_default());
+
+        if let Some(value) = value {
+            return value;
+        }
+        AttachStatePolicy::Profile
+    }
+
     pub async fn get_sampler_reset_on_clear(&self, device_serial: &str) -> bool {
         let settings = self.settings.read().await;
         settings
@@ -490,6 +904,17 @@ impl SettingsHandle {
             .unwrap_or(true)
     }
 
+    pub async fn get_sampler_normalisation(&self, device_serial: &str) -> bool {
+        let settings = self.settings.read().await;
+        settings
+            .devices
+            .as_ref()
+            .unwrap()
+            .get(device_serial)
+            .map(|d| d.sampler_normalisation.unwrap_or(true))
+            .unwrap_or(true)
+    }
+
     pub async fn get_sample_gain_percent(&self, name: String) -> u8 {
         let settings = self.settings.read().await;
         if let Some(gain) = &settings.sample_gain {
@@ -548,6 +973,185 @@ impl SettingsHandle {
         commands.clone_into(&mut entry.shutdown_commands);
     }
 
+    pub async fn set_device_button_binding(
+        &self,
+        device_serial: &str,
+        button: Button,
+        binding: Option<ButtonBinding>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        if let Some(binding) = binding {
+            entry.button_bindings.insert(button, binding);
+        } else {
+            entry.button_bindings.remove(&button);
+        }
+    }
+
+    pub async fn set_device_hotkey(
+        &self,
+        device_serial: &str,
+        hotkey: String,
+        commands: Option<Vec<GoXLRCommand>>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        if let Some(commands) = commands {
+            entry.hotkeys.insert(hotkey, commands);
+        } else {
+            entry.hotkeys.remove(&hotkey);
+        }
+    }
+
+    pub async fn set_device_scheduled_action(
+        &self,
+        device_serial: &str,
+        name: String,
+        action: Option<ScheduledAction>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        if let Some(action) = action {
+            entry.schedule.insert(name, action);
+        } else {
+            entry.schedule.remove(&name);
+        }
+    }
+
+    pub async fn set_device_profile_fallbacks(&self, device_serial: &str, fallbacks: Vec<String>) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.profile_fallbacks = fallbacks;
+    }
+
+    pub async fn set_device_volume_limit(
+        &self,
+        device_serial: &str,
+        channel: ChannelName,
+        limit: Option<u8>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        if let Some(limit) = limit {
+            entry.volume_limits.insert(channel, limit);
+        } else {
+            entry.volume_limits.remove(&channel);
+        }
+    }
+
+    pub async fn set_device_output_trim(
+        &self,
+        device_serial: &str,
+        channel: ChannelName,
+        trim: u8,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        // A full trim is the same as not having one..
+        if trim >= 100 {
+            entry.output_trims.remove(&channel);
+        } else {
+            entry.output_trims.insert(channel, trim);
+        }
+    }
+
+    pub async fn set_device_volume_preset(
+        &self,
+        device_serial: &str,
+        name: String,
+        preset: Option<VolumePreset>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+
+        if let Some(preset) = preset {
+            entry.volume_presets.insert(name, preset);
+        } else {
+            entry.volume_presets.remove(&name);
+        }
+    }
+
+    pub async fn set_device_ducking(&self, device_serial: &str, ducking: Ducking) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.ducking = ducking;
+    }
+
+    pub async fn set_device_interaction_lock(
+        &self,
+        device_serial: &str,
+        interaction_lock: InteractionLock,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.interaction_lock = interaction_lock;
+    }
+
+    pub async fn set_device_now_playing_fader(
+        &self,
+        device_serial: &str,
+        fader: Option<FaderName>,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.now_playing_fader = fader;
+    }
+
     pub async fn set_device_sleep_commands(
         &self,
         device_serial: &str,
@@ -618,6 +1222,28 @@ impl SettingsHandle {
         entry.lock_faders = Some(setting);
     }
 
+    pub async fn set_device_lighting_brightness(&self, device_serial: &str, brightness: u8) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.lighting_brightness = Some(brightness);
+    }
+
+    pub async fn set_device_lighting_crossfade(&self, device_serial: &str, duration: u16) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.lighting_crossfade = Some(duration);
+    }
+
     pub async fn set_enable_monitor_with_fx(&self, device_serial: &str, setting: bool) {
         let mut settings = self.settings.write().await;
         let entry = settings
@@ -629,6 +1255,43 @@ impl SettingsHandle {
         entry.enable_monitor_with_fx = Some(setting);
     }
 
+    pub async fn set_device_label(&self, device_serial: &str, label: Option<String>) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.label = label;
+    }
+
+    pub async fn set_device_error_feedback(&self, device_serial: &str, setting: bool) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.error_feedback = Some(setting);
+    }
+
+    pub async fn set_device_attach_state_policy(
+        &self,
+        device_serial: &str,
+        setting: AttachStatePolicy,
+    ) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.attach_state_policy = Some(setting);
+    }
+
     pub async fn set_device_vod_mode(&self, device_serial: &str, setting: VodMode) {
         let mut settings = self.settings.write().await;
         let entry = settings
@@ -651,6 +1314,17 @@ impl SettingsHandle {
         entry.sampler_reset_on_clear = Some(setting);
     }
 
+    pub async fn set_sampler_normalisation(&self, device_serial: &str, setting: bool) {
+        let mut settings = self.settings.write().await;
+        let entry = settings
+            .devices
+            .as_mut()
+            .unwrap()
+            .entry(device_serial.to_owned())
+            .or_insert_with(DeviceSettings::default);
+        entry.sampler_normalisation = Some(setting);
+    }
+
     pub async fn set_sample_gain_percent(&self, name: String, value: u8) {
         let mut settings = self.settings.write().await;
         if settings.sample_gain.is_none() {
@@ -662,12 +1336,22 @@ impl SettingsHandle {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ApiToken {
+    token: String,
+    scope: TokenScope,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Settings {
+    #[serde(default)]
+    version: u32,
     show_tray_icon: Option<bool>,
     selected_locale: Option<String>,
     tts_enabled: Option<bool>,
     allow_network_access: Option<bool>,
+    profile_autosave_interval: Option<u16>,
+    input_polling: Option<InputPolling>,
     macos_handle_aggregates: Option<bool>,
     profile_directory: Option<PathBuf>,
     mic_profile_directory: Option<PathBuf>,
@@ -677,30 +1361,57 @@ pub struct Settings {
     logs_directory: Option<PathBuf>,
     backup_directory: Option<PathBuf>,
     log_level: Option<LogLevel>,
+    log_rotation: Option<LogRotation>,
+
+    // How many rotated log files are kept before the oldest is removed
+    log_retention: Option<u8>,
+    audit_log_enabled: Option<bool>,
     open_ui_on_launch: Option<bool>,
     activate: Option<String>,
+    event_hooks: Option<HashMap<EventHook, String>>,
+
+    // Applications whose audio streams are moved to GoXLR channels, keyed by application name
+    app_routes: Option<HashMap<String, Vec<AudioChannel>>>,
+
+    // Tokens which allow access to the API over the network, keyed by a user provided name
+    api_tokens: Option<HashMap<String, ApiToken>>,
     devices: Option<HashMap<String, DeviceSettings>>,
     sample_gain: Option<HashMap<String, u8>>,
+
+    // Anything we don't recognise (likely from a newer version), kept so it's not lost on save
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 impl Settings {
     pub fn read(path: &Path) -> Result<Option<Settings>> {
         match File::open(path) {
             Ok(reader) => {
-                let settings = serde_json::from_reader(reader);
+                let settings = serde_json::from_reader(reader)
+                    .map_err(anyhow::Error::from)
+                    .and_then(Settings::migrate)
+                    .and_then(|value| Ok(serde_json::from_value(value)?));
 
                 match settings {
                     Ok(settings) => Ok(Some(settings)),
-                    Err(_) => {
+                    Err(e) => {
                         // Something's gone wrong loading the settings, rather than immediately
-                        // exiting, we'll try to backup the original file, and reload the defaults.
-                        let mut backup = PathBuf::from(path);
-                        backup.set_extension(".failed");
-
-                        let copy_result = fs::copy(path, backup);
-                        println!("{:?}", copy_result);
-
-                        println!("Error Loading configuration, loading defaults.");
+                        // exiting, we'll backup the original file (the defaults will be saved
+                        // over it), and reload the defaults. If we can't back it up, bail out
+                        // rather than lose it.
+                        let backup = path.with_extension("failed");
+                        fs::copy(path, &backup).with_context(|| {
+                            format!(
+                                "Unable to backup the settings file to {}",
+                                backup.to_string_lossy()
+                            )
+                        })?;
+
+                        error!(
+                            "Error Loading configuration ({}), backed up to {} and loading defaults.",
+                            e,
+                            backup.to_string_lossy()
+                        );
                         Ok(None)
                     }
                 }
@@ -713,6 +1424,33 @@ impl Settings {
         }
     }
 
+    fn migrate(mut value: Value) -> Result<Value> {
+        if !value.is_object() {
+            bail!("Settings file is not a JSON object");
+        }
+
+        let mut version = value["version"].as_u64().unwrap_or(0) as u32;
+        if version > SETTINGS_VERSION {
+            warn!(
+                "Settings file is from a newer version ({}), unknown settings will be preserved",
+                version
+            );
+            return Ok(value);
+        }
+
+        while version < SETTINGS_VERSION {
+            info!("Migrating Settings from v{} to v{}", version, version + 1);
+            if let Some((_, migration)) = MIGRATIONS.iter().find(|(from, _)| *from == version) {
+                migration(&mut value)
+                    .with_context(|| format!("Unable to migrate settings from v{}", version))?;
+            }
+            version += 1;
+        }
+
+        value["version"] = Value::from(version);
+        Ok(value)
+    }
+
     pub fn write(&self, path: &Path) -> Result<()> {
         debug!("Saving Settings");
         if let Some(parent) = path.parent() {
@@ -757,9 +1495,15 @@ impl Settings {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(default)]
 struct DeviceSettings {
+    // A friendly name for the device, which can be used in place of its serial
+    label: Option<String>,
+
     profile: String,
     mic_profile: String,
 
+    // Profiles to try, in order, if the main profile can't be loaded
+    profile_fallbacks: Vec<String>,
+
     hold_delay: Option<u16>,
     sampler_pre_buffer: Option<u16>,
 
@@ -769,39 +1513,132 @@ struct DeviceSettings {
     // Disables the Movement of the Faders when Muting to All (full device only)
     lock_faders: Option<bool>,
 
+    // Dims all the lighting on the device, regardless of profile
+    lighting_brightness: Option<u8>,
+
+    // How long (in ms) to fade the lighting between profiles when switching, 0 to snap
+    lighting_crossfade: Option<u16>,
+
     // Enable Monitoring when FX are Enabled
     enable_monitor_with_fx: Option<bool>,
 
     // Clear Sample Settings when Clearing Button
     sampler_reset_on_clear: Option<bool>,
 
+    // Apply each Sample's calculated gain, so they play back at a similar loudness
+    sampler_normalisation: Option<bool>,
+
     // VoD 'Mode'
     vod_mode: Option<VodMode>,
 
+    // Whether the Profile or the Device 'wins' for fader volumes when attaching
+    attach_state_policy: Option<AttachStatePolicy>,
+    error_feedback: Option<bool>,
+
     // 'Shutdown' commands..
     shutdown_commands: Vec<GoXLRCommand>,
     sleep_commands: Vec<GoXLRCommand>,
     wake_commands: Vec<GoXLRCommand>,
+
+    // Buttons which have had their default behaviour replaced
+    button_bindings: HashMap<Button, ButtonBinding>,
+    hotkeys: HashMap<String, Vec<GoXLRCommand>>,
+    schedule: HashMap<String, ScheduledAction>,
+
+    // The maximum volume a channel can be set to, regardless of source
+    volume_limits: HashMap<ChannelName, u8>,
+    output_trims: HashMap<ChannelName, u8>,
+
+    // Named sets of volumes which can be switched between
+    volume_presets: HashMap<String, VolumePreset>,
+
+    // Lowers other channels while the mic is in use
+    ducking: Ducking,
+
+    // The controls which stop working while the interaction lock is on
+    interaction_lock: InteractionLock,
+
+    // The fader whose scribble shows the currently playing media
+    now_playing_fader: Option<FaderName>,
+
+    #[serde(flatten)]
+    extra: HashMap<String, Value>,
 }
 
 impl Default for DeviceSettings {
     fn default() -> Self {
         DeviceSettings {
+            label: None,
             profile: DEFAULT_PROFILE_NAME.to_owned(),
             mic_profile: DEFAULT_MIC_PROFILE_NAME.to_owned(),
+            profile_fallbacks: vec![],
 
             hold_delay: Some(500),
             sampler_pre_buffer: None,
             chat_mute_mutes_mic_to_chat: Some(true),
             lock_faders: Some(false),
+            lighting_brightness: None,
+            lighting_crossfade: None,
             enable_monitor_with_fx: Some(false),
             sampler_reset_on_clear: Some(true),
+            sampler_normalisation: Some(true),
 
             vod_mode: Some(Routable),
+            attach_state_policy: Some(AttachStatePolicy::Profile),
+            error_feedback: Some(true),
 
             shutdown_commands: vec![],
             sleep_commands: vec![],
             wake_commands: vec![],
+
+            button_bindings: Default::default(),
+            hotkeys: Default::default(),
+            schedule: Default::default(),
+            volume_limits: Default::default(),
+            output_trims: Default::default(),
+            volume_presets: Default::default(),
+            ducking: Default::default(),
+            interaction_lock: Default::default(),
+            now_playing_fader: None,
+
+            extra: Default::default(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn migrating_brings_the_version_up_to_date() {
+        let value = Settings::migrate(json!({ "show_tray_icon": false })).unwrap();
+        assert_eq!(value["version"], SETTINGS_VERSION);
+        assert_eq!(value["show_tray_icon"], false);
+    }
+
+    #[test]
+    fn unknown_settings_survive_a_load_and_save() {
+        let path = std::env::temp_dir().join(format!("goxlr-settings-{}.json", std::process::id()));
+        let original = json!({
+            "version": SETTINGS_VERSION,
+            "a_newer_setting": "value",
+            "devices": {
+                "S0000000000": { "a_newer_device_setting": [1, 2, 3] }
+            }
+        });
+        fs::write(&path, original.to_string()).unwrap();
+
+        let settings = Settings::read(&path).unwrap().unwrap();
+        settings.write(&path).unwrap();
+        let saved: Value = serde_json::from_reader(File::open(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(saved["a_newer_setting"], "value");
+        assert_eq!(
+            saved["devices"]["S0000000000"]["a_newer_device_setting"],
+            json!([1, 2, 3])
+        );
+    }
+}
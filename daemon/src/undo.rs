@@ -0,0 +1,614 @@
+/*
+   Keeps a bounded history of changes made to the device, so they can be undone (and redone).
+
+   Before a command is run, its inverse is worked out from the current status of the device. Only
+   commands which set a single value (volumes, routing, colours and effects) are tracked, anything
+   else (profile loads, sampler changes, etc) simply can't be undone.
+*/
+
+use std::collections::VecDeque;
+use std::mem::discriminant;
+use std::time::Duration;
+
+use goxlr_ipc::{EffectLevelValue, GoXLRCommand, MixerStatus};
+use goxlr_types::{EncoderName, RobotRange};
+use tokio::time::Instant;
+
+use crate::profile::channel_name_to_submix;
+
+const HISTORY_LIMIT: usize = 100;
+
+// When a slider is dragged, changes to the same value this close together are treated as one..
+const MERGE_WINDOW: Duration = Duration::from_millis(1000);
+
+pub struct UndoEntry {
+    pub command: GoXLRCommand,
+    pub inverse: GoXLRCommand,
+    time: Instant,
+}
+
+#[derive(Default)]
+pub struct UndoHistory {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+impl UndoHistory {
+    pub fn record(&mut self, command: GoXLRCommand, inverse: GoXLRCommand) {
+        self.redo.clear();
+
+        if let Some(last) = self.undo.back_mut() {
+            if last.time.elapsed() < MERGE_WINDOW && changes_same_value(&last.command, &command) {
+                // Keep the original inverse, so the whole drag is undone at once..
+                last.command = command;
+                last.time = Instant::now();
+                return;
+            }
+        }
+
+        self.undo.push_back(UndoEntry {
+            command,
+            inverse,
+            time: Instant::now(),
+        });
+        if self.undo.len() > HISTORY_LIMIT {
+            self.undo.pop_front();
+        }
+    }
+
+    pub fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.undo.pop_back()
+    }
+
+    pub fn pop_redo(&mut self) -> Option<UndoEntry> {
+        self.redo.pop()
+    }
+
+    pub fn push_undo(&mut self, mut entry: UndoEntry) {
+        // This shouldn't be merged with anything that comes after it..
+        entry.time = Instant::now() - MERGE_WINDOW;
+        self.undo.push_back(entry);
+    }
+
+    pub fn push_redo(&mut self, entry: UndoEntry) {
+        self.redo.push(entry);
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+}
+
+// Commands which replace the state the history refers to, so it can't be undone past them.
+pub fn clears_history(command: &GoXLRCommand) -> bool {
+    matches!(
+        command,
+        GoXLRCommand::NewProfile(_)
+            | GoXLRCommand::LoadProfile(_, _)
+            | GoXLRCommand::LoadProfileColours(_)
+            | GoXLRCommand::MergeProfile(_, _)
+            | GoXLRCommand::DiscardProfileChanges()
+            | GoXLRCommand::ResetToDefaultProfile()
+            | GoXLRCommand::ResetDevice()
+            | GoXLRCommand::ResolveProfileConflict(_)
+            | GoXLRCommand::LoadEffectPreset(_)
+            | GoXLRCommand::SetActiveEffectPreset(_)
+    )
+}
+
+// Whether two commands change the same value, used to merge changes to the same value. Only
+// commands which get_inverse() handles end up in the history, so only those with a target (a
+// channel, fader, button, etc) need checking here, the rest only ever have a single value..
+fn changes_same_value(first: &GoXLRCommand, second: &GoXLRCommand) -> bool {
+    if discriminant(first) != discriminant(second) {
+        return false;
+    }
+
+    match (first, second) {
+        (GoXLRCommand::SetVolume(a, _), GoXLRCommand::SetVolume(b, _))
+        | (GoXLRCommand::SetSubMixVolume(a, _), GoXLRCommand::SetSubMixVolume(b, _)) => a == b,
+        (GoXLRCommand::SetRouter(a_in, a_out, _), GoXLRCommand::SetRouter(b_in, b_out, _)) => {
+            a_in == b_in && a_out == b_out
+        }
+        (GoXLRCommand::SetOutputMuted(a, _), GoXLRCommand::SetOutputMuted(b, _)) => a == b,
+        (GoXLRCommand::SetFader(a, _), GoXLRCommand::SetFader(b, _))
+        | (GoXLRCommand::SetFaderMuteFunction(a, _), GoXLRCommand::SetFaderMuteFunction(b, _))
+        | (GoXLRCommand::SetFaderDisplayStyle(a, _), GoXLRCommand::SetFaderDisplayStyle(b, _))
+        | (GoXLRCommand::SetFaderColours(a, _, _), GoXLRCommand::SetFaderColours(b, _, _)) => {
+            a == b
+        }
+        (GoXLRCommand::RenameFaderPage(a, _), GoXLRCommand::RenameFaderPage(b, _)) => a == b,
+        (GoXLRCommand::SetMicrophoneGain(a, _), GoXLRCommand::SetMicrophoneGain(b, _)) => a == b,
+        (GoXLRCommand::SetButtonColours(a, _, _), GoXLRCommand::SetButtonColours(b, _, _))
+        | (GoXLRCommand::SetButtonOffStyle(a, _), GoXLRCommand::SetButtonOffStyle(b, _))
+        | (GoXLRCommand::SetButtonMutedStyle(a, _), GoXLRCommand::SetButtonMutedStyle(b, _)) => {
+            a == b
+        }
+        (GoXLRCommand::SetSimpleColour(a, _), GoXLRCommand::SetSimpleColour(b, _)) => a == b,
+        (
+            GoXLRCommand::SetEncoderColour(a, _, _, _),
+            GoXLRCommand::SetEncoderColour(b, _, _, _),
+        ) => a == b,
+        (GoXLRCommand::SetSampleColour(a, _, _, _), GoXLRCommand::SetSampleColour(b, _, _, _))
+        | (GoXLRCommand::SetSampleOffStyle(a, _), GoXLRCommand::SetSampleOffStyle(b, _)) => a == b,
+        (GoXLRCommand::SetEffectLevel(a, _), GoXLRCommand::SetEffectLevel(b, _))
+        | (GoXLRCommand::SetEncoderSensitivity(a, _), GoXLRCommand::SetEncoderSensitivity(b, _)) => {
+            a == b
+        }
+        (GoXLRCommand::SetRobotGain(a, _), GoXLRCommand::SetRobotGain(b, _))
+        | (GoXLRCommand::SetRobotFreq(a, _), GoXLRCommand::SetRobotFreq(b, _))
+        | (GoXLRCommand::SetRobotWidth(a, _), GoXLRCommand::SetRobotWidth(b, _)) => a == b,
+
+        // A batch can change any number of values, so each one stays as its own step..
+        (GoXLRCommand::ExecuteBatch(_), _) => false,
+        _ => true,
+    }
+}
+
+/// Whether `command` is one get_inverse() is able to undo, building the status it needs isn't
+/// free, so it's only done for these. Anything handled by get_inverse() needs to be listed here.
+pub fn is_undoable(command: &GoXLRCommand) -> bool {
+    matches!(
+        command,
+        GoXLRCommand::SetVolume(_, _)
+            | GoXLRCommand::SetSubMixVolume(_, _)
+            | GoXLRCommand::SetRouter(_, _, _)
+            | GoXLRCommand::SoloChannel(_)
+            | GoXLRCommand::PanicMute()
+            | GoXLRCommand::PanicRestore()
+            | GoXLRCommand::SetInteractionLock(_)
+            | GoXLRCommand::SetOutputMuted(_, _)
+            | GoXLRCommand::SetFader(_, _)
+            | GoXLRCommand::SetFaderPage(_)
+            | GoXLRCommand::NextFaderPage()
+            | GoXLRCommand::RenameFaderPage(_, _)
+            | GoXLRCommand::SetFaderMuteFunction(_, _)
+            | GoXLRCommand::SetMicrophoneGain(_, _)
+            | GoXLRCommand::SetSwearButtonVolume(_)
+            | GoXLRCommand::SetDeeser(_)
+            | GoXLRCommand::SetAnimationMode(_)
+            | GoXLRCommand::SetAnimationMod1(_)
+            | GoXLRCommand::SetAnimationMod2(_)
+            | GoXLRCommand::SetAnimationWaterfall(_)
+            | GoXLRCommand::SetLightingBrightness(_)
+            | GoXLRCommand::SetVoiceMeterEnabled(_)
+            | GoXLRCommand::SetVoiceMeterSensitivity(_)
+            | GoXLRCommand::SetVoiceMeterColours(_, _)
+            | GoXLRCommand::SetFaderDisplayStyle(_, _)
+            | GoXLRCommand::SetFaderColours(_, _, _)
+            | GoXLRCommand::SetButtonColours(_, _, _)
+            | GoXLRCommand::SetButtonOffStyle(_, _)
+            | GoXLRCommand::SetButtonMutedStyle(_, _)
+            | GoXLRCommand::SetSimpleColour(_, _)
+            | GoXLRCommand::SetEncoderColour(_, _, _, _)
+            | GoXLRCommand::SetSampleColour(_, _, _, _)
+            | GoXLRCommand::SetSampleOffStyle(_, _)
+            | GoXLRCommand::SetLighting(_)
+    ) || is_undoable_effect(command)
+}
+
+fn is_undoable_effect(command: &GoXLRCommand) -> bool {
+    matches!(
+        command,
+        GoXLRCommand::SetEffectLevel(_, _)
+            | GoXLRCommand::SetEncoderSensitivity(_, _)
+            | GoXLRCommand::SetReverbStyle(_)
+            | GoXLRCommand::SetReverbAmount(_)
+            | GoXLRCommand::SetReverbDecay(_)
+            | GoXLRCommand::SetReverbEarlyLevel(_)
+            | GoXLRCommand::SetReverbTailLevel(_)
+            | GoXLRCommand::SetReverbPreDelay(_)
+            | GoXLRCommand::SetReverbLowColour(_)
+            | GoXLRCommand::SetReverbHighColour(_)
+            | GoXLRCommand::SetReverbHighFactor(_)
+            | GoXLRCommand::SetReverbDiffuse(_)
+            | GoXLRCommand::SetReverbModSpeed(_)
+            | GoXLRCommand::SetReverbModDepth(_)
+            | GoXLRCommand::SetEchoStyle(_)
+            | GoXLRCommand::SetEchoAmount(_)
+            | GoXLRCommand::SetEchoFeedback(_)
+            | GoXLRCommand::SetEchoTempo(_)
+            | GoXLRCommand::SetEchoDelayLeft(_)
+            | GoXLRCommand::SetEchoDelayRight(_)
+            | GoXLRCommand::SetEchoFeedbackLeft(_)
+            | GoXLRCommand::SetEchoFeedbackRight(_)
+            | GoXLRCommand::SetEchoFeedbackXFBLtoR(_)
+            | GoXLRCommand::SetEchoFeedbackXFBRtoL(_)
+            | GoXLRCommand::SetPitchStyle(_)
+            | GoXLRCommand::SetPitchAmount(_)
+            | GoXLRCommand::SetPitchCharacter(_)
+            | GoXLRCommand::SetGenderStyle(_)
+            | GoXLRCommand::SetGenderAmount(_)
+            | GoXLRCommand::SetMegaphoneStyle(_)
+            | GoXLRCommand::SetMegaphoneAmount(_)
+            | GoXLRCommand::SetMegaphonePostGain(_)
+            | GoXLRCommand::SetRobotStyle(_)
+            | GoXLRCommand::SetRobotGain(_, _)
+            | GoXLRCommand::SetRobotFreq(_, _)
+            | GoXLRCommand::SetRobotWidth(_, _)
+            | GoXLRCommand::SetRobotWaveform(_)
+            | GoXLRCommand::SetRobotPulseWidth(_)
+            | GoXLRCommand::SetRobotThreshold(_)
+            | GoXLRCommand::SetRobotDryMix(_)
+            | GoXLRCommand::SetHardTuneStyle(_)
+            | GoXLRCommand::SetHardTuneAmount(_)
+            | GoXLRCommand::SetHardTuneRate(_)
+            | GoXLRCommand::SetHardTuneWindow(_)
+            | GoXLRCommand::SetHardTuneSource(_)
+    )
+}
+
+/// Returns a command which will restore the value `command` is about to change.
+pub fn get_inverse(command: &GoXLRCommand, status: &MixerStatus) -> Option<GoXLRCommand> {
+    let lighting = &status.lighting;
+
+    let inverse = match command {
+        // Volumes and Routing
+        GoXLRCommand::SetVolume(channel, _) => {
+            GoXLRCommand::SetVolume(*channel, status.levels.volumes[*channel])
+        }
+        GoXLRCommand::SetSubMixVolume(channel, _) => {
+            let submix = status.levels.submix.as_ref()?;
+            let input = channel_name_to_submix(*channel)?;
+            GoXLRCommand::SetSubMixVolume(*channel, submix.inputs[input].volume)
+        }
+        GoXLRCommand::SetRouter(input, output, _) => {
+            GoXLRCommand::SetRouter(*input, *output, status.router[*input][*output])
+        }
+        GoXLRCommand::SoloChannel(_) => GoXLRCommand::SoloChannel(status.solo_input),
+        GoXLRCommand::PanicMute() | GoXLRCommand::PanicRestore() => match status.panic_muted {
+            true => GoXLRCommand::PanicMute(),
+            false => GoXLRCommand::PanicRestore(),
+        },
+        GoXLRCommand::SetInteractionLock(_) => {
+            GoXLRCommand::SetInteractionLock(status.interaction_locked)
+        }
+        GoXLRCommand::SetOutputMuted(output, _) => {
+            GoXLRCommand::SetOutputMuted(*output, status.muted_outputs[*output])
+        }
+        GoXLRCommand::SetFader(fader, _) => {
+            GoXLRCommand::SetFader(*fader, status.fader_status[*fader].channel)
+        }
+        GoXLRCommand::SetFaderPage(_) | GoXLRCommand::NextFaderPage() => {
+            GoXLRCommand::SetFaderPage(status.fader_pages.active)
+        }
+        GoXLRCommand::RenameFaderPage(page, _) => {
+            GoXLRCommand::RenameFaderPage(*page, status.fader_pages.pages.get(*page)?.clone())
+        }
+        GoXLRCommand::SetFaderMuteFunction(fader, _) => {
+            GoXLRCommand::SetFaderMuteFunction(*fader, status.fader_status[*fader].mute_type)
+        }
+        GoXLRCommand::SetMicrophoneGain(mic_type, _) => {
+            GoXLRCommand::SetMicrophoneGain(*mic_type, status.mic_status.mic_gains[*mic_type])
+        }
+        GoXLRCommand::SetSwearButtonVolume(_) => {
+            GoXLRCommand::SetSwearButtonVolume(status.levels.bleep)
+        }
+        GoXLRCommand::SetDeeser(_) => GoXLRCommand::SetDeeser(status.levels.deess),
+
+        // Lighting
+        GoXLRCommand::SetAnimationMode(_) => {
+            GoXLRCommand::SetAnimationMode(lighting.animation.mode)
+        }
+        GoXLRCommand::SetAnimationMod1(_) => {
+            GoXLRCommand::SetAnimationMod1(lighting.animation.mod1)
+        }
+        GoXLRCommand::SetAnimationMod2(_) => {
+            GoXLRCommand::SetAnimationMod2(lighting.animation.mod2)
+        }
+        GoXLRCommand::SetAnimationWaterfall(_) => {
+            GoXLRCommand::SetAnimationWaterfall(lighting.animation.waterfall_direction)
+        }
+        GoXLRCommand::SetLightingBrightness(_) => {
+            GoXLRCommand::SetLightingBrightness(lighting.animation.brightness)
+        }
+        GoXLRCommand::SetVoiceMeterEnabled(_) => {
+            GoXLRCommand::SetVoiceMeterEnabled(lighting.animation.voice_meter.enabled)
+        }
+        GoXLRCommand::SetVoiceMeterSensitivity(_) => {
+            GoXLRCommand::SetVoiceMeterSensitivity(lighting.animation.voice_meter.sensitivity)
+        }
+        GoXLRCommand::SetVoiceMeterColours(_, _) => GoXLRCommand::SetVoiceMeterColours(
+            lighting.animation.voice_meter.low_colour.clone(),
+            lighting.animation.voice_meter.high_colour.clone(),
+        ),
+        GoXLRCommand::SetFaderDisplayStyle(fader, _) => {
+            GoXLRCommand::SetFaderDisplayStyle(*fader, lighting.faders.get(fader)?.style)
+        }
+        GoXLRCommand::SetFaderColours(fader, _, _) => {
+            let colours = &lighting.faders.get(fader)?.colours;
+            GoXLRCommand::SetFaderColours(
+                *fader,
+                colours.colour_one.clone(),
+                colours.colour_two.clone(),
+            )
+        }
+        GoXLRCommand::SetButtonColours(button, _, _) => {
+            let colours = &lighting.buttons.get(button)?.colours;
+            GoXLRCommand::SetButtonColours(
+                *button,
+                colours.colour_one.clone(),
+                Some(colours.colour_two.clone()),
+            )
+        }
+        GoXLRCommand::SetButtonOffStyle(button, _) => {
+            GoXLRCommand::SetButtonOffStyle(*button, lighting.buttons.get(button)?.off_style)
+        }
+        GoXLRCommand::SetButtonMutedStyle(button, _) => {
+            GoXLRCommand::SetButtonMutedStyle(*button, lighting.buttons.get(button)?.muted_style?)
+        }
+        GoXLRCommand::SetSimpleColour(target, _) => {
+            GoXLRCommand::SetSimpleColour(*target, lighting.simple.get(target)?.colour_one.clone())
+        }
+        GoXLRCommand::SetEncoderColour(target, _, _, _) => {
+            let colours = lighting.encoders.get(target)?;
+            GoXLRCommand::SetEncoderColour(
+                *target,
+                colours.colour_one.clone(),
+                colours.colour_two.clone(),
+                colours.colour_three.clone(),
+            )
+        }
+        GoXLRCommand::SetSampleColour(target, _, _, _) => {
+            let colours = &lighting.sampler.get(target)?.colours;
+            GoXLRCommand::SetSampleColour(
+                *target,
+                colours.colour_one.clone(),
+                colours.colour_two.clone(),
+                colours.colour_three.clone(),
+            )
+        }
+        GoXLRCommand::SetSampleOffStyle(target, _) => {
+            GoXLRCommand::SetSampleOffStyle(*target, lighting.sampler.get(target)?.off_style)
+        }
+        GoXLRCommand::SetLighting(_) => GoXLRCommand::SetLighting(lighting.clone()),
+
+        // Effects, only available on the full device..
+        _ => return get_effect_inverse(command, status),
+    };
+
+    Some(inverse)
+}
+
+fn get_effect_inverse(command: &GoXLRCommand, status: &MixerStatus) -> Option<GoXLRCommand> {
+    let levels = &status.effects.as_ref()?.levels;
+    let effects = &status.effects.as_ref()?.current;
+    let reverb = &effects.reverb;
+    let echo = &effects.echo;
+    let robot = &effects.robot;
+    let hard_tune = &effects.hard_tune;
+
+    let inverse = match command {
+        GoXLRCommand::SetEffectLevel(encoder, _) => {
+            let level = match encoder {
+                EncoderName::Pitch => &levels.pitch,
+                EncoderName::Gender => &levels.gender,
+                EncoderName::Reverb => &levels.reverb,
+                EncoderName::Echo => &levels.echo,
+            };
+            GoXLRCommand::SetEffectLevel(*encoder, EffectLevelValue::Raw(level.raw))
+        }
+        GoXLRCommand::SetEncoderSensitivity(encoder, _) => {
+            let level = match encoder {
+                EncoderName::Pitch => &levels.pitch,
+                EncoderName::Gender => &levels.gender,
+                EncoderName::Reverb => &levels.reverb,
+                EncoderName::Echo => &levels.echo,
+            };
+            GoXLRCommand::SetEncoderSensitivity(*encoder, level.sensitivity)
+        }
+
+        GoXLRCommand::SetReverbStyle(_) => GoXLRCommand::SetReverbStyle(reverb.style),
+        GoXLRCommand::SetReverbAmount(_) => GoXLRCommand::SetReverbAmount(reverb.amount),
+        GoXLRCommand::SetReverbDecay(_) => GoXLRCommand::SetReverbDecay(reverb.decay),
+        GoXLRCommand::SetReverbEarlyLevel(_) => {
+            GoXLRCommand::SetReverbEarlyLevel(reverb.early_level)
+        }
+        GoXLRCommand::SetReverbTailLevel(_) => GoXLRCommand::SetReverbTailLevel(reverb.tail_level),
+        GoXLRCommand::SetReverbPreDelay(_) => GoXLRCommand::SetReverbPreDelay(reverb.pre_delay),
+        GoXLRCommand::SetReverbLowColour(_) => GoXLRCommand::SetReverbLowColour(reverb.lo_colour),
+        GoXLRCommand::SetReverbHighColour(_) => GoXLRCommand::SetReverbHighColour(reverb.hi_colour),
+        GoXLRCommand::SetReverbHighFactor(_) => GoXLRCommand::SetReverbHighFactor(reverb.hi_factor),
+        GoXLRCommand::SetReverbDiffuse(_) => GoXLRCommand::SetReverbDiffuse(reverb.diffuse),
+        GoXLRCommand::SetReverbModSpeed(_) => GoXLRCommand::SetReverbModSpeed(reverb.mod_speed),
+        GoXLRCommand::SetReverbModDepth(_) => GoXLRCommand::SetReverbModDepth(reverb.mod_depth),
+
+        GoXLRCommand::SetEchoStyle(_) => GoXLRCommand::SetEchoStyle(echo.style),
+        GoXLRCommand::SetEchoAmount(_) => GoXLRCommand::SetEchoAmount(echo.amount),
+        GoXLRCommand::SetEchoFeedback(_) => GoXLRCommand::SetEchoFeedback(echo.feedback),
+        GoXLRCommand::SetEchoTempo(_) => GoXLRCommand::SetEchoTempo(echo.tempo),
+        GoXLRCommand::SetEchoDelayLeft(_) => GoXLRCommand::SetEchoDelayLeft(echo.delay_left),
+        GoXLRCommand::SetEchoDelayRight(_) => GoXLRCommand::SetEchoDelayRight(echo.delay_right),
+        GoXLRCommand::SetEchoFeedbackLeft(_) => {
+            GoXLRCommand::SetEchoFeedbackLeft(echo.feedback_left)
+        }
+        GoXLRCommand::SetEchoFeedbackRight(_) => {
+            GoXLRCommand::SetEchoFeedbackRight(echo.feedback_right)
+        }
+        GoXLRCommand::SetEchoFeedbackXFBLtoR(_) => {
+            GoXLRCommand::SetEchoFeedbackXFBLtoR(echo.feedback_xfb_l_to_r)
+        }
+        GoXLRCommand::SetEchoFeedbackXFBRtoL(_) => {
+            GoXLRCommand::SetEchoFeedbackXFBRtoL(echo.feedback_xfb_r_to_l)
+        }
+
+        GoXLRCommand::SetPitchStyle(_) => GoXLRCommand::SetPitchStyle(effects.pitch.style),
+        GoXLRCommand::SetPitchAmount(_) => GoXLRCommand::SetPitchAmount(effects.pitch.amount),
+        GoXLRCommand::SetPitchCharacter(_) => {
+            GoXLRCommand::SetPitchCharacter(effects.pitch.character)
+        }
+
+        GoXLRCommand::SetGenderStyle(_) => GoXLRCommand::SetGenderStyle(effects.gender.style),
+        GoXLRCommand::SetGenderAmount(_) => GoXLRCommand::SetGenderAmount(effects.gender.amount),
+
+        GoXLRCommand::SetMegaphoneStyle(_) => {
+            GoXLRCommand::SetMegaphoneStyle(effects.megaphone.style)
+        }
+        GoXLRCommand::SetMegaphoneAmount(_) => {
+            GoXLRCommand::SetMegaphoneAmount(effects.megaphone.amount)
+        }
+        GoXLRCommand::SetMegaphonePostGain(_) => {
+            GoXLRCommand::SetMegaphonePostGain(effects.megaphone.post_gain)
+        }
+
+        GoXLRCommand::SetRobotStyle(_) => GoXLRCommand::SetRobotStyle(robot.style),
+        GoXLRCommand::SetRobotGain(range, _) => GoXLRCommand::SetRobotGain(
+            *range,
+            match range {
+                RobotRange::Low => robot.low_gain,
+                RobotRange::Medium => robot.mid_gain,
+                RobotRange::High => robot.high_gain,
+            },
+        ),
+        GoXLRCommand::SetRobotFreq(range, _) => GoXLRCommand::SetRobotFreq(
+            *range,
+            match range {
+                RobotRange::Low => robot.low_freq,
+                RobotRange::Medium => robot.mid_freq,
+                RobotRange::High => robot.high_freq,
+            },
+        ),
+        GoXLRCommand::SetRobotWidth(range, _) => GoXLRCommand::SetRobotWidth(
+            *range,
+            match range {
+                RobotRange::Low => robot.low_width,
+                RobotRange::Medium => robot.mid_width,
+                RobotRange::High => robot.high_width,
+            },
+        ),
+        GoXLRCommand::SetRobotWaveform(_) => GoXLRCommand::SetRobotWaveform(robot.waveform),
+        GoXLRCommand::SetRobotPulseWidth(_) => GoXLRCommand::SetRobotPulseWidth(robot.pulse_width),
+        GoXLRCommand::SetRobotThreshold(_) => GoXLRCommand::SetRobotThreshold(robot.threshold),
+        GoXLRCommand::SetRobotDryMix(_) => GoXLRCommand::SetRobotDryMix(robot.dry_mix),
+
+        GoXLRCommand::SetHardTuneStyle(_) => GoXLRCommand::SetHardTuneStyle(hard_tune.style),
+        GoXLRCommand::SetHardTuneAmount(_) => GoXLRCommand::SetHardTuneAmount(hard_tune.amount),
+        GoXLRCommand::SetHardTuneRate(_) => GoXLRCommand::SetHardTuneRate(hard_tune.rate),
+        GoXLRCommand::SetHardTuneWindow(_) => GoXLRCommand::SetHardTuneWindow(hard_tune.window),
+        GoXLRCommand::SetHardTuneSource(_) => GoXLRCommand::SetHardTuneSource(hard_tune.source),
+
+        _ => return None,
+    };
+
+    Some(inverse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goxlr_types::{ChannelName, InputDevice, OutputDevice};
+
+    #[test]
+    fn changes_to_the_same_value_are_merged() {
+        let mut history = UndoHistory::default();
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 0),
+        );
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 20),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+        );
+
+        // The merged entry goes to the latest value, and back to the original one..
+        let entry = history.pop_undo().unwrap();
+        assert!(matches!(
+            entry.command,
+            GoXLRCommand::SetVolume(ChannelName::Mic, 20)
+        ));
+        assert!(matches!(
+            entry.inverse,
+            GoXLRCommand::SetVolume(ChannelName::Mic, 0)
+        ));
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn changes_to_different_values_are_kept_apart() {
+        let mut history = UndoHistory::default();
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 0),
+        );
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Game, 10),
+            GoXLRCommand::SetVolume(ChannelName::Game, 0),
+        );
+        history.record(
+            GoXLRCommand::SetRouter(InputDevice::Music, OutputDevice::Headphones, true),
+            GoXLRCommand::SetRouter(InputDevice::Music, OutputDevice::Headphones, false),
+        );
+        history.record(
+            GoXLRCommand::SetRouter(InputDevice::Music, OutputDevice::LineOut, true),
+            GoXLRCommand::SetRouter(InputDevice::Music, OutputDevice::LineOut, false),
+        );
+
+        let mut count = 0;
+        while history.pop_undo().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn batches_are_never_merged() {
+        let mut history = UndoHistory::default();
+        for volume in [10, 20] {
+            history.record(
+                GoXLRCommand::ExecuteBatch(vec![GoXLRCommand::SetVolume(ChannelName::Mic, volume)]),
+                GoXLRCommand::ExecuteBatch(vec![GoXLRCommand::SetVolume(ChannelName::Mic, 0)]),
+            );
+        }
+
+        assert!(history.pop_undo().is_some());
+        assert!(history.pop_undo().is_some());
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn pushed_back_entries_are_not_merged() {
+        let mut history = UndoHistory::default();
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 0),
+        );
+
+        // Putting an entry back (after a failed undo) shouldn't let the next change merge in..
+        let entry = history.pop_undo().unwrap();
+        history.push_undo(entry);
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 20),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+        );
+
+        assert!(history.pop_undo().is_some());
+        assert!(history.pop_undo().is_some());
+    }
+
+    #[test]
+    fn new_changes_clear_redo() {
+        let mut history = UndoHistory::default();
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Mic, 10),
+            GoXLRCommand::SetVolume(ChannelName::Mic, 0),
+        );
+        let entry = history.pop_undo().unwrap();
+        history.push_redo(entry);
+        assert!(history.can_redo());
+
+        history.record(
+            GoXLRCommand::SetVolume(ChannelName::Game, 10),
+            GoXLRCommand::SetVolume(ChannelName::Game, 0),
+        );
+        assert!(!history.can_redo());
+    }
+}
@@ -0,0 +1,78 @@
+/*
+ * C interface for driving a GoXLR directly, see ffi/src/lib.rs for the details of each call.
+ *
+ * This talks to the hardware without the daemon, so make sure the daemon isn't running.
+ */
+
+#ifndef GOXLR_H
+#define GOXLR_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct GoXLRHandle GoXLRHandle;
+
+/* These must match the order of the enums in goxlr-types */
+typedef enum {
+    GOXLR_CHANNEL_MIC = 0,
+    GOXLR_CHANNEL_LINE_IN,
+    GOXLR_CHANNEL_CONSOLE,
+    GOXLR_CHANNEL_SYSTEM,
+    GOXLR_CHANNEL_GAME,
+    GOXLR_CHANNEL_CHAT,
+    GOXLR_CHANNEL_SAMPLE,
+    GOXLR_CHANNEL_MUSIC,
+    GOXLR_CHANNEL_HEADPHONES,
+    GOXLR_CHANNEL_MIC_MONITOR,
+    GOXLR_CHANNEL_LINE_OUT,
+} GoXLRChannel;
+
+typedef enum {
+    GOXLR_FADER_A = 0,
+    GOXLR_FADER_B,
+    GOXLR_FADER_C,
+    GOXLR_FADER_D,
+} GoXLRFader;
+
+typedef enum {
+    GOXLR_INPUT_MICROPHONE = 0,
+    GOXLR_INPUT_CHAT,
+    GOXLR_INPUT_MUSIC,
+    GOXLR_INPUT_GAME,
+    GOXLR_INPUT_CONSOLE,
+    GOXLR_INPUT_LINE_IN,
+    GOXLR_INPUT_SYSTEM,
+    GOXLR_INPUT_SAMPLES,
+} GoXLRInput;
+
+/* Outputs are combined as a bitmask, eg. (1 << GOXLR_OUTPUT_HEADPHONES) */
+typedef enum {
+    GOXLR_OUTPUT_HEADPHONES = 0,
+    GOXLR_OUTPUT_BROADCAST_MIX,
+    GOXLR_OUTPUT_CHAT_MIC,
+    GOXLR_OUTPUT_SAMPLER,
+    GOXLR_OUTPUT_LINE_OUT,
+} GoXLROutput;
+
+/* Returns NULL on failure, pass NULL as the serial to open the first device found */
+GoXLRHandle *goxlr_open(const char *serial);
+void goxlr_close(GoXLRHandle *handle);
+
+/* The message from the last failed call on this thread, or NULL */
+const char *goxlr_last_error(void);
+
+/* All of these return 0 on success, and -1 on failure */
+int32_t goxlr_set_volume(GoXLRHandle *handle, uint32_t channel, uint8_t volume);
+int32_t goxlr_set_fader(GoXLRHandle *handle, uint32_t fader, uint32_t channel);
+int32_t goxlr_set_colours(GoXLRHandle *handle, const uint8_t *data, size_t length);
+int32_t goxlr_set_routing(GoXLRHandle *handle, uint32_t input, uint32_t outputs);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* GOXLR_H */
@@ -0,0 +1,279 @@
+//! A small C interface around goxlr-usb, so applications that aren't written in Rust can drive
+//! a GoXLR directly. This bypasses the daemon entirely, so shouldn't be used while the daemon is
+//! running, the two will simply fight over the device.
+//!
+//! Enums are passed as their index, in the order they're declared in goxlr-types, see
+//! include/goxlr.h for the C side of things.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use anyhow::{anyhow, bail, Result};
+use goxlr_types::{ChannelName, FaderName, InputDevice, OutputDevice};
+use goxlr_usb::colouring::ColourMapFormat;
+use goxlr_usb::device::base::FullGoXLRDevice;
+use goxlr_usb::device::{find_devices, from_device};
+use goxlr_usb::routing::{InputDevice as RoutingInput, OutputDevice as RoutingOutput};
+use strum::IntoEnumIterator;
+use tokio::runtime::{Builder, Runtime};
+use tokio::sync::mpsc;
+
+// Colour maps are one of two sizes, depending on whether the firmware is 1.3.40 or newer..
+const COLOUR_MAP_SIZE: usize = ColourMapFormat::Legacy.size();
+const COLOUR_MAP_SIZE_1_3_40: usize = ColourMapFormat::Animated.size();
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+pub struct GoXLRHandle {
+    device: Box<dyn FullGoXLRDevice>,
+
+    // The USB layer reports disconnects and events through these, we don't do anything with
+    // them, but they need to stay open. The runtime needs to outlive the device, so comes last.
+    _disconnect_rx: mpsc::Receiver<String>,
+    _event_rx: mpsc::Receiver<String>,
+    _runtime: Runtime,
+}
+
+/// Opens a GoXLR by its serial number, or the first one found if `serial` is NULL. Returns NULL
+/// on failure, see goxlr_last_error() for why.
+///
+/// # Safety
+/// `serial` must be NULL, or a valid NUL terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_open(serial: *const c_char) -> *mut GoXLRHandle {
+    guard(ptr::null_mut(), || {
+        let serial = if serial.is_null() {
+            None
+        } else {
+            match CStr::from_ptr(serial).to_str() {
+                Ok(serial) => Some(serial.to_string()),
+                Err(e) => {
+                    set_error(anyhow!("Serial is not valid UTF-8: {}", e));
+                    return ptr::null_mut();
+                }
+            }
+        };
+
+        match open(serial) {
+            Ok(handle) => Box::into_raw(Box::new(handle)),
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Closes a handle returned by goxlr_open(), passing NULL does nothing.
+///
+/// # Safety
+/// `handle` must have come from goxlr_open(), and not already been closed.
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_close(handle: *mut GoXLRHandle) {
+    guard((), || {
+        if !handle.is_null() {
+            drop(Box::from_raw(handle));
+        }
+    })
+}
+
+/// Returns the error message from the last failed call on this thread, or NULL if there hasn't
+/// been one. The string stays valid until the next failing call on the same thread.
+#[no_mangle]
+pub extern "C" fn goxlr_last_error() -> *const c_char {
+    guard(ptr::null(), || {
+        LAST_ERROR.with(|error| match &*error.borrow() {
+            Some(message) => message.as_ptr(),
+            None => ptr::null(),
+        })
+    })
+}
+
+/// Sets the volume (0-255) of a channel. Returns 0 on success, -1 on failure.
+///
+/// # Safety
+/// `handle` must be a valid handle from goxlr_open().
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_set_volume(
+    handle: *mut GoXLRHandle,
+    channel: u32,
+    volume: u8,
+) -> i32 {
+    run(handle, |handle| {
+        let channel = from_index::<ChannelName>(channel, "Channel")?;
+        handle.device.set_volume(channel, volume)
+    })
+}
+
+/// Assigns a channel to a fader. Returns 0 on success, -1 on failure.
+///
+/// # Safety
+/// `handle` must be a valid handle from goxlr_open().
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_set_fader(
+    handle: *mut GoXLRHandle,
+    fader: u32,
+    channel: u32,
+) -> i32 {
+    run(handle, |handle| {
+        let fader = from_index::<FaderName>(fader, "Fader")?;
+        let channel = from_index::<ChannelName>(channel, "Channel")?;
+        handle.device.set_fader(fader, channel)
+    })
+}
+
+/// Sends a raw colour map to the device, this should be 328 bytes, or 520 bytes for firmware
+/// 1.3.40 and above. Returns 0 on success, -1 on failure.
+///
+/// # Safety
+/// `handle` must be a valid handle from goxlr_open(), and `data` must point to `length` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_set_colours(
+    handle: *mut GoXLRHandle,
+    data: *const u8,
+    length: usize,
+) -> i32 {
+    run(handle, |handle| {
+        if data.is_null() {
+            bail!("Colour data is NULL");
+        }
+        let data = std::slice::from_raw_parts(data, length);
+
+        match length {
+            COLOUR_MAP_SIZE => handle.device.set_button_colours(data.try_into()?),
+            COLOUR_MAP_SIZE_1_3_40 => handle.device.set_button_colours_1_3_40(data.try_into()?),
+            _ => bail!(
+                "Colour map must be {} or {} bytes",
+                COLOUR_MAP_SIZE,
+                COLOUR_MAP_SIZE_1_3_40
+            ),
+        }
+    })
+}
+
+/// Sets where an input is routed, `outputs` is a bitmask of output indexes (so 1 << Headphones
+/// routes to the headphones only). Returns 0 on success, -1 on failure.
+///
+/// # Safety
+/// `handle` must be a valid handle from goxlr_open().
+#[no_mangle]
+pub unsafe extern "C" fn goxlr_set_routing(
+    handle: *mut GoXLRHandle,
+    input: u32,
+    outputs: u32,
+) -> i32 {
+    run(handle, |handle| {
+        let input = from_index::<InputDevice>(input, "Input")?;
+        let (left_input, right_input) = RoutingInput::from_basic(&input);
+
+        let mut left = [0; 22];
+        let mut right = [0; 22];
+        for (index, output) in OutputDevice::iter().enumerate() {
+            if outputs & (1 << index) != 0 {
+                let (left_output, right_output) = RoutingOutput::from_basic(&output);
+                left[left_output.position()] = 0x20;
+                right[right_output.position()] = 0x20;
+            }
+        }
+
+        handle.device.set_routing(left_input, left)?;
+        handle.device.set_routing(right_input, right)
+    })
+}
+
+fn open(serial: Option<String>) -> Result<GoXLRHandle> {
+    // The USB layer expects to be inside a tokio runtime, so give it one..
+    let runtime = Builder::new_current_thread().enable_all().build()?;
+    let guard = runtime.enter();
+
+    for device in find_devices() {
+        let (disconnect_tx, disconnect_rx) = mpsc::channel(1);
+        let (event_tx, event_rx) = mpsc::channel(1);
+
+        let Ok(mut goxlr) = from_device(device, disconnect_tx, event_tx, false) else {
+            continue;
+        };
+
+        if let Some(serial) = &serial {
+            let (found, _) = goxlr.get_serial_number()?;
+            if found != *serial {
+                continue;
+            }
+        }
+
+        drop(guard);
+        return Ok(GoXLRHandle {
+            device: goxlr,
+            _disconnect_rx: disconnect_rx,
+            _event_rx: event_rx,
+            _runtime: runtime,
+        });
+    }
+
+    match serial {
+        Some(serial) => bail!("Unable to find GoXLR with serial {}", serial),
+        None => bail!("No GoXLR devices found"),
+    }
+}
+
+unsafe fn run<F>(handle: *mut GoXLRHandle, function: F) -> i32
+where
+    F: FnOnce(&mut GoXLRHandle) -> Result<()>,
+{
+    guard(-1, || {
+        let Some(handle) = handle.as_mut() else {
+            set_error(anyhow!("Handle is NULL"));
+            return -1;
+        };
+
+        match function(handle) {
+            Ok(()) => 0,
+            Err(e) => {
+                set_error(e);
+                -1
+            }
+        }
+    })
+}
+
+// Unwinding across the C boundary is undefined behaviour, so anything which panics is turned
+// into a normal failure, with the panic message available from goxlr_last_error()..
+fn guard<T, F>(failed: T, function: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    match catch_unwind(AssertUnwindSafe(function)) {
+        Ok(result) => result,
+        Err(panic) => {
+            set_error(anyhow!("Internal error: {}", panic_message(&*panic)));
+            failed
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "Unknown panic"
+    }
+}
+
+fn from_index<T: IntoEnumIterator>(index: u32, name: &str) -> Result<T> {
+    T::iter()
+        .nth(index as usize)
+        .ok_or_else(|| anyhow!("{} {} is out of range", name, index))
+}
+
+fn set_error(error: anyhow::Error) {
+    // Strings can't contain a NUL for C, so swap any out rather than lose the message..
+    let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| last.replace(Some(message)));
+}
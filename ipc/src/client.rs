@@ -1,5 +1,8 @@
-use crate::{DaemonRequest, DaemonStatus, GoXLRCommand, HttpSettings};
-use anyhow::Result;
+use crate::{
+    AuditEntry, AuditQuery, DaemonError, DaemonRequest, DaemonStatus, GoXLRCommand, HttpSettings,
+    TokenScope, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION,
+};
+use anyhow::{bail, Result};
 use async_trait::async_trait;
 
 #[async_trait]
@@ -7,6 +10,26 @@ pub trait Client {
     async fn send(&mut self, request: DaemonRequest) -> Result<()>;
     async fn poll_status(&mut self) -> Result<()>;
     async fn command(&mut self, serial: &str, command: GoXLRCommand) -> Result<()>;
+
+    /// Exchanges protocol versions with the daemon, returning the version both sides will use
+    async fn handshake(&mut self) -> Result<u32>;
+
+    /// Creates a token for network access to the API, returning the token itself
+    async fn create_api_token(&mut self, name: &str, scope: TokenScope) -> Result<String>;
+
+    /// Fetches the commands recorded in the daemon's audit log
+    async fn get_audit_log(&mut self, query: AuditQuery) -> Result<Vec<AuditEntry>>;
     fn status(&self) -> &DaemonStatus;
     fn http_status(&self) -> &HttpSettings;
 }
+
+/// Works out which protocol version to speak, given the version the daemon reported
+pub fn negotiate_version(daemon: u32) -> Result<u32> {
+    if daemon < MIN_PROTOCOL_VERSION {
+        bail!(DaemonError::ProtocolMismatch {
+            daemon,
+            client: PROTOCOL_VERSION,
+        });
+    }
+    Ok(daemon.min(PROTOCOL_VERSION))
+}
@@ -1,8 +1,12 @@
-use crate::client::Client;
+use crate::client::{negotiate_version, Client};
 use crate::clients::ipc::ipc_socket::Socket;
-use crate::{DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand, HttpSettings};
+use crate::{
+    AuditEntry, AuditQuery, DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand,
+    HttpSettings, TokenScope, PROTOCOL_VERSION,
+};
 use anyhow::{anyhow, bail, Context, Result};
 use async_trait::async_trait;
+use json_patch::Patch;
 
 #[derive(Debug)]
 pub struct IPCClient {
@@ -19,36 +23,108 @@ impl IPCClient {
             http_settings: Default::default(),
         }
     }
-}
 
-#[async_trait]
-impl Client for IPCClient {
-    async fn send(&mut self, request: DaemonRequest) -> Result<()> {
+    /// Once subscribed, waits for the next status patch from the daemon and applies it.
+    pub async fn next_patch(&mut self) -> Result<Patch> {
+        match self.read().await? {
+            DaemonResponse::Patch(patch) => {
+                self.apply_patch(&patch)?;
+                Ok(patch)
+            }
+            response => bail!("Expected a Patch, received {:?}", response),
+        }
+    }
+
+    // Sends a request, and waits for its response. If we're subscribed, patches may arrive
+    // before it, so they're applied as they come in..
+    async fn request(&mut self, request: DaemonRequest) -> Result<DaemonResponse> {
         self.socket
             .send(request)
             .await
             .context("Failed to send a command to the GoXLR daemon process")?;
-        let result = self
-            .socket
+
+        let mut result = self.read().await?;
+        while let DaemonResponse::Patch(patch) = result {
+            self.apply_patch(&patch)?;
+            result = self.read().await?;
+        }
+        Ok(result)
+    }
+
+    async fn read(&mut self) -> Result<DaemonResponse> {
+        self.socket
             .read()
             .await
             .context("Failed to retrieve the command result from the GoXLR daemon process")?
-            .context("Failed to parse the command result from the GoXLR daemon process")?;
+            .context("Failed to parse the command result from the GoXLR daemon process")
+    }
 
-        match result {
+    fn apply_patch(&mut self, patch: &Patch) -> Result<()> {
+        let mut status = serde_json::to_value(&self.status)?;
+        json_patch::patch(&mut status, patch)?;
+        self.status = serde_json::from_value(status)?;
+        self.http_settings = self.status.config.http_settings.clone();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Client for IPCClient {
+    async fn send(&mut self, request: DaemonRequest) -> Result<()> {
+        match self.request(request).await? {
             DaemonResponse::Status(status) => {
-                self.status = status.clone();
+                self.status = (*status).clone();
                 self.http_settings = status.config.http_settings;
                 Ok(())
             }
             DaemonResponse::Ok => Ok(()),
             DaemonResponse::Error(error) => Err(anyhow!("{}", error)),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
             DaemonResponse::MicLevel(_level) => {
                 bail!("Received Mic Level as Response, shouldn't happen!");
             }
+            DaemonResponse::GateLevel(_level) => {
+                bail!("Received Gate Level as Response, shouldn't happen!");
+            }
+            DaemonResponse::ChannelLevels(_levels) => {
+                bail!("Received Channel Levels as response, shouldn't happen!")
+            }
             DaemonResponse::Patch(_patch) => {
                 Err(anyhow!("Received Patch as response, shouldn't happen!"))
             }
+            DaemonResponse::Events(_events) => {
+                bail!("Received Events as response, shouldn't happen!")
+            }
+            DaemonResponse::SampleWaveform(_waveform) => {
+                bail!("Received Waveform as response, shouldn't happen!")
+            }
+            DaemonResponse::Handshake(_version) => {
+                bail!("Received Handshake as response, shouldn't happen!")
+            }
+            DaemonResponse::ProfilePreview(_preview) => {
+                bail!("Received Profile Preview as response, shouldn't happen!")
+            }
+            DaemonResponse::ScribbleImage(_image) => {
+                bail!("Received Scribble Image as response, shouldn't happen!")
+            }
+            DaemonResponse::Lighting(_lighting) => {
+                bail!("Received Lighting as response, shouldn't happen!")
+            }
+            DaemonResponse::Files(_files) => {
+                bail!("Received Files as response, shouldn't happen!")
+            }
+            DaemonResponse::Metrics(_metrics) => {
+                bail!("Received Metrics as response, shouldn't happen!")
+            }
+            DaemonResponse::Validation(_errors) => {
+                bail!("Received Validation as response, shouldn't happen!")
+            }
+            DaemonResponse::ApiToken(_token) => {
+                bail!("Received API Token as response, shouldn't happen!")
+            }
+            DaemonResponse::AuditLog(_entries) => {
+                bail!("Received Audit Log as response, shouldn't happen!")
+            }
         }
     }
 
@@ -61,6 +137,42 @@ impl Client for IPCClient {
             .await
     }
 
+    async fn handshake(&mut self) -> Result<u32> {
+        // Older daemons don't know what a handshake is, and will drop the request..
+        let result = self
+            .request(DaemonRequest::Handshake(PROTOCOL_VERSION))
+            .await
+            .context("The GoXLR daemon did not accept the handshake, it may need updating")?;
+
+        match result {
+            DaemonResponse::Handshake(version) => negotiate_version(version),
+            DaemonResponse::Error(error) => Err(anyhow!("{}", error)),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Handshake: {:?}", response),
+        }
+    }
+
+    async fn create_api_token(&mut self, name: &str, scope: TokenScope) -> Result<String> {
+        match self
+            .request(DaemonRequest::CreateApiToken(name.to_string(), scope))
+            .await?
+        {
+            DaemonResponse::ApiToken(token) => Ok(token),
+            DaemonResponse::Error(error) => Err(anyhow!("{}", error)),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Token Creation: {:?}", response),
+        }
+    }
+
+    async fn get_audit_log(&mut self, query: AuditQuery) -> Result<Vec<AuditEntry>> {
+        match self.request(DaemonRequest::GetAuditLog(query)).await? {
+            DaemonResponse::AuditLog(entries) => Ok(*entries),
+            DaemonResponse::Error(error) => Err(anyhow!("{}", error)),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Audit Log: {:?}", response),
+        }
+    }
+
     fn status(&self) -> &DaemonStatus {
         &self.status
     }
@@ -1,51 +1,106 @@
-use crate::client::Client;
-use crate::{DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand, HttpSettings};
+use crate::client::{negotiate_version, Client};
+use crate::{
+    AuditEntry, AuditQuery, DaemonRequest, DaemonResponse, DaemonStatus, GoXLRCommand,
+    HttpSettings, TokenScope, PROTOCOL_VERSION,
+};
 use anyhow::bail;
 use async_trait::async_trait;
+use reqwest::{RequestBuilder, StatusCode};
 
 #[derive(Debug)]
 pub struct WebClient {
     url: String,
+    token: Option<String>,
     status: DaemonStatus,
     http_settings: HttpSettings,
 }
 
 impl WebClient {
-    pub fn new(url: String) -> Self {
+    /// The token is only needed when connecting to a daemon on another machine
+    pub fn new(url: String, token: Option<String>) -> Self {
         Self {
             url,
+            token,
             status: DaemonStatus::default(),
             http_settings: Default::default(),
         }
     }
+
+    fn post(&self, request: &DaemonRequest) -> RequestBuilder {
+        let builder = reqwest::Client::new().post(&self.url).json(request);
+        match &self.token {
+            Some(token) => builder.bearer_auth(token),
+            None => builder,
+        }
+    }
 }
 
 #[async_trait]
 impl Client for WebClient {
     async fn send(&mut self, request: DaemonRequest) -> anyhow::Result<()> {
-        let resp = reqwest::Client::new()
-            .post(&self.url)
-            .json(&request)
-            .send()
-            .await?
-            .json::<DaemonResponse>()
-            .await?;
+        let resp = self.post(&request).send().await?;
+        match resp.status() {
+            StatusCode::UNAUTHORIZED => bail!("The GoXLR daemon requires a valid API token"),
+            StatusCode::FORBIDDEN => bail!("The API token doesn't permit this request"),
+            _ => {}
+        }
+        let resp = resp.json::<DaemonResponse>().await?;
 
         // Should probably abstract this part, it's common between clients..
         match resp {
             DaemonResponse::Status(status) => {
-                self.status = status.clone();
+                self.status = (*status).clone();
                 self.http_settings = status.config.http_settings;
                 Ok(())
             }
             DaemonResponse::Ok => Ok(()),
             DaemonResponse::Error(error) => bail!("{}", error),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
             DaemonResponse::MicLevel(_level) => {
                 bail!("Received Mic Level as response, shouldn't happen!")
             }
+            DaemonResponse::GateLevel(_level) => {
+                bail!("Received Gate Level as response, shouldn't happen!")
+            }
+            DaemonResponse::ChannelLevels(_levels) => {
+                bail!("Received Channel Levels as response, shouldn't happen!")
+            }
             DaemonResponse::Patch(_patch) => {
                 bail!("Received Patch as response, shouldn't happen!")
             }
+            DaemonResponse::Events(_events) => {
+                bail!("Received Events as response, shouldn't happen!")
+            }
+            DaemonResponse::SampleWaveform(_waveform) => {
+                bail!("Received Waveform as response, shouldn't happen!")
+            }
+            DaemonResponse::Handshake(_version) => {
+                bail!("Received Handshake as response, shouldn't happen!")
+            }
+            DaemonResponse::ProfilePreview(_preview) => {
+                bail!("Received Profile Preview as response, shouldn't happen!")
+            }
+            DaemonResponse::ScribbleImage(_image) => {
+                bail!("Received Scribble Image as response, shouldn't happen!")
+            }
+            DaemonResponse::Lighting(_lighting) => {
+                bail!("Received Lighting as response, shouldn't happen!")
+            }
+            DaemonResponse::Files(_files) => {
+                bail!("Received Files as response, shouldn't happen!")
+            }
+            DaemonResponse::Metrics(_metrics) => {
+                bail!("Received Metrics as response, shouldn't happen!")
+            }
+            DaemonResponse::Validation(_errors) => {
+                bail!("Received Validation as response, shouldn't happen!")
+            }
+            DaemonResponse::ApiToken(_token) => {
+                bail!("Received API Token as response, shouldn't happen!")
+            }
+            DaemonResponse::AuditLog(_entries) => {
+                bail!("Received Audit Log as response, shouldn't happen!")
+            }
         }
     }
 
@@ -58,6 +113,61 @@ impl Client for WebClient {
             .await
     }
 
+    async fn handshake(&mut self) -> anyhow::Result<u32> {
+        let resp = self
+            .post(&DaemonRequest::Handshake(PROTOCOL_VERSION))
+            .send()
+            .await?;
+
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            bail!("The GoXLR daemon requires a valid API token");
+        }
+
+        // Older daemons won't be able to parse the request at all..
+        if !resp.status().is_success() {
+            bail!("The GoXLR daemon did not accept the handshake, it may need updating");
+        }
+
+        match resp.json::<DaemonResponse>().await? {
+            DaemonResponse::Handshake(version) => negotiate_version(version),
+            DaemonResponse::Error(error) => bail!("{}", error),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Handshake: {:?}", response),
+        }
+    }
+
+    async fn create_api_token(&mut self, name: &str, scope: TokenScope) -> anyhow::Result<String> {
+        let request = DaemonRequest::CreateApiToken(name.to_string(), scope);
+        match self
+            .post(&request)
+            .send()
+            .await?
+            .json::<DaemonResponse>()
+            .await?
+        {
+            DaemonResponse::ApiToken(token) => Ok(token),
+            DaemonResponse::Error(error) => bail!("{}", error),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Token Creation: {:?}", response),
+        }
+    }
+
+    async fn get_audit_log(&mut self, query: AuditQuery) -> anyhow::Result<Vec<AuditEntry>> {
+        let request = DaemonRequest::GetAuditLog(query);
+        match self
+            .post(&request)
+            .send()
+            .await?
+            .json::<DaemonResponse>()
+            .await?
+        {
+            DaemonResponse::AuditLog(entries) => Ok(*entries),
+            DaemonResponse::Error(error) => bail!("{}", error),
+            DaemonResponse::DetailedError(error) => Err(error.error.into()),
+            response => bail!("Unexpected response to Audit Log: {:?}", response),
+        }
+    }
+
     fn status(&self) -> &DaemonStatus {
         &self.status
     }
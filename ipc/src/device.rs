@@ -1,19 +1,23 @@
-use crate::{ColourWay, GoXLRCommand, LogLevel};
+use crate::{
+    ButtonBinding, ColourWay, Ducking, EventHook, GoXLRCommand, InteractionLock, LogLevel,
+    LogRotation, ScheduledAction, TokenScope, VolumePreset,
+};
 use enum_map::EnumMap;
 use goxlr_types::MuteState::Unmuted;
 use goxlr_types::{
-    AnimationMode, Button, ButtonColourOffStyle, ChannelName, CompressorAttackTime,
-    CompressorRatio, CompressorReleaseTime, DeviceType, DisplayMode, DriverInterface, EchoStyle,
-    EffectBankPresets, EncoderColourTargets, EqFrequencies, FaderDisplayStyle, FaderName,
-    FirmwareVersions, GateTimes, GenderStyle, HardTuneSource, HardTuneStyle, InputDevice,
-    MegaphoneStyle, MicrophoneType, MiniEqFrequencies, Mix, MuteFunction, MuteState, OutputDevice,
-    PitchStyle, ReverbStyle, RobotStyle, SampleBank, SampleButtons, SamplePlayOrder,
-    SamplePlaybackMode, SamplerColourTargets, SimpleColourTargets, SubMixChannelName,
-    VersionNumber, VodMode, WaterfallDirection,
+    AnimationMode, AttachStatePolicy, Button, ButtonColourOffStyle, ChannelName,
+    CompressorAttackTime, CompressorRatio, CompressorReleaseTime, DeviceType, DisplayMode,
+    DriverInterface, EchoStyle, EffectBankPresets, EncoderColourTargets, EqFrequencies,
+    FaderDisplayStyle, FaderName, FirmwareVersions, GateTimes, GenderStyle, HardTuneSource,
+    HardTuneStyle, InputDevice, MegaphoneStyle, MicrophoneType, MiniEqFrequencies, Mix,
+    MuteFunction, MuteLightingStyle, MuteState, OutputDevice, PitchStyle, ReverbStyle, RobotStyle,
+    SampleBank, SampleButtons, SamplePlayOrder, SamplePlaybackMode, SamplerColourTargets,
+    SimpleColourTargets, SubMixChannelName, VersionNumber, VodMode, WaterfallDirection,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use strum::EnumIter;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DaemonStatus {
@@ -21,6 +25,131 @@ pub struct DaemonStatus {
     pub mixers: HashMap<String, MixerStatus>,
     pub paths: Paths,
     pub files: Files,
+    pub audio_nodes: Vec<AudioNode>,
+
+    // Devices which stopped responding and couldn't be recovered, keyed by serial, with a
+    // message explaining what the user can do about it
+    pub errored_devices: HashMap<String, String>,
+}
+
+impl DaemonStatus {
+    /// Finds a device by its serial, its label, or the start of either, as long as only one
+    /// device matches. Labels are matched regardless of case.
+    pub fn find_serial(&self, name: &str) -> Option<&str> {
+        if let Some((serial, _)) = self.mixers.get_key_value(name) {
+            return Some(serial);
+        }
+
+        let name = name.to_lowercase();
+        let label = |mixer: &MixerStatus| mixer.settings.label.as_deref().map(str::to_lowercase);
+
+        let labelled: Vec<&String> = self
+            .mixers
+            .iter()
+            .filter(|(_, mixer)| label(mixer).as_deref() == Some(name.as_str()))
+            .map(|(serial, _)| serial)
+            .collect();
+        if let [serial] = labelled.as_slice() {
+            return Some(serial);
+        }
+
+        let prefixed: Vec<&String> = self
+            .mixers
+            .iter()
+            .filter(|(serial, mixer)| {
+                serial.to_lowercase().starts_with(&name)
+                    || label(mixer).is_some_and(|label| label.starts_with(&name))
+            })
+            .map(|(serial, _)| serial)
+            .collect();
+        match prefixed.as_slice() {
+            [serial] => Some(serial),
+            _ => None,
+        }
+    }
+}
+
+// Timing information, used for diagnosing devices which are slow to respond
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DaemonMetrics {
+    // The number of device commands waiting to be run, and the most there have ever been
+    pub queue_depth: usize,
+    pub peak_queue_depth: usize,
+    pub devices: HashMap<String, DeviceMetrics>,
+    pub rate_limits: RateLimitMetrics,
+}
+
+// Counts of IPC and WebSocket clients which have sent requests faster than they're allowed to
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RateLimitMetrics {
+    // Requests which were rejected, rather than being run
+    pub throttled_requests: u64,
+
+    // Clients which kept going after being throttled, and were disconnected
+    pub disconnected_clients: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DeviceMetrics {
+    // Every request sent to the device over USB
+    pub requests: TimingMetrics,
+
+    // Each run of the polling loop, which checks buttons, faders and encoders
+    pub poll_loop: TimingMetrics,
+}
+
+// All times are in microseconds..
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TimingMetrics {
+    pub count: u64,
+    pub errors: u64,
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+// The audio server nodes for each of the GoXLR's channels (currently only PipeWire)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AudioNode {
+    pub channel: AudioChannel,
+    pub node_id: u32,
+    pub node_name: String,
+    pub description: String,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, EnumIter, Serialize, Deserialize)]
+pub enum AudioChannel {
+    System,
+    Game,
+    Chat,
+    Music,
+    Sample,
+    StreamMix,
+    ChatMic,
+    Sampler,
+}
+
+impl AudioChannel {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            AudioChannel::System => "System",
+            AudioChannel::Game => "Game",
+            AudioChannel::Chat => "Chat",
+            AudioChannel::Music => "Music",
+            AudioChannel::Sample => "Sample",
+            AudioChannel::StreamMix => "Stream Mix",
+            AudioChannel::ChatMic => "Chat Mic",
+            AudioChannel::Sampler => "Sampler",
+        }
+    }
+
+    pub fn is_capture(&self) -> bool {
+        matches!(
+            self,
+            AudioChannel::StreamMix | AudioChannel::ChatMic | AudioChannel::Sampler
+        )
+    }
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -34,11 +163,22 @@ pub struct DaemonConfig {
     pub autostart_enabled: bool,
     pub show_tray_icon: bool,
     pub tts_enabled: Option<bool>,
+    pub hotkeys_supported: bool,
     pub allow_network_access: bool,
+    pub profile_autosave_interval: u16,
+    pub input_polling: InputPolling,
     pub log_level: LogLevel,
+    pub log_rotation: LogRotation,
+    pub log_retention: u8,
+    pub audit_log_enabled: bool,
     pub open_ui_on_launch: bool,
     pub platform: String,
     pub handle_macos_aggregates: bool,
+    pub event_hooks: HashMap<EventHook, String>,
+    pub app_routes: HashMap<String, Vec<AudioChannel>>,
+
+    // The names of the network API tokens, and their scopes (the tokens themselves aren't sent)
+    pub api_tokens: HashMap<String, TokenScope>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -53,6 +193,15 @@ pub struct Locale {
     pub system_locale: String,
 }
 
+// How often (in milliseconds) the device's inputs are polled when in use and when idle, and how
+// long (in seconds) without activity before it's considered idle
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct InputPolling {
+    pub active_interval: u16,
+    pub idle_interval: u16,
+    pub idle_timeout: u16,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Activation {
     pub active_path: Option<String>,
@@ -74,9 +223,17 @@ pub struct MixerStatus {
     pub sleep_commands: Vec<GoXLRCommand>,
     pub wake_commands: Vec<GoXLRCommand>,
     pub fader_status: EnumMap<FaderName, FaderStatus>,
+    pub fader_pages: FaderPages,
     pub mic_status: MicSettings,
     pub levels: Levels,
     pub router: EnumMap<InputDevice, EnumMap<OutputDevice, bool>>,
+    pub solo_input: Option<InputDevice>,
+    pub panic_muted: bool,
+    pub interaction_locked: bool,
+    pub muted_outputs: EnumMap<OutputDevice, bool>,
+
+    // The routing sent to the device, once mutes, solo and panic have been applied to the router
+    pub active_router: EnumMap<InputDevice, EnumMap<OutputDevice, bool>>,
     pub cough_button: CoughButton,
     pub lighting: Lighting,
     pub effects: Option<Effects>,
@@ -85,6 +242,7 @@ pub struct MixerStatus {
     pub button_down: EnumMap<Button, bool>,
     pub profile_name: String,
     pub mic_profile_name: String,
+    pub profile_conflict: Option<ProfileConflict>,
 }
 
 impl MixerStatus {
@@ -101,6 +259,19 @@ impl MixerStatus {
     }
 }
 
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FaderPages {
+    pub active: usize,
+    pub pages: Vec<String>,
+}
+
+// Set when the active profile has been changed on disk by something other than the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileConflict {
+    pub name: String,
+    pub unsaved_changes: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HardwareStatus {
     pub versions: FirmwareVersions,
@@ -151,6 +322,7 @@ pub struct MicSettings {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Levels {
     pub submix_supported: bool,
+    pub output_tone_supported: bool,
     pub output_monitor: OutputDevice,
     pub volumes: EnumMap<ChannelName, u8>,
     pub submix: Option<Submixes>,
@@ -192,6 +364,21 @@ pub struct NoiseGate {
     pub attenuation: u8,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GateState {
+    Open,
+    Attenuating,
+    Closed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GateLevel {
+    pub level: f64,
+    pub threshold: i8,
+    pub attenuation: u8,
+    pub state: GateState,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Compressor {
     pub threshold: i8,
@@ -218,11 +405,22 @@ pub struct AnimationLighting {
     pub mod1: u8,
     pub mod2: u8,
     pub waterfall_direction: WaterfallDirection,
+    pub brightness: u8,
+    pub voice_meter: VoiceMeter,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceMeter {
+    pub enabled: bool,
+    pub sensitivity: u8,
+    pub low_colour: String,
+    pub high_colour: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ButtonLighting {
     pub off_style: ButtonColourOffStyle,
+    pub muted_style: Option<MuteLightingStyle>,
     pub colours: TwoColours,
 }
 
@@ -262,6 +460,28 @@ pub struct Effects {
     pub active_preset: EffectBankPresets,
     pub preset_names: HashMap<EffectBankPresets, String>,
     pub current: ActiveEffects,
+    pub levels: EffectLevels,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectLevels {
+    pub reverb: EffectLevel,
+    pub echo: EffectLevel,
+    pub pitch: EffectLevel,
+    pub gender: EffectLevel,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EffectLevel {
+    // The encoder's knob position, and how far along its range that is
+    pub raw: i8,
+    pub percent: u8,
+
+    // The value sent to the GoXLR (dB for Reverb and Echo, semitones for Pitch)
+    pub value: i32,
+
+    // How many steps of the knob cover the whole range, None if it's the knob's own resolution
+    pub sensitivity: Option<u8>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -392,13 +612,29 @@ pub struct Sample {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Settings {
+    pub label: Option<String>,
     pub display: Display,
     pub mute_hold_duration: u16,
     pub vc_mute_also_mute_cm: bool,
     pub enable_monitor_with_fx: bool,
     pub reset_sampler_on_clear: bool,
+    pub sampler_normalisation: bool,
     pub lock_faders: bool,
+    pub lighting_brightness: u8,
+    pub lighting_crossfade: u16,
     pub vod_mode: VodMode,
+    pub attach_state_policy: AttachStatePolicy,
+    pub error_feedback: bool,
+    pub profile_fallbacks: Vec<String>,
+    pub volume_limits: HashMap<ChannelName, u8>,
+    pub output_trims: HashMap<ChannelName, u8>,
+    pub button_bindings: HashMap<Button, ButtonBinding>,
+    pub hotkeys: HashMap<String, Vec<GoXLRCommand>>,
+    pub schedule: HashMap<String, ScheduledAction>,
+    pub volume_presets: HashMap<String, VolumePreset>,
+    pub ducking: Ducking,
+    pub interaction_lock: InteractionLock,
+    pub now_playing_fader: Option<FaderName>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -432,6 +668,14 @@ pub struct Files {
 pub struct SampleFile {
     pub name: String,
     pub gain_pct: u8,
+    pub metadata: Option<SampleMetadata>,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct SampleMetadata {
+    pub duration_ms: Option<u64>,
+    pub sample_rate: u32,
+    pub channels: u16,
 }
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
@@ -440,6 +684,10 @@ pub struct Scribble {
     pub bottom_text: Option<String>,
     pub left_text: Option<String>,
     pub inverted: bool,
+    pub left_bar: bool,
+
+    // Text being shown in place of the above, until it's cleared
+    pub display_text: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
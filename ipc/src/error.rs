@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Machine readable errors which can be returned by the daemon, clients can use the code
+/// (or the variant itself) to handle a failure without needing to match on the message.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DaemonError {
+    DeviceNotFound {
+        serial: String,
+    },
+    ProfileParse {
+        name: String,
+        message: String,
+    },
+    ValueOutOfRange {
+        field: String,
+        min: i64,
+        max: i64,
+        value: i64,
+    },
+    UnsupportedOnMini {
+        feature: String,
+    },
+    UnsupportedFirmware {
+        feature: String,
+    },
+    UsbFailure {
+        message: String,
+    },
+    ProtocolMismatch {
+        daemon: u32,
+        client: u32,
+    },
+    InvalidState {
+        reason: String,
+    },
+    RateLimited {
+        limit: u32,
+    },
+}
+
+impl DaemonError {
+    pub fn code(&self) -> u16 {
+        match self {
+            DaemonError::DeviceNotFound { .. } => 1,
+            DaemonError::ProfileParse { .. } => 2,
+            DaemonError::ValueOutOfRange { .. } => 3,
+            DaemonError::UnsupportedOnMini { .. } => 4,
+            DaemonError::UnsupportedFirmware { .. } => 5,
+            DaemonError::UsbFailure { .. } => 6,
+            DaemonError::ProtocolMismatch { .. } => 7,
+            DaemonError::InvalidState { .. } => 8,
+            DaemonError::RateLimited { .. } => 9,
+        }
+    }
+
+    /// The id of this error's message, clients with their own translations can use this (along
+    /// with the variant's fields) rather than the daemon's text.
+    pub fn message_id(&self) -> &'static str {
+        match self {
+            DaemonError::DeviceNotFound { .. } => "error.device_not_found",
+            DaemonError::ProfileParse { .. } => "error.profile_parse",
+            DaemonError::ValueOutOfRange { .. } => "error.value_out_of_range",
+            DaemonError::UnsupportedOnMini { .. } => "error.unsupported_on_mini",
+            DaemonError::UnsupportedFirmware { .. } => "error.unsupported_firmware",
+            DaemonError::UsbFailure { .. } => "error.usb_failure",
+            DaemonError::ProtocolMismatch { .. } => "error.protocol_mismatch",
+            DaemonError::InvalidState { .. } => "error.invalid_state",
+            DaemonError::RateLimited { .. } => "error.rate_limited",
+        }
+    }
+}
+
+impl Display for DaemonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaemonError::DeviceNotFound { serial } => {
+                write!(f, "Device {} is not connected", serial)
+            }
+            DaemonError::ProfileParse { name, message } => {
+                write!(f, "Unable to load profile {}: {}", name, message)
+            }
+            DaemonError::ValueOutOfRange {
+                field,
+                min,
+                max,
+                value,
+            } => write!(
+                f,
+                "{} must be between {} and {} (received {})",
+                field, min, max, value
+            ),
+            DaemonError::UnsupportedOnMini { feature } => {
+                write!(f, "{} is not supported on the GoXLR Mini", feature)
+            }
+            DaemonError::UnsupportedFirmware { feature } => {
+                write!(f, "{} is not supported on this firmware", feature)
+            }
+            DaemonError::UsbFailure { message } => write!(f, "USB Error: {}", message),
+            DaemonError::ProtocolMismatch { daemon, client } => write!(
+                f,
+                "Protocol version {} is not compatible with version {}, please update the older side",
+                client, daemon
+            ),
+            DaemonError::InvalidState { reason } => write!(f, "{}", reason),
+            DaemonError::RateLimited { limit } => {
+                write!(f, "Too many requests, the limit is {} per second", limit)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DaemonError {}
+
+/// A message from the daemon, translated into its locale, along with the id it was found by.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LocalisedMessage {
+    pub id: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LocalisedError {
+    // The error's code, for clients which would rather branch on a number than the variant
+    pub code: u16,
+    pub error: DaemonError,
+    pub message: LocalisedMessage,
+}
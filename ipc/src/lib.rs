@@ -1,39 +1,119 @@
+use enum_map::EnumMap;
 use json_patch::Patch;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 pub mod client;
 pub mod clients;
 mod device;
+mod error;
+mod streamdeck;
 
 pub use device::*;
+pub use error::*;
 use goxlr_types::{
-    AnimationMode, Button, ButtonColourGroups, ButtonColourOffStyle, ChannelName,
-    CompressorAttackTime, CompressorRatio, CompressorReleaseTime, DisplayMode,
-    DisplayModeComponents, EchoStyle, EffectBankPresets, EncoderColourTargets, EqFrequencies,
-    FaderDisplayStyle, FaderName, GateTimes, GenderStyle, HardTuneSource, HardTuneStyle,
-    InputDevice, MegaphoneStyle, MicrophoneType, MiniEqFrequencies, Mix, MuteFunction, MuteState,
-    OutputDevice, PitchStyle, ReverbStyle, RobotRange, RobotStyle, SampleBank, SampleButtons,
-    SamplePlayOrder, SamplePlaybackMode, SamplerColourTargets, SimpleColourTargets, VodMode,
-    WaterfallDirection,
+    AnimationMode, AttachStatePolicy, Button, ButtonColourGroups, ButtonColourOffStyle,
+    ChannelName, CompressorAttackTime, CompressorRatio, CompressorReleaseTime, DisplayMode,
+    DisplayModeComponents, EchoStyle, EffectBankPresets, EncoderColourTargets, EncoderName,
+    EqFrequencies, FaderDisplayStyle, FaderName, GateTimes, GenderStyle, HardTuneSource,
+    HardTuneStyle, InputDevice, MegaphoneStyle, MicrophoneType, MiniEqFrequencies, Mix,
+    MuteFunction, MuteLightingStyle, MuteState, OutputDevice, PitchStyle,
+    ProfileConflictResolution, ProfileMergeSection, ReverbStyle, RobotRange, RobotStyle,
+    SampleBank, SampleButtons, SamplePlayOrder, SamplePlaybackMode, SamplerColourTargets,
+    SimpleColourTargets, ToneBand, VodMode, WaterfallDirection,
 };
+pub use streamdeck::*;
+
+/// The version of the IPC protocol, this should be bumped whenever a change to the requests,
+/// responses, commands or status structures would be misread by an older client.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The oldest protocol version the daemon (and clients) are still able to talk to, version 2
+/// changed the shape of errors to include their translated message.
+pub const MIN_PROTOCOL_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DaemonRequest {
     Ping,
+
+    // Sent by clients with their protocol version when connecting, the daemon responds with its
+    // own version, or a ProtocolMismatch error if the client is too old to be understood
+    Handshake(u32),
     GetStatus,
     Daemon(DaemonCommand),
     GetMicLevel(String),
+
+    // Returns the mic level alongside the noise gate's threshold and (inferred) state, this is
+    // expected to be polled frequently, like GetMicLevel
+    GetGateLevel(String),
+
+    // Returns the level (in dB) of every channel the device is able to meter, and None for those
+    // it can't. Currently the device only reports the mic's level, so the rest are always None.
+    GetChannelLevels(String),
     Command(String, GoXLRCommand),
+
+    // Checks whether a command could be run against a device without running it, returning
+    // every problem found, an empty list means the command is fine
+    ValidateCommand(String, GoXLRCommand),
+
+    // Returns all logged events with an ID greater than the one provided
+    GetEvents(u64),
+
+    // Returns the peak levels of a sample (relative to the samples directory), split into the
+    // requested number of points (at most 4096)
+    GetSampleWaveform(String, usize),
+
+    // Returns a small base64 encoded PNG showing the lighting layout of a saved profile
+    GetProfilePreview(String),
+
+    // Returns the scribble image stored in a device's active profile, as a base64 encoded PNG
+    GetScribbleImage(String, FaderName),
+
+    // Returns a device's full lighting state, this matches MixerStatus.lighting, and can be
+    // changed as a whole with GoXLRCommand::SetLighting
+    GetLighting(String),
+
+    // Returns the cached file lists, without needing to build the full status
+    GetFiles,
+
+    // Returns timing information for the command queue and each device's USB requests
+    GetDaemonMetrics,
+
+    // Creates (or replaces) a named token for accessing the API over the network, and returns
+    // it. Tokens can only be created from the local machine.
+    CreateApiToken(String, TokenScope),
+
+    // Sends a DaemonResponse::Patch to this connection whenever the DaemonStatus changes, these
+    // may arrive at any time, including before the response to another request. WebSockets
+    // always receive patches, so don't need this.
+    Subscribe,
+
+    // Returns the device and daemon commands recorded in the audit log, oldest first
+    GetAuditLog(AuditQuery),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DaemonResponse {
     Ok,
     Error(String),
+    DetailedError(LocalisedError),
     MicLevel(f64),
-    Status(DaemonStatus),
+    GateLevel(GateLevel),
+    ChannelLevels(EnumMap<ChannelName, Option<f64>>),
+    Status(Box<DaemonStatus>),
     Patch(Patch),
+    Events(Vec<DaemonEvent>),
+    SampleWaveform(Vec<f32>),
+    Handshake(u32),
+    ProfilePreview(String),
+    ScribbleImage(String),
+    Lighting(Box<Lighting>),
+    Files(Files),
+    Metrics(DaemonMetrics),
+    Validation(Vec<LocalisedError>),
+    ApiToken(String),
+    AuditLog(Box<Vec<AuditEntry>>),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,6 +128,86 @@ pub struct WebsocketResponse {
     pub data: DaemonResponse,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DaemonEvent {
+    pub id: u64,
+    pub timestamp: u64,
+    pub serial: Option<String>,
+    pub event: DaemonEventType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DaemonEventType {
+    DeviceAttached,
+    DeviceDetached,
+    ButtonPressed(Button),
+    ButtonHeld(Button),
+    ProfileLoaded(String),
+    MicProfileLoaded(String),
+    DeviceReset(ResetStage),
+    Warning(String),
+    Error(String),
+}
+
+// Who sent a command to the daemon..
+#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub enum CommandSource {
+    Ipc,
+    Http,
+
+    // A client on another machine, using the named API token
+    Token(String),
+    Hotkey,
+    Schedule,
+}
+
+// A command which was run against a device (or the daemon), along with every value that changed
+// because of it. Paths are JSON Pointers into the MixerStatus for device commands, and into the
+// DaemonConfig for daemon commands (which have no serial).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub source: CommandSource,
+    pub serial: Option<String>,
+    pub command: AuditCommand,
+    pub changes: Vec<AuditChange>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum AuditCommand {
+    Device(GoXLRCommand),
+    Daemon(DaemonCommand),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditChange {
+    pub path: String,
+    pub old: Option<serde_json::Value>,
+    pub new: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub serial: Option<String>,
+
+    // Only return entries after this time, in milliseconds since the epoch
+    pub since: Option<u64>,
+
+    // Only return this many of the most recent entries
+    pub limit: Option<usize>,
+}
+
+// The steps of a device reset, each is logged as it completes so clients can follow along
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub enum ResetStage {
+    Started,
+    Reinitialised,
+    ProfileApplied,
+    MicProfileApplied,
+    Complete,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub enum ColourWay {
     Black,
@@ -76,6 +236,21 @@ pub enum LogLevel {
     Trace,
 }
 
+// When the daemon's log file is rotated, Size is in megabytes
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+pub enum LogRotation {
+    Size(u16),
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl Default for LogRotation {
+    fn default() -> Self {
+        LogRotation::Size(2)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DaemonCommand {
     OpenUi,
@@ -83,11 +258,18 @@ pub enum DaemonCommand {
     StopDaemon,
     OpenPath(PathTypes),
     SetLogLevel(LogLevel),
+
+    // Rotation changes take effect the next time the daemon starts
+    SetLogRotation(LogRotation),
+    SetLogRetention(u8),
+    SetAuditLogEnabled(bool),
     SetShowTrayIcon(bool),
     SetLocale(Option<String>),
     SetTTSEnabled(bool),
     SetAutoStartEnabled(bool),
     SetAllowNetworkAccess(bool),
+    SetProfileAutosaveInterval(u16),
+    SetInputPolling(InputPolling),
     SetUiLaunchOnLoad(bool),
     RecoverDefaults(PathTypes),
     SetActivatorPath(Option<PathBuf>),
@@ -96,6 +278,157 @@ pub enum DaemonCommand {
     ApplySampleChange,
 
     HandleMacOSAggregates(bool),
+
+    SetEventHook(EventHook, Option<String>),
+
+    // Moves an application's audio streams to GoXLR channels (Linux / PipeWire only), an empty
+    // list of channels removes the route. Only one playback and one capture channel are used.
+    SetApplicationRoute(String, Vec<AudioChannel>),
+
+    // Icons are validated, resized and stored as a PNG in the icons directory
+    UploadIcon(String, Vec<u8>),
+    DeleteIcon(String),
+
+    // Revokes a network API token by name, like creating them this is only accepted locally
+    RemoveApiToken(String),
+}
+
+// What a network API token is allowed to do, ReadOnly tokens can fetch the status (and other
+// information) but can't change anything. Connections from the local machine don't need one.
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum TokenScope {
+    ReadOnly,
+    Control,
+}
+
+// Events which can trigger a user configured executable, details of the event are passed
+// to the executable as environment variables (GOXLR_EVENT, GOXLR_SERIAL, etc)
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum EventHook {
+    DeviceConnected,
+    DeviceDisconnected,
+    ProfileLoaded,
+    MicProfileLoaded,
+    MicMuted,
+    MicUnmuted,
+}
+
+// Replaces the default behaviour of a button, either with a list of commands to run against the
+// device, or an external command to execute.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ButtonBinding {
+    Commands(Vec<GoXLRCommand>),
+    Execute(String),
+    Media(MediaAction),
+}
+
+// Controls for whichever media player is active (currently only supported under Linux)
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum MediaAction {
+    PlayPause,
+    Next,
+    Previous,
+}
+
+// Commands run against a device at a time of day ('HH:MM', local time), on the listed days, or
+// every day if none are listed. If the machine was asleep at that time, the commands are run
+// once it wakes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledAction {
+    pub time: String,
+    pub days: Vec<Weekday>,
+    pub commands: Vec<GoXLRCommand>,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+// A named set of channel volumes (and output trims), which can be switched between without
+// touching anything else in the profile, for example a quieter 'Night' set of levels.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VolumePreset {
+    pub volumes: HashMap<ChannelName, u8>,
+    pub output_trims: HashMap<ChannelName, u8>,
+}
+
+// Pulls the volume of the listed channels down while the mic is above the threshold (in dB),
+// attenuation is how far down (as a percentage), with attack and release being how long (in
+// milliseconds) it takes to get there, and to come back up once the mic drops below it. Channels
+// which are on a fader aren't ducked, as that would mean moving the fader.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ducking {
+    pub enabled: bool,
+    pub channels: Vec<ChannelName>,
+    pub threshold: i8,
+    pub attenuation: u8,
+    pub attack: u16,
+    pub release: u16,
+}
+
+impl Default for Ducking {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            channels: vec![ChannelName::Music, ChannelName::Game],
+            threshold: -30,
+            attenuation: 50,
+            attack: 100,
+            release: 1000,
+        }
+    }
+}
+
+// Controls which are held in place while the interaction lock is on, to stop them being knocked
+// mid-stream. Locked faders are always put back, the mode only changes how buttons behave.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionLock {
+    pub mode: InteractionLockMode,
+    pub buttons: Vec<Button>,
+    pub faders: Vec<FaderName>,
+}
+
+impl Default for InteractionLock {
+    fn default() -> Self {
+        Self {
+            mode: InteractionLockMode::Ignore,
+            buttons: vec![
+                Button::Fader1Mute,
+                Button::Fader2Mute,
+                Button::Fader3Mute,
+                Button::Fader4Mute,
+                Button::Cough,
+            ],
+            faders: vec![FaderName::A, FaderName::B, FaderName::C, FaderName::D],
+        }
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InteractionLockMode {
+    // Presses on a locked button do nothing at all
+    #[default]
+    Ignore,
+
+    // A locked button has to be held (for the hold time) before it does anything, and then
+    // behaves as a normal press
+    Hold,
+}
+
+// The encoder based effects can be set by their knob position, a percentage along the knob, or
+// the value sent to the GoXLR (dB for Reverb and Echo, semitones for Pitch, and Gender's amount)
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub enum EffectLevelValue {
+    Raw(i8),
+    Percent(u8),
+    Value(i32),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -103,9 +436,19 @@ pub enum GoXLRCommand {
     SetShutdownCommands(Vec<GoXLRCommand>),
     SetSleepCommands(Vec<GoXLRCommand>),
     SetWakeCommands(Vec<GoXLRCommand>),
+    SetButtonBinding(Button, Option<ButtonBinding>),
+    SetHotkey(String, Option<Vec<GoXLRCommand>>),
+    SetScheduledAction(String, Option<ScheduledAction>),
     SetSamplerPreBufferDuration(u16),
 
     SetFader(FaderName, ChannelName),
+
+    // Fader Pages
+    AddFaderPage(String),
+    RemoveFaderPage(usize),
+    RenameFaderPage(usize, String),
+    SetFaderPage(usize),
+    NextFaderPage(),
     SetFaderMuteFunction(FaderName, MuteFunction),
 
     SetVolume(ChannelName, u8),
@@ -113,6 +456,20 @@ pub enum GoXLRCommand {
     SetMicrophoneGain(MicrophoneType, u16),
     SetRouter(InputDevice, OutputDevice, bool),
 
+    // Temporarily route only this input to the Headphones, None restores the normal routing
+    SoloChannel(Option<InputDevice>),
+
+    // Temporarily cuts every input from every output except the Headphones, so nothing reaches
+    // the stream (or chat), PanicRestore puts the normal routing back
+    PanicMute(),
+    PanicRestore(),
+
+    // Stops the configured buttons and faders on the device doing anything (not saved)
+    SetInteractionLock(bool),
+
+    // Temporarily cuts every input from a single output, this doesn't touch the profile either
+    SetOutputMuted(OutputDevice, bool),
+
     // Cough Button
     SetCoughMuteFunction(MuteFunction),
     SetCoughIsHold(bool),
@@ -151,6 +508,10 @@ pub enum GoXLRCommand {
     SetAnimationMod1(u8),
     SetAnimationMod2(u8),
     SetAnimationWaterfall(WaterfallDirection),
+    SetLightingBrightness(u8),
+    SetVoiceMeterEnabled(bool),
+    SetVoiceMeterSensitivity(u8),
+    SetVoiceMeterColours(String, String),
 
     SetGlobalColour(String),
 
@@ -161,6 +522,7 @@ pub enum GoXLRCommand {
 
     SetButtonColours(Button, String, Option<String>),
     SetButtonOffStyle(Button, ButtonColourOffStyle),
+    SetButtonMutedStyle(Button, MuteLightingStyle),
     SetButtonGroupColours(ButtonColourGroups, String, Option<String>),
     SetButtonGroupOffStyle(ButtonColourGroups, ButtonColourOffStyle),
 
@@ -169,11 +531,21 @@ pub enum GoXLRCommand {
     SetSampleColour(SamplerColourTargets, String, String, String),
     SetSampleOffStyle(SamplerColourTargets, ButtonColourOffStyle),
 
+    // Replaces the whole lighting state (as returned by GetLighting) in one go, anything left
+    // out of the maps is unchanged. If any colour is invalid, nothing is changed.
+    SetLighting(Lighting),
+
     // Effect Related Settings..
     LoadEffectPreset(String),
     RenameActivePreset(String),
     SaveActivePreset(),
 
+    // Sets the Reverb, Echo, Pitch or Gender encoder, using whichever representation is handy
+    SetEffectLevel(EncoderName, EffectLevelValue),
+
+    // How many steps of the encoder cover its whole range, None uses the knob's own resolution
+    SetEncoderSensitivity(EncoderName, Option<u8>),
+
     // Reverb
     SetReverbStyle(ReverbStyle),
     SetReverbAmount(u8),
@@ -238,6 +610,7 @@ pub enum GoXLRCommand {
     AddSample(SampleBank, SampleButtons, String),
     SetSampleStartPercent(SampleBank, SampleButtons, usize, f32),
     SetSampleStopPercent(SampleBank, SampleButtons, usize, f32),
+    SetSampleTrim(SampleBank, SampleButtons, usize, f32, f32),
     RemoveSampleByIndex(SampleBank, SampleButtons, usize),
     PlaySampleByIndex(SampleBank, SampleButtons, usize),
     PlayNextSample(SampleBank, SampleButtons),
@@ -246,18 +619,45 @@ pub enum GoXLRCommand {
     // Scribbles
     SetScribbleIcon(FaderName, Option<String>),
     SetScribbleText(FaderName, String),
+
+    // Shows lines of text on the scribble in place of the profile's, without saving them, long
+    // lines are scrolled across the display. None puts the profile's scribble back.
+    SetScribbleDisplayText(FaderName, Option<Vec<String>>),
     SetScribbleNumber(FaderName, String),
     SetScribbleInvert(FaderName, bool),
+    SetScribbleLeftBar(FaderName, bool),
+
+    // Replaces the scribble's image in the active profile with a PNG (resized to 128x64), and
+    // displays it. The image is kept until the icon, text, number or left bar next change, at
+    // which point it's removed and the display is redrawn from them.
+    SetScribbleImage(FaderName, Vec<u8>),
 
     // Profile Handling..
     NewProfile(String),
     LoadProfile(String, bool),
     LoadProfileColours(String),
+    MergeProfile(String, Vec<ProfileMergeSection>),
     SaveProfile(),
     SaveProfileAs(String),
+    DiscardProfileChanges(),
+    ResetToDefaultProfile(),
+
+    // Resyncs with the device, drops any temporary state (solo, mutes, fades, etc) and applies
+    // the default profiles to everything, for when the device has ended up in an odd state
+    ResetDevice(),
+    ResolveProfileConflict(ProfileConflictResolution),
     DeleteProfile(String),
     ReloadSettings(),
 
+    // Undo / Redo the last change to the device..
+    Undo(),
+    Redo(),
+
+    // Runs several commands as one, if any fail the earlier ones are rolled back where they
+    // can be (the error names any which couldn't), and only a single status update is sent once
+    // they're all done
+    ExecuteBatch(Vec<GoXLRCommand>),
+
     NewMicProfile(String),
     LoadMicProfile(String, bool),
     SaveMicProfile(),
@@ -265,12 +665,36 @@ pub enum GoXLRCommand {
     DeleteMicProfile(String),
 
     // General Settings
+    SetDeviceLabel(Option<String>),
     SetMuteHoldDuration(u16),
     SetVCMuteAlsoMuteCM(bool),
     SetMonitorWithFx(bool),
     SetSamplerResetOnClear(bool),
+    SetSamplerNormalisation(bool),
     SetLockFaders(bool),
+    SetGlobalLightingBrightness(u8),
+    SetLightingCrossfade(u16),
     SetVodMode(VodMode),
+    SetVolumeLimit(ChannelName, Option<u8>),
+    SetOutputTrim(ChannelName, u8),
+
+    // Cuts or boosts the Headphones or Stream output in dB (-6 to 6), this is only accepted when
+    // the device's firmware has output processing (see Levels.output_tone_supported).
+    SetOutputTone(OutputDevice, ToneBand, i8),
+    SetAttachStatePolicy(AttachStatePolicy),
+    SetErrorFeedback(bool),
+    SetProfileFallbacks(Vec<String>),
+
+    // Volume Presets, the preset is applied over the given number of milliseconds..
+    SetVolumePreset(String, Option<VolumePreset>),
+    SaveVolumePreset(String),
+    ApplyVolumePreset(String, u32),
+    SetDucking(Ducking),
+    SetInteractionLockControls(InteractionLock),
+
+    // The fader whose scribble shows what's currently playing, taken from the active media
+    // player (currently only supported under Linux). None leaves the scribbles alone.
+    SetNowPlayingFader(Option<FaderName>),
 
     // These control the current GoXLR 'State'..
     SetActiveEffectPreset(EffectBankPresets),
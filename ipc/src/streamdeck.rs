@@ -0,0 +1,93 @@
+use enum_map::EnumMap;
+use goxlr_types::{ChannelName, FaderName, MuteState};
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+
+use crate::{Files, MixerStatus};
+
+/// A flattened view of a single GoXLR, shaped around what a Stream Deck plugin needs to draw its
+/// keys. Volumes here are percentages rather than the raw 0-255 values used elsewhere.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamDeckState {
+    pub serial: String,
+    pub profile_name: String,
+    pub mic_profile_name: String,
+    pub profiles: Vec<String>,
+    pub mic_profiles: Vec<String>,
+    pub faders: EnumMap<FaderName, StreamDeckFader>,
+    pub volumes: EnumMap<ChannelName, u8>,
+    pub cough_muted: bool,
+    pub effects_enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamDeckFader {
+    pub channel: ChannelName,
+    pub mute_state: MuteState,
+    pub volume: u8,
+
+    // The location of a key image for this fader, relative to the HTTP server
+    pub image: String,
+}
+
+impl Default for StreamDeckFader {
+    fn default() -> Self {
+        Self {
+            channel: ChannelName::Mic,
+            mute_state: MuteState::Unmuted,
+            volume: 0,
+            image: String::new(),
+        }
+    }
+}
+
+/// Moves a channel's volume by `step` percent (negative to lower it)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamDeckVolumeStep {
+    pub channel: ChannelName,
+    pub step: i8,
+}
+
+impl StreamDeckState {
+    pub fn from_status(serial: &str, mixer: &MixerStatus, files: &Files) -> Self {
+        let mut volumes: EnumMap<ChannelName, u8> = Default::default();
+        for channel in ChannelName::iter() {
+            volumes[channel] = to_percent(mixer.get_channel_volume(channel));
+        }
+
+        let mut faders: EnumMap<FaderName, StreamDeckFader> = Default::default();
+        for fader in FaderName::iter() {
+            let status = mixer.get_fader_status(fader);
+            faders[fader] = StreamDeckFader {
+                channel: status.channel,
+                mute_state: status.mute_state,
+                volume: volumes[status.channel],
+                image: format!("/api/streamdeck/{serial}/fader/{fader}.png"),
+            };
+        }
+
+        Self {
+            serial: serial.to_string(),
+            profile_name: mixer.profile_name.clone(),
+            mic_profile_name: mixer.mic_profile_name.clone(),
+            profiles: files.profiles.clone(),
+            mic_profiles: files.mic_profiles.clone(),
+            faders,
+            volumes,
+            cough_muted: mixer.cough_button.state != MuteState::Unmuted,
+            effects_enabled: mixer.effects.as_ref().map(|effects| effects.is_enabled),
+        }
+    }
+}
+
+impl StreamDeckVolumeStep {
+    /// Calculates the new raw volume for a channel currently at `volume`
+    pub fn apply(&self, volume: u8) -> u8 {
+        let step = (self.step as i16 * 255) / 100;
+        (volume as i16 + step).clamp(0, 255) as u8
+    }
+}
+
+fn to_percent(volume: u8) -> u8 {
+    ((volume as u16 * 100) / 255) as u8
+}
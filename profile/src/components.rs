@@ -4,7 +4,9 @@ pub mod colours;
 pub mod context;
 pub mod echo;
 pub mod effects;
+pub mod encoder_sensitivity;
 pub mod fader;
+pub mod fader_pages;
 pub mod gender;
 pub mod hardtune;
 pub mod megaphone;
@@ -18,5 +20,7 @@ pub mod robot;
 pub mod root;
 pub mod sample;
 pub mod scribble;
+pub mod sequence;
 pub mod simple;
 pub mod submix;
+pub mod unknown;
@@ -1,12 +1,13 @@
 use crate::components::animation::AnimationMode::{RainbowBright, RainbowDark, RetroRainbow};
-use crate::profile::Attribute;
+use crate::components::colours::Colour;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{bail, Result};
 use log::warn;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 use strum::{EnumIter, IntoEnumIterator};
 
 #[derive(Debug, Default)]
@@ -17,6 +18,17 @@ pub struct AnimationTree {
     mod1: u8,
     mod2: u8,
     waterfall: WaterfallDirection,
+
+    // Not part of the official profile format, so only written if it's been set
+    brightness: Option<u8>,
+
+    // Also ours, a daemon driven mode which lights the faders based on the mic level
+    voice_meter: Option<bool>,
+    voice_meter_sensitivity: Option<u8>,
+    voice_meter_low: Option<Colour>,
+    voice_meter_high: Option<Colour>,
+
+    unknown: UnknownAttributes,
 }
 
 impl AnimationTree {
@@ -37,11 +49,11 @@ impl AnimationTree {
                 continue;
             }
             if attr.name == "mod1" {
-                self.mod1 = attr.value.parse::<c_float>()? as u8;
+                self.mod1 = parse_modifier(&attr.name, &attr.value)?;
                 continue;
             }
             if attr.name == "mod2" {
-                self.mod2 = attr.value.parse::<c_float>()? as u8;
+                self.mod2 = parse_modifier(&attr.name, &attr.value)?;
                 continue;
             }
             if attr.name == "mod3" {
@@ -51,22 +63,66 @@ impl AnimationTree {
                 }
                 continue;
             }
+            if attr.name == "brightness" {
+                self.brightness = Some(attr.value.parse::<u8>()?.min(100));
+                continue;
+            }
+            if attr.name == "voiceMeter" {
+                self.voice_meter = Some(attr.value == "1");
+                continue;
+            }
+            if attr.name == "voiceMeterSensitivity" {
+                self.voice_meter_sensitivity = Some(attr.value.parse::<u8>()?.min(100));
+                continue;
+            }
+            if attr.name == "voiceMeterLow" {
+                self.voice_meter_low = Some(Colour::fromrgb(&attr.value)?);
+                continue;
+            }
+            if attr.name == "voiceMeterHigh" {
+                self.voice_meter_high = Some(Colour::fromrgb(&attr.value)?);
+                continue;
+            }
             warn!("Unmatched Attribute: {}", attr.name);
+            self.unknown.store(attr);
         }
 
         Ok(())
     }
 
-    pub fn write_animation<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+    pub fn write_animation<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        include_extensions: bool,
+    ) -> Result<()> {
         //<animationTree animationMode="3" mod1="39.0" mod2="39.0" mod3="0"/>
 
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("animationMode".to_string(), format!("{}", self.mode as u8));
         attributes.insert("mod1".to_string(), format!("{}", self.mod1));
         attributes.insert("mod2".to_string(), format!("{}", self.mod2));
         attributes.insert("mod3".to_string(), format!("{}", self.waterfall as u8));
+        if include_extensions {
+            if let Some(brightness) = self.brightness {
+                attributes.insert("brightness".to_string(), format!("{}", brightness));
+            }
+            if let Some(enabled) = self.voice_meter {
+                attributes.insert("voiceMeter".to_string(), format!("{}", enabled as u8));
+            }
+            if let Some(sensitivity) = self.voice_meter_sensitivity {
+                let value = format!("{}", sensitivity);
+                attributes.insert("voiceMeterSensitivity".to_string(), value);
+            }
+            if let Some(colour) = self.voice_meter_low {
+                attributes.insert("voiceMeterLow".to_string(), colour.to_rgb());
+            }
+            if let Some(colour) = self.voice_meter_high {
+                attributes.insert("voiceMeterHigh".to_string(), colour.to_rgb());
+            }
+        }
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -80,6 +136,11 @@ impl AnimationTree {
         self.mod1 = new.mod1;
         self.mod2 = new.mod2;
         self.waterfall = new.waterfall;
+        self.brightness = new.brightness;
+        self.voice_meter = new.voice_meter;
+        self.voice_meter_sensitivity = new.voice_meter_sensitivity;
+        self.voice_meter_low = new.voice_meter_low;
+        self.voice_meter_high = new.voice_meter_high;
     }
 
     pub fn mode(&self) -> AnimationMode {
@@ -94,6 +155,23 @@ impl AnimationTree {
     pub fn waterfall(&self) -> WaterfallDirection {
         self.waterfall
     }
+    pub fn brightness(&self) -> u8 {
+        self.brightness.unwrap_or(100)
+    }
+    pub fn voice_meter(&self) -> bool {
+        self.voice_meter.unwrap_or(false)
+    }
+    pub fn voice_meter_sensitivity(&self) -> u8 {
+        self.voice_meter_sensitivity.unwrap_or(50)
+    }
+    pub fn voice_meter_low(&self) -> Colour {
+        self.voice_meter_low
+            .unwrap_or_else(|| Colour::fromrgb("00FF00").unwrap())
+    }
+    pub fn voice_meter_high(&self) -> Colour {
+        self.voice_meter_high
+            .unwrap_or_else(|| Colour::fromrgb("FF0000").unwrap())
+    }
 
     pub fn set_mode(&mut self, mode: AnimationMode) -> Result<()> {
         self.mode = mode;
@@ -131,6 +209,43 @@ impl AnimationTree {
         self.waterfall = waterfall;
         Ok(())
     }
+
+    pub fn set_brightness(&mut self, brightness: u8) -> Result<()> {
+        if brightness > 100 {
+            bail!("Brightness must be between 0 and 100");
+        }
+
+        self.brightness = Some(brightness);
+        Ok(())
+    }
+
+    pub fn set_voice_meter(&mut self, enabled: bool) {
+        self.voice_meter = Some(enabled);
+    }
+
+    pub fn set_voice_meter_sensitivity(&mut self, sensitivity: u8) -> Result<()> {
+        if sensitivity > 100 {
+            bail!("Sensitivity must be between 0 and 100");
+        }
+
+        self.voice_meter_sensitivity = Some(sensitivity);
+        Ok(())
+    }
+
+    pub fn set_voice_meter_colours(&mut self, low: Colour, high: Colour) {
+        self.voice_meter_low = Some(low);
+        self.voice_meter_high = Some(high);
+    }
+}
+
+// The official app stores modifiers as floats, but the device only accepts whole values up to
+// 100, so anything outside that is pulled back in rather than failing when the profile is applied..
+fn parse_modifier(name: &str, value: &str) -> Result<u8> {
+    let parsed = parse_float(value)?.round();
+    if !(0.0..=100.0).contains(&parsed) {
+        warn!("Animation {} of {} is out of range, clamping", name, value);
+    }
+    Ok(parsed.clamp(0.0, 100.0) as u8)
 }
 
 #[derive(Debug, Default, Copy, Clone, EnumIter, PartialEq)]
@@ -145,7 +260,7 @@ pub enum AnimationMode {
     None,
 }
 
-#[derive(Debug, Default, Copy, Clone, EnumIter)]
+#[derive(Debug, Default, Copy, Clone, EnumIter, PartialEq)]
 pub enum WaterfallDirection {
     #[default]
     Down,
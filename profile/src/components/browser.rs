@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use anyhow::Result;
@@ -6,6 +6,7 @@ use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
 
 use crate::components::colours::ColourMap;
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 
 #[derive(thiserror::Error, Debug)]
@@ -37,6 +38,7 @@ pub struct BrowserPreviewTree {
     file: String,
     play_toggle: u8,
     current_relative_time: f64,
+    unknown: UnknownAttributes,
 }
 
 impl BrowserPreviewTree {
@@ -50,6 +52,7 @@ impl BrowserPreviewTree {
             file: "".to_string(),
             play_toggle: 0,
             current_relative_time: 0.0,
+            unknown: Default::default(),
         }
     }
 
@@ -77,6 +80,7 @@ impl BrowserPreviewTree {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[{}] Unparsed Attribute: {}", self.element_name, attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -86,7 +90,7 @@ impl BrowserPreviewTree {
     pub fn write_browser<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("playing".to_string(), format!("{}", self.playing));
         attributes.insert("playToggle".to_string(), format!("{}", self.play_toggle));
         attributes.insert("file".to_string(), self.file.clone());
@@ -96,6 +100,7 @@ impl BrowserPreviewTree {
         );
 
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::str::FromStr;
 
 #[derive(thiserror::Error, Debug)]
@@ -154,14 +154,14 @@ impl ColourMap {
         self.colour_display = colour_map.colour_display;
     }
 
-    pub fn write_colours(&self, attributes: &mut HashMap<String, String>) {
+    pub fn write_colours(&self, attributes: &mut LinkedHashMap<String, String>) {
         self.write_colours_with_prefix(self.prefix.clone(), attributes)
     }
 
     pub fn write_colours_with_prefix(
         &self,
         prefix: String,
-        attributes: &mut HashMap<String, String>,
+        attributes: &mut LinkedHashMap<String, String>,
     ) {
         // Add the 'OffStyle'
         let mut key = format!("{prefix}offStyle");
@@ -212,6 +212,9 @@ impl ColourMap {
     pub(crate) fn set_velocity(&mut self, velocity: i8) {
         self.velocity = Some(velocity);
     }
+    pub(crate) fn prefix(&self) -> &str {
+        &self.prefix
+    }
 
     pub fn colour(&self, index: u8) -> &Colour {
         self.colour_or_default(index)
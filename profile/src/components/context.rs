@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use strum::EnumProperty;
@@ -11,6 +11,7 @@ use quick_xml::Writer;
 
 use crate::components::colours::ColourMap;
 use crate::components::sample::SampleBank;
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::Preset;
 
@@ -43,6 +44,7 @@ pub struct Context {
     selected_id: Option<u8>,
     selected_sample: SampleBank, // These two should probably map to enums somewhere, matched up against
     selected_effects: Preset,    // the relevant sections of the tags (for quickly pulling presets)
+    unknown: UnknownAttributes,
 }
 
 impl Context {
@@ -56,6 +58,7 @@ impl Context {
             selected_id: None,
             selected_sample: SampleBank::A,
             selected_effects: Preset::Preset1,
+            unknown: Default::default(),
         }
     }
 
@@ -97,6 +100,7 @@ impl Context {
 
             if !self.colour_map.read_colours(attr)? {
                 warn!("[{}] Unparsed Attribute: {}", self.element_name, attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -106,7 +110,7 @@ impl Context {
     pub fn write_context<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("numselected".to_string(), format!("{}", self.selected));
 
         if let Some(selected_id) = self.selected_id {
@@ -131,6 +135,7 @@ impl Context {
         );
 
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::{Enum, EnumMap};
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -10,8 +9,9 @@ use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap};
+use crate::components::unknown::UnknownAttributes;
 
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -40,6 +40,7 @@ pub struct EchoEncoderBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, EchoEncoder>,
     active_set: u8, // Not sure what this does?
+    unknown: UnknownAttributes,
 }
 
 impl EchoEncoderBase {
@@ -54,6 +55,7 @@ impl EchoEncoderBase {
             colour_map,
             preset_map: EnumMap::default(),
             active_set: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -66,6 +68,7 @@ impl EchoEncoderBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[EchoEncoder] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -90,60 +93,61 @@ impl EchoEncoderBase {
             }
 
             if attr.name == "DELAY_KNOB_POSITION" {
-                preset.set_knob_position(attr.value.parse::<c_float>()? as i8)?;
+                preset.set_knob_position(parse_float(&attr.value)? as i8)?;
                 continue;
             }
 
             if attr.name == "DELAY_SOURCE" {
-                preset.source = attr.value.parse::<c_float>()? as u8;
+                preset.source = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_DIV_L" {
-                preset.div_l = attr.value.parse::<c_float>()? as u8;
+                preset.div_l = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_DIV_R" {
-                preset.div_r = attr.value.parse::<c_float>()? as u8;
+                preset.div_r = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_FB_L" {
-                preset.feedback_left = attr.value.parse::<c_float>()? as u8;
+                preset.feedback_left = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_FB_R" {
-                preset.feedback_right = attr.value.parse::<c_float>()? as u8;
+                preset.feedback_right = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_XFB_L_R" {
-                preset.xfb_l_to_r = attr.value.parse::<c_float>()? as u8;
+                preset.xfb_l_to_r = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_XFB_R_L" {
-                preset.xfb_r_to_l = attr.value.parse::<c_float>()? as u8;
+                preset.xfb_r_to_l = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_FB_CONTROL" {
-                preset.feedback_control = attr.value.parse::<c_float>()? as u8;
+                preset.feedback_control = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_FILTER_STYLE" {
-                preset.filter_style = attr.value.parse::<c_float>()? as u8;
+                preset.filter_style = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "DELAY_TIME_L" {
-                preset.time_left = attr.value.parse::<c_float>()? as u16;
+                preset.time_left = parse_float(&attr.value)? as u16;
                 continue;
             }
             if attr.name == "DELAY_TIME_R" {
-                preset.time_right = attr.value.parse::<c_float>()? as u16;
+                preset.time_right = parse_float(&attr.value)? as u16;
                 continue;
             }
             if attr.name == "DELAY_TEMPO" {
-                preset.tempo = attr.value.parse::<c_float>()? as u16;
+                preset.tempo = parse_float(&attr.value)? as u16;
                 continue;
             }
 
             println!("[EchoEncoder] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
 
         self.preset_map[preset_enum] = preset;
@@ -153,9 +157,10 @@ impl EchoEncoderBase {
     pub fn write_echo<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("echoEncoder");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("active_set".to_string(), format!("{}", self.active_set));
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -182,8 +187,8 @@ impl EchoEncoderBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -216,6 +221,8 @@ impl EchoEncoderBase {
         attributes.insert("DELAY_TIME_R".to_string(), format!("{}", value.time_right));
         attributes.insert("DELAY_TEMPO".to_string(), format!("{}", value.tempo));
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -252,6 +259,7 @@ pub struct EchoEncoder {
     time_left: u16,
     time_right: u16,
     tempo: u16,
+    unknown: UnknownAttributes,
 }
 
 impl EchoEncoder {
@@ -271,6 +279,7 @@ impl EchoEncoder {
             time_left: 0,
             time_right: 0,
             tempo: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -278,18 +287,6 @@ impl EchoEncoder {
         ((36 * self.knob_position as i32) / 24 - 36) as i8
     }
 
-    // TODO: This should probably be handled by UIs like basically everything else!
-    pub fn get_percentage_amount(&self) -> u8 {
-        ((self.knob_position as u16 * 100) / 24) as u8
-    }
-    pub fn set_percentage_value(&mut self, percentage: u8) -> Result<()> {
-        if percentage > 100 {
-            return Err(anyhow!("Value must be a percentage"));
-        }
-        self.set_knob_position(((percentage as i16 * 24) / 100) as i8)?;
-        Ok(())
-    }
-
     pub fn knob_position(&self) -> i8 {
         self.knob_position
     }
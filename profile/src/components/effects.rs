@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use anyhow::{anyhow, Result};
@@ -7,6 +7,7 @@ use quick_xml::Writer;
 use strum::EnumProperty;
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::Preset;
 
@@ -33,6 +34,7 @@ pub struct Effects {
 
     // This is represented only in the UI.
     name: String,
+    unknown: UnknownAttributes,
 }
 
 impl Effects {
@@ -56,6 +58,7 @@ impl Effects {
             element_name,
             colour_map,
             name: default_name,
+            unknown: Default::default(),
         }
     }
 
@@ -69,6 +72,7 @@ impl Effects {
             // Send the rest out for colouring..
             if !self.colour_map.read_colours(attr)? {
                 println!("[EFFECTS] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -78,10 +82,11 @@ impl Effects {
     pub fn write_effects<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert(format!("{}Name", self.element_name), self.name.clone());
 
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use enum_map::{Enum, EnumMap};
+use log::warn;
+use std::io::Write;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Writer;
+use strum::{Display, EnumIter, IntoEnumIterator};
+
+use crate::profile::Attribute;
+
+// Anything more than this and the knob barely moves the value at all..
+pub const MAX_ENCODER_STEPS: u8 = 100;
+
+/**
+ * Another thing the official app doesn't know about, this is how many steps (detents) of each
+ * effect encoder it takes to go across its whole range. An encoder without a value uses the knob's
+ * own resolution (one step per position), and if none are set the element isn't written.
+ */
+#[derive(Debug, Clone)]
+pub struct EncoderSensitivity {
+    element_name: String,
+    steps: EnumMap<Encoders, Option<u8>>,
+}
+
+impl EncoderSensitivity {
+    pub fn new(element_name: String) -> Self {
+        Self {
+            element_name,
+            steps: EnumMap::default(),
+        }
+    }
+
+    pub fn parse_sensitivity(&mut self, attributes: &Vec<Attribute>) -> Result<()> {
+        'attr: for attr in attributes {
+            for encoder in Encoders::iter() {
+                if attr.name == encoder.to_string() {
+                    let steps: u8 = attr.value.parse()?;
+                    if !(1..=MAX_ENCODER_STEPS).contains(&steps) {
+                        warn!(
+                            "{} steps for the {} encoder is out of range",
+                            steps, encoder
+                        );
+                    }
+                    self.steps[encoder] = Some(steps.clamp(1, MAX_ENCODER_STEPS));
+                    continue 'attr;
+                }
+            }
+            warn!("Unmatched Attribute: {}", attr.name);
+        }
+        Ok(())
+    }
+
+    pub fn write_sensitivity<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        if self.is_default() {
+            return Ok(());
+        }
+
+        let mut elem = BytesStart::new(self.element_name.as_str());
+        for (encoder, steps) in self.steps {
+            if let Some(steps) = steps {
+                elem.push_attribute((encoder.to_string().as_str(), steps.to_string().as_str()));
+            }
+        }
+        writer.write_event(Event::Empty(elem))?;
+        Ok(())
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.steps.values().all(Option::is_none)
+    }
+
+    pub fn steps(&self, encoder: Encoders) -> Option<u8> {
+        self.steps[encoder]
+    }
+
+    pub fn set_steps(&mut self, encoder: Encoders, steps: Option<u8>) {
+        self.steps[encoder] = steps.map(|steps| steps.clamp(1, MAX_ENCODER_STEPS));
+    }
+}
+
+#[derive(Debug, Display, Enum, EnumIter, Copy, Clone, PartialEq)]
+#[strum(serialize_all = "lowercase")]
+pub enum Encoders {
+    Pitch,
+    Gender,
+    Reverb,
+    Echo,
+}
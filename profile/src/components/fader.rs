@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use strum::{EnumProperty, IntoEnumIterator};
@@ -9,6 +9,7 @@ use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourDisplay, ColourMap, ColourOffStyle};
 use crate::components::mixer::FullChannelList;
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::Faders;
 
@@ -32,6 +33,7 @@ pub enum ParseError {
 pub struct Fader {
     colour_map: ColourMap,
     channel: FullChannelList,
+    unknown: UnknownAttributes,
 }
 
 impl Fader {
@@ -57,6 +59,7 @@ impl Fader {
         Self {
             colour_map,
             channel,
+            unknown: Default::default(),
         }
     }
 
@@ -83,6 +86,8 @@ impl Fader {
             // Send the rest out for colouring..
             if !self.colour_map.read_colours(attr)? {
                 println!("[FADER] Unparsed Attribute: {}", attr.name);
+                self.unknown
+                    .store_with_prefix(self.colour_map.prefix(), attr);
             }
         }
 
@@ -93,7 +98,7 @@ impl Fader {
         let element_name = fader.get_str("faderContext").unwrap();
         let mut elem = BytesStart::new(element_name);
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert(
             format!("{element_name}listIndex"),
             self.channel.get_str("faderIndex").unwrap().to_string(),
@@ -101,6 +106,8 @@ impl Fader {
 
         self.colour_map
             .write_colours_with_prefix(element_name.into(), &mut attributes);
+        self.unknown
+            .write_with_prefix(element_name, &mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
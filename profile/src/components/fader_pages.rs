@@ -0,0 +1,231 @@
+use anyhow::Result;
+use enum_map::EnumMap;
+use ritelinked::LinkedHashMap;
+use std::io::Write;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Writer;
+use strum::{EnumProperty, IntoEnumIterator};
+
+use crate::components::mixer::FullChannelList;
+use crate::components::mute::MuteFunction;
+use crate::profile::Attribute;
+use crate::Faders;
+
+/**
+ * Fader Pages aren't something the official app knows about, they allow a profile to hold
+ * several sets of fader assignments (along with their scribbles and mute states) which can
+ * be flicked between. When no pages are defined, the profile behaves exactly as normal.
+ */
+#[derive(Debug, Default)]
+pub struct FaderPages {
+    active: usize,
+    pages: Vec<FaderPage>,
+}
+
+impl FaderPages {
+    pub fn parse_page(&mut self, attributes: &Vec<Attribute>) -> Result<()> {
+        let mut page = FaderPage::new(format!("Page {}", self.pages.len() + 1));
+
+        for attr in attributes {
+            if attr.name == "name" {
+                page.name = attr.value.clone();
+                continue;
+            }
+
+            if attr.name == "active" {
+                if attr.value == "1" {
+                    self.active = self.pages.len();
+                }
+                continue;
+            }
+
+            for fader in Faders::iter() {
+                let prefix = format!("fader{fader:?}");
+                if let Some(name) = attr.name.strip_prefix(&prefix) {
+                    page.faders[fader].parse_attribute(name, &attr.value)?;
+                }
+            }
+        }
+
+        self.pages.push(page);
+        Ok(())
+    }
+
+    pub fn write_pages<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        for (index, page) in self.pages.iter().enumerate() {
+            let mut elem = BytesStart::new("faderPage");
+
+            let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
+            attributes.insert("name".to_string(), page.name.clone());
+            attributes.insert(
+                "active".to_string(),
+                if index == self.active { "1" } else { "0" }.to_string(),
+            );
+
+            for fader in Faders::iter() {
+                page.faders[fader].write_attributes(&format!("fader{fader:?}"), &mut attributes);
+            }
+
+            for (key, value) in &attributes {
+                elem.push_attribute((key.as_str(), value.as_str()));
+            }
+
+            writer.write_event(Event::Empty(elem))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn pages(&self) -> &Vec<FaderPage> {
+        &self.pages
+    }
+
+    pub fn page(&self, index: usize) -> Option<&FaderPage> {
+        self.pages.get(index)
+    }
+
+    pub fn page_mut(&mut self, index: usize) -> Option<&mut FaderPage> {
+        self.pages.get_mut(index)
+    }
+
+    pub fn add_page(&mut self, page: FaderPage) {
+        self.pages.push(page);
+    }
+
+    pub fn remove_page(&mut self, index: usize) -> FaderPage {
+        let page = self.pages.remove(index);
+        if self.active > index {
+            self.active -= 1;
+        }
+        page
+    }
+
+    pub fn active(&self) -> usize {
+        self.active
+    }
+
+    pub fn set_active(&mut self, active: usize) {
+        self.active = active;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FaderPage {
+    name: String,
+    faders: EnumMap<Faders, FaderPageSlot>,
+}
+
+impl FaderPage {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            faders: EnumMap::from_fn(|_| FaderPageSlot::default()),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+
+    pub fn slot(&self, fader: Faders) -> &FaderPageSlot {
+        &self.faders[fader]
+    }
+
+    pub fn set_slot(&mut self, fader: Faders, slot: FaderPageSlot) {
+        self.faders[fader] = slot;
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FaderPageSlot {
+    pub channel: FullChannelList,
+    pub mute_function: MuteFunction,
+    pub muted_to_x: bool,
+    pub muted_to_all: bool,
+    pub scribble_icon: Option<String>,
+    pub scribble_text: String,
+    pub scribble_number: String,
+    pub scribble_inverted: bool,
+}
+
+impl Default for FaderPageSlot {
+    fn default() -> Self {
+        Self {
+            channel: FullChannelList::Mic,
+            mute_function: MuteFunction::All,
+            muted_to_x: false,
+            muted_to_all: false,
+            scribble_icon: None,
+            scribble_text: "".to_string(),
+            scribble_number: "".to_string(),
+            scribble_inverted: false,
+        }
+    }
+}
+
+impl FaderPageSlot {
+    fn parse_attribute(&mut self, name: &str, value: &str) -> Result<()> {
+        match name {
+            "Channel" => {
+                if let Some(channel) =
+                    FullChannelList::iter().find(|c| c.get_str("faderIndex").unwrap() == value)
+                {
+                    self.channel = channel;
+                }
+            }
+            "MuteFunction" => {
+                if let Some(function) =
+                    MuteFunction::iter().find(|f| f.get_str("uiIndex").unwrap() == value)
+                {
+                    self.mute_function = function;
+                }
+            }
+            "MuteState" => {
+                // 0 = Unmuted, 1 = Muted to X, 2 = Muted to All..
+                let state = value.parse::<u8>()?;
+                self.muted_to_x = state == 1;
+                self.muted_to_all = state == 2;
+            }
+            "Icon" => self.scribble_icon = Some(value.to_string()),
+            "Text" => self.scribble_text = value.to_string(),
+            "Number" => self.scribble_number = value.to_string(),
+            "Inverted" => self.scribble_inverted = value == "1",
+            _ => println!("[FaderPage] Unparsed Attribute: {name}"),
+        }
+        Ok(())
+    }
+
+    fn write_attributes(&self, prefix: &str, attributes: &mut LinkedHashMap<String, String>) {
+        let mute_state = if self.muted_to_all {
+            "2"
+        } else if self.muted_to_x {
+            "1"
+        } else {
+            "0"
+        };
+
+        attributes.insert(
+            format!("{prefix}Channel"),
+            self.channel.get_str("faderIndex").unwrap().to_string(),
+        );
+        attributes.insert(
+            format!("{prefix}MuteFunction"),
+            self.mute_function.get_str("uiIndex").unwrap().to_string(),
+        );
+        attributes.insert(format!("{prefix}MuteState"), mute_state.to_string());
+        if let Some(icon) = &self.scribble_icon {
+            attributes.insert(format!("{prefix}Icon"), icon.clone());
+        }
+        attributes.insert(format!("{prefix}Text"), self.scribble_text.clone());
+        attributes.insert(format!("{prefix}Number"), self.scribble_number.clone());
+        attributes.insert(
+            format!("{prefix}Inverted"),
+            if self.scribble_inverted { "1" } else { "0" }.to_string(),
+        );
+    }
+}
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::{Enum, EnumMap};
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -10,7 +9,8 @@ use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap};
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -39,6 +39,7 @@ pub struct GenderEncoderBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, GenderEncoder>,
     active_set: u8, // Not sure what this does?
+    unknown: UnknownAttributes,
 }
 
 impl GenderEncoderBase {
@@ -53,6 +54,7 @@ impl GenderEncoderBase {
             colour_map,
             preset_map: EnumMap::default(),
             active_set: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -65,6 +67,7 @@ impl GenderEncoderBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[GenderEncoder] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -89,16 +92,17 @@ impl GenderEncoderBase {
             }
 
             if attr.name == "GENDER_KNOB_POSITION" {
-                preset.knob_position = attr.value.parse::<c_float>()? as i8;
+                preset.knob_position = parse_float(&attr.value)? as i8;
                 continue;
             }
 
             if attr.name == "GENDER_RANGE" {
-                preset.range = attr.value.parse::<c_float>()? as u8;
+                preset.range = parse_float(&attr.value)? as u8;
                 continue;
             }
 
             println!("[GenderEncoder] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
 
         self.preset_map[preset_enum] = preset;
@@ -108,9 +112,10 @@ impl GenderEncoderBase {
     pub fn write_gender<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("genderEncoder");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("active_set".to_string(), format!("{}", self.active_set));
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -137,8 +142,8 @@ impl GenderEncoderBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -151,6 +156,8 @@ impl GenderEncoderBase {
         );
         attributes.insert("GENDER_RANGE".to_string(), format!("{}", value.range));
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -176,6 +183,7 @@ pub struct GenderEncoder {
     knob_position: i8,
     style: GenderStyle,
     range: u8,
+    unknown: UnknownAttributes,
 }
 
 impl GenderEncoder {
@@ -183,55 +191,32 @@ impl GenderEncoder {
         Self {
             knob_position: 0,
             style: GenderStyle::Narrow,
-            range: 0,
+            range: GenderStyle::Narrow.range(),
+            unknown: Default::default(),
         }
     }
 
     pub fn amount(&self) -> i8 {
-        // Amount is dependent on Style, and knob position, lets work with positive numbers.
-        let knob_position = self.knob_position as f32; // Between -24 and 24..
-
-        match self.style {
-            GenderStyle::Narrow => ((12. * knob_position) / 24.).round() as i8,
-            GenderStyle::Medium => ((25. * knob_position) / 24.).round() as i8,
-            GenderStyle::Wide => ((50. * knob_position) / 24.).round() as i8,
-        }
+        // Amount is dependent on Style, and knob position (between -24 and 24)..
+        let range = self.style.range() as f32;
+        ((range * self.knob_position as f32) / 24.).round() as i8
     }
 
     pub fn set_amount(&mut self, amount: i8) -> Result<()> {
-        match self.style {
-            GenderStyle::Narrow => {
-                if !(-12..=12).contains(&amount) {
-                    return Err(anyhow!(
-                        "Amount should be between -12 and 12 (Style: Narrow)"
-                    ));
-                }
-                let base = amount as i32 + 12;
-                let percent = base * 48 / 24;
-                self.knob_position = (percent - 24) as i8;
-                Ok(())
-            }
-            GenderStyle::Medium => {
-                if !(-25..=25).contains(&amount) {
-                    return Err(anyhow!(
-                        "Amount should be between -25 and 25 (Style: Medium)"
-                    ));
-                }
-                let base = amount as i32 + 25;
-                let percent = base * 48 / 50;
-                self.knob_position = (percent - 24) as i8;
-                Ok(())
-            }
-            GenderStyle::Wide => {
-                if !(-50..=50).contains(&amount) {
-                    return Err(anyhow!("Amount should be between -50 and 50 (Style: Wide)"));
-                }
-                let base = amount as i32 + 50;
-                let percent = base * 48 / 100;
-                self.knob_position = (percent - 24) as i8;
-                Ok(())
-            }
+        let range = self.style.range() as i32;
+        if !(-range..=range).contains(&(amount as i32)) {
+            return Err(anyhow!(
+                "Amount should be between -{} and {} (Style: {:?})",
+                range,
+                range,
+                self.style
+            ));
         }
+
+        let base = amount as i32 + range;
+        let percent = base * 48 / (range * 2);
+        self.knob_position = (percent - 24) as i8;
+        Ok(())
     }
 
     pub fn knob_position(&self) -> i8 {
@@ -251,6 +236,8 @@ impl GenderEncoder {
         &self.style
     }
     pub fn set_style(&mut self, style: GenderStyle) {
+        // The range is fixed by the style, so keep it in sync..
+        self.range = style.range();
         self.style = style;
     }
     pub fn range(&self) -> u8 {
@@ -261,12 +248,19 @@ impl GenderEncoder {
 #[derive(Default, Debug, EnumIter, Enum, EnumProperty)]
 pub enum GenderStyle {
     #[default]
-    #[strum(props(uiIndex = "0"))]
+    #[strum(props(uiIndex = "0", range = "12"))]
     Narrow,
 
-    #[strum(props(uiIndex = "1"))]
+    #[strum(props(uiIndex = "1", range = "25"))]
     Medium,
 
-    #[strum(props(uiIndex = "2"))]
+    #[strum(props(uiIndex = "2", range = "50"))]
     Wide,
 }
+
+impl GenderStyle {
+    // How far the amount can go either side of 0 in this style
+    pub fn range(&self) -> u8 {
+        self.get_str("range").unwrap().parse().unwrap()
+    }
+}
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 use std::str::FromStr;
 
 use enum_map::EnumMap;
@@ -13,7 +12,8 @@ use quick_xml::Writer;
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
 use crate::components::hardtune::HardTuneSource::All;
 use crate::components::hardtune::HardTuneStyle::Natural;
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -42,6 +42,7 @@ pub struct HardtuneEffectBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, HardTuneEffect>,
     source: HardTuneSource,
+    unknown: UnknownAttributes,
 }
 
 impl HardtuneEffectBase {
@@ -58,6 +59,7 @@ impl HardtuneEffectBase {
             colour_map,
             preset_map: EnumMap::default(),
             source: HardTuneSource::Music,
+            unknown: Default::default(),
         }
     }
 
@@ -71,6 +73,7 @@ impl HardtuneEffectBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[hardTuneEffect] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -99,27 +102,27 @@ impl HardtuneEffectBase {
             }
 
             if attr.name == "HARDTUNE_KEYSOURCE" {
-                preset.key_source = attr.value.parse::<c_float>()? as u8;
+                preset.key_source = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "HARDTUNE_AMOUNT" {
-                preset.amount = attr.value.parse::<c_float>()? as u8;
+                preset.amount = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "HARDTUNE_WINDOW" {
-                preset.window = attr.value.parse::<c_float>()? as u16;
+                preset.window = parse_float(&attr.value)? as u16;
                 continue;
             }
             if attr.name == "HARDTUNE_RATE" {
-                preset.rate = attr.value.parse::<c_float>()? as u8;
+                preset.rate = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "HARDTUNE_SCALE" {
-                preset.scale = attr.value.parse::<c_float>()? as u8;
+                preset.scale = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "HARDTUNE_PITCH_AMT" {
-                preset.pitch_amt = attr.value.parse::<c_float>()? as u8;
+                preset.pitch_amt = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "HARDTUNE_SOURCE" {
@@ -128,6 +131,7 @@ impl HardtuneEffectBase {
             }
 
             println!("[HardTuneEffect] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
 
         self.preset_map[preset_enum] = preset;
@@ -137,9 +141,10 @@ impl HardtuneEffectBase {
     pub fn write_hardtune<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("hardtuneEffect");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("HARDTUNE_SOURCE".to_string(), self.source.to_string());
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -166,8 +171,8 @@ impl HardtuneEffectBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -199,6 +204,8 @@ impl HardtuneEffectBase {
             attributes.insert("HARDTUNE_SOURCE".to_string(), source.to_string());
         }
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -232,6 +239,7 @@ pub struct HardTuneEffect {
     scale: u8,
     pitch_amt: u8,
     source: Option<HardTuneSource>,
+    unknown: UnknownAttributes,
 }
 
 impl HardTuneEffect {
@@ -246,6 +254,7 @@ impl HardTuneEffect {
             scale: 0,
             pitch_amt: 0,
             source: None,
+            unknown: Default::default(),
         }
     }
 
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::EnumMap;
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -11,7 +10,8 @@ use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
 use crate::components::megaphone::MegaphoneStyle::Megaphone;
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -39,6 +39,7 @@ pub enum ParseError {
 pub struct MegaphoneEffectBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, MegaphoneEffect>,
+    unknown: UnknownAttributes,
 }
 
 impl MegaphoneEffectBase {
@@ -54,6 +55,7 @@ impl MegaphoneEffectBase {
         Self {
             colour_map,
             preset_map: EnumMap::default(),
+            unknown: Default::default(),
         }
     }
 
@@ -61,6 +63,7 @@ impl MegaphoneEffectBase {
         for attr in attributes {
             if !self.colour_map.read_colours(attr)? {
                 println!("[megaphoneEffect] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -96,39 +99,39 @@ impl MegaphoneEffectBase {
              */
 
             if attr.name == "TRANS_DIST_AMT" {
-                preset.trans_dist_amt = attr.value.parse::<c_float>()? as u8;
+                preset.trans_dist_amt = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_HP" {
-                preset.trans_hp = attr.value.parse::<c_float>()? as u8;
+                preset.trans_hp = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_LP" {
-                preset.trans_lp = attr.value.parse::<c_float>()? as u8;
+                preset.trans_lp = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_PREGAIN" {
-                preset.trans_pregain = attr.value.parse::<c_float>()? as u8;
+                preset.trans_pregain = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_POSTGAIN" {
-                preset.trans_postgain = attr.value.parse::<c_float>()? as i8;
+                preset.trans_postgain = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "TRANS_DIST_TYPE" {
-                preset.trans_dist_type = attr.value.parse::<c_float>()? as u8;
+                preset.trans_dist_type = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_PRESENCE_GAIN" {
-                preset.trans_presence_gain = attr.value.parse::<c_float>()? as u8;
+                preset.trans_presence_gain = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_PRESENCE_FC" {
-                preset.trans_presence_fc = attr.value.parse::<c_float>()? as u8;
+                preset.trans_presence_fc = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_PRESENCE_BW" {
-                preset.trans_presence_bw = attr.value.parse::<c_float>()? as u8;
+                preset.trans_presence_bw = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_BEATBOX_ENABLE" {
@@ -136,22 +139,23 @@ impl MegaphoneEffectBase {
                 continue;
             }
             if attr.name == "TRANS_FILTER_CONTROL" {
-                preset.trans_filter_control = attr.value.parse::<c_float>()? as u8;
+                preset.trans_filter_control = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_FILTER" {
-                preset.trans_filter = attr.value.parse::<c_float>()? as u8;
+                preset.trans_filter = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_DRIVE_POT_GAIN_COMP_MID" {
-                preset.trans_drive_pot_gain_comp_mid = attr.value.parse::<c_float>()? as u8;
+                preset.trans_drive_pot_gain_comp_mid = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "TRANS_DRIVE_POT_GAIN_COMP_MAX" {
-                preset.trans_drive_pot_gain_comp_max = attr.value.parse::<c_float>()? as u8;
+                preset.trans_drive_pot_gain_comp_max = parse_float(&attr.value)? as u8;
                 continue;
             }
             println!("[MegaphoneEffect] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
         self.preset_map[preset_enum] = preset;
         Ok(())
@@ -160,8 +164,9 @@ impl MegaphoneEffectBase {
     pub fn write_megaphone<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("megaphoneEffect");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -188,8 +193,8 @@ impl MegaphoneEffectBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -259,6 +264,8 @@ impl MegaphoneEffectBase {
             format!("{}", value.trans_drive_pot_gain_comp_max),
         );
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -307,6 +314,7 @@ pub struct MegaphoneEffect {
     trans_filter: u8,
     trans_drive_pot_gain_comp_mid: u8,
     trans_drive_pot_gain_comp_max: u8,
+    unknown: UnknownAttributes,
 }
 
 impl MegaphoneEffect {
@@ -328,6 +336,7 @@ impl MegaphoneEffect {
             trans_filter: 0,
             trans_drive_pot_gain_comp_mid: 0,
             trans_drive_pot_gain_comp_max: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use enum_map::{Enum, EnumMap};
@@ -10,6 +10,7 @@ use quick_xml::Writer;
 
 use crate::components::colours::ColourMap;
 use crate::components::mixer::FullChannelList::LineOut;
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 
 #[derive(thiserror::Error, Debug)]
@@ -35,6 +36,7 @@ pub struct Mixers {
     mixer_table: RoutingTable,
     volume_table: EnumMap<FullChannelList, u8>,
     colour_map: ColourMap,
+    unknown: UnknownAttributes,
 }
 
 impl Default for Mixers {
@@ -89,6 +91,7 @@ impl Mixers {
             mixer_table,
             volume_table,
             colour_map: ColourMap::new("mixerTree".to_string()),
+            unknown: Default::default(),
         }
     }
 
@@ -149,6 +152,7 @@ impl Mixers {
             // Check to see if this is a colour related attribute..
             if !self.colour_map.read_colours(attr)? {
                 println!("[MIXER] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -159,7 +163,7 @@ impl Mixers {
         let mut elem = BytesStart::new("mixerTree");
 
         // Create the values..
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         for volume in FullChannelList::iter() {
             let key = format!("{}Level", volume.get_str("Name").unwrap());
             let value = format!("{}", self.volume_table[volume]);
@@ -181,6 +185,7 @@ impl Mixers {
         }
 
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Set the attributes into the XML object..
         for (key, value) in &attributes {
@@ -1,8 +1,9 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
+use std::str::FromStr;
 
 use enum_map_derive::Enum;
-use strum::{EnumIter, EnumProperty, IntoEnumIterator};
+use strum::{Display, EnumIter, EnumProperty, EnumString, IntoEnumIterator};
 
 use anyhow::Result;
 use log::warn;
@@ -10,6 +11,7 @@ use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::Faders;
 
@@ -38,6 +40,11 @@ pub struct MuteButton {
     // Labelled as 'fromMuteAllFlag' in the XML, honestly, not sure what this does either,
     // it's either 1, 0 or simply not there.
     from_mute_all: Option<bool>,
+
+    // How the button is lit while muted, this isn't part of the official profile format, so
+    // it's only written when it's been changed.
+    muted_style: Option<MuteLightingStyle>,
+    unknown: UnknownAttributes,
 }
 
 impl MuteButton {
@@ -58,6 +65,8 @@ impl MuteButton {
             previous_volume: 0,
 
             from_mute_all: None,
+            muted_style: None,
+            unknown: Default::default(),
         }
     }
 
@@ -106,20 +115,32 @@ impl MuteButton {
                 continue;
             }
 
+            if attr.name == "mutedLightingStyle" {
+                self.muted_style = Some(MuteLightingStyle::from_str(&attr.value)?);
+                continue;
+            }
+
             // Check to see if this is a colour related attribute..
             if !self.colour_map.read_colours(attr)? {
                 println!("[MUTE BUTTON] Unparsed Attribute: {}", attr.name);
+                self.unknown
+                    .store_with_prefix(self.colour_map.prefix(), attr);
             }
         }
 
         Ok(())
     }
 
-    pub fn write_button<W: Write>(&self, writer: &mut Writer<W>, fader: Faders) -> Result<()> {
+    pub fn write_button<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        fader: Faders,
+        include_extensions: bool,
+    ) -> Result<()> {
         let element_name = fader.get_str("muteContext").unwrap();
         let mut elem = BytesStart::new(element_name);
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         let mute_value = if self.mute_function == MuteFunction::ToVoiceChat {
             String::from("Mute to Chat Mic")
         } else {
@@ -138,8 +159,14 @@ impl MuteButton {
             );
         }
 
+        if let Some(muted_style) = self.muted_style.filter(|_| include_extensions) {
+            attributes.insert("mutedLightingStyle".to_string(), muted_style.to_string());
+        }
+
         self.colour_map
             .write_colours_with_prefix(element_name.into(), &mut attributes);
+        self.unknown
+            .write_with_prefix(element_name, &mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -170,6 +197,23 @@ impl MuteButton {
     pub fn previous_volume(&self) -> u8 {
         self.previous_volume
     }
+
+    pub fn muted_style(&self) -> MuteLightingStyle {
+        self.muted_style.unwrap_or_default()
+    }
+    pub fn set_muted_style(&mut self, muted_style: MuteLightingStyle) {
+        self.muted_style = Some(muted_style);
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Display, EnumString, EnumIter, PartialEq, Eq)]
+pub enum MuteLightingStyle {
+    // The official behaviour, the button is lit with colour 1
+    #[default]
+    Colour1,
+    Dimmed,
+    Colour2,
+    Off,
 }
 
 // MuteChat
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use enum_map::Enum;
@@ -7,8 +7,9 @@ use strum::EnumProperty;
 use anyhow::{anyhow, Result};
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle, ColourState};
-use crate::components::mute::MuteFunction;
+use crate::components::mute::{MuteFunction, MuteLightingStyle};
 use crate::components::mute_chat::CoughToggle::Hold;
+use crate::components::unknown::UnknownAttributes;
 
 #[derive(thiserror::Error, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -46,6 +47,10 @@ pub struct MuteChat {
     cough_behaviour: CoughToggle,
     cough_mute_source: MuteFunction,
     cough_button_on: bool,
+
+    // Not part of the official profile format, see MuteButton
+    muted_style: Option<MuteLightingStyle>,
+    unknown: UnknownAttributes,
 }
 
 impl MuteChat {
@@ -66,6 +71,8 @@ impl MuteChat {
             cough_behaviour: Hold,
             cough_mute_source: MuteFunction::All,
             cough_button_on: false,
+            muted_style: None,
+            unknown: Default::default(),
         }
     }
 
@@ -100,18 +107,28 @@ impl MuteChat {
                 continue;
             }
 
+            if attr.name == "mutedLightingStyle" {
+                self.muted_style = Some(MuteLightingStyle::from_str(&attr.value)?);
+                continue;
+            }
+
             if !self.colour_map.read_colours(attr)? {
                 println!("[{}] Unparsed Attribute: {}", self.element_name, attr.name);
+                self.unknown.store(attr);
             }
         }
 
         Ok(())
     }
 
-    pub fn write_mute_chat<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+    pub fn write_mute_chat<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        include_extensions: bool,
+    ) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
 
         attributes.insert(
             "micIsAnActiveFader".to_string(),
@@ -141,8 +158,12 @@ impl MuteChat {
             },
         );
         attributes.insert("blink".to_string(), self.blink.to_string());
+        if let Some(muted_style) = self.muted_style.filter(|_| include_extensions) {
+            attributes.insert("mutedLightingStyle".to_string(), muted_style.to_string());
+        }
 
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -219,6 +240,13 @@ impl MuteChat {
     pub fn set_cough_behaviour(&mut self, cough_behaviour: CoughToggle) {
         self.cough_behaviour = cough_behaviour;
     }
+
+    pub fn muted_style(&self) -> MuteLightingStyle {
+        self.muted_style.unwrap_or_default()
+    }
+    pub fn set_muted_style(&mut self, muted_style: MuteLightingStyle) {
+        self.muted_style = Some(muted_style);
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
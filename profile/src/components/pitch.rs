@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::{Enum, EnumMap};
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -10,7 +9,8 @@ use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap};
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -39,6 +39,7 @@ pub struct PitchEncoderBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, PitchEncoder>,
     active_set: u8, // Not sure what this does?
+    unknown: UnknownAttributes,
 }
 
 impl PitchEncoderBase {
@@ -53,6 +54,7 @@ impl PitchEncoderBase {
             colour_map,
             preset_map: EnumMap::default(),
             active_set: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -65,6 +67,7 @@ impl PitchEncoderBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[PitchEncoder] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -96,12 +99,12 @@ impl PitchEncoderBase {
             }
 
             if attr.name == "PITCH_KNOB_POSITION" {
-                preset.knob_position = attr.value.parse::<c_float>()? as i8;
+                preset.knob_position = parse_float(&attr.value)? as i8;
                 continue;
             }
 
             if attr.name == "PITCH_RANGE" {
-                preset.range = attr.value.parse::<c_float>()? as u8;
+                preset.range = parse_float(&attr.value)? as u8;
                 continue;
             }
 
@@ -111,11 +114,12 @@ impl PitchEncoderBase {
             }
 
             if attr.name == "PITCH_SHIFT_INST_RATIO" {
-                preset.inst_ratio = Some(attr.value.parse::<c_float>()? as u8);
+                preset.inst_ratio = Some(parse_float(&attr.value)? as u8);
                 continue;
             }
 
             println!("[PitchEncoder] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
 
         self.preset_map[preset_enum] = preset;
@@ -125,9 +129,10 @@ impl PitchEncoderBase {
     pub fn write_pitch<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("pitchEncoder");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("active_set".to_string(), format!("{}", self.active_set));
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -154,8 +159,8 @@ impl PitchEncoderBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -179,6 +184,8 @@ impl PitchEncoderBase {
             );
         }
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -206,6 +213,7 @@ pub struct PitchEncoder {
     range: u8,
     threshold: i8,
     inst_ratio: Option<u8>,
+    unknown: UnknownAttributes,
 }
 
 impl PitchEncoder {
@@ -217,6 +225,7 @@ impl PitchEncoder {
 
             threshold: -26,
             inst_ratio: None,
+            unknown: Default::default(),
         }
     }
 
@@ -300,6 +309,31 @@ impl PitchEncoder {
         self.knob_position
     }
 
+    // Sets the pitch in semitones, rather than by knob position..
+    pub fn set_pitch_value(&mut self, semitones: i8, hardtune_enabled: bool) -> Result<()> {
+        let limit = match self.style {
+            PitchStyle::Narrow => 12,
+            PitchStyle::Wide => 24,
+        };
+
+        if !(-limit..=limit).contains(&semitones) {
+            return Err(anyhow!(
+                "Pitch should be between -{} and {} semitones",
+                limit,
+                limit
+            ));
+        }
+
+        if hardtune_enabled && semitones % 12 != 0 {
+            return Err(anyhow!(
+                "Pitch can only be changed in whole octaves while Hardtune is enabled"
+            ));
+        }
+
+        self.knob_position = semitones;
+        Ok(())
+    }
+
     pub fn style(&self) -> &PitchStyle {
         &self.style
     }
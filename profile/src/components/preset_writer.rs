@@ -1,7 +1,7 @@
 use anyhow::Result;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 pub struct PresetWriter {
@@ -26,7 +26,7 @@ impl PresetWriter {
         &self,
         writer: &mut Writer<W>,
         name: &str,
-        attribute_map: HashMap<String, String>,
+        attribute_map: LinkedHashMap<String, String>,
     ) -> Result<()> {
         let mut elem = BytesStart::new(name);
         for (key, value) in &attribute_map {
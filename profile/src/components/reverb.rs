@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::{Enum, EnumMap};
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -11,7 +10,8 @@ use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap};
 use crate::components::reverb::ReverbStyle::Library;
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -40,6 +40,7 @@ pub struct ReverbEncoderBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, ReverbEncoder>,
     active_set: u8, // Not sure what this does?
+    unknown: UnknownAttributes,
 }
 
 impl ReverbEncoderBase {
@@ -54,6 +55,7 @@ impl ReverbEncoderBase {
             colour_map,
             preset_map: EnumMap::default(),
             active_set: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -66,6 +68,7 @@ impl ReverbEncoderBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[ReverbEncoder] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -90,7 +93,7 @@ impl ReverbEncoderBase {
             }
 
             if attr.name == "REVERB_KNOB_POSITION" {
-                let mut position = attr.value.parse::<c_float>()? as i8;
+                let mut position = parse_float(&attr.value)? as i8;
                 if position < 0 {
                     position = 0
                 };
@@ -103,55 +106,56 @@ impl ReverbEncoderBase {
             }
 
             if attr.name == "REVERB_TYPE" {
-                preset.reverb_type = attr.value.parse::<c_float>()? as u8;
+                preset.reverb_type = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "REVERB_DECAY" {
-                preset.decay = attr.value.parse::<c_float>()? as u16;
+                preset.decay = parse_float(&attr.value)? as u16;
                 continue;
             }
             if attr.name == "REVERB_PREDELAY" {
-                preset.pre_delay = attr.value.parse::<c_float>()? as u8;
+                preset.pre_delay = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "REVERB_DIFFUSE" {
-                preset.diffuse = attr.value.parse::<c_float>()? as i8;
+                preset.diffuse = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_LOCOLOR" {
-                preset.low_color = attr.value.parse::<c_float>()? as i8;
+                preset.low_color = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_HICOLOR" {
-                preset.high_color = attr.value.parse::<c_float>()? as i8;
+                preset.high_color = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_HIFACTOR" {
-                preset.high_factor = attr.value.parse::<c_float>()? as i8;
+                preset.high_factor = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_MODSPEED" {
-                preset.mod_speed = attr.value.parse::<c_float>()? as i8;
+                preset.mod_speed = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_MODDEPTH" {
-                preset.mod_depth = attr.value.parse::<c_float>()? as i8;
+                preset.mod_depth = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_EARLYLEVEL" {
-                preset.early_level = attr.value.parse::<c_float>()? as i8;
+                preset.early_level = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_TAILLEVEL" {
-                preset.tail_level = attr.value.parse::<c_float>()? as i8;
+                preset.tail_level = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "REVERB_DRYLEVEL" {
-                preset.dry_level = attr.value.parse::<c_float>()? as i8;
+                preset.dry_level = parse_float(&attr.value)? as i8;
                 continue;
             }
 
             println!("[ReverbEncoder] Unparsed Child Attribute: {}", &attr.name);
+            preset.unknown.store(attr);
         }
 
         // Ok, we should be able to store this now..
@@ -162,9 +166,10 @@ impl ReverbEncoderBase {
     pub fn write_reverb<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("reverbEncoder");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("active_set".to_string(), format!("{}", self.active_set));
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -191,8 +196,8 @@ impl ReverbEncoderBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -240,6 +245,8 @@ impl ReverbEncoderBase {
             format!("{}", value.dry_level),
         );
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -274,6 +281,7 @@ pub struct ReverbEncoder {
     early_level: i8,
     tail_level: i8,
     dry_level: i8, // Dry level exists in the config, but is never sent?
+    unknown: UnknownAttributes,
 }
 
 impl ReverbEncoder {
@@ -293,6 +301,7 @@ impl ReverbEncoder {
             early_level: 0,
             tail_level: 0,
             dry_level: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -300,19 +309,6 @@ impl ReverbEncoder {
         ((36 * self.knob_position as i32) / 24 - 36) as i8
     }
 
-    // TODO: As with echo, we probably shouldn't do this!
-    pub fn get_percentage_amount(&self) -> u8 {
-        // Knob Position and Amount are two very different things, so is percentage :)
-        ((self.knob_position as u16 * 100) / 24) as u8
-    }
-    pub fn set_percentage_amount(&mut self, percentage: u8) -> Result<()> {
-        if percentage > 100 {
-            return Err(anyhow!("Value must be a percentage"));
-        }
-        self.set_knob_position(((percentage as i16 * 24) / 100) as i8)?;
-        Ok(())
-    }
-
     pub fn knob_position(&self) -> i8 {
         self.knob_position
     }
@@ -1,6 +1,5 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
-use std::os::raw::c_float;
 
 use enum_map::EnumMap;
 use strum::{EnumIter, EnumProperty, IntoEnumIterator};
@@ -11,7 +10,8 @@ use quick_xml::Writer;
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
 use crate::components::robot::RobotStyle::Robot1;
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 use crate::Preset;
 
 #[derive(thiserror::Error, Debug)]
@@ -39,6 +39,7 @@ pub enum ParseError {
 pub struct RobotEffectBase {
     colour_map: ColourMap,
     preset_map: EnumMap<Preset, RobotEffect>,
+    unknown: UnknownAttributes,
 }
 
 impl RobotEffectBase {
@@ -54,6 +55,7 @@ impl RobotEffectBase {
         Self {
             colour_map,
             preset_map: EnumMap::default(),
+            unknown: Default::default(),
         }
     }
 
@@ -61,6 +63,7 @@ impl RobotEffectBase {
         for attr in attributes {
             if !self.colour_map.read_colours(attr)? {
                 println!("[robotEffect] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -92,58 +95,59 @@ impl RobotEffectBase {
              * but I'm not gonna rule it out.. */
 
             if attr.name == "ROBOT_SYNTHOSC_PULSEWIDTH" {
-                preset.synthosc_pulse_width = attr.value.parse::<c_float>()? as u8;
+                preset.synthosc_pulse_width = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_SYNTHOSC_WAVEFORM" {
-                preset.synthosc_waveform = attr.value.parse::<c_float>()? as u8;
+                preset.synthosc_waveform = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_GATE_THRESHOLD" {
-                preset.vocoder_gate_threshold = attr.value.parse::<c_float>()? as i8;
+                preset.vocoder_gate_threshold = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "ROBOT_DRY_MIX" {
-                preset.dry_mix = attr.value.parse::<c_float>()? as i8;
+                preset.dry_mix = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_LOW_FREQ" {
-                preset.vocoder_low_freq = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_low_freq = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_LOW_GAIN" {
-                preset.vocoder_low_gain = attr.value.parse::<c_float>()? as i8;
+                preset.vocoder_low_gain = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_LOW_BW" {
-                preset.vocoder_low_bw = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_low_bw = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_MID_FREQ" {
-                preset.vocoder_mid_freq = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_mid_freq = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_MID_GAIN" {
-                preset.vocoder_mid_gain = attr.value.parse::<c_float>()? as i8;
+                preset.vocoder_mid_gain = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_MID_BW" {
-                preset.vocoder_mid_bw = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_mid_bw = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_HIGH_FREQ" {
-                preset.vocoder_high_freq = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_high_freq = parse_float(&attr.value)? as u8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_HIGH_GAIN" {
-                preset.vocoder_high_gain = attr.value.parse::<c_float>()? as i8;
+                preset.vocoder_high_gain = parse_float(&attr.value)? as i8;
                 continue;
             }
             if attr.name == "ROBOT_VOCODER_HIGH_BW" {
-                preset.vocoder_high_bw = attr.value.parse::<c_float>()? as u8;
+                preset.vocoder_high_bw = parse_float(&attr.value)? as u8;
                 continue;
             }
             println!("[RobotEffect] Unparsed Child Attribute: {}", attr.name);
+            preset.unknown.store(attr);
         }
 
         self.preset_map[preset_enum] = preset;
@@ -153,8 +157,9 @@ impl RobotEffectBase {
     pub fn write_robot<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("robotEffect");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
@@ -181,8 +186,8 @@ impl RobotEffectBase {
         Ok(())
     }
 
-    pub fn get_preset_attributes(&self, preset: Preset) -> HashMap<String, String> {
-        let mut attributes = HashMap::new();
+    pub fn get_preset_attributes(&self, preset: Preset) -> LinkedHashMap<String, String> {
+        let mut attributes = LinkedHashMap::new();
         let value = &self.preset_map[preset];
 
         attributes.insert(
@@ -247,6 +252,8 @@ impl RobotEffectBase {
             format!("{}", value.vocoder_high_bw),
         );
 
+        value.unknown.write(&mut attributes);
+
         attributes
     }
 
@@ -287,6 +294,7 @@ pub struct RobotEffect {
     vocoder_high_freq: u8,
     vocoder_high_gain: i8,
     vocoder_high_bw: u8,
+    unknown: UnknownAttributes,
 }
 
 impl RobotEffect {
@@ -308,6 +316,7 @@ impl RobotEffect {
             vocoder_high_freq: 0,
             vocoder_high_gain: 0,
             vocoder_high_bw: 0,
+            unknown: Default::default(),
         }
     }
 
@@ -1,12 +1,12 @@
-use std::collections::HashMap;
-use std::ffi::c_float;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use anyhow::Result;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
 
-use crate::profile::Attribute;
+use crate::components::unknown::UnknownAttributes;
+use crate::profile::{parse_float, Attribute};
 
 #[derive(thiserror::Error, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -33,6 +33,8 @@ pub struct RootElement {
     version: u8,
     loudness: u8,
     device: u64,
+
+    unknown: UnknownAttributes,
 }
 
 impl Default for RootElement {
@@ -47,13 +49,14 @@ impl RootElement {
             version: 0,
             loudness: 0,
             device: 0,
+            unknown: Default::default(),
         }
     }
 
     pub fn parse_root(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "version" {
-                self.version = attr.value.parse::<c_float>()? as u8;
+                self.version = parse_float(&attr.value)? as u8;
                 continue;
             }
 
@@ -64,7 +67,10 @@ impl RootElement {
 
             if attr.name == "device" {
                 self.device = attr.value.parse()?;
+                continue;
             }
+
+            self.unknown.store(attr);
         }
 
         Ok(())
@@ -74,10 +80,11 @@ impl RootElement {
         let mut elem = BytesStart::new("ValueTreeRoot");
 
         // Create the hashmap of values..
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("version".to_string(), "2".to_string());
         attributes.insert("loudness".to_string(), format!("{}", self.loudness));
         attributes.insert("device".to_string(), format!("{}", self.device));
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -91,7 +98,7 @@ impl RootElement {
     pub fn write_final<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("AppTree");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("ConnectedDeviceID".to_string(), format!("{}", &self.device));
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
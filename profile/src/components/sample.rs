@@ -12,6 +12,7 @@ use strum::{Display, EnumIter, EnumProperty, EnumString};
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
 use crate::components::sample::PlayOrder::{Random, Sequential};
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::SampleButtons;
 
@@ -43,6 +44,7 @@ pub struct SampleBase {
     colour_map: ColourMap,
     state: String, // Seems to be "Empty" most of the time..
     sample_stack: EnumMap<SampleBank, SampleStack>,
+    unknown: UnknownAttributes,
 }
 
 impl SampleBase {
@@ -66,6 +68,7 @@ impl SampleBase {
             colour_map,
             state: "Empty".to_string(),
             sample_stack: Default::default(),
+            unknown: Default::default(),
         }
     }
 
@@ -81,6 +84,7 @@ impl SampleBase {
 
             if !self.colour_map.read_colours(attr)? {
                 println!("[Sampler] Unparsed Attribute: {}", attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -117,6 +121,7 @@ impl SampleBase {
         }
 
         if let Some(track_count) = map.get(key.as_str()) {
+            sample_stack.has_stack_size = true;
             let track_count: u8 = track_count.parse()?;
             for i in 0..track_count {
                 if let (Some(track), Some(start), Some(end), Some(gain)) = (
@@ -152,7 +157,7 @@ impl SampleBase {
     pub fn write_sample<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         self.colour_map.write_colours(&mut attributes);
 
         // TODO: Solve the 'State' problem properly..
@@ -172,6 +177,8 @@ impl SampleBase {
             );
         }
 
+        self.unknown.write(&mut attributes);
+
         // Write out the attributes etc for this element, but don't close it yet..
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -195,7 +202,7 @@ impl SampleBase {
                 );
             }
 
-            if !value.tracks.is_empty() {
+            if !value.tracks.is_empty() || value.has_stack_size {
                 sub_attributes.insert(
                     format!("sampleStack{key}stackSize"),
                     format!("{}", value.tracks.len()),
@@ -263,6 +270,9 @@ pub struct SampleStack {
     playback_mode: Option<PlaybackMode>,
     play_order: Option<PlayOrder>,
 
+    // The official app writes an empty stack's size as 0, which we keep if it was there..
+    has_stack_size: bool,
+
     // Transient value, keep track of where we may be sequentially..
     transient_seq_position: usize,
 }
@@ -279,6 +289,7 @@ impl SampleStack {
             tracks: vec![],
             playback_mode: None,
             play_order: None,
+            has_stack_size: false,
 
             transient_seq_position: 0,
         }
@@ -444,6 +455,23 @@ impl Track {
         self.end_position = end;
         Ok(())
     }
+
+    // Setting both at once avoids the start / end ordering checks failing when moving the
+    // whole trim window past one of its current positions
+    pub fn set_positions(&mut self, start: f32, end: f32) -> Result<()> {
+        if !(0. ..=100.).contains(&start) {
+            bail!("Start Value should be a percentage! {}", start);
+        }
+        if !(0. ..=100.).contains(&end) {
+            bail!("End Value should be a percentage! {}", end);
+        }
+        if start > end {
+            bail!("Start position should be before end");
+        }
+        self.start_position = start;
+        self.end_position = end;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Copy, Clone, Enum, EnumProperty)]
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 use std::str::FromStr;
 
@@ -9,6 +9,7 @@ use strum::EnumProperty;
 
 use crate::components::colours::{Colour, ColourMap};
 use crate::components::scribble::ScribbleStyle::{Inverted, Normal};
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 use crate::Faders;
 
@@ -57,6 +58,10 @@ pub struct Scribble {
 
     // Filename in the .goxlr zip file to the prepared bitmap
     bitmap_file: String,
+
+    // Draws a bar down the left of the display, not in the official format so only written if set
+    left_bar: Option<bool>,
+    unknown: UnknownAttributes,
 }
 
 impl Scribble {
@@ -81,6 +86,8 @@ impl Scribble {
             alpha: 0.0,
             style: Normal,
             bitmap_file: "".to_string(),
+            left_bar: None,
+            unknown: Default::default(),
         }
     }
 
@@ -129,20 +136,32 @@ impl Scribble {
                 continue;
             }
 
+            if attr.name.ends_with("leftBar") {
+                self.left_bar = Some(attr.value != "0");
+                continue;
+            }
+
             // Send the rest out for colouring..
             if !self.colour_map.read_colours(attr)? {
                 println!("[SCRIBBLE] Unparsed Attribute: {}", attr.name);
+                self.unknown
+                    .store_with_prefix(self.colour_map.prefix(), attr);
             }
         }
 
         Ok(())
     }
 
-    pub fn write_scribble<W: Write>(&self, writer: &mut Writer<W>, fader: Faders) -> Result<()> {
+    pub fn write_scribble<W: Write>(
+        &self,
+        writer: &mut Writer<W>,
+        fader: Faders,
+        include_extensions: bool,
+    ) -> Result<()> {
         let element_name = fader.get_str("scribbleContext").unwrap();
         let mut elem = BytesStart::new(element_name);
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert(
             format!("{}iconFile", element_name),
             if self.icon_file.is_none() {
@@ -171,9 +190,17 @@ impl Scribble {
             format!("{}", self.text_size),
         );
         attributes.insert(format!("{}bitmap", element_name), self.bitmap_file.clone());
+        if let Some(left_bar) = self.left_bar.filter(|_| include_extensions) {
+            attributes.insert(
+                format!("{}leftBar", element_name),
+                format!("{}", left_bar as u8),
+            );
+        }
 
         self.colour_map
             .write_colours_with_prefix(element_name.into(), &mut attributes);
+        self.unknown
+            .write_with_prefix(element_name, &mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
@@ -208,6 +235,9 @@ impl Scribble {
     pub fn is_style_invert(&self) -> bool {
         self.style == Inverted
     }
+    pub fn has_left_bar(&self) -> bool {
+        self.left_bar.unwrap_or(false)
+    }
 
     pub fn style(&self) -> &ScribbleStyle {
         &self.style
@@ -226,6 +256,9 @@ impl Scribble {
     pub fn set_scribble_inverted(&mut self, inverted: bool) {
         self.style = if inverted { Inverted } else { Normal }
     }
+    pub fn set_left_bar(&mut self, left_bar: bool) {
+        self.left_bar = Some(left_bar);
+    }
 }
 
 #[derive(PartialEq, Eq, Debug)]
@@ -0,0 +1,112 @@
+use anyhow::Result;
+use log::warn;
+use ritelinked::LinkedHashMap;
+use std::io::Write;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Writer;
+use strum::{EnumIter, IntoEnumIterator};
+
+use crate::components::colours::Colour;
+use crate::profile::Attribute;
+
+// Sequences hold up attaching and shutting down, so don't let them run on..
+pub const MAX_SEQUENCE_DURATION: u16 = 3000;
+
+/**
+ * Lighting Sequences aren't something the official app knows about, they're a short animation
+ * played across the device when it's attached (startupSequence) or when the daemon shuts down
+ * (shutdownSequence), stepping through the listed colours over the duration.
+ */
+#[derive(Debug, Clone)]
+pub struct LightingSequence {
+    element_name: String,
+
+    style: SequenceStyle,
+    colours: Vec<Colour>,
+    duration: u16,
+}
+
+impl LightingSequence {
+    pub fn new(element_name: String) -> Self {
+        Self {
+            element_name,
+            style: SequenceStyle::default(),
+            colours: vec![],
+            duration: 1500,
+        }
+    }
+
+    pub fn parse_sequence(&mut self, attributes: &Vec<Attribute>) -> Result<()> {
+        for attr in attributes {
+            if attr.name == "style" {
+                match SequenceStyle::iter().nth(attr.value.parse()?) {
+                    None => warn!("Unknown Sequence Style, using Default."),
+                    Some(value) => self.style = value,
+                }
+                continue;
+            }
+            if attr.name == "colours" {
+                self.colours = attr
+                    .value
+                    .split(',')
+                    .filter(|colour| !colour.is_empty())
+                    .map(Colour::fromrgb)
+                    .collect::<Result<_, _>>()?;
+                continue;
+            }
+            if attr.name == "duration" {
+                // Parsed wide, so an overly long duration gets capped rather than failing..
+                let duration: u32 = attr.value.parse()?;
+                if duration > MAX_SEQUENCE_DURATION as u32 {
+                    warn!("Sequence of {}ms is too long, capping", duration);
+                }
+                self.duration = duration.min(MAX_SEQUENCE_DURATION as u32) as u16;
+                continue;
+            }
+            warn!("Unmatched Attribute: {}", attr.name);
+        }
+
+        if self.colours.is_empty() {
+            warn!("Lighting Sequence {} has no colours", self.element_name);
+        }
+        Ok(())
+    }
+
+    pub fn write_sequence<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        let mut elem = BytesStart::new(self.element_name.as_str());
+
+        let colours: Vec<String> = self.colours.iter().map(Colour::to_rgb).collect();
+
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
+        attributes.insert("style".to_string(), format!("{}", self.style as u8));
+        attributes.insert("colours".to_string(), colours.join(","));
+        attributes.insert("duration".to_string(), format!("{}", self.duration));
+
+        for (key, value) in &attributes {
+            elem.push_attribute((key.as_str(), value.as_str()));
+        }
+        writer.write_event(Event::Empty(elem))?;
+        Ok(())
+    }
+
+    pub fn style(&self) -> SequenceStyle {
+        self.style
+    }
+    pub fn colours(&self) -> &Vec<Colour> {
+        &self.colours
+    }
+    pub fn duration(&self) -> u16 {
+        self.duration
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, EnumIter, PartialEq)]
+pub enum SequenceStyle {
+    // Lights the faders up one at a time, left to right, for each colour
+    #[default]
+    Sweep,
+
+    // Fades the whole device from one colour to the next
+    Fade,
+}
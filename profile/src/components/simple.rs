@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use anyhow::Result;
@@ -9,6 +9,7 @@ use quick_xml::Writer;
 use strum::{Display, EnumIter, EnumString};
 
 use crate::components::colours::{Colour, ColourMap, ColourOffStyle};
+use crate::components::unknown::UnknownAttributes;
 use crate::profile::Attribute;
 
 #[derive(thiserror::Error, Debug)]
@@ -35,6 +36,7 @@ pub struct SimpleElement {
     // Ok.
     element_name: String,
     colour_map: ColourMap,
+    unknown: UnknownAttributes,
 }
 
 impl SimpleElement {
@@ -68,6 +70,7 @@ impl SimpleElement {
         Self {
             element_name,
             colour_map,
+            unknown: Default::default(),
         }
     }
 
@@ -75,6 +78,7 @@ impl SimpleElement {
         for attr in attributes {
             if !self.colour_map.read_colours(attr)? {
                 println!("[{}] Unparsed Attribute: {}", self.element_name, attr.name);
+                self.unknown.store(attr);
             }
         }
 
@@ -84,8 +88,9 @@ impl SimpleElement {
     pub fn write_simple<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new(self.element_name.as_str());
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         self.colour_map.write_colours(&mut attributes);
+        self.unknown.write(&mut attributes);
 
         for (key, value) in &attributes {
             elem.push_attribute((key.as_str(), value.as_str()));
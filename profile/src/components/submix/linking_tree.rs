@@ -4,7 +4,7 @@ use anyhow::{bail, Result};
 use enum_map::EnumMap;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 use strum::{EnumProperty, IntoEnumIterator};
 
@@ -102,7 +102,7 @@ impl LinkingTree {
         let mut elem = BytesStart::new("linkingTree");
 
         // This one's actually incredibly straight forward :)
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         for input in InputChannels::iter() {
             let key = format!("{}Linked", input.get_str("Name").unwrap());
             let value = format!("{}", self.linked_list[input] as u8);
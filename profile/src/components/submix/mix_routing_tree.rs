@@ -1,5 +1,5 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 use crate::components::mixer::OutputChannels;
@@ -67,7 +67,7 @@ impl MixRoutingTree {
         let mut elem = BytesStart::new("mixRoutingTree");
 
         // This one's actually incredibly straight forward :)
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
 
         attributes.insert(
             String::from("headphone"),
@@ -5,7 +5,7 @@ use anyhow::Result;
 use enum_map::EnumMap;
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 use strum::{EnumProperty, IntoEnumIterator};
 
@@ -88,7 +88,7 @@ impl MonitorTree {
     pub fn write_monitor_tree<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("monitorTree");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert(
             String::from("monitoredOutput"),
             format!("{}", self.monitored_output as usize),
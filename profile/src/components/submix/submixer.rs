@@ -6,7 +6,7 @@ use anyhow::Result;
 use enum_map::EnumMap;
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 use strum::{EnumProperty, IntoEnumIterator};
 
@@ -72,7 +72,7 @@ impl SubMixer {
         let mut elem = BytesStart::new("submixerTree");
 
         // Create the values..
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert(
             String::from("submixMode"),
             (self.submix_enabled as u8).to_string(),
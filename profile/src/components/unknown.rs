@@ -0,0 +1,139 @@
+use std::io::{BufRead, Write};
+
+use anyhow::{bail, Result};
+use quick_xml::events::{BytesEnd, BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use ritelinked::LinkedHashMap;
+
+use crate::profile::{wrap_start_event, Attribute};
+
+/**
+ * Newer versions of the official app may add attributes we don't know about yet, rather than
+ * dropping them on save, we hold onto them and write them back as they were.
+ */
+#[derive(Debug, Default, Clone)]
+pub struct UnknownAttributes {
+    attributes: Vec<UnknownAttribute>,
+}
+
+#[derive(Debug, Clone)]
+struct UnknownAttribute {
+    name: String,
+    value: String,
+
+    // Whether the name should have the element name put in front of it when written, needed for
+    // the elements which get swapped around (Faders, Mute Buttons and Scribbles).
+    prefixed: bool,
+}
+
+impl UnknownAttributes {
+    pub fn store(&mut self, attribute: &Attribute) {
+        self.attributes.push(UnknownAttribute {
+            name: attribute.name.clone(),
+            value: attribute.value.clone(),
+            prefixed: false,
+        });
+    }
+
+    pub fn store_with_prefix(&mut self, prefix: &str, attribute: &Attribute) {
+        match attribute.name.strip_prefix(prefix) {
+            Some(name) => self.attributes.push(UnknownAttribute {
+                name: name.to_string(),
+                value: attribute.value.clone(),
+                prefixed: true,
+            }),
+            None => self.store(attribute),
+        }
+    }
+
+    pub fn write(&self, attributes: &mut LinkedHashMap<String, String>) {
+        self.write_with_prefix("", attributes);
+    }
+
+    pub fn write_with_prefix(&self, prefix: &str, attributes: &mut LinkedHashMap<String, String>) {
+        for attribute in &self.attributes {
+            let name = if attribute.prefixed {
+                format!("{}{}", prefix, attribute.name)
+            } else {
+                attribute.name.clone()
+            };
+
+            // If we've since written this ourselves, ours wins..
+            if !attributes.contains_key(&name) {
+                attributes.insert(name, attribute.value.clone());
+            }
+        }
+    }
+}
+
+/**
+ * As above, but for entire elements (along with any children), these are stored by the
+ * ProfileSettings and written back as-is.
+ */
+#[derive(Debug, Clone)]
+pub struct UnknownElement {
+    name: String,
+    attributes: Vec<(String, String)>,
+    children: Vec<UnknownElement>,
+}
+
+impl UnknownElement {
+    pub fn new(name: String, attributes: &[Attribute]) -> Self {
+        Self {
+            name,
+            attributes: attributes
+                .iter()
+                .map(|a| (a.name.clone(), a.value.clone()))
+                .collect(),
+            children: vec![],
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Reads everything up to (and including) the end tag of this element, this should be
+    /// called immediately after the element's start tag has been read.
+    pub fn read_children<B: BufRead>(&mut self, reader: &mut Reader<B>) -> Result<()> {
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let (name, attributes) = wrap_start_event(e)?;
+                    let mut child = UnknownElement::new(name, &attributes);
+                    child.read_children(reader)?;
+                    self.children.push(child);
+                }
+                Ok(Event::Empty(ref e)) => {
+                    let (name, attributes) = wrap_start_event(e)?;
+                    self.children.push(UnknownElement::new(name, &attributes));
+                }
+                Ok(Event::End(_)) => return Ok(()),
+                Ok(Event::Eof) => bail!("Unexpected end of file inside {}", self.name),
+                Ok(_) => {}
+                Err(e) => bail!("Error Parsing Profile: {}", e),
+            }
+            buf.clear();
+        }
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
+        let mut elem = BytesStart::new(self.name.as_str());
+        for (key, value) in &self.attributes {
+            elem.push_attribute((key.as_str(), value.as_str()));
+        }
+
+        if self.children.is_empty() {
+            writer.write_event(Event::Empty(elem))?;
+            return Ok(());
+        }
+
+        writer.write_event(Event::Start(elem))?;
+        for child in &self.children {
+            child.write(writer)?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(self.name.as_str())))?;
+        Ok(())
+    }
+}
@@ -6,6 +6,23 @@ pub mod error;
 pub mod mic_profile;
 pub mod microphone;
 pub mod profile;
+pub mod structure;
+
+/// The version of the official GoXLR App that a profile is being written for, oldest first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AppVersion {
+    /// Before Lighting Animations were added
+    Legacy,
+
+    /// Supports Animations, but not Submixes
+    PreSubmix,
+
+    /// The current 'Release' version of the official app
+    Release,
+
+    /// Everything we know about, including attributes the official app doesn't understand
+    Current,
+}
 
 #[derive(Debug, Display, Enum, EnumIter, EnumProperty, Copy, Clone, PartialEq, Eq, Hash)]
 pub enum SampleButtons {
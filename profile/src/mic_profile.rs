@@ -4,16 +4,15 @@ use crate::microphone::equalizer_mini::EqualizerMini;
 use crate::microphone::gate::Gate;
 use crate::microphone::mic_setup::MicSetup;
 use crate::microphone::ui_setup::UiSetup;
-use crate::profile::wrap_start_event;
+use crate::profile::{parse_float, wrap_start_event};
 use anyhow::{anyhow, bail, Result};
 use log::{debug, warn};
 use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, Event};
 use quick_xml::{Reader, Writer};
-use std::collections::HashMap;
+use ritelinked::LinkedHashMap;
 use std::fs;
 use std::fs::File;
 use std::io::{BufReader, Read, Write};
-use std::os::raw::c_float;
 use std::path::Path;
 
 #[derive(Debug)]
@@ -65,19 +64,19 @@ impl MicProfileSettings {
                         // any of the above categories, find it and handle it here..
                         for attr in &attributes {
                             if attr.name == "MIC_DEESS_AMOUNT" {
-                                deess = attr.value.parse::<c_float>()? as u8;
+                                deess = parse_float(&attr.value)? as u8;
                                 continue;
                             }
                             if attr.name == "BLEEP_LEVEL" {
-                                bleep_level = attr.value.parse::<c_float>()? as i8;
+                                bleep_level = parse_float(&attr.value)? as i8;
                                 continue;
                             }
                             if attr.name == "MIC_COMP_SELECT" {
-                                comp_select = attr.value.parse::<c_float>()? as u8;
+                                comp_select = parse_float(&attr.value)? as u8;
                                 continue;
                             }
                             if attr.name == "MIC_GATE_MODE" {
-                                gate_mode = attr.value.parse::<c_float>()? as u8;
+                                gate_mode = parse_float(&attr.value)? as u8;
                                 continue;
                             }
                         }
@@ -174,7 +173,7 @@ impl MicProfileSettings {
         writer.write_event(Event::Start(BytesStart::new("MicProfileTree")))?;
 
         // First, we need to write the EQ, Compressor and Gate..
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
 
         // The mini and main can both have configs in the same file.
         self.equalizer.write_equaliser(&mut attributes);
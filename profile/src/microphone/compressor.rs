@@ -1,7 +1,6 @@
-use std::collections::HashMap;
-use std::os::raw::c_float;
+use ritelinked::LinkedHashMap;
 
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{anyhow, Result};
 
 #[derive(thiserror::Error, Debug)]
@@ -46,12 +45,12 @@ impl Compressor {
     pub fn parse_compressor(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "MIC_COMP_THRESHOLD" {
-                self.set_threshold(attr.value.parse::<c_float>()? as i8)?;
+                self.set_threshold(parse_float(&attr.value)? as i8)?;
                 continue;
             }
 
             if attr.name == "MIC_COMP_RATIO" {
-                let value = attr.value.parse::<c_float>()?;
+                let value = parse_float(&attr.value)?;
                 if value > 14. {
                     continue;
                 }
@@ -60,7 +59,7 @@ impl Compressor {
             }
 
             if attr.name == "MIC_COMP_ATTACK" {
-                let value = attr.value.parse::<c_float>()?;
+                let value = parse_float(&attr.value)?;
                 if value > 19. {
                     continue;
                 }
@@ -69,7 +68,7 @@ impl Compressor {
             }
 
             if attr.name == "MIC_COMP_RELEASE" {
-                let value = attr.value.parse::<c_float>()?;
+                let value = parse_float(&attr.value)?;
                 if value > 19. {
                     continue;
                 }
@@ -78,7 +77,7 @@ impl Compressor {
             }
 
             if attr.name == "MIC_COMP_MAKEUPGAIN" {
-                self.set_makeup_gain(attr.value.parse::<c_float>().unwrap_or(0.) as i8)?;
+                self.set_makeup_gain(parse_float(&attr.value).unwrap_or(0.) as i8)?;
                 continue;
             }
         }
@@ -86,7 +85,7 @@ impl Compressor {
         Ok(())
     }
 
-    pub fn write_compressor(&self, attributes: &mut HashMap<String, String>) {
+    pub fn write_compressor(&self, attributes: &mut LinkedHashMap<String, String>) {
         attributes.insert(
             "MIC_COMP_THRESHOLD".to_string(),
             format!("{}", self.threshold),
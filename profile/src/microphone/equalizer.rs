@@ -1,8 +1,7 @@
-use std::collections::HashMap;
-use std::os::raw::c_float;
+use ritelinked::LinkedHashMap;
 use std::str::FromStr;
 
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{anyhow, bail, Result};
 
 #[derive(thiserror::Error, Debug)]
@@ -80,43 +79,43 @@ impl Equalizer {
     pub fn parse_equaliser(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "MIC_EQ_31.5HZ_GAIN" {
-                self.set_eq_31h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_31h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_63HZ_GAIN" {
-                self.set_eq_63h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_63h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_125HZ_GAIN" {
-                self.set_eq_125h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_125h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_250HZ_GAIN" {
-                self.set_eq_250h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_250h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_500HZ_GAIN" {
-                self.set_eq_500h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_500h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_1KHZ_GAIN" {
-                self.set_eq_1k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_1k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_2KHZ_GAIN" {
-                self.set_eq_2k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_2k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_4KHZ_GAIN" {
-                self.set_eq_4k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_4k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_8KHZ_GAIN" {
-                self.set_eq_8k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_8k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_16KHZ_GAIN" {
-                self.set_eq_16k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_16k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_EQ_31.5HZ_F" {
@@ -163,7 +162,7 @@ impl Equalizer {
         Ok(())
     }
 
-    pub fn write_equaliser(&self, attributes: &mut HashMap<String, String>) {
+    pub fn write_equaliser(&self, attributes: &mut LinkedHashMap<String, String>) {
         attributes.insert(
             "MIC_EQ_31.5HZ_GAIN".to_string(),
             format!("{}", self.eq_31h_gain),
@@ -1,8 +1,7 @@
 use crate::microphone::equalizer::validate_gain;
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{anyhow, Result};
-use std::collections::HashMap;
-use std::os::raw::c_float;
+use ritelinked::LinkedHashMap;
 use std::str::FromStr;
 
 #[derive(thiserror::Error, Debug)]
@@ -66,27 +65,27 @@ impl EqualizerMini {
     pub fn parse_equaliser(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "MIC_MINI_EQ_90HZ_GAIN" {
-                self.set_eq_90h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_90h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_250HZ_GAIN" {
-                self.set_eq_250h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_250h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_500HZ_GAIN" {
-                self.set_eq_500h_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_500h_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_1KHZ_GAIN" {
-                self.set_eq_1k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_1k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_3KHZ_GAIN" {
-                self.set_eq_3k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_3k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_8KHZ_GAIN" {
-                self.set_eq_8k_gain(attr.value.parse::<c_float>()? as i8)?;
+                self.set_eq_8k_gain(parse_float(&attr.value)? as i8)?;
             }
 
             if attr.name == "MIC_MINI_EQ_90HZ_F" {
@@ -117,7 +116,7 @@ impl EqualizerMini {
         Ok(())
     }
 
-    pub fn write_equaliser(&self, attributes: &mut HashMap<String, String>) {
+    pub fn write_equaliser(&self, attributes: &mut LinkedHashMap<String, String>) {
         attributes.insert(
             "MIC_MINI_EQ_90HZ_GAIN".to_string(),
             format!("{}", self.eq_90h_gain),
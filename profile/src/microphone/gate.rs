@@ -1,7 +1,6 @@
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{anyhow, Result};
-use std::collections::HashMap;
-use std::os::raw::c_float;
+use ritelinked::LinkedHashMap;
 
 #[derive(thiserror::Error, Debug)]
 #[allow(clippy::enum_variant_names)]
@@ -47,17 +46,17 @@ impl Gate {
     pub fn parse_gate(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "MIC_GATE_MACRO_AMOUNT" {
-                self.amount = attr.value.parse::<c_float>()? as u8;
+                self.amount = parse_float(&attr.value)? as u8;
                 continue;
             }
 
             if attr.name == "MIC_GATE_THRESOLD" {
-                self.set_threshold(attr.value.parse::<c_float>()? as i8)?;
+                self.set_threshold(parse_float(&attr.value)? as i8)?;
                 continue;
             }
 
             if attr.name == "MIC_GATE_ATTACK" {
-                let value = attr.value.parse::<c_float>()?;
+                let value = parse_float(&attr.value)?;
                 if value > 45. {
                     // If the value is out of range, use the default.
                     continue;
@@ -67,7 +66,7 @@ impl Gate {
             }
 
             if attr.name == "MIC_GATE_RELEASE" {
-                let value = attr.value.parse::<c_float>()?;
+                let value = parse_float(&attr.value)?;
                 if value > 45. {
                     continue;
                 }
@@ -77,7 +76,7 @@ impl Gate {
 
             // Read and handle as a percentage.
             if attr.name == "MIC_GATE_ATTEN" {
-                self.set_attenuation(attr.value.parse::<c_float>()? as u8)?;
+                self.set_attenuation(parse_float(&attr.value)? as u8)?;
                 continue;
             }
 
@@ -94,7 +93,7 @@ impl Gate {
         Ok(())
     }
 
-    pub fn write_gate(&self, attributes: &mut HashMap<String, String>) {
+    pub fn write_gate(&self, attributes: &mut LinkedHashMap<String, String>) {
         attributes.insert(
             "MIC_GATE_MACRO_AMOUNT".to_string(),
             format!("{}", self.amount),
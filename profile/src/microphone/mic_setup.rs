@@ -1,9 +1,8 @@
-use crate::profile::Attribute;
+use crate::profile::{parse_float, Attribute};
 use anyhow::{anyhow, Result};
 use quick_xml::events::{BytesStart, Event};
 use quick_xml::Writer;
-use std::collections::HashMap;
-use std::ffi::c_float;
+use ritelinked::LinkedHashMap;
 use std::io::Write;
 
 #[derive(thiserror::Error, Debug)]
@@ -48,24 +47,22 @@ impl MicSetup {
     pub fn parse_config(&mut self, attributes: &Vec<Attribute>) -> Result<(), ParseError> {
         for attr in attributes {
             if attr.name == "MIC_TYPE" {
-                self.set_mic_type(attr.value.parse::<c_float>()? as u8)?;
+                self.set_mic_type(parse_float(&attr.value)? as u8)?;
                 continue;
             }
 
             if attr.name == "DYNAMIC_MIC_GAIN" {
-                self.set_dynamic_mic_gain((attr.value.parse::<c_float>()? as u32 / 65536) as u16)?;
+                self.set_dynamic_mic_gain((parse_float(&attr.value)? as u32 / 65536) as u16)?;
                 continue;
             }
 
             if attr.name == "CONDENSER_MIC_GAIN" {
-                self.set_condenser_mic_gain(
-                    (attr.value.parse::<c_float>()? as u32 / 65536) as u16,
-                )?;
+                self.set_condenser_mic_gain((parse_float(&attr.value)? as u32 / 65536) as u16)?;
                 continue;
             }
 
             if attr.name == "TRS_MIC_GAIN" {
-                self.set_trs_mic_gain((attr.value.parse::<c_float>()? as u32 / 65536) as u16)?;
+                self.set_trs_mic_gain((parse_float(&attr.value)? as u32 / 65536) as u16)?;
                 continue;
             }
         }
@@ -76,7 +73,7 @@ impl MicSetup {
     pub fn write_config<W: Write>(&self, writer: &mut Writer<W>) -> Result<()> {
         let mut elem = BytesStart::new("setupTreeMicProfile");
 
-        let mut attributes: HashMap<String, String> = HashMap::default();
+        let mut attributes: LinkedHashMap<String, String> = LinkedHashMap::default();
         attributes.insert("MIC_TYPE".to_string(), format!("{}", self.mic_type));
         attributes.insert(
             "DYNAMIC_MIC_GAIN".to_string(),
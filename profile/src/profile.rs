@@ -1,6 +1,8 @@
 use std::fs;
 use std::fs::File;
-use std::io::{BufReader, Read, Write};
+use std::io::{BufReader, Read, Seek, Write};
+use std::num::ParseFloatError;
+use std::os::raw::c_float;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -15,10 +17,13 @@ use zip::write::SimpleFileOptions;
 
 use crate::components::animation::AnimationTree;
 use crate::components::browser::BrowserPreviewTree;
+use crate::components::colours::{ColourMap, ColourState};
 use crate::components::context::Context;
 use crate::components::echo::EchoEncoderBase;
 use crate::components::effects::Effects;
+use crate::components::encoder_sensitivity::EncoderSensitivity;
 use crate::components::fader::Fader;
+use crate::components::fader_pages::{FaderPage, FaderPageSlot, FaderPages};
 use crate::components::gender::GenderEncoderBase;
 use crate::components::hardtune::HardtuneEffectBase;
 use crate::components::megaphone::MegaphoneEffectBase;
@@ -32,11 +37,14 @@ use crate::components::robot::RobotEffectBase;
 use crate::components::root::RootElement;
 use crate::components::sample::SampleBase;
 use crate::components::scribble::Scribble;
+use crate::components::sequence::LightingSequence;
 use crate::components::simple::{SimpleElement, SimpleElements};
 use crate::components::submix::mix_routing_tree::{Mix, MixRoutingTree};
 use crate::components::submix::submixer::SubMixer;
+use crate::components::unknown::UnknownElement;
+use crate::structure::ProfileStructure;
 use crate::SampleButtons::{BottomLeft, BottomRight, Clear, TopLeft, TopRight};
-use crate::{Faders, Preset, SampleButtons};
+use crate::{AppVersion, Faders, Preset, SampleButtons};
 
 #[derive(Debug)]
 pub struct Profile {
@@ -51,7 +59,7 @@ pub struct Attribute {
 }
 
 impl Profile {
-    pub fn load<R: Read + std::io::Seek>(read: R) -> Result<Self> {
+    pub fn load<R: Read + Seek>(read: R) -> Result<Self> {
         debug!("Loading Profile Archive..");
 
         let mut archive = zip::ZipArchive::new(read)?;
@@ -92,24 +100,7 @@ impl Profile {
 
         debug!("Creating Temporary Save File: {:?}", &tmp_file_name);
         let temp_file = File::create(&tmp_file_name)?;
-
-        // Create a new ZipFile at the requested location
-        let mut archive = zip::ZipWriter::new(&temp_file);
-
-        // Store the profile..
-        archive.start_file("profile.xml", SimpleFileOptions::default())?;
-        self.settings.write_to(&mut archive)?;
-
-        // Write the scribbles..
-        for (i, scribble) in self.scribbles.iter().enumerate() {
-            // Only write if there's actually data stored..
-            if !self.scribbles[i].is_empty() {
-                let filename = format!("scribble{}.png", i + 1);
-                archive.start_file(filename, SimpleFileOptions::default())?;
-                archive.write_all(scribble)?;
-            }
-        }
-        archive.finish()?;
+        self.write_to(&temp_file)?;
 
         // The archive has finished writing, we don't need it anymore (keeping it live prevents
         // us from removing the temporary file).
@@ -128,11 +119,52 @@ impl Profile {
         Ok(())
     }
 
+    /// Writes the profile archive (settings and scribbles) to any seekable sink.
+    pub fn write_to<W: Write + Seek>(&mut self, sink: W) -> Result<()> {
+        self.write_compatible_to(sink, AppVersion::Current)
+    }
+
+    /// Exports the profile in a form that the given version of the official app can load,
+    /// useful for moving profiles back to a Windows install.
+    pub fn export_compatible(&mut self, path: impl AsRef<Path>, version: AppVersion) -> Result<()> {
+        let file = File::create(path)?;
+        self.write_compatible_to(&file, version)?;
+        file.sync_all().context("Unable to Sync File")
+    }
+
+    pub fn write_compatible_to<W: Write + Seek>(
+        &mut self,
+        sink: W,
+        version: AppVersion,
+    ) -> Result<()> {
+        let mut archive = zip::ZipWriter::new(sink);
+
+        // Store the profile..
+        archive.start_file("profile.xml", SimpleFileOptions::default())?;
+        self.settings.write_compatible_to(&mut archive, version)?;
+
+        // Write the scribbles..
+        for (i, scribble) in self.scribbles.iter().enumerate() {
+            // Only write if there's actually data stored..
+            if !self.scribbles[i].is_empty() {
+                let filename = format!("scribble{}.png", i + 1);
+                archive.start_file(filename, SimpleFileOptions::default())?;
+                archive.write_all(scribble)?;
+            }
+        }
+        archive.finish()?;
+        Ok(())
+    }
+
     pub fn save_preset(&self, path: impl AsRef<Path>) -> Result<()> {
         self.settings.write_preset(path)?;
         Ok(())
     }
 
+    pub fn save_preset_to<W: Write>(&self, sink: W) -> Result<()> {
+        self.settings.write_preset_to(sink)
+    }
+
     pub fn settings(&self) -> &ProfileSettings {
         &self.settings
     }
@@ -144,6 +176,31 @@ impl Profile {
     pub fn get_scribble(&self, id: usize) -> &Vec<u8> {
         &self.scribbles[id]
     }
+
+    /// Replaces the stored image for a scribble, an empty Vec removes it from the archive.
+    pub fn set_scribble(&mut self, id: usize, png: Vec<u8>) {
+        self.scribbles[id] = png;
+    }
+
+    pub fn into_settings(self) -> ProfileSettings {
+        self.settings
+    }
+}
+
+/// The parts of another profile to take when merging, see ProfileSettings::merge()
+#[derive(Debug, Default, Copy, Clone)]
+pub struct MergeSelection {
+    /// Every colour, and the animation settings
+    pub lighting: bool,
+
+    /// Volumes, routing and submixes
+    pub mixer: bool,
+
+    /// The effect presets, and the current state of the effect encoders
+    pub effects: bool,
+
+    /// Sample assignments and playback settings
+    pub sampler: bool,
 }
 
 #[derive(Debug)]
@@ -151,6 +208,8 @@ pub struct ProfileSettings {
     root: RootElement,
     browser: BrowserPreviewTree,
     animation_tree: AnimationTree,
+    startup_sequence: Option<LightingSequence>,
+    shutdown_sequence: Option<LightingSequence>,
     mix_routing: MixRoutingTree,
     submix_tree: SubMixer,
     mixer: Mixers,
@@ -160,6 +219,7 @@ pub struct ProfileSettings {
     faders: EnumMap<Faders, Fader>,
     mute_buttons: EnumMap<Faders, MuteButton>,
     scribbles: EnumMap<Faders, Scribble>,
+    fader_pages: FaderPages,
 
     sampler_map: EnumMap<SampleButtons, SampleBase>,
     simple_elements: EnumMap<SimpleElements, SimpleElement>,
@@ -172,6 +232,10 @@ pub struct ProfileSettings {
     echo_encoder: EchoEncoderBase,
     pitch_encoder: PitchEncoderBase,
     gender_encoder: GenderEncoderBase,
+    encoder_sensitivity: EncoderSensitivity,
+
+    // Elements under the root which we don't know about, written back as they were found
+    unknown_elements: Vec<UnknownElement>,
 }
 
 impl ProfileSettings {
@@ -186,6 +250,9 @@ impl ProfileSettings {
         let mut browser = BrowserPreviewTree::new("browserPreviewTree".to_string());
 
         let mut animation_tree = AnimationTree::new("animationTree".to_string());
+        let mut startup_sequence = None;
+        let mut shutdown_sequence = None;
+        let mut encoder_sensitivity = EncoderSensitivity::new("encoderSensitivity".to_string());
 
         let mut mix_routing = MixRoutingTree::new();
         let mut submix_tree = SubMixer::new();
@@ -193,6 +260,7 @@ impl ProfileSettings {
         let mut mixer = Mixers::new();
         let mut context = Context::new("selectedContext".to_string());
         let mut mute_chat = MuteChat::new("muteChat".to_string());
+        let mut fader_pages = FaderPages::default();
 
         let mut faders = enum_map! {
             Faders::A => Fader::new(Faders::A),
@@ -253,8 +321,11 @@ impl ProfileSettings {
             Clear => SampleBase::new(Clear),
         };
 
-        // This value isn't stored in the struct.
+        let mut unknown_elements = vec![];
+
+        // These values aren't stored in the struct.
         let mut active_sample_button: Option<&mut SampleBase> = None;
+        let mut depth = 0;
 
         let mut buf = Vec::new();
         loop {
@@ -272,11 +343,33 @@ impl ProfileSettings {
                         continue;
                     }
 
+                    if name == "startupSequence" || name == "shutdownSequence" {
+                        let mut sequence = LightingSequence::new(name.clone());
+                        sequence.parse_sequence(&attributes)?;
+
+                        if name == "startupSequence" {
+                            startup_sequence = Some(sequence);
+                        } else {
+                            shutdown_sequence = Some(sequence);
+                        }
+                        continue;
+                    }
+
+                    if name == "encoderSensitivity" {
+                        encoder_sensitivity.parse_sensitivity(&attributes)?;
+                        continue;
+                    }
+
                     if name == "mixRoutingTree" {
                         mix_routing.parse_mix_tree(&attributes)?;
                         continue;
                     }
 
+                    if name == "faderPage" {
+                        fader_pages.parse_page(&attributes)?;
+                        continue;
+                    }
+
                     if name == "monitorTree" {
                         submix_tree.parse_monitor(&attributes)?;
                         continue;
@@ -420,12 +513,19 @@ impl ProfileSettings {
                         continue;
                     }
 
+                    if depth == 1 {
+                        debug!("Storing Unknown Tag: {}", name);
+                        unknown_elements.push(UnknownElement::new(name, &attributes));
+                        continue;
+                    }
+
                     warn!("Unhandled Tag: {}", name);
                 }
 
                 // Represents a tag which has children
                 Ok(Event::Start(ref e)) => {
                     let (name, attributes) = wrap_start_event(e)?;
+                    depth += 1;
 
                     if name == "ValueTreeRoot" {
                         // This also handles <AppTree, due to a single shared value.
@@ -508,10 +608,25 @@ impl ProfileSettings {
                         active_sample_button = Some(&mut sampler_map[Clear]);
                         continue;
                     }
+
+                    // We don't know what this is, so read the whole thing (including the
+                    // end tag) and hold onto it..
+                    let mut element = UnknownElement::new(name, &attributes);
+                    element.read_children(&mut reader)?;
+                    depth -= 1;
+
+                    if depth == 1 {
+                        debug!("Storing Unknown Tag: {}", element.name());
+                        unknown_elements.push(element);
+                    } else {
+                        warn!("Unhandled Tag: {}", element.name());
+                    }
                 }
 
                 // Ends a tag with children
-                Ok(Event::End(_)) => {}
+                Ok(Event::End(_)) => {
+                    depth -= 1;
+                }
                 Ok(Event::Eof) => {
                     break;
                 }
@@ -529,6 +644,8 @@ impl ProfileSettings {
             root,
             browser,
             animation_tree,
+            startup_sequence,
+            shutdown_sequence,
             mix_routing,
             submix_tree,
             mixer,
@@ -537,6 +654,7 @@ impl ProfileSettings {
             faders,
             mute_buttons,
             scribbles,
+            fader_pages,
             effects,
             sampler_map,
             simple_elements,
@@ -547,6 +665,8 @@ impl ProfileSettings {
             echo_encoder,
             pitch_encoder,
             gender_encoder,
+            encoder_sensitivity,
+            unknown_elements,
         })
     }
 
@@ -629,7 +749,27 @@ impl ProfileSettings {
         self.write_to(out_file)
     }
 
+    /// Returns the structure of the XML these settings would be saved as.
+    pub fn structure(&mut self) -> Result<ProfileStructure> {
+        let mut output = Vec::new();
+        self.write_to(&mut output)?;
+        ProfileStructure::from_xml(output.as_slice())
+    }
+
+    /// Compares the saved form of two profiles, returning a description of each difference.
+    pub fn compare(&mut self, other: &mut ProfileSettings) -> Result<Vec<String>> {
+        Ok(self.structure()?.differences(&other.structure()?))
+    }
+
     pub fn write_to<W: Write>(&mut self, sink: W) -> Result<()> {
+        self.write_compatible_to(sink, AppVersion::Current)
+    }
+
+    /// Writes the settings for a specific version of the official app. Anything the target
+    /// version doesn't know about (Animations, Submixes, our own extra attributes) is left out,
+    /// attributes and elements we didn't recognise when loading are written back untouched.
+    pub fn write_compatible_to<W: Write>(&mut self, sink: W, version: AppVersion) -> Result<()> {
+        let include_extensions = version == AppVersion::Current;
         let mut writer = Writer::new_with_indent(sink, u8::try_from('\t')?, 1);
         writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
 
@@ -656,15 +796,31 @@ impl ProfileSettings {
 
         self.root.write_initial(&mut writer)?;
         self.browser.write_browser(&mut writer)?;
-        self.animation_tree.write_animation(&mut writer)?;
+        if version >= AppVersion::PreSubmix {
+            self.animation_tree
+                .write_animation(&mut writer, include_extensions)?;
+        }
 
-        self.mix_routing.write_mix_tree(&mut writer)?;
-        self.submix_tree.write_submixer(&mut writer)?;
+        if version == AppVersion::Current {
+            for sequence in [&self.startup_sequence, &self.shutdown_sequence]
+                .into_iter()
+                .flatten()
+            {
+                sequence.write_sequence(&mut writer)?;
+            }
+            self.encoder_sensitivity.write_sensitivity(&mut writer)?;
+        }
+
+        if version >= AppVersion::Release {
+            self.mix_routing.write_mix_tree(&mut writer)?;
+            self.submix_tree.write_submixer(&mut writer)?;
+        }
 
         self.mixer.write_mixers(&mut writer)?;
         self.context.write_context(&mut writer)?;
 
-        self.mute_chat.write_mute_chat(&mut writer)?;
+        self.mute_chat
+            .write_mute_chat(&mut writer, include_extensions)?;
 
         // The following three iters need the FaderName to be defined and passed
         // forward, the tag names need to be dynamically generated to prevent breakage
@@ -674,11 +830,15 @@ impl ProfileSettings {
         }
 
         for (fader, button) in &self.mute_buttons {
-            button.write_button(&mut writer, fader)?;
+            button.write_button(&mut writer, fader, include_extensions)?;
         }
 
         for (fader, scribble) in &self.scribbles {
-            scribble.write_scribble(&mut writer, fader)?;
+            scribble.write_scribble(&mut writer, fader, include_extensions)?;
+        }
+
+        if version == AppVersion::Current {
+            self.fader_pages.write_pages(&mut writer)?;
         }
 
         for effect in self.effects.values() {
@@ -702,6 +862,10 @@ impl ProfileSettings {
             element.write_simple(&mut writer)?;
         }
 
+        for element in &self.unknown_elements {
+            element.write(&mut writer)?;
+        }
+
         // Finalise the XML..
         self.root.write_final(&mut writer)?;
 
@@ -795,6 +959,116 @@ impl ProfileSettings {
         Err(anyhow!("Unable to Parse Preset from Number"))
     }
 
+    /// Replaces the selected parts of this profile with those from another
+    pub fn merge(&mut self, mut other: ProfileSettings, selection: MergeSelection) {
+        // The effects and sampler carry their own colours, so settle the colours first, if we're
+        // keeping ours they're pushed into the other profile, to come back with those parts..
+        if selection.lighting {
+            for (ours, theirs) in self.colour_maps_mut().into_iter().zip(other.colour_maps()) {
+                ours.replace(theirs);
+            }
+            self.animation_tree.replace(&other.animation_tree);
+            std::mem::swap(&mut self.startup_sequence, &mut other.startup_sequence);
+            std::mem::swap(&mut self.shutdown_sequence, &mut other.shutdown_sequence);
+        } else {
+            for (ours, theirs) in self.colour_maps().into_iter().zip(other.colour_maps_mut()) {
+                theirs.replace(ours);
+            }
+        }
+
+        if selection.mixer {
+            std::mem::swap(&mut self.mixer, &mut other.mixer);
+            std::mem::swap(&mut self.mix_routing, &mut other.mix_routing);
+            std::mem::swap(&mut self.submix_tree, &mut other.submix_tree);
+        }
+
+        if selection.effects {
+            std::mem::swap(&mut self.effects, &mut other.effects);
+            std::mem::swap(&mut self.megaphone_effect, &mut other.megaphone_effect);
+            std::mem::swap(&mut self.robot_effect, &mut other.robot_effect);
+            std::mem::swap(&mut self.hardtune_effect, &mut other.hardtune_effect);
+            std::mem::swap(&mut self.reverb_encoder, &mut other.reverb_encoder);
+            std::mem::swap(&mut self.echo_encoder, &mut other.echo_encoder);
+            std::mem::swap(&mut self.pitch_encoder, &mut other.pitch_encoder);
+            std::mem::swap(&mut self.gender_encoder, &mut other.gender_encoder);
+            std::mem::swap(
+                &mut self.encoder_sensitivity,
+                &mut other.encoder_sensitivity,
+            );
+        }
+
+        if selection.sampler {
+            std::mem::swap(&mut self.sampler_map, &mut other.sampler_map);
+        }
+    }
+
+    // Every colour map in the profile, colour_maps_mut() must return them in the same order..
+    fn colour_maps(&self) -> Vec<&ColourMap> {
+        let mut maps = vec![];
+        maps.extend(self.faders.values().map(|fader| fader.colour_map()));
+        maps.extend(self.mute_buttons.values().map(|mute| mute.colour_map()));
+        maps.extend(
+            self.scribbles
+                .values()
+                .map(|scribble| scribble.colour_map()),
+        );
+        maps.extend(self.effects.values().map(|effects| effects.colour_map()));
+        maps.extend(self.sampler_map.values().map(|sample| sample.colour_map()));
+        maps.extend(
+            self.simple_elements
+                .values()
+                .map(|simple| simple.colour_map()),
+        );
+        maps.push(self.mute_chat.colour_map());
+        maps.push(self.megaphone_effect.colour_map());
+        maps.push(self.robot_effect.colour_map());
+        maps.push(self.hardtune_effect.colour_map());
+        maps.push(self.reverb_encoder.colour_map());
+        maps.push(self.echo_encoder.colour_map());
+        maps.push(self.pitch_encoder.colour_map());
+        maps.push(self.gender_encoder.colour_map());
+        maps
+    }
+
+    fn colour_maps_mut(&mut self) -> Vec<&mut ColourMap> {
+        let mut maps = vec![];
+        maps.extend(self.faders.values_mut().map(|fader| fader.colour_map_mut()));
+        maps.extend(
+            self.mute_buttons
+                .values_mut()
+                .map(|mute| mute.colour_map_mut()),
+        );
+        maps.extend(
+            self.scribbles
+                .values_mut()
+                .map(|scribble| scribble.colour_map_mut()),
+        );
+        maps.extend(
+            self.effects
+                .values_mut()
+                .map(|effects| effects.colour_map_mut()),
+        );
+        maps.extend(
+            self.sampler_map
+                .values_mut()
+                .map(|sample| sample.colour_map_mut()),
+        );
+        maps.extend(
+            self.simple_elements
+                .values_mut()
+                .map(|simple| simple.colour_map_mut()),
+        );
+        maps.push(self.mute_chat.colour_map_mut());
+        maps.push(self.megaphone_effect.colour_map_mut());
+        maps.push(self.robot_effect.colour_map_mut());
+        maps.push(self.hardtune_effect.colour_map_mut());
+        maps.push(self.reverb_encoder.colour_map_mut());
+        maps.push(self.echo_encoder.colour_map_mut());
+        maps.push(self.pitch_encoder.colour_map_mut());
+        maps.push(self.gender_encoder.colour_map_mut());
+        maps
+    }
+
     pub fn animation(&self) -> &AnimationTree {
         &self.animation_tree
     }
@@ -803,6 +1077,22 @@ impl ProfileSettings {
         &mut self.animation_tree
     }
 
+    pub fn startup_sequence(&self) -> Option<&LightingSequence> {
+        self.startup_sequence.as_ref()
+    }
+
+    pub fn shutdown_sequence(&self) -> Option<&LightingSequence> {
+        self.shutdown_sequence.as_ref()
+    }
+
+    pub fn encoder_sensitivity(&self) -> &EncoderSensitivity {
+        &self.encoder_sensitivity
+    }
+
+    pub fn encoder_sensitivity_mut(&mut self) -> &mut EncoderSensitivity {
+        &mut self.encoder_sensitivity
+    }
+
     pub fn mixer_mut(&mut self) -> &mut Mixers {
         &mut self.mixer
     }
@@ -843,6 +1133,58 @@ impl ProfileSettings {
         &self.scribbles[fader]
     }
 
+    pub fn fader_pages(&self) -> &FaderPages {
+        &self.fader_pages
+    }
+
+    pub fn fader_pages_mut(&mut self) -> &mut FaderPages {
+        &mut self.fader_pages
+    }
+
+    /// Builds a page from the current fader assignments, scribbles and mute states
+    pub fn capture_fader_page(&self, name: String) -> FaderPage {
+        let mut page = FaderPage::new(name);
+        for fader in Faders::iter() {
+            let mute_button = &self.mute_buttons[fader];
+            let scribble = &self.scribbles[fader];
+
+            page.set_slot(
+                fader,
+                FaderPageSlot {
+                    channel: self.faders[fader].channel(),
+                    mute_function: *mute_button.mute_function(),
+                    muted_to_x: mute_button.colour_map().state() == &Some(ColourState::On),
+                    muted_to_all: mute_button.colour_map().is_blink(),
+                    scribble_icon: scribble.icon_file(),
+                    scribble_text: scribble.text_bottom_middle().unwrap_or_default(),
+                    scribble_number: scribble.text_top_left().unwrap_or_default(),
+                    scribble_inverted: scribble.is_style_invert(),
+                },
+            );
+        }
+        page
+    }
+
+    /// Replaces the current fader assignments, scribbles and mute states with those of a page
+    pub fn apply_fader_page(&mut self, page: &FaderPage) {
+        for fader in Faders::iter() {
+            let slot = page.slot(fader).clone();
+
+            self.faders[fader].set_channel(slot.channel);
+
+            let mute_button = &mut self.mute_buttons[fader];
+            mute_button.set_mute_function(slot.mute_function);
+            mute_button.colour_map_mut().set_state_on(slot.muted_to_x);
+            mute_button.colour_map_mut().set_blink_on(slot.muted_to_all);
+
+            let scribble = &mut self.scribbles[fader];
+            scribble.set_icon_file(slot.scribble_icon);
+            scribble.set_text_bottom_middle(slot.scribble_text);
+            scribble.set_text_top_left(slot.scribble_number);
+            scribble.set_scribble_inverted(slot.scribble_inverted);
+        }
+    }
+
     pub fn scribble_mut(&mut self, fader: Faders) -> &mut Scribble {
         &mut self.scribbles[fader]
     }
@@ -958,6 +1300,20 @@ impl ProfileSettings {
     }
 }
 
+/// Parses a numeric attribute value. Profiles saved on systems which use a comma as the
+/// decimal separator can end up with values like "1,5", so we accept those as well. Values are
+/// always written back out with a '.', so the profile will be normalised on the next save.
+pub(crate) fn parse_float(value: &str) -> std::result::Result<c_float, ParseFloatError> {
+    if value.contains(',') && !value.contains('.') {
+        let corrected = value.replace(',', ".");
+        if let Ok(parsed) = corrected.parse::<c_float>() {
+            debug!("Corrected Numeric Value {} to {}", value, corrected);
+            return Ok(parsed);
+        }
+    }
+    value.parse::<c_float>()
+}
+
 /// This will wrap a 'Start' XML event into a name, and attribute Vec. We're using
 /// our own Attribute Struct here to allow easy moving between XML libraries in future.
 /// TODO: If we're doing this, we might as well make the attributes a HashMap
@@ -980,3 +1336,24 @@ pub(crate) fn wrap_start_event(event: &BytesStart) -> Result<(String, Vec<Attrib
     }
     Ok((name, attributes))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_float_reads_a_decimal_point() {
+        assert_eq!(parse_float("1.5"), Ok(1.5));
+    }
+
+    #[test]
+    fn parse_float_reads_a_decimal_comma() {
+        assert_eq!(parse_float("1,5"), Ok(1.5));
+    }
+
+    #[test]
+    fn parse_float_rejects_a_thousands_separator() {
+        // With a '.' present the comma can't be a decimal separator, so it's left alone..
+        assert!(parse_float("1,000.5").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+/*
+   A flattened view of a profile's XML, used to check two profiles against each other without
+   caring about formatting, attribute order, or the order of differently named elements (none of
+   which the official app cares about either). Elements sharing a name are compared in order.
+*/
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufReader, Read, Write};
+
+use anyhow::{bail, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+
+use crate::profile::{wrap_start_event, Attribute};
+
+type Attributes = BTreeMap<String, String>;
+
+/// An attribute which has a new value in one structure compared to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeChange {
+    pub path: String,
+    pub index: usize,
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ProfileStructure {
+    // Keyed by the path to the element, eg. ValueTreeRoot/reverbEncoder/reverbEncoderpreset1
+    elements: BTreeMap<String, Vec<Attributes>>,
+}
+
+impl ProfileStructure {
+    pub fn from_xml<R: Read>(read: R) -> Result<Self> {
+        let mut reader = Reader::from_reader(BufReader::new(read));
+        let mut structure = Self::default();
+        let mut path: Vec<String> = vec![];
+
+        let mut buf = Vec::new();
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) => {
+                    let (name, attributes) = wrap_start_event(e)?;
+                    path.push(name);
+                    structure.add(&path, attributes);
+                }
+                Ok(Event::Empty(ref e)) => {
+                    let (name, attributes) = wrap_start_event(e)?;
+                    path.push(name);
+                    structure.add(&path, attributes);
+                    path.pop();
+                }
+                Ok(Event::End(_)) => {
+                    path.pop();
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => bail!("Error Parsing XML: {}", e),
+            }
+            buf.clear();
+        }
+
+        Ok(structure)
+    }
+
+    fn add(&mut self, path: &[String], attributes: Vec<Attribute>) {
+        let attributes = attributes.into_iter().map(|a| (a.name, a.value)).collect();
+        self.elements
+            .entry(path.join("/"))
+            .or_default()
+            .push(attributes);
+    }
+
+    /// Returns a description of every difference between this structure and `other`, an empty
+    /// list means they're equal.
+    pub fn differences(&self, other: &Self) -> Vec<String> {
+        let mut differences = self.missing_from(other);
+
+        for (path, elements) in &other.elements {
+            let ours = self.elements.get(path).map(Vec::as_slice).unwrap_or(&[]);
+            for (index, attributes) in elements.iter().enumerate() {
+                let Some(our_attributes) = ours.get(index) else {
+                    differences.push(format!("Element {}[{}] was added", path, index));
+                    continue;
+                };
+
+                for (name, value) in attributes {
+                    match our_attributes.get(name) {
+                        None => differences
+                            .push(format!("Attribute {}[{}]@{} was added", path, index, name)),
+                        Some(our_value) if our_value != value => differences.push(format!(
+                            "Attribute {}[{}]@{} changed from '{}' to '{}'",
+                            path, index, name, our_value, value
+                        )),
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        differences
+    }
+
+    /// Returns the elements and attributes in this structure which aren't present in `other`,
+    /// this ignores values, so is useful for checking nothing gets lost during a save.
+    pub fn missing_from(&self, other: &Self) -> Vec<String> {
+        let mut missing = vec![];
+
+        for (path, elements) in &self.elements {
+            let theirs = other.elements.get(path).map(Vec::as_slice).unwrap_or(&[]);
+            for (index, attributes) in elements.iter().enumerate() {
+                let Some(their_attributes) = theirs.get(index) else {
+                    missing.push(format!("Element {}[{}] is missing", path, index));
+                    continue;
+                };
+
+                for name in attributes.keys() {
+                    if !their_attributes.contains_key(name) {
+                        missing.push(format!("Attribute {}[{}]@{} is missing", path, index, name));
+                    }
+                }
+            }
+        }
+
+        missing
+    }
+
+    /// Returns every attribute in this structure which is new, or has a different value, when
+    /// compared to `base`.
+    pub fn changes_from(&self, base: &Self) -> Vec<AttributeChange> {
+        let mut changes = vec![];
+
+        for (path, elements) in &self.elements {
+            let theirs = base.elements.get(path).map(Vec::as_slice).unwrap_or(&[]);
+            for (index, attributes) in elements.iter().enumerate() {
+                let their_attributes = theirs.get(index);
+                for (name, value) in attributes {
+                    if their_attributes.and_then(|a| a.get(name)) != Some(value) {
+                        changes.push(AttributeChange {
+                            path: path.clone(),
+                            index,
+                            name: name.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        changes
+    }
+}
+
+/// Copies the profile XML from `read` to `write`, replacing (or adding) any attributes listed in
+/// `changes`. Changes for elements which don't exist in the XML are ignored.
+pub fn apply_changes<R: Read, W: Write>(
+    read: R,
+    write: W,
+    changes: &[AttributeChange],
+) -> Result<()> {
+    let mut reader = Reader::from_reader(BufReader::new(read));
+    let mut writer = Writer::new(write);
+
+    let mut path: Vec<String> = vec![];
+    let mut seen: HashMap<String, usize> = HashMap::new();
+
+    let mut buf = Vec::new();
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                let elem = apply_element_changes(e, &mut path, &mut seen, changes)?;
+                writer.write_event(Event::Start(elem))?;
+            }
+            Ok(Event::Empty(ref e)) => {
+                let elem = apply_element_changes(e, &mut path, &mut seen, changes)?;
+                writer.write_event(Event::Empty(elem))?;
+                path.pop();
+            }
+            Ok(Event::End(e)) => {
+                path.pop();
+                writer.write_event(Event::End(e))?;
+            }
+            Ok(Event::Eof) => break,
+            Ok(event) => writer.write_event(event)?,
+            Err(e) => bail!("Error Parsing XML: {}", e),
+        }
+        buf.clear();
+    }
+
+    Ok(())
+}
+
+fn apply_element_changes(
+    event: &BytesStart,
+    path: &mut Vec<String>,
+    seen: &mut HashMap<String, usize>,
+    changes: &[AttributeChange],
+) -> Result<BytesStart<'static>> {
+    let (name, attributes) = wrap_start_event(event)?;
+    path.push(name.clone());
+
+    let key = path.join("/");
+    let index = *seen.entry(key.clone()).and_modify(|i| *i += 1).or_insert(0);
+
+    let mut values: Vec<(String, String)> =
+        attributes.into_iter().map(|a| (a.name, a.value)).collect();
+    for change in changes.iter().filter(|c| c.path == key && c.index == index) {
+        match values.iter_mut().find(|(name, _)| name == &change.name) {
+            Some((_, value)) => *value = change.value.clone(),
+            None => values.push((change.name.clone(), change.value.clone())),
+        }
+    }
+
+    let mut elem = BytesStart::new(name);
+    for (key, value) in &values {
+        elem.push_attribute((key.as_str(), value.as_str()));
+    }
+    Ok(elem)
+}
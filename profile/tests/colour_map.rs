@@ -0,0 +1,156 @@
+mod common;
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use goxlr_profile_loader::components::colours::ColourMap;
+use goxlr_profile_loader::components::simple::SimpleElements;
+use goxlr_profile_loader::profile::ProfileSettings;
+use goxlr_profile_loader::SampleButtons::{BottomLeft, BottomRight, Clear, TopLeft, TopRight};
+use goxlr_profile_loader::{Faders, Preset};
+use goxlr_usb::colouring::{ColourMapFormat, ColourScheme, ColourTargets};
+use strum::IntoEnumIterator;
+
+use common::load_fixtures;
+
+/// Builds the colour map for every fixture and compares it byte for byte against the one stored
+/// in tests/snapshots, if something moves a colour to a different offset the lighting on the
+/// device gets scrambled, so this should never change unless it's meant to.
+///
+/// After an intentional change to the layout, run with UPDATE_SNAPSHOTS=1 to rewrite them.
+#[test]
+fn colour_maps_match_snapshots() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let directory = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/colour_maps");
+    if update {
+        fs::create_dir_all(&directory).expect("Unable to create snapshot directory");
+    }
+
+    let mut failures = vec![];
+    for fixture in load_fixtures() {
+        let settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+
+        for format in ColourMapFormat::iter() {
+            let map = build_colour_map(&settings, format);
+            assert_eq!(map.len(), format.size());
+
+            let path = directory.join(format!("{}.{:?}.bin", fixture.name, format));
+            if update {
+                fs::write(&path, &map).expect("Unable to write snapshot");
+                continue;
+            }
+
+            let Ok(expected) = fs::read(&path) else {
+                failures.push(format!(
+                    "{} ({:?}): missing snapshot {}, run with UPDATE_SNAPSHOTS=1 to create it",
+                    fixture.name,
+                    format,
+                    path.display()
+                ));
+                continue;
+            };
+
+            if expected.len() != map.len() {
+                failures.push(format!(
+                    "{} ({:?}): expected {} bytes, got {}",
+                    fixture.name,
+                    format,
+                    expected.len(),
+                    map.len()
+                ));
+                continue;
+            }
+
+            // Report by colour rather than by byte, so it's clear which target has moved..
+            for (index, (expected, actual)) in expected.chunks(4).zip(map.chunks(4)).enumerate() {
+                if expected != actual {
+                    failures.push(format!(
+                        "{} ({:?}): offset {} ({}) expected {:?}, got {:?}",
+                        fixture.name,
+                        format,
+                        index * 4,
+                        get_owner(index * 4, format),
+                        expected,
+                        actual
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+// This is the profile half of the daemon's get_colour_map, without any of the device state (mute
+// and sampler lighting, voice meter, brightness) layered on top..
+fn build_colour_map(settings: &ProfileSettings, format: ColourMapFormat) -> Vec<u8> {
+    ColourScheme::from_fn(format, |target, index| {
+        get_colour_map(settings, target)
+            .colour(index)
+            .to_reverse_bytes()
+    })
+    .into_bytes()
+}
+
+fn get_owner(offset: usize, format: ColourMapFormat) -> String {
+    for target in ColourTargets::iter() {
+        for colour in 0..target.get_colour_count() {
+            if target.position(colour, format) == offset {
+                return format!("{:?} colour {}", target, colour);
+            }
+        }
+    }
+    String::from("unused")
+}
+
+fn get_colour_map(settings: &ProfileSettings, target: ColourTargets) -> &ColourMap {
+    match target {
+        ColourTargets::Fader1Mute => settings.mute_button(Faders::A).colour_map(),
+        ColourTargets::Fader2Mute => settings.mute_button(Faders::B).colour_map(),
+        ColourTargets::Fader3Mute => settings.mute_button(Faders::C).colour_map(),
+        ColourTargets::Fader4Mute => settings.mute_button(Faders::D).colour_map(),
+        ColourTargets::Bleep => settings.simple_element(SimpleElements::Swear).colour_map(),
+        ColourTargets::MicrophoneMute => settings.mute_chat().colour_map(),
+        ColourTargets::EffectSelect1 => settings.effects(Preset::Preset1).colour_map(),
+        ColourTargets::EffectSelect2 => settings.effects(Preset::Preset2).colour_map(),
+        ColourTargets::EffectSelect3 => settings.effects(Preset::Preset3).colour_map(),
+        ColourTargets::EffectSelect4 => settings.effects(Preset::Preset4).colour_map(),
+        ColourTargets::EffectSelect5 => settings.effects(Preset::Preset5).colour_map(),
+        ColourTargets::EffectSelect6 => settings.effects(Preset::Preset6).colour_map(),
+        ColourTargets::EffectFx => settings
+            .simple_element(SimpleElements::FxClear)
+            .colour_map(),
+        ColourTargets::EffectMegaphone => settings.megaphone_effect().colour_map(),
+        ColourTargets::EffectRobot => settings.robot_effect().colour_map(),
+        ColourTargets::EffectHardTune => settings.hardtune_effect().colour_map(),
+        ColourTargets::SamplerSelectA => settings
+            .simple_element(SimpleElements::SampleBankA)
+            .colour_map(),
+        ColourTargets::SamplerSelectB => settings
+            .simple_element(SimpleElements::SampleBankB)
+            .colour_map(),
+        ColourTargets::SamplerSelectC => settings
+            .simple_element(SimpleElements::SampleBankC)
+            .colour_map(),
+        ColourTargets::SamplerTopLeft => settings.sample_button(TopLeft).colour_map(),
+        ColourTargets::SamplerTopRight => settings.sample_button(TopRight).colour_map(),
+        ColourTargets::SamplerBottomLeft => settings.sample_button(BottomLeft).colour_map(),
+        ColourTargets::SamplerBottomRight => settings.sample_button(BottomRight).colour_map(),
+        ColourTargets::SamplerClear => settings.sample_button(Clear).colour_map(),
+        ColourTargets::FadeMeter1 => settings.fader(Faders::A).colour_map(),
+        ColourTargets::FadeMeter2 => settings.fader(Faders::B).colour_map(),
+        ColourTargets::FadeMeter3 => settings.fader(Faders::C).colour_map(),
+        ColourTargets::FadeMeter4 => settings.fader(Faders::D).colour_map(),
+        ColourTargets::Scribble1 => settings.scribble(Faders::A).colour_map(),
+        ColourTargets::Scribble2 => settings.scribble(Faders::B).colour_map(),
+        ColourTargets::Scribble3 => settings.scribble(Faders::C).colour_map(),
+        ColourTargets::Scribble4 => settings.scribble(Faders::D).colour_map(),
+        ColourTargets::PitchEncoder => settings.pitch_encoder().colour_map(),
+        ColourTargets::GenderEncoder => settings.gender_encoder().colour_map(),
+        ColourTargets::ReverbEncoder => settings.reverb_encoder().colour_map(),
+        ColourTargets::EchoEncoder => settings.echo_encoder().colour_map(),
+        ColourTargets::LogoX => settings.simple_element(SimpleElements::LogoX).colour_map(),
+        ColourTargets::InternalLight => settings.simple_element(SimpleElements::LogoX).colour_map(),
+    }
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+pub struct Fixture {
+    pub name: String,
+    pub xml: Vec<u8>,
+}
+
+/// Loads every profile we have on hand, both raw profile.xml files from test-data, and the
+/// profile.xml from inside the bundled .goxlr profiles (which are official app exports).
+pub fn load_fixtures() -> Vec<Fixture> {
+    let base = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut fixtures = vec![];
+
+    for path in list_files(&base.join("test-data"), "xml") {
+        fixtures.push(Fixture {
+            name: get_name(&path),
+            xml: fs::read(&path).expect("Unable to read fixture"),
+        });
+    }
+
+    for path in list_files(&base.join("../defaults/resources/profiles"), "goxlr") {
+        let file = File::open(&path).expect("Unable to open fixture");
+        let mut archive = zip::ZipArchive::new(file).expect("Fixture is not a valid archive");
+        let mut profile = archive
+            .by_name("profile.xml")
+            .expect("Fixture is missing profile.xml");
+
+        let mut xml = vec![];
+        profile
+            .read_to_end(&mut xml)
+            .expect("Unable to read fixture");
+        fixtures.push(Fixture {
+            name: get_name(&path),
+            xml,
+        });
+    }
+
+    assert!(!fixtures.is_empty(), "No profile fixtures found");
+    fixtures
+}
+
+fn list_files(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("Unable to read fixture directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some(extension))
+        .collect();
+    files.sort();
+    files
+}
+
+fn get_name(path: &Path) -> String {
+    path.file_name().unwrap().to_string_lossy().to_string()
+}
@@ -0,0 +1,334 @@
+mod common;
+
+use std::fs::File;
+use std::io::Cursor;
+
+use goxlr_profile_loader::components::animation::{AnimationMode, WaterfallDirection};
+use goxlr_profile_loader::components::encoder_sensitivity::{Encoders, MAX_ENCODER_STEPS};
+use goxlr_profile_loader::components::mute::MuteLightingStyle;
+use goxlr_profile_loader::components::sequence::{SequenceStyle, MAX_SEQUENCE_DURATION};
+use goxlr_profile_loader::profile::{MergeSelection, Profile, ProfileSettings};
+use goxlr_profile_loader::structure::ProfileStructure;
+use goxlr_profile_loader::{AppVersion, Faders, Preset, SampleButtons};
+use strum::IntoEnumIterator;
+
+use common::load_fixtures;
+
+#[test]
+fn saving_keeps_all_elements_and_attributes() {
+    let mut failures = vec![];
+
+    for fixture in load_fixtures() {
+        let original = ProfileStructure::from_xml(fixture.xml.as_slice()).unwrap();
+        let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+        let saved = settings.structure().unwrap();
+
+        for missing in original.missing_from(&saved) {
+            failures.push(format!("{}: {}", fixture.name, missing));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn saved_profiles_are_stable() {
+    let mut failures = vec![];
+
+    for fixture in load_fixtures() {
+        let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+        let mut first = vec![];
+        settings.write_to(&mut first).unwrap();
+
+        // Loading our own output and saving it again should produce exactly the same profile..
+        let mut reloaded = ProfileSettings::load(first.as_slice()).unwrap();
+        for difference in settings.compare(&mut reloaded).unwrap() {
+            failures.push(format!("{}: {}", fixture.name, difference));
+        }
+    }
+
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn saving_is_deterministic() {
+    for fixture in load_fixtures() {
+        let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+
+        let mut first = vec![];
+        settings.write_to(&mut first).unwrap();
+        let mut second = vec![];
+        settings.write_to(&mut second).unwrap();
+
+        assert_eq!(first, second, "{} produced different output", fixture.name);
+    }
+}
+
+#[test]
+fn unknown_content_is_kept() {
+    let fixture = load_fixtures()
+        .into_iter()
+        .find(|fixture| fixture.name == "Default.goxlr")
+        .unwrap();
+    let xml = String::from_utf8(fixture.xml).unwrap();
+
+    // Pretend a newer version of the app has added some things we don't know about..
+    let xml = xml
+        .replacen(
+            "<AppTree",
+            "<futureTree value=\"1\"><futureChild/></futureTree><AppTree",
+            1,
+        )
+        .replacen("<mixerTree ", "<mixerTree futureAttribute=\"2\" ", 1)
+        .replacen("<mute1 ", "<mute1 mute1futureAttribute=\"3\" ", 1);
+
+    let original = ProfileStructure::from_xml(xml.as_bytes()).unwrap();
+    let mut settings = ProfileSettings::load(xml.as_bytes()).unwrap();
+    let saved = settings.structure().unwrap();
+
+    let missing = original.missing_from(&saved);
+    assert!(missing.is_empty(), "\n{}", missing.join("\n"));
+}
+
+#[test]
+fn profiles_can_be_written_to_memory() {
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../defaults/resources/profiles/Default.goxlr"
+    );
+    let mut profile = Profile::load(File::open(path).unwrap()).unwrap();
+
+    let mut buffer = Cursor::new(vec![]);
+    profile.write_to(&mut buffer).unwrap();
+    buffer.set_position(0);
+
+    let mut reloaded = Profile::load(buffer).unwrap();
+    let differences = profile
+        .settings_mut()
+        .compare(reloaded.settings_mut())
+        .unwrap();
+    assert!(differences.is_empty(), "\n{}", differences.join("\n"));
+
+    let mut preset = vec![];
+    profile.save_preset_to(&mut preset).unwrap();
+    assert!(!preset.is_empty());
+}
+
+#[test]
+fn compatible_exports_drop_newer_trees() {
+    for fixture in load_fixtures() {
+        let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+
+        let mut release = vec![];
+        settings
+            .write_compatible_to(&mut release, AppVersion::Release)
+            .unwrap();
+        let release = String::from_utf8(release).unwrap();
+        assert!(release.contains("<submixerTree"), "{}", fixture.name);
+
+        let mut legacy = vec![];
+        settings
+            .write_compatible_to(&mut legacy, AppVersion::Legacy)
+            .unwrap();
+        let legacy = String::from_utf8(legacy).unwrap();
+        assert!(!legacy.contains("<animationTree"), "{}", fixture.name);
+        assert!(!legacy.contains("<submixerTree"), "{}", fixture.name);
+        assert!(!legacy.contains("<mixRoutingTree"), "{}", fixture.name);
+
+        // Whatever's left should still be loadable..
+        ProfileSettings::load(legacy.as_bytes()).unwrap();
+    }
+}
+
+#[test]
+fn compatible_exports_drop_our_own_attributes() {
+    let fixture = &load_fixtures()[0];
+    let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+    settings
+        .mute_button_mut(Faders::A)
+        .set_muted_style(MuteLightingStyle::Dimmed);
+    settings
+        .mute_chat_mut()
+        .set_muted_style(MuteLightingStyle::Off);
+    settings.scribble_mut(Faders::A).set_left_bar(true);
+
+    let mut current = vec![];
+    settings.write_to(&mut current).unwrap();
+    let current = String::from_utf8(current).unwrap();
+    assert!(current.contains("mutedLightingStyle"));
+    assert!(current.contains("leftBar"));
+
+    let mut release = vec![];
+    settings
+        .write_compatible_to(&mut release, AppVersion::Release)
+        .unwrap();
+    let release = String::from_utf8(release).unwrap();
+    assert!(!release.contains("mutedLightingStyle"));
+    assert!(!release.contains("leftBar"));
+}
+
+#[test]
+fn fader_pages_survive_saving() {
+    for fixture in load_fixtures() {
+        let mut settings = ProfileSettings::load(fixture.xml.as_slice()).unwrap();
+        let page = settings.capture_fader_page("Second".to_string());
+        settings.fader_pages_mut().add_page(page);
+
+        let mut saved = vec![];
+        settings.write_to(&mut saved).unwrap();
+        let reloaded = ProfileSettings::load(saved.as_slice()).unwrap();
+
+        let pages = reloaded.fader_pages().pages();
+        assert_eq!(pages.len(), 1, "{}", fixture.name);
+        assert_eq!(pages[0].name(), "Second", "{}", fixture.name);
+
+        let mut release = vec![];
+        settings
+            .write_compatible_to(&mut release, AppVersion::Release)
+            .unwrap();
+        let release = String::from_utf8(release).unwrap();
+        assert!(!release.contains("<faderPage"), "{}", fixture.name);
+    }
+}
+
+#[test]
+fn animations_from_the_official_app_are_loaded() {
+    let fixture = &load_fixtures()[0];
+    let xml = String::from_utf8(fixture.xml.clone()).unwrap().replace(
+        "</ValueTreeRoot>",
+        r#"<animationTree animationMode="1" mod1="39.0" mod2="250.0" mod3="1"/></ValueTreeRoot>"#,
+    );
+
+    let settings = ProfileSettings::load(xml.as_bytes()).unwrap();
+    let animation = settings.animation();
+    assert_eq!(animation.mode(), AnimationMode::RainbowDark);
+    assert_eq!(animation.mod1(), 39);
+    assert_eq!(animation.waterfall(), WaterfallDirection::Up);
+
+    // Modifiers the device won't accept are pulled back into range, rather than failing later..
+    assert_eq!(animation.mod2(), 100);
+}
+
+#[test]
+fn lighting_sequences_are_capped_and_saved() {
+    let fixture = &load_fixtures()[0];
+    let xml = String::from_utf8(fixture.xml.clone()).unwrap().replace(
+        "</ValueTreeRoot>",
+        r#"<startupSequence style="1" colours="FF0000,00FF00" duration="70000"/></ValueTreeRoot>"#,
+    );
+
+    let mut settings = ProfileSettings::load(xml.as_bytes()).unwrap();
+    let sequence = settings.startup_sequence().unwrap();
+    assert_eq!(sequence.style(), SequenceStyle::Fade);
+    assert_eq!(sequence.colours().len(), 2);
+    assert_eq!(sequence.duration(), MAX_SEQUENCE_DURATION);
+    assert!(settings.shutdown_sequence().is_none());
+
+    let mut saved = vec![];
+    settings.write_to(&mut saved).unwrap();
+    let reloaded = ProfileSettings::load(saved.as_slice()).unwrap();
+    let sequence = reloaded.startup_sequence().unwrap();
+    assert_eq!(sequence.colours()[1].to_rgb(), "00FF00");
+
+    let mut release = vec![];
+    settings
+        .write_compatible_to(&mut release, AppVersion::Release)
+        .unwrap();
+    let release = String::from_utf8(release).unwrap();
+    assert!(!release.contains("<startupSequence"));
+}
+
+#[test]
+fn encoder_sensitivity_is_capped_and_saved() {
+    let fixture = &load_fixtures()[0];
+    let xml = String::from_utf8(fixture.xml.clone()).unwrap().replace(
+        "</ValueTreeRoot>",
+        r#"<encoderSensitivity reverb="12" echo="250"/></ValueTreeRoot>"#,
+    );
+
+    let mut settings = ProfileSettings::load(xml.as_bytes()).unwrap();
+    let sensitivity = settings.encoder_sensitivity();
+    assert_eq!(sensitivity.steps(Encoders::Reverb), Some(12));
+    assert_eq!(sensitivity.steps(Encoders::Echo), Some(MAX_ENCODER_STEPS));
+    assert_eq!(sensitivity.steps(Encoders::Pitch), None);
+
+    let mut saved = vec![];
+    settings.write_to(&mut saved).unwrap();
+    let reloaded = ProfileSettings::load(saved.as_slice()).unwrap();
+    assert_eq!(
+        reloaded.encoder_sensitivity().steps(Encoders::Reverb),
+        Some(12)
+    );
+
+    let mut release = vec![];
+    settings
+        .write_compatible_to(&mut release, AppVersion::Release)
+        .unwrap();
+    let release = String::from_utf8(release).unwrap();
+    assert!(!release.contains("<encoderSensitivity"));
+
+    // Without any custom sensitivity, there's nothing to write..
+    for encoder in [Encoders::Reverb, Encoders::Echo] {
+        settings.encoder_sensitivity_mut().set_steps(encoder, None);
+    }
+    let mut cleared = vec![];
+    settings.write_to(&mut cleared).unwrap();
+    assert!(!String::from_utf8(cleared)
+        .unwrap()
+        .contains("<encoderSensitivity"));
+}
+
+#[test]
+fn merging_only_takes_the_selected_parts() {
+    let fixtures = load_fixtures();
+    let first = &fixtures[0].xml;
+    let second = &fixtures[fixtures.len() - 1].xml;
+
+    let original = ProfileSettings::load(first.as_slice()).unwrap();
+    let other = ProfileSettings::load(second.as_slice()).unwrap();
+
+    // Taking the lighting should leave the effects and mixer alone..
+    let mut merged = ProfileSettings::load(first.as_slice()).unwrap();
+    let selection = MergeSelection {
+        lighting: true,
+        ..Default::default()
+    };
+    merged.merge(ProfileSettings::load(second.as_slice()).unwrap(), selection);
+
+    assert_eq!(get_colours(&merged), get_colours(&other));
+    assert_eq!(get_effect_names(&merged), get_effect_names(&original));
+    assert_eq!(merged.mixer().mixer_table(), original.mixer().mixer_table());
+
+    // While taking the effects should keep our colours on the effect buttons..
+    let mut merged = ProfileSettings::load(first.as_slice()).unwrap();
+    let selection = MergeSelection {
+        effects: true,
+        mixer: true,
+        ..Default::default()
+    };
+    merged.merge(ProfileSettings::load(second.as_slice()).unwrap(), selection);
+
+    assert_eq!(get_colours(&merged), get_colours(&original));
+    assert_eq!(get_effect_names(&merged), get_effect_names(&other));
+    assert_eq!(merged.mixer().mixer_table(), other.mixer().mixer_table());
+}
+
+fn get_colours(settings: &ProfileSettings) -> Vec<String> {
+    let fader = settings.fader(Faders::A).colour_map();
+    let effect = settings.effects(Preset::Preset1).colour_map();
+    let sample = settings.sample_button(SampleButtons::TopLeft).colour_map();
+
+    let mut colours = vec![];
+    for index in 0..2 {
+        colours.push(fader.colour_or_default(index).to_rgb());
+        colours.push(effect.colour_or_default(index).to_rgb());
+        colours.push(sample.colour_or_default(index).to_rgb());
+    }
+    colours
+}
+
+fn get_effect_names(settings: &ProfileSettings) -> Vec<String> {
+    Preset::iter()
+        .map(|preset| settings.effects(preset).name().to_string())
+        .collect()
+}
@@ -0,0 +1,227 @@
+//! Python bindings for the profile loader, so bulk changes (like recolouring every profile in a
+//! directory) can be scripted rather than clicked through one profile at a time.
+//!
+//! Colours are handled as 'RRGGBB' hex strings, and everything else is referenced by name, see
+//! Profile.colour_targets() and Profile.routing() for what's available.
+
+// The wrappers pyo3 generates convert each PyResult's error into itself..
+#![allow(clippy::useless_conversion)]
+
+use std::collections::HashMap;
+use std::fs::File;
+
+use anyhow::{anyhow, bail, Result};
+use goxlr_profile_loader::components::colours::{Colour, ColourMap};
+use goxlr_profile_loader::components::mixer::{InputChannels, OutputChannels};
+use goxlr_profile_loader::components::simple::SimpleElements;
+use goxlr_profile_loader::profile::{Profile as ProfileFile, ProfileSettings};
+use goxlr_profile_loader::{Faders, Preset, SampleButtons};
+use pyo3::prelude::*;
+use strum::IntoEnumIterator;
+
+// The routing table stores an enabled route as this, and a disabled one as 0, older profiles may
+// use other non-zero values so anything above 0 is treated as enabled (same as the daemon)..
+const ROUTE_ENABLED: u16 = 8192;
+
+// Buttons have two colours, and faders have three..
+const COLOUR_COUNT: usize = 3;
+
+#[pyclass(unsendable)]
+struct Profile {
+    profile: ProfileFile,
+}
+
+#[pymethods]
+impl Profile {
+    #[staticmethod]
+    fn load(path: &str) -> PyResult<Self> {
+        let file = File::open(path)?;
+        let profile = ProfileFile::load(file)?;
+        Ok(Self { profile })
+    }
+
+    fn save(&mut self, path: &str) -> PyResult<()> {
+        Ok(self.profile.save(path)?)
+    }
+
+    /// The names of everything in a profile which can be coloured
+    #[staticmethod]
+    fn colour_targets() -> Vec<String> {
+        let mut targets = vec![];
+        for fader in Faders::iter() {
+            targets.push(format!("Fader{fader:?}"));
+            targets.push(format!("Mute{fader:?}"));
+            targets.push(format!("Scribble{fader:?}"));
+        }
+        for (index, _) in Preset::iter().enumerate() {
+            targets.push(format!("Effects{}", index + 1));
+        }
+        for button in SampleButtons::iter() {
+            targets.push(format!("Sample{button:?}"));
+        }
+        for element in SimpleElements::iter() {
+            targets.push(format!("{element:?}"));
+        }
+        for name in EFFECT_TARGETS {
+            targets.push(name.to_string());
+        }
+        targets
+    }
+
+    fn get_colours(&mut self, target: &str) -> PyResult<Vec<String>> {
+        let colour_map = get_colour_map(self.profile.settings_mut(), target)?;
+        Ok((0..COLOUR_COUNT)
+            .map(|index| colour_map.colour_or_default(index as u8).to_rgb())
+            .collect())
+    }
+
+    fn set_colour(&mut self, target: &str, index: usize, colour: &str) -> PyResult<()> {
+        if index >= COLOUR_COUNT {
+            return Err(anyhow!("Colour index must be below {}", COLOUR_COUNT).into());
+        }
+        let colour = Colour::fromrgb(colour).map_err(anyhow::Error::from)?;
+        get_colour_map(self.profile.settings_mut(), target)?.set_colour(index, colour);
+        Ok(())
+    }
+
+    /// Returns the routing table as {input: {output: enabled}}
+    fn routing(&self) -> HashMap<String, HashMap<String, bool>> {
+        let table = self.profile.settings().mixer().mixer_table();
+
+        let mut routing = HashMap::new();
+        for input in InputChannels::iter() {
+            let outputs = OutputChannels::iter()
+                .map(|output| (format!("{output:?}"), table[input][output] > 0))
+                .collect();
+            routing.insert(format!("{input:?}"), outputs);
+        }
+        routing
+    }
+
+    fn set_routing(&mut self, input: &str, output: &str, enabled: bool) -> PyResult<()> {
+        let input = from_name(InputChannels::iter(), input, "Input")?;
+        let output = from_name(OutputChannels::iter(), output, "Output")?;
+
+        // Same as the daemon, Chat can't be routed back into itself..
+        if matches!(input, InputChannels::Chat) && output == OutputChannels::ChatMic {
+            return Err(anyhow!("Invalid Route: Chat -> Chat Mic").into());
+        }
+
+        let value = if enabled { ROUTE_ENABLED } else { 0 };
+        self.profile.settings_mut().mixer_mut().mixer_table_mut()[input][output] = value;
+        Ok(())
+    }
+
+    /// The names of the six effect presets
+    fn effect_presets(&self) -> Vec<String> {
+        let settings = self.profile.settings();
+        Preset::iter()
+            .map(|preset| settings.effects(preset).name().to_string())
+            .collect()
+    }
+
+    /// Writes an effect preset (1-6) out as a .preset file
+    fn export_effect_preset(&mut self, preset: usize, path: &str) -> PyResult<()> {
+        with_preset(self.profile.settings_mut(), preset, |settings| {
+            settings.write_preset(path)
+        })?;
+        Ok(())
+    }
+
+    /// Replaces an effect preset (1-6) with the contents of a .preset file
+    fn import_effect_preset(&mut self, preset: usize, path: &str) -> PyResult<()> {
+        let file = File::open(path)?;
+        with_preset(self.profile.settings_mut(), preset, |settings| {
+            settings.load_preset(file)
+        })?;
+        Ok(())
+    }
+}
+
+const EFFECT_TARGETS: [&str; 8] = [
+    "Cough",
+    "Megaphone",
+    "Robot",
+    "HardTune",
+    "Pitch",
+    "Echo",
+    "Gender",
+    "Reverb",
+];
+
+fn get_colour_map<'a>(
+    settings: &'a mut ProfileSettings,
+    target: &str,
+) -> Result<&'a mut ColourMap> {
+    for fader in Faders::iter() {
+        if target == format!("Fader{fader:?}") {
+            return Ok(settings.fader_mut(fader).colour_map_mut());
+        }
+        if target == format!("Mute{fader:?}") {
+            return Ok(settings.mute_button_mut(fader).colour_map_mut());
+        }
+        if target == format!("Scribble{fader:?}") {
+            return Ok(settings.scribble_mut(fader).colour_map_mut());
+        }
+    }
+    for (index, preset) in Preset::iter().enumerate() {
+        if target == format!("Effects{}", index + 1) {
+            return Ok(settings.effects_mut(preset).colour_map_mut());
+        }
+    }
+    for button in SampleButtons::iter() {
+        if target == format!("Sample{button:?}") {
+            return Ok(settings.sample_button_mut(button).colour_map_mut());
+        }
+    }
+    for element in SimpleElements::iter() {
+        if target == format!("{element:?}") {
+            return Ok(settings.simple_element_mut(element).colour_map_mut());
+        }
+    }
+
+    Ok(match target {
+        "Cough" => settings.mute_chat_mut().colour_map_mut(),
+        "Megaphone" => settings.megaphone_effect_mut().colour_map_mut(),
+        "Robot" => settings.robot_effect_mut().colour_map_mut(),
+        "HardTune" => settings.hardtune_effect_mut().colour_map_mut(),
+        "Pitch" => settings.pitch_encoder_mut().colour_map_mut(),
+        "Echo" => settings.echo_encoder_mut().colour_map_mut(),
+        "Gender" => settings.gender_encoder_mut().colour_map_mut(),
+        "Reverb" => settings.reverb_encoder_mut().colour_map_mut(),
+        _ => bail!("Unknown Colour Target: {}", target),
+    })
+}
+
+// Presets are read and written from the selected slot, so briefly select the one we want..
+fn with_preset<F>(settings: &mut ProfileSettings, preset: usize, function: F) -> Result<()>
+where
+    F: FnOnce(&mut ProfileSettings) -> Result<()>,
+{
+    let preset = preset
+        .checked_sub(1)
+        .and_then(|index| Preset::iter().nth(index))
+        .ok_or_else(|| anyhow!("Preset must be between 1 and 6"))?;
+
+    let selected = settings.context().selected_effects();
+    settings.context_mut().set_selected_effects(preset);
+    let result = function(settings);
+    settings.context_mut().set_selected_effects(selected);
+    result
+}
+
+fn from_name<T: std::fmt::Debug>(
+    mut values: impl Iterator<Item = T>,
+    name: &str,
+    kind: &str,
+) -> Result<T> {
+    values
+        .find(|value| format!("{value:?}") == name)
+        .ok_or_else(|| anyhow!("Unknown {}: {}", kind, name))
+}
+
+#[pymodule]
+fn goxlr_profile(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<Profile>()?;
+    Ok(())
+}
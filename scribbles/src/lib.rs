@@ -1,7 +1,7 @@
 use ab_glyph::{FontRef, PxScale};
 use anyhow::{bail, Result};
 use image::imageops::{dither, overlay, BiLevel, FilterType};
-use image::ImageFormat::Png;
+use image::ImageFormat::{Gif, Jpeg, Png};
 use image::{ColorType, DynamicImage, GenericImage, GenericImageView, GrayImage, Luma, Rgba};
 use imageproc::drawing::{draw_text_mut, text_size};
 use log::warn;
@@ -10,14 +10,27 @@ use std::io::Cursor;
 use std::path::PathBuf;
 
 static FONT: &[u8] = include_bytes!("../fonts/Play-Bold.ttf");
+const LEFT_BAR_WIDTH: u32 = 4;
+
+// Only three lines of text fit on the display, and there's no point rendering a line which would
+// take minutes to scroll past..
+pub const MAX_TEXT_LINES: usize = 3;
+pub const MAX_LINE_LENGTH: usize = 100;
+
+const LINE_HEIGHT: u32 = 19;
+const LINE_SPACING: u32 = 2;
+
+// The gap between the end of a scrolling line, and its start coming back around
+const SCROLL_GAP: u32 = 32;
 
 pub fn get_scribble(
     path: Option<PathBuf>,
     bottom: Option<String>,
     top: Option<String>,
     invert: bool,
+    left_bar: bool,
 ) -> [u8; 1024] {
-    let image = get_scribble_base(path, bottom, top);
+    let image = get_scribble_base(path, bottom, top, left_bar);
 
     to_goxlr(image, invert).unwrap_or([0; 1024])
 }
@@ -27,11 +40,12 @@ pub fn get_scribble_png(
     bottom: Option<String>,
     top: Option<String>,
     invert: bool,
+    left_bar: bool,
     width: u32,
     height: u32,
 ) -> Result<Vec<u8>> {
     // First, get the GrayScale version..
-    let mut image = get_scribble_base(path, bottom, top);
+    let mut image = get_scribble_base(path, bottom, top, left_bar);
 
     let white = Luma::from([255_u8]);
     let black = Luma::from([0_u8]);
@@ -69,6 +83,7 @@ pub fn get_scribble_base(
     path: Option<PathBuf>,
     bottom: Option<String>,
     top: Option<String>,
+    left_bar: bool,
 ) -> GrayImage {
     let mut processed_image = None;
     let mut bottom_image = None;
@@ -92,7 +107,130 @@ pub fn get_scribble_base(
         }
     }
 
-    create_final_image(processed_image, bottom_image, top_right_image)
+    create_final_image(processed_image, bottom_image, top_right_image, left_bar)
+}
+
+/// Renders lines of text on their own, any lines which are too wide for the display are scrolled
+/// along by `scroll` pixels, wrapping back around to the start.
+pub fn get_text_scribble(lines: &[String], scroll: u32, invert: bool) -> [u8; 1024] {
+    let mut image = DynamicImage::new_rgb8(128, 64);
+    image
+        .clone()
+        .pixels()
+        .for_each(|(x, y, _pixel)| image.put_pixel(x, y, Rgba::from([255, 255, 255, 255])));
+
+    let lines: Vec<DynamicImage> = truncate_lines(lines)
+        .iter()
+        .filter_map(|line| create_line_image(line).ok())
+        .collect();
+
+    let count = lines.len() as u32;
+    let height = (count * LINE_HEIGHT) + (count.saturating_sub(1) * LINE_SPACING);
+    let mut y = image.height().saturating_sub(height) / 2;
+
+    for line in lines {
+        if line.width() <= image.width() {
+            let x = (image.width() - line.width()) / 2;
+            overlay(&mut image, &line, x as i64, y as i64);
+        } else {
+            // Draw it twice, so the start follows the end around..
+            let length = line.width() + SCROLL_GAP;
+            let x = -((scroll % length) as i64);
+            overlay(&mut image, &line, x, y as i64);
+            overlay(&mut image, &line, x + length as i64, y as i64);
+        }
+        y += LINE_HEIGHT + LINE_SPACING;
+    }
+
+    let mut final_image = image.to_luma8();
+    dither(final_image.borrow_mut(), &BiLevel);
+
+    to_goxlr(final_image, invert).unwrap_or([0; 1024])
+}
+
+/// Returns true if any of the lines are too wide for the display, and will need scrolling
+pub fn is_text_scrolling(lines: &[String]) -> bool {
+    truncate_lines(lines)
+        .iter()
+        .filter_map(|line| create_line_image(line).ok())
+        .any(|line| line.width() > 128)
+}
+
+fn truncate_lines(lines: &[String]) -> Vec<String> {
+    lines
+        .iter()
+        .take(MAX_TEXT_LINES)
+        .map(|line| line.chars().take(MAX_LINE_LENGTH).collect())
+        .collect()
+}
+
+/// Validates an uploaded icon, and converts it to a greyscale PNG no larger than the scribble
+/// display. Icons are resized again when they're drawn, so dithering is left until then.
+pub fn prepare_icon(data: &[u8]) -> Result<Vec<u8>> {
+    let format = image::guess_format(data)?;
+    if !matches!(format, Png | Jpeg | Gif) {
+        bail!("Unsupported Image Format: {:?}", format);
+    }
+
+    let image = image::load_from_memory_with_format(data, format)?;
+    let oversized = image.width() > 128 || image.height() > 64;
+
+    // A PNG which already fits can be stored as it is..
+    if format == Png && !oversized {
+        return Ok(data.to_vec());
+    }
+
+    // Shrink it down to fit the display, there's no value in storing anything bigger..
+    let flattened = flatten(&image);
+    let resized = if oversized {
+        flattened.resize(128, 64, FilterType::Gaussian)
+    } else {
+        flattened
+    };
+
+    let mut bytes = Vec::new();
+    DynamicImage::from(resized.to_luma8()).write_to(&mut Cursor::new(&mut bytes), Png)?;
+    Ok(bytes)
+}
+
+/// Validates a scribble image from a profile, and re-encodes it as a dithered PNG at the size of
+/// the scribble display.
+pub fn prepare_scribble(data: &[u8]) -> Result<Vec<u8>> {
+    let image = load_scribble_png(data)?;
+
+    let mut bytes = Vec::new();
+    DynamicImage::from(image).write_to(&mut Cursor::new(&mut bytes), Png)?;
+    Ok(bytes)
+}
+
+/// Converts a scribble image into the format the GoXLR expects
+pub fn get_scribble_from_png(data: &[u8], invert: bool) -> Result<[u8; 1024]> {
+    to_goxlr(load_scribble_png(data)?, invert)
+}
+
+fn load_scribble_png(data: &[u8]) -> Result<GrayImage> {
+    let format = image::guess_format(data)?;
+    if format != Png {
+        bail!("Scribble images must be a PNG, not {:?}", format);
+    }
+
+    let image = image::load_from_memory_with_format(data, format)?;
+    let resized = flatten(&image).resize_exact(128, 64, FilterType::Gaussian);
+
+    let mut gray = resized.to_luma8();
+    dither(gray.borrow_mut(), &BiLevel);
+    Ok(gray)
+}
+
+// Flatten any transparency onto white, so it's treated as 'off'..
+fn flatten(image: &DynamicImage) -> DynamicImage {
+    let mut flattened = DynamicImage::new_rgb8(image.width(), image.height());
+    flattened
+        .clone()
+        .pixels()
+        .for_each(|(x, y, _)| flattened.put_pixel(x, y, Rgba::from([255, 255, 255, 255])));
+    overlay(&mut flattened, image, 0, 0);
+    flattened
 }
 
 fn load_grayscale_image(path: PathBuf) -> Result<DynamicImage> {
@@ -117,6 +255,12 @@ fn load_grayscale_image(path: PathBuf) -> Result<DynamicImage> {
 }
 
 fn create_text_image(text: &str) -> Result<DynamicImage> {
+    // Anything past the edge of the display is cut off..
+    let image = create_line_image(text)?;
+    Ok(image.crop_imm(0, 0, image.width().min(128), image.height()))
+}
+
+fn create_line_image(text: &str) -> Result<DynamicImage> {
     let draw_font = FontRef::try_from_slice(FONT)?;
 
     let scale = PxScale {
@@ -124,11 +268,8 @@ fn create_text_image(text: &str) -> Result<DynamicImage> {
         y: 19_f32,
     };
 
-    // Calculate the draw width..
     let (width, _height) = text_size(scale, &draw_font, text);
-    let draw_width = if width < 128 { width } else { 128 };
-
-    let mut image = DynamicImage::new_rgb8(draw_width, 19);
+    let mut image = DynamicImage::new_rgb8(width.max(1), LINE_HEIGHT);
     image
         .clone()
         .pixels()
@@ -151,6 +292,7 @@ fn create_final_image(
     mut icon: Option<DynamicImage>,
     text: Option<DynamicImage>,
     number: Option<DynamicImage>,
+    left_bar: bool,
 ) -> GrayImage {
     // Ok, firstly, create an image and make it completely white..
     let mut image = DynamicImage::new_rgb8(128, 64);
@@ -194,9 +336,21 @@ fn create_final_image(
         overlay(&mut image, &text, position_x as i64, position_y as i64);
     }
 
+    let mut number_x = 5;
+    if left_bar {
+        // Draw a solid bar down the left hand side, and push the number across to make room..
+        let black = Rgba::from([0, 0, 0, 255]);
+        for x in 0..LEFT_BAR_WIDTH {
+            for y in 0..image.height() {
+                image.put_pixel(x, y, black);
+            }
+        }
+        number_x += LEFT_BAR_WIDTH as i64;
+    }
+
     if let Some(number) = number {
         // Shove this in the top left corner with a safety buffer..
-        overlay(&mut image, &number, 5, 3);
+        overlay(&mut image, &number, number_x, 3);
     }
 
     let mut final_image = image.to_luma8();
@@ -0,0 +1,182 @@
+/*
+   The GoXLR takes all its effect values as raw i32s, but what those numbers actually mean
+   differs per effect (decibels, milliseconds, semitones, etc). These types validate a value
+   in the units it's normally expressed in, and handle the conversion to what the GoXLR expects.
+*/
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueRangeError {
+    pub name: &'static str,
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl Display for ValueRangeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} should be between {} and {} (got {})",
+            self.name, self.min, self.max, self.value
+        )
+    }
+}
+
+impl Error for ValueRangeError {}
+
+fn check_range(name: &'static str, value: i32, min: i32, max: i32) -> Result<(), ValueRangeError> {
+    if !(min..=max).contains(&value) {
+        return Err(ValueRangeError {
+            name,
+            value,
+            min,
+            max,
+        });
+    }
+    Ok(())
+}
+
+// Both Reverb and Echo are stored as a knob position (0 - 24), and sent as -36dB to 0dB
+const AMOUNT_KNOB_MAX: i8 = 24;
+
+fn knob_to_percent(position: i8) -> u8 {
+    ((position as u16 * 100) / AMOUNT_KNOB_MAX as u16) as u8
+}
+
+fn percent_to_knob(percent: u8) -> i8 {
+    ((percent as i16 * AMOUNT_KNOB_MAX as i16) / 100) as i8
+}
+
+fn knob_to_decibels(position: i8) -> i32 {
+    (36 * position as i32) / AMOUNT_KNOB_MAX as i32 - 36
+}
+
+// Rounds up, so that any value produced by knob_to_decibels maps back to the same position
+fn decibels_to_knob(decibels: i32) -> i8 {
+    (((decibels + 36) * AMOUNT_KNOB_MAX as i32 + 35) / 36) as i8
+}
+
+/// The amount of Reverb applied, from 0% (-36dB) to 100% (0dB)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReverbAmount(i8);
+
+impl ReverbAmount {
+    pub fn from_percent(percent: u8) -> Result<Self, ValueRangeError> {
+        check_range("Reverb Amount", percent as i32, 0, 100)?;
+        Ok(Self(percent_to_knob(percent)))
+    }
+
+    pub fn from_knob_position(position: i8) -> Result<Self, ValueRangeError> {
+        check_range(
+            "Reverb Knob Position",
+            position as i32,
+            0,
+            AMOUNT_KNOB_MAX as i32,
+        )?;
+        Ok(Self(position))
+    }
+
+    pub fn from_decibels(decibels: i32) -> Result<Self, ValueRangeError> {
+        check_range("Reverb Amount", decibels, -36, 0)?;
+        Ok(Self(decibels_to_knob(decibels)))
+    }
+
+    pub fn knob_position(&self) -> i8 {
+        self.0
+    }
+
+    pub fn percent(&self) -> u8 {
+        knob_to_percent(self.0)
+    }
+}
+
+impl From<ReverbAmount> for i32 {
+    fn from(value: ReverbAmount) -> Self {
+        knob_to_decibels(value.0)
+    }
+}
+
+/// The amount of Echo applied, from 0% (-36dB) to 100% (0dB)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EchoAmount(i8);
+
+impl EchoAmount {
+    pub fn from_percent(percent: u8) -> Result<Self, ValueRangeError> {
+        check_range("Echo Amount", percent as i32, 0, 100)?;
+        Ok(Self(percent_to_knob(percent)))
+    }
+
+    pub fn from_knob_position(position: i8) -> Result<Self, ValueRangeError> {
+        check_range(
+            "Echo Knob Position",
+            position as i32,
+            0,
+            AMOUNT_KNOB_MAX as i32,
+        )?;
+        Ok(Self(position))
+    }
+
+    pub fn from_decibels(decibels: i32) -> Result<Self, ValueRangeError> {
+        check_range("Echo Amount", decibels, -36, 0)?;
+        Ok(Self(decibels_to_knob(decibels)))
+    }
+
+    pub fn knob_position(&self) -> i8 {
+        self.0
+    }
+
+    pub fn percent(&self) -> u8 {
+        knob_to_percent(self.0)
+    }
+}
+
+impl From<EchoAmount> for i32 {
+    fn from(value: EchoAmount) -> Self {
+        knob_to_decibels(value.0)
+    }
+}
+
+/// The Left or Right delay of the ClassicSlap Echo style, in milliseconds
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EchoDelayMs(u16);
+
+impl EchoDelayMs {
+    pub fn new(milliseconds: u16) -> Result<Self, ValueRangeError> {
+        check_range("Echo Delay", milliseconds as i32, 0, 2500)?;
+        Ok(Self(milliseconds))
+    }
+
+    pub fn millis(&self) -> u16 {
+        self.0
+    }
+}
+
+impl From<EchoDelayMs> for i32 {
+    fn from(value: EchoDelayMs) -> Self {
+        value.0 as i32
+    }
+}
+
+/// The Pitch shift in semitones, Wide mode (or HardTune) allows up to two octaves either way.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PitchSemitones(i8);
+
+impl PitchSemitones {
+    pub fn new(semitones: i8) -> Result<Self, ValueRangeError> {
+        check_range("Pitch", semitones as i32, -24, 24)?;
+        Ok(Self(semitones))
+    }
+
+    pub fn semitones(&self) -> i8 {
+        self.0
+    }
+}
+
+impl From<PitchSemitones> for i32 {
+    fn from(value: PitchSemitones) -> Self {
+        value.0 as i32
+    }
+}
@@ -11,7 +11,10 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 use std::fmt::{Display, Formatter};
 use strum::{Display, EnumCount, EnumIter};
 
-#[derive(Default, Debug, Copy, Clone, Display, Enum, EnumIter, EnumCount, PartialEq, Eq)]
+mod effect_values;
+pub use effect_values::*;
+
+#[derive(Default, Debug, Copy, Clone, Display, Enum, EnumIter, EnumCount, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum ChannelName {
@@ -151,6 +154,15 @@ pub enum OutputDevice {
     LineOut,
 }
 
+// The tone controls for an output, no known firmware has these yet..
+#[derive(Debug, Copy, Clone, Display, Enum, EnumIter, EnumCount, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ToneBand {
+    Bass,
+    Treble,
+}
+
 #[derive(Debug, Copy, Clone, Display, Enum, EnumIter, EnumCount, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -440,6 +452,17 @@ pub enum ButtonColourOffStyle {
     DimmedColour2,
 }
 
+// How a Mute button is lit while muted
+#[derive(Debug, Copy, Clone, Display, EnumIter, EnumCount, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MuteLightingStyle {
+    Colour1,
+    Dimmed,
+    Colour2,
+    Off,
+}
+
 // MuteChat
 #[derive(Debug, Copy, Clone, Display, EnumIter, EnumCount, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
@@ -609,6 +632,18 @@ pub enum GateTimes {
     Gate2000ms,
 }
 
+impl GateTimes {
+    pub fn as_millis(&self) -> u32 {
+        // 10ms steps up to 200ms, then 50ms steps up to 1s, then 100ms steps..
+        let index = *self as u32;
+        match index {
+            0..=19 => (index + 1) * 10,
+            20..=35 => 200 + (index - 19) * 50,
+            _ => 1000 + (index - 35) * 100,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, EnumIter, Display, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "serde", derive(Serialize_repr, Deserialize_repr))]
@@ -843,6 +878,35 @@ pub enum VodMode {
     StreamNoMusic,
 }
 
+#[derive(Debug, Copy, Clone, EnumIter, Display, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProfileConflictResolution {
+    Reload,
+    Keep,
+    Merge,
+}
+
+// The parts of a profile which can be taken from another when merging..
+#[derive(Debug, Copy, Clone, EnumIter, Display, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum ProfileMergeSection {
+    Lighting,
+    Mixer,
+    Effects,
+    Sampler,
+}
+
+#[derive(Default, Debug, Copy, Clone, EnumIter, Display, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(ValueEnum))]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum AttachStatePolicy {
+    #[default]
+    Profile,
+    Device,
+}
+
 #[derive(Default, Debug, Clone, Enum, PartialEq, Eq)]
 #[cfg_attr(feature = "clap", derive(ValueEnum))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -0,0 +1,32 @@
+#![no_main]
+
+use goxlr_types::{EffectKey, MicrophoneParamKey};
+use goxlr_usb::commands::Command;
+use goxlr_usb::protocol;
+use libfuzzer_sys::fuzz_target;
+use strum::IntoEnumIterator;
+
+fuzz_target!(|input: (u16, Vec<(u8, i32)>, Vec<(u8, [u8; 4])>)| {
+    let (index, effects, params) = input;
+
+    let effects: Vec<(EffectKey, i32)> = effects
+        .into_iter()
+        .filter_map(|(key, value)| Some((EffectKey::iter().nth(key as usize)?, value)))
+        .collect();
+    let params: Vec<(MicrophoneParamKey, [u8; 4])> = params
+        .into_iter()
+        .filter_map(|(key, value)| Some((MicrophoneParamKey::iter().nth(key as usize)?, value)))
+        .collect();
+
+    let body = protocol::encode_effect_values(&effects);
+    assert_eq!(body.len(), effects.len() * 8);
+    if let Ok(request) = protocol::build_request(Command::SetEffectParameters, index, &body) {
+        assert_eq!(request.len(), protocol::REQUEST_HEADER_SIZE + body.len());
+    }
+
+    let body = protocol::encode_mic_params(&params);
+    assert_eq!(body.len(), params.len() * 8);
+    if let Ok(request) = protocol::build_request(Command::SetMicrophoneParameters, index, &body) {
+        assert_eq!(request.len(), protocol::REQUEST_HEADER_SIZE + body.len());
+    }
+});
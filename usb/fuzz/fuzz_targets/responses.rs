@@ -0,0 +1,12 @@
+#![no_main]
+
+use goxlr_usb::protocol;
+use libfuzzer_sys::fuzz_target;
+
+// Responses come straight off the device, none of these should ever panic..
+fuzz_target!(|data: &[u8]| {
+    let _ = protocol::parse_firmware_version(data);
+    let _ = protocol::parse_serial_number(data);
+    let _ = protocol::parse_button_states(data);
+    let _ = protocol::parse_microphone_level(data);
+});
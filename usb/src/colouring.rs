@@ -1,4 +1,4 @@
-use strum::EnumIter;
+use strum::{EnumIter, IntoEnumIterator};
 
 /**
  * This can probably be handled a lot better, there's a lot of duplication going on here
@@ -6,6 +6,74 @@ use strum::EnumIter;
  * better building of structures, and definitions. Todo: Later (started in colours.rs).
  */
 
+/// The layout of the colour map sent to the device. Firmware 1.3.40 (1.1.8 on the Mini) moved
+/// to a larger map, with more space between the faders for animations.
+#[derive(Copy, Clone, Debug, EnumIter, PartialEq, Eq)]
+pub enum ColourMapFormat {
+    Legacy,
+    Animated,
+}
+
+impl ColourMapFormat {
+    pub const fn size(self) -> usize {
+        match self {
+            ColourMapFormat::Legacy => 328,
+            ColourMapFormat::Animated => 520,
+        }
+    }
+}
+
+/// Builds up the colour map sent to the device, colours are set per target as BGRA bytes and
+/// placed wherever that target lives in the map's format, anything not set is left off.
+#[derive(Clone, Debug)]
+pub struct ColourScheme {
+    format: ColourMapFormat,
+    map: Vec<u8>,
+}
+
+impl ColourScheme {
+    pub fn new(format: ColourMapFormat) -> Self {
+        Self {
+            format,
+            map: vec![0; format.size()],
+        }
+    }
+
+    /// Creates a scheme with every colour of every target taken from `colour`
+    pub fn from_fn<F>(format: ColourMapFormat, mut colour: F) -> Self
+    where
+        F: FnMut(ColourTargets, u8) -> [u8; 4],
+    {
+        let mut scheme = Self::new(format);
+        for target in ColourTargets::iter() {
+            for index in 0..target.get_colour_count() {
+                scheme.set(target, index, colour(target, index));
+            }
+        }
+        scheme
+    }
+
+    pub fn set(&mut self, target: ColourTargets, index: u8, colour: [u8; 4]) {
+        let position = target.position(index, self.format);
+        self.map[position..position + 4].copy_from_slice(&colour);
+    }
+
+    /// Sets every colour of a target to the same value
+    pub fn set_all(&mut self, target: ColourTargets, colour: [u8; 4]) {
+        for index in 0..target.get_colour_count() {
+            self.set(target, index, colour);
+        }
+    }
+
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.map
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.map
+    }
+}
+
 #[derive(Copy, Clone, Debug, EnumIter, PartialEq)]
 pub enum ColourTargets {
     // These are all the buttons from the GoXLR Mini.
@@ -163,17 +231,16 @@ impl ColourTargets {
         }
     }
 
-    pub fn position(&self, colour: u8, format_1_3_40: bool) -> usize {
+    pub fn position(&self, colour: u8, format: ColourMapFormat) -> usize {
         // For some odd reason, the Encoder dial order seems to be 1, 0, 2 as the colour definitions
         // where as all other buttons are 0, 1.. We *COULD* make this simpler by assuming that if
         // there are three colours, the order will be different, for now I'm just adding a simple
         // exception for the Encoders.
 
         // We should also error check here, make sure colour is in the range of get_colour_count..
-        let start_point = if format_1_3_40 {
-            self.get_start_1_3_40()
-        } else {
-            self.get_start()
+        let start_point = match format {
+            ColourMapFormat::Legacy => self.get_start(),
+            ColourMapFormat::Animated => self.get_start_1_3_40(),
         };
 
         match self {
@@ -1,5 +1,5 @@
 use crate::animation::{AnimationMode, WaterFallDir};
-use crate::buttonstate::{ButtonStates, Buttons, CurrentButtonStates};
+use crate::buttonstate::{ButtonStates, CurrentButtonStates};
 use crate::channelstate::ChannelState;
 use crate::commands::Command::ExecuteFirmwareUpdateAction;
 use crate::commands::SystemInfoCommand::SupportsDCPCategory;
@@ -7,17 +7,22 @@ use crate::commands::{
     Command, FirmwareAction, FirmwareCommand, HardwareInfoCommand, SystemInfoCommand,
 };
 use crate::dcp::DCPCategory;
+use crate::protocol;
 use crate::routing::InputDevice;
 use anyhow::{bail, Result};
-use byteorder::{ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
-use enumset::EnumSet;
+use byteorder::{ByteOrder, LittleEndian, ReadBytesExt};
 use goxlr_types::{
     ChannelName, EffectKey, EncoderName, FaderName, FirmwareVersions, MicrophoneParamKey,
-    MicrophoneType, Mix, SubMixChannelName, VersionNumber,
+    MicrophoneType, Mix, SubMixChannelName,
 };
-use std::io::{Cursor, Write};
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc::Sender;
 
+// How many of the most recent request timings to keep for calculating percentiles
+const METRICS_HISTORY: usize = 500;
+
 // This is a basic SuperTrait which defines all the 'Parts' of the GoXLR for use.
 pub trait FullGoXLRDevice: AttachGoXLR + GoXLRCommands + Sync + Send {}
 
@@ -34,15 +39,26 @@ pub trait AttachGoXLR {
     fn set_unique_identifier(&mut self, identifier: String);
     fn is_connected(&mut self) -> bool;
     fn stop_polling(&mut self);
+    fn set_poll_interval(&mut self, interval: Duration);
+
+    // Whether a kernel driver is bound to the GoXLR's interface, None where there's no such thing
+    fn kernel_driver_active(&self) -> Option<bool> {
+        None
+    }
 }
 
 pub trait ExecutableGoXLR {
     fn request_data(&mut self, command: Command, body: &[u8]) -> Result<Vec<u8>> {
-        self.perform_request(command, body, false)
+        let start = Instant::now();
+        let result = self.perform_request(command, body, false);
+        self.request_metrics()
+            .record(start.elapsed(), result.is_ok());
+        result
     }
 
     fn perform_request(&mut self, command: Command, body: &[u8], retry: bool) -> Result<Vec<u8>>;
     fn get_descriptor(&self) -> Result<UsbData>;
+    fn request_metrics(&mut self) -> &mut RequestMetrics;
 }
 
 // These are commands that can be executed, but perform_request must be implemented..
@@ -54,6 +70,11 @@ pub trait GoXLRCommands: ExecutableGoXLR {
         Ok(LittleEndian::read_u16(&result) == 1)
     }
 
+    fn reset_command_index(&mut self) -> Result<()> {
+        self.request_data(Command::ResetCommandIndex, &[])?;
+        Ok(())
+    }
+
     fn get_system_info(&mut self) -> Result<()> {
         let _result =
             self.request_data(Command::SystemInfo(SystemInfoCommand::FirmwareVersion), &[])?;
@@ -66,33 +87,7 @@ pub trait GoXLRCommands: ExecutableGoXLR {
             Command::GetHardwareInfo(HardwareInfoCommand::FirmwareVersion),
             &[],
         )?;
-        let mut cursor = Cursor::new(result);
-        let firmware_packed = cursor.read_u32::<LittleEndian>()?;
-        let firmware_build = cursor.read_u32::<LittleEndian>()?;
-        let firmware = VersionNumber(
-            firmware_packed >> 12,
-            (firmware_packed >> 8) & 0xF,
-            Some(firmware_packed & 0xFF),
-            Some(firmware_build),
-        );
-
-        let _unknown = cursor.read_u32::<LittleEndian>()?;
-        let fpga_count = cursor.read_u32::<LittleEndian>()?;
-
-        let dice_build = cursor.read_u32::<LittleEndian>()?;
-        let dice_packed = cursor.read_u32::<LittleEndian>()?;
-        let dice = VersionNumber(
-            (dice_packed >> 20) & 0xF,
-            (dice_packed >> 12) & 0xFF,
-            Some(dice_packed & 0xFFF),
-            Some(dice_build),
-        );
-
-        Ok(FirmwareVersions {
-            firmware,
-            fpga_count,
-            dice,
-        })
+        protocol::parse_firmware_version(&result)
     }
 
     fn get_serial_number(&mut self) -> Result<(String, String)> {
@@ -100,22 +95,7 @@ pub trait GoXLRCommands: ExecutableGoXLR {
             Command::GetHardwareInfo(HardwareInfoCommand::SerialNumber),
             &[],
         )?;
-
-        let serial_slice = &result[..24];
-        let serial_len = serial_slice
-            .iter()
-            .position(|&c| c == 0)
-            .unwrap_or(serial_slice.len());
-        let serial_number = String::from_utf8_lossy(&serial_slice[..serial_len]).to_string();
-
-        let date_slice = &result[24..];
-        let date_len = date_slice
-            .iter()
-            .position(|&c| c == 0)
-            .unwrap_or(date_slice.len());
-        let manufacture_date = String::from_utf8_lossy(&date_slice[..date_len]).to_string();
-
-        Ok((serial_number, manufacture_date))
+        protocol::parse_serial_number(&result)
     }
 
     fn set_fader(&mut self, fader: FaderName, channel: ChannelName) -> Result<()> {
@@ -223,28 +203,18 @@ pub trait GoXLRCommands: ExecutableGoXLR {
 
     fn get_microphone_level(&mut self) -> Result<u16> {
         let result = self.request_data(Command::GetMicrophoneLevel, &[])?;
-        Ok(LittleEndian::read_u16(&result))
+        protocol::parse_microphone_level(&result)
     }
 
     fn set_effect_values(&mut self, effects: &[(EffectKey, i32)]) -> Result<()> {
-        let mut data = Vec::with_capacity(effects.len() * 8);
-        let mut cursor = Cursor::new(&mut data);
-        for (key, value) in effects {
-            cursor.write_u32::<LittleEndian>(*key as u32)?;
-            cursor.write_i32::<LittleEndian>(*value)?;
-        }
+        let data = protocol::encode_effect_values(effects);
         self.request_data(Command::SetEffectParameters, &data)?;
 
         Ok(())
     }
 
     fn set_mic_param(&mut self, params: &[(MicrophoneParamKey, [u8; 4])]) -> Result<()> {
-        let mut data = Vec::with_capacity(params.len() * 8);
-        let mut cursor = Cursor::new(&mut data);
-        for (key, value) in params {
-            cursor.write_u32::<LittleEndian>(*key as u32)?;
-            cursor.write_all(value)?;
-        }
+        let data = protocol::encode_mic_params(params);
         self.request_data(Command::SetMicrophoneParameters, &data)?;
 
         Ok(())
@@ -252,33 +222,7 @@ pub trait GoXLRCommands: ExecutableGoXLR {
 
     fn get_button_states(&mut self) -> Result<CurrentButtonStates> {
         let result = self.request_data(Command::GetButtonStates, &[])?;
-        let mut pressed = EnumSet::empty();
-        let mut mixers = [0; 4];
-        let mut encoders = [0; 4];
-        let button_states = LittleEndian::read_u32(&result[0..4]);
-
-        mixers[0] = result[8];
-        mixers[1] = result[9];
-        mixers[2] = result[10];
-        mixers[3] = result[11];
-
-        // These can technically be negative, cast straight to i8
-        encoders[0] = result[4] as i8; // Pitch
-        encoders[1] = result[5] as i8; // Gender
-        encoders[2] = result[6] as i8; // Reverb
-        encoders[3] = result[7] as i8; // Echo
-
-        for button in EnumSet::<Buttons>::all() {
-            if button_states & (1 << button as u8) != 0 {
-                pressed.insert(button);
-            }
-        }
-
-        Ok(CurrentButtonStates {
-            pressed,
-            volumes: mixers,
-            encoders,
-        })
+        protocol::parse_button_states(&result)
     }
 
     fn set_animation_mode(
@@ -561,6 +505,65 @@ impl GoXLRDevice {
     }
 }
 
+/// Tracks how long things are taking, along with how many of them failed. Only the most recent
+/// timings are kept, so the percentiles reflect how things are behaving now.
+#[derive(Debug, Default, Clone)]
+pub struct RequestMetrics {
+    count: u64,
+    errors: u64,
+    mismatches: u64,
+    timings: VecDeque<Duration>,
+}
+
+impl RequestMetrics {
+    pub fn record(&mut self, duration: Duration, success: bool) {
+        self.count += 1;
+        if !success {
+            self.errors += 1;
+        }
+
+        if self.timings.len() == METRICS_HISTORY {
+            self.timings.pop_front();
+        }
+        self.timings.push_back(duration);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    /// A response came back with a different command index to its request, and the device had
+    /// to be resynced.
+    pub fn record_mismatch(&mut self) {
+        self.mismatches += 1;
+    }
+
+    pub fn mismatches(&self) -> u64 {
+        self.mismatches
+    }
+
+    /// Returns the timing below which `percent` of the recent timings fall.
+    pub fn percentile(&self, percent: f64) -> Duration {
+        if self.timings.is_empty() {
+            return Duration::ZERO;
+        }
+
+        let mut timings: Vec<Duration> = self.timings.iter().copied().collect();
+        timings.sort();
+
+        let index = ((timings.len() - 1) as f64 * percent / 100.0).round() as usize;
+        timings[index.min(timings.len() - 1)]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.timings.iter().max().copied().unwrap_or_default()
+    }
+}
+
 pub struct UsbData {
     pub(crate) vendor_id: u16,
     pub(crate) product_id: u16,
@@ -1,7 +1,10 @@
 use crate::commands::Command;
 use crate::device::base::{
-    AttachGoXLR, ExecutableGoXLR, FullGoXLRDevice, GoXLRCommands, GoXLRDevice, UsbData,
+    AttachGoXLR, ExecutableGoXLR, FullGoXLRDevice, GoXLRCommands, GoXLRDevice, RequestMetrics,
+    UsbData,
 };
+use crate::error::ConnectError;
+use crate::protocol;
 use crate::{PID_GOXLR_FULL, PID_GOXLR_MINI, VID_GOXLR};
 use anyhow::{anyhow, bail, Error, Result};
 use byteorder::{ByteOrder, LittleEndian};
@@ -12,7 +15,7 @@ use rusb::{
     Device, DeviceDescriptor, DeviceHandle, Direction, GlobalContext, Language, Recipient,
     RequestType,
 };
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
@@ -30,13 +33,19 @@ pub struct GoXLRUSB {
 
     pause_polling: Arc<AtomicBool>,
     stop_polling: Arc<AtomicBool>,
+    poll_millis: Arc<AtomicU64>,
 
     stopping: Arc<AtomicBool>,
     disconnecting: bool,
 
+    // Set while a USB reset is being attempted, so a failure during it doesn't trigger another
+    resetting: bool,
+
     language: Language,
     command_count: u16,
     timeout: Duration,
+
+    request_metrics: RequestMetrics,
 }
 
 impl GoXLRUSB {
@@ -98,6 +107,39 @@ impl GoXLRUSB {
         Ok(())
     }
 
+    /// When a resync hasn't helped, the firmware has likely hung. Resetting the device at the USB
+    /// level and reactivating the command pipe (as is done during initialisation) will often
+    /// bring it back without needing to be replugged.
+    fn reset_device(&mut self) -> Result<()> {
+        warn!("GoXLR is not responding, attempting a USB reset..");
+        self.handle.reset()?;
+        self.write_control(1, 0, 0, &[])?;
+        self.read_control(3, 0, 0, 1040)?;
+        self.command_count = 0;
+        Ok(())
+    }
+
+    // Resets the device and retries the request, returns None if a reset isn't possible..
+    fn reset_and_retry(&mut self, command: Command, body: &[u8]) -> Option<Result<Vec<u8>>> {
+        if self.resetting || self.disconnecting {
+            return None;
+        }
+
+        self.resetting = true;
+        let result = match self.reset_device() {
+            Ok(()) => {
+                info!("USB reset complete, retrying {:?}", command);
+                Some(self.perform_request(command, body, false))
+            }
+            Err(error) => {
+                warn!("Unable to reset GoXLR: {}", error);
+                None
+            }
+        };
+        self.resetting = false;
+        result
+    }
+
     pub(crate) fn write_control(
         &mut self,
         request: u8,
@@ -147,7 +189,10 @@ impl AttachGoXLR for GoXLRUSB {
     ) -> Result<Box<(dyn FullGoXLRDevice)>> {
         // Firstly, we need to locate the USB device based on the location..
         let (device, descriptor) = GoXLRUSB::find_device(device)?;
-        let handle = device.open()?;
+        let handle = device.open().map_err(|e| match e {
+            rusb::Error::Access => anyhow!(ConnectError::PermissionDenied),
+            e => anyhow!(e),
+        })?;
 
         let timeout = Duration::from_secs(1);
 
@@ -173,9 +218,12 @@ impl AttachGoXLR for GoXLRUSB {
             command_count: 0,
             stopping: Arc::new(AtomicBool::new(false)),
             disconnecting: false,
+            resetting: false,
             timeout,
             pause_polling: Arc::new(AtomicBool::new(false)),
             stop_polling: Arc::new(AtomicBool::new(false)),
+            poll_millis: Arc::new(AtomicU64::new(20)),
+            request_metrics: RequestMetrics::default(),
         };
 
         // Resets the state of the device (unconfirmed - Might just be the command id counter)
@@ -191,10 +239,16 @@ impl AttachGoXLR for GoXLRUSB {
             if device_is_claimed {
                 goxlr.handle.release_interface(0)?;
             }
-            goxlr.handle.set_auto_detach_kernel_driver(true)?;
+            // Windows (WinUSB) has no kernel driver to detach, so this isn't supported there..
+            match goxlr.handle.set_auto_detach_kernel_driver(true) {
+                Ok(()) | Err(rusb::Error::NotSupported) => {}
+                Err(e) => return Err(e.into()),
+            }
 
-            if goxlr.handle.claim_interface(0).is_err() {
-                return Err(anyhow!("Unable to Claim Device"));
+            match goxlr.handle.claim_interface(0) {
+                Ok(()) => {}
+                Err(rusb::Error::Access) => return Err(anyhow!(ConnectError::PermissionDenied)),
+                Err(_) => return Err(anyhow!("Unable to Claim Device")),
             }
 
             debug!("Activating Vendor Interface...");
@@ -234,14 +288,16 @@ impl AttachGoXLR for GoXLRUSB {
         let stopping = self.stopping.clone();
         let paused = self.pause_polling.clone();
         let stopped = self.stop_polling.clone();
+        let interval = self.poll_millis.clone();
 
-        let poll_millis = 20;
         task::spawn(async move {
             loop {
                 if stopping.load(Ordering::Relaxed) {
                     break;
                 }
 
+                let poll_millis = interval.load(Ordering::Relaxed);
+
                 if paused.load(Ordering::Relaxed) || stopped.load(Ordering::Relaxed) {
                     tokio::time::sleep(Duration::from_millis(poll_millis)).await;
                     continue;
@@ -284,6 +340,15 @@ impl AttachGoXLR for GoXLRUSB {
         warn!("Disabling GoXLR Value Polling");
         self.stop_polling.store(true, Ordering::Relaxed);
     }
+
+    fn set_poll_interval(&mut self, interval: Duration) {
+        self.poll_millis
+            .store(interval.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    fn kernel_driver_active(&self) -> Option<bool> {
+        self.handle.kernel_driver_active(0).ok()
+    }
 }
 
 impl ExecutableGoXLR for GoXLRUSB {
@@ -304,11 +369,13 @@ impl ExecutableGoXLR for GoXLRUSB {
         }
 
         let command_index = self.command_count;
-        let mut full_request = vec![0; 16];
-        LittleEndian::write_u32(&mut full_request[0..4], command.command_id());
-        LittleEndian::write_u16(&mut full_request[4..6], body.len() as u16);
-        LittleEndian::write_u16(&mut full_request[6..8], command_index);
-        full_request.extend(body);
+        let full_request = match protocol::build_request(command, command_index, body) {
+            Ok(request) => request,
+            Err(error) => {
+                self.pause_polling.store(false, Ordering::Relaxed);
+                return Err(error);
+            }
+        };
 
         if let Err(error) = self.write_control(2, 0, 0, &full_request) {
             debug!("Error when attempting to write control.");
@@ -334,10 +401,14 @@ impl ExecutableGoXLR for GoXLRUSB {
                     sleep(sleep_time);
                     continue;
                 } else {
+                    warn!("Failed to receive response (Attempt 20 of 20), possible Dead GoXLR?");
+                    if let Some(result) = self.reset_and_retry(command, body) {
+                        return result;
+                    }
+
                     // We can't read from this GoXLR, flag as disconnected.
                     self.pause_polling.store(false, Ordering::Relaxed);
                     self.trigger_disconnect()?;
-                    warn!("Failed to receive response (Attempt 20 of 20), possible Dead GoXLR?");
                     return Err(Error::from(response_value.err().unwrap()));
                 }
             }
@@ -367,6 +438,7 @@ impl ExecutableGoXLR for GoXLRUSB {
 
             if response_command_index != command_index {
                 debug!("Mismatched Command Indexes..");
+                self.request_metrics.record_mismatch();
                 debug!(
                     "Expected {}, received: {}",
                     command_index, response_command_index
@@ -390,7 +462,12 @@ impl ExecutableGoXLR for GoXLRUSB {
                     }
                     return result;
                 } else {
-                    debug!("Resync Failed, Throwing Error..");
+                    debug!("Resync Failed..");
+                    if let Some(result) = self.reset_and_retry(command, body) {
+                        return result;
+                    }
+
+                    debug!("Unable to recover, Throwing Error..");
                     self.pause_polling.store(false, Ordering::Relaxed);
                     self.trigger_disconnect()?;
                     Err(Error::from(rusb::Error::Other))
@@ -429,6 +506,10 @@ impl ExecutableGoXLR for GoXLRUSB {
             product_name,
         })
     }
+
+    fn request_metrics(&mut self) -> &mut RequestMetrics {
+        &mut self.request_metrics
+    }
 }
 
 impl GoXLRCommands for GoXLRUSB {}
@@ -9,16 +9,31 @@ pub mod base;
 
 cfg_if::cfg_if! {
     if #[cfg(target_os = "windows")] {
-        // Under Windows, we need to utilise the official GoXLR Driver to communicate..
+        // Under Windows, we generally need to utilise the official GoXLR Driver to communicate,
+        // however if it's not installed the device may have been bound to WinUSB (via Zadig or
+        // similar), in which case we can fall back to libUSB.
         mod tusb;
-        use crate::device::tusb::device;
+        mod libusb;
+        use lazy_static::lazy_static;
+
+        lazy_static! {
+            static ref USE_TUSB: bool = tusb::device::is_driver_available();
+        }
 
         pub fn get_version() -> (DriverInterface, VersionNumber) {
-            device::get_interface_version()
+            if *USE_TUSB {
+                tusb::device::get_interface_version()
+            } else {
+                libusb::device::get_interface_version()
+            }
         }
 
         pub fn find_devices() -> Vec<GoXLRDevice> {
-            device::find_devices()
+            if *USE_TUSB {
+                tusb::device::find_devices()
+            } else {
+                libusb::device::find_devices()
+            }
         }
 
         pub fn from_device(
@@ -27,7 +42,11 @@ cfg_if::cfg_if! {
             event_sender: Sender<String>,
             skip_pause: bool,
         ) -> Result<Box<dyn FullGoXLRDevice>> {
-            device::TUSBAudioGoXLR::from_device(device, disconnect_sender, event_sender, skip_pause)
+            if *USE_TUSB {
+                tusb::device::TUSBAudioGoXLR::from_device(device, disconnect_sender, event_sender, skip_pause)
+            } else {
+                libusb::device::GoXLRUSB::from_device(device, disconnect_sender, event_sender, skip_pause)
+            }
         }
     } else {
         // If we're using Linux / MacOS / etc, utilise libUSB for control.
@@ -1,11 +1,13 @@
 use crate::commands::Command;
 use crate::device::base::{
-    AttachGoXLR, ExecutableGoXLR, FullGoXLRDevice, GoXLRCommands, GoXLRDevice, UsbData,
+    AttachGoXLR, ExecutableGoXLR, FullGoXLRDevice, GoXLRCommands, GoXLRDevice, RequestMetrics,
+    UsbData,
 };
 use crate::device::tusb::tusbaudio::{
-    get_devices, get_version, DeviceHandle, EventChannelReceiver, EventChannelSender,
-    TUSB_INTERFACE,
+    driver_available, get_devices, get_version, DeviceHandle, EventChannelReceiver,
+    EventChannelSender, TUSB_INTERFACE,
 };
+use crate::protocol;
 use anyhow::{bail, Result};
 use byteorder::{ByteOrder, LittleEndian};
 use goxlr_types::{DriverInterface, VersionNumber};
@@ -35,6 +37,8 @@ pub struct TUSBAudioGoXLR {
 
     // Thread states
     stopped: Arc<AtomicBool>,
+
+    request_metrics: RequestMetrics,
 }
 
 impl TUSBAudioGoXLR {
@@ -149,6 +153,8 @@ impl AttachGoXLR for TUSBAudioGoXLR {
             daemon_identifier: Arc::new(Mutex::new(None)),
 
             stopped: Arc::new(AtomicBool::new(false)),
+
+            request_metrics: RequestMetrics::default(),
         });
 
         let (ready_sender, ready_recv) = tokio::sync::oneshot::channel();
@@ -243,6 +249,10 @@ impl AttachGoXLR for TUSBAudioGoXLR {
     fn stop_polling(&mut self) {
         // The TUSB implementation is event driven, so there's no polling to stop.
     }
+
+    fn set_poll_interval(&mut self, _interval: Duration) {
+        // Event driven, as above.
+    }
 }
 
 impl ExecutableGoXLR for TUSBAudioGoXLR {
@@ -257,11 +267,7 @@ impl ExecutableGoXLR for TUSBAudioGoXLR {
         }
 
         let command_index = self.command_count;
-        let mut full_request = vec![0; 16];
-        LittleEndian::write_u32(&mut full_request[0..4], command.command_id());
-        LittleEndian::write_u16(&mut full_request[4..6], body.len() as u16);
-        LittleEndian::write_u16(&mut full_request[6..8], command_index);
-        full_request.extend(body);
+        let full_request = protocol::build_request(command, command_index, body)?;
 
         if let Err(error) = self.write_control(2, 0, 0, &full_request) {
             if error.to_string() == "TSTATUS_INVALID_HANDLE" {
@@ -328,6 +334,7 @@ impl ExecutableGoXLR for TUSBAudioGoXLR {
 
         if response_command_index != command_index {
             debug!("Mismatched Command Indexes..");
+            self.request_metrics.record_mismatch();
             debug!(
                 "Expected {}, received: {}",
                 command_index, response_command_index
@@ -364,6 +371,10 @@ impl ExecutableGoXLR for TUSBAudioGoXLR {
             product_name: properties.model()?,
         })
     }
+
+    fn request_metrics(&mut self) -> &mut RequestMetrics {
+        &mut self.request_metrics
+    }
 }
 
 impl GoXLRCommands for TUSBAudioGoXLR {}
@@ -373,6 +384,10 @@ pub fn find_devices() -> Vec<GoXLRDevice> {
     get_devices()
 }
 
+pub fn is_driver_available() -> bool {
+    driver_available()
+}
+
 pub fn get_interface_version() -> (DriverInterface, VersionNumber) {
     (DriverInterface::TUSB, get_version())
 }
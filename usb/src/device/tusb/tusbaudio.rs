@@ -78,6 +78,15 @@ fn locate_library() -> String {
     String::from("C:/Program Files/TC-HELICON/GoXLR_Audio_Driver/W10_x64/goxlr_audioapi_x64.dll")
 }
 
+// Checked before anything touches TUSB_INTERFACE, as loading it will panic if it's missing..
+pub fn driver_available() -> bool {
+    let available = PathBuf::from(locate_library()).exists();
+    if !available {
+        warn!("GoXLR API Driver not found, falling back to libUSB");
+    }
+    available
+}
+
 #[allow(dead_code)]
 pub struct TUSBAudio<'lib> {
     // DriverInfo
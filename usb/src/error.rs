@@ -11,6 +11,9 @@ pub enum ConnectError {
 
     #[error("Unable to Claim Interface")]
     DeviceNotClaimed,
+
+    #[error("Permission denied while accessing the GoXLR")]
+    PermissionDenied,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -7,6 +7,7 @@ pub mod dcp;
 pub mod devices;
 pub mod error;
 pub mod microphone;
+pub mod protocol;
 pub mod routing;
 
 pub mod animation;
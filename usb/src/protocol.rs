@@ -0,0 +1,127 @@
+//! Builds the bodies of requests sent to the GoXLR, and parses its responses. These live apart
+//! from the devices so they can be tested (and fuzzed) without any hardware attached.
+
+use crate::buttonstate::{Buttons, CurrentButtonStates};
+use crate::commands::Command;
+use anyhow::{bail, Result};
+use byteorder::{ByteOrder, LittleEndian};
+use enumset::EnumSet;
+use goxlr_types::{EffectKey, FirmwareVersions, MicrophoneParamKey, VersionNumber};
+
+pub const REQUEST_HEADER_SIZE: usize = 16;
+
+/// Prefixes a request body with the header the GoXLR expects
+pub fn build_request(command: Command, command_index: u16, body: &[u8]) -> Result<Vec<u8>> {
+    let Ok(body_length) = u16::try_from(body.len()) else {
+        bail!("Request body is too large ({} bytes)", body.len());
+    };
+
+    let mut request = vec![0; REQUEST_HEADER_SIZE];
+    LittleEndian::write_u32(&mut request[0..4], command.command_id());
+    LittleEndian::write_u16(&mut request[4..6], body_length);
+    LittleEndian::write_u16(&mut request[6..8], command_index);
+    request.extend(body);
+    Ok(request)
+}
+
+pub fn encode_effect_values(effects: &[(EffectKey, i32)]) -> Vec<u8> {
+    let mut data = vec![0; effects.len() * 8];
+    for ((key, value), chunk) in effects.iter().zip(data.chunks_exact_mut(8)) {
+        LittleEndian::write_u32(&mut chunk[0..4], *key as u32);
+        LittleEndian::write_i32(&mut chunk[4..8], *value);
+    }
+    data
+}
+
+pub fn encode_mic_params(params: &[(MicrophoneParamKey, [u8; 4])]) -> Vec<u8> {
+    let mut data = vec![0; params.len() * 8];
+    for ((key, value), chunk) in params.iter().zip(data.chunks_exact_mut(8)) {
+        LittleEndian::write_u32(&mut chunk[0..4], *key as u32);
+        chunk[4..8].copy_from_slice(value);
+    }
+    data
+}
+
+pub fn parse_firmware_version(data: &[u8]) -> Result<FirmwareVersions> {
+    check_length(data, 24, "Firmware Version")?;
+
+    let firmware_packed = LittleEndian::read_u32(&data[0..4]);
+    let firmware_build = LittleEndian::read_u32(&data[4..8]);
+    let firmware = VersionNumber(
+        firmware_packed >> 12,
+        (firmware_packed >> 8) & 0xF,
+        Some(firmware_packed & 0xFF),
+        Some(firmware_build),
+    );
+
+    // data[8..12] is unknown..
+    let fpga_count = LittleEndian::read_u32(&data[12..16]);
+
+    let dice_build = LittleEndian::read_u32(&data[16..20]);
+    let dice_packed = LittleEndian::read_u32(&data[20..24]);
+    let dice = VersionNumber(
+        (dice_packed >> 20) & 0xF,
+        (dice_packed >> 12) & 0xFF,
+        Some(dice_packed & 0xFFF),
+        Some(dice_build),
+    );
+
+    Ok(FirmwareVersions {
+        firmware,
+        fpga_count,
+        dice,
+    })
+}
+
+/// Returns the serial number, and the date the device was manufactured
+pub fn parse_serial_number(data: &[u8]) -> Result<(String, String)> {
+    check_length(data, 24, "Serial Number")?;
+
+    let (serial, date) = data.split_at(24);
+    Ok((read_string(serial), read_string(date)))
+}
+
+pub fn parse_button_states(data: &[u8]) -> Result<CurrentButtonStates> {
+    check_length(data, 12, "Button States")?;
+
+    let button_states = LittleEndian::read_u32(&data[0..4]);
+    let mut pressed = EnumSet::empty();
+    for button in EnumSet::<Buttons>::all() {
+        if button_states & (1 << button as u8) != 0 {
+            pressed.insert(button);
+        }
+    }
+
+    // Pitch, Gender, Reverb and Echo, these can technically be negative, cast straight to i8
+    let encoders = [data[4] as i8, data[5] as i8, data[6] as i8, data[7] as i8];
+    let volumes = [data[8], data[9], data[10], data[11]];
+
+    Ok(CurrentButtonStates {
+        pressed,
+        volumes,
+        encoders,
+    })
+}
+
+pub fn parse_microphone_level(data: &[u8]) -> Result<u16> {
+    check_length(data, 2, "Microphone Level")?;
+    Ok(LittleEndian::read_u16(data))
+}
+
+fn check_length(data: &[u8], length: usize, name: &str) -> Result<()> {
+    if data.len() < length {
+        bail!(
+            "{} response too short, expected {} bytes, received {}",
+            name,
+            length,
+            data.len()
+        );
+    }
+    Ok(())
+}
+
+// Strings are NUL terminated, unless they fill the space they're given..
+fn read_string(data: &[u8]) -> String {
+    let length = data.iter().position(|&c| c == 0).unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..length]).to_string()
+}
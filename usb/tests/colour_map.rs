@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use goxlr_usb::colouring::{ColourMapFormat, ColourTargets};
+use strum::IntoEnumIterator;
+
+#[test]
+fn colours_fit_inside_the_map() {
+    for format in ColourMapFormat::iter() {
+        for target in ColourTargets::iter() {
+            for colour in 0..target.get_colour_count() {
+                let position = target.position(colour, format);
+                assert!(
+                    position + 4 <= format.size(),
+                    "{:?} colour {} is outside the {:?} map",
+                    target,
+                    colour,
+                    format
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn colours_dont_overlap() {
+    for format in ColourMapFormat::iter() {
+        let mut used = HashMap::new();
+
+        for target in ColourTargets::iter() {
+            for colour in 0..target.get_colour_count() {
+                let position = target.position(colour, format);
+                assert_eq!(
+                    position % 4,
+                    0,
+                    "{:?} colour {} isn't aligned",
+                    target,
+                    colour
+                );
+
+                if let Some((other, other_colour)) = used.insert(position, (target, colour)) {
+                    panic!(
+                        "{:?} colour {} overlaps {:?} colour {} in the {:?} map",
+                        target, colour, other, other_colour, format
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn legacy_positions() {
+    let format = ColourMapFormat::Legacy;
+
+    assert_eq!(ColourTargets::Scribble1.position(0, format), 0);
+    assert_eq!(ColourTargets::Fader1Mute.position(0, format), 48);
+    assert_eq!(ColourTargets::FadeMeter2.position(1, format), 92);
+    assert_eq!(ColourTargets::MicrophoneMute.position(1, format), 324);
+
+    // Encoders store their first two colours the other way around..
+    assert_eq!(ColourTargets::PitchEncoder.position(0, format), 168);
+    assert_eq!(ColourTargets::PitchEncoder.position(1, format), 164);
+    assert_eq!(ColourTargets::PitchEncoder.position(2, format), 172);
+}
+
+#[test]
+fn animated_positions() {
+    let format = ColourMapFormat::Animated;
+
+    // Everything before the faders stays where it was..
+    assert_eq!(ColourTargets::Scribble1.position(0, format), 0);
+    assert_eq!(ColourTargets::Fader1Mute.position(0, format), 48);
+
+    // While the faders are spread out, and everything after them moves along..
+    assert_eq!(ColourTargets::FadeMeter2.position(1, format), 140);
+    assert_eq!(ColourTargets::MicrophoneMute.position(1, format), 516);
+
+    assert_eq!(ColourTargets::PitchEncoder.position(0, format), 360);
+    assert_eq!(ColourTargets::PitchEncoder.position(1, format), 356);
+    assert_eq!(ColourTargets::PitchEncoder.position(2, format), 364);
+}
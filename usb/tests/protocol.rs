@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+
+use goxlr_types::{
+    EffectKey, InputDevice, MicrophoneParamKey, OutputDevice as BasicOutputDevice, VersionNumber,
+};
+use goxlr_usb::buttonstate::Buttons;
+use goxlr_usb::commands::Command;
+use goxlr_usb::protocol;
+use goxlr_usb::routing::{InputDevice as RoutingInput, OutputDevice};
+use proptest::prelude::*;
+use proptest::sample::select;
+use strum::IntoEnumIterator;
+
+fn effect_key() -> impl Strategy<Value = EffectKey> {
+    select(EffectKey::iter().collect::<Vec<_>>())
+}
+
+fn mic_param_key() -> impl Strategy<Value = MicrophoneParamKey> {
+    select(MicrophoneParamKey::iter().collect::<Vec<_>>())
+}
+
+fn read_u32(data: &[u8]) -> u32 {
+    u32::from_le_bytes(data.try_into().unwrap())
+}
+
+proptest! {
+    #[test]
+    fn request_header_layout(index: u16, body in prop::collection::vec(any::<u8>(), 0..1024)) {
+        let command = Command::SetEffectParameters;
+        let request = protocol::build_request(command, index, &body).unwrap();
+
+        prop_assert_eq!(request.len(), protocol::REQUEST_HEADER_SIZE + body.len());
+        prop_assert_eq!(read_u32(&request[0..4]), command.command_id());
+        prop_assert_eq!(u16::from_le_bytes([request[4], request[5]]) as usize, body.len());
+        prop_assert_eq!(u16::from_le_bytes([request[6], request[7]]), index);
+        prop_assert!(request[8..16].iter().all(|&byte| byte == 0));
+        prop_assert_eq!(&request[16..], &body[..]);
+    }
+
+    #[test]
+    fn effect_values_round_trip(
+        effects in prop::collection::vec((effect_key(), any::<i32>()), 0..64)
+    ) {
+        let data = protocol::encode_effect_values(&effects);
+        prop_assert_eq!(data.len(), effects.len() * 8);
+
+        for ((key, value), chunk) in effects.iter().zip(data.chunks_exact(8)) {
+            prop_assert_eq!(read_u32(&chunk[0..4]), *key as u32);
+            prop_assert_eq!(i32::from_le_bytes(chunk[4..8].try_into().unwrap()), *value);
+        }
+    }
+
+    #[test]
+    fn mic_params_round_trip(
+        params in prop::collection::vec((mic_param_key(), any::<[u8; 4]>()), 0..16)
+    ) {
+        let data = protocol::encode_mic_params(&params);
+        prop_assert_eq!(data.len(), params.len() * 8);
+
+        for ((key, value), chunk) in params.iter().zip(data.chunks_exact(8)) {
+            prop_assert_eq!(read_u32(&chunk[0..4]), *key as u32);
+            prop_assert_eq!(&chunk[4..8], &value[..]);
+        }
+    }
+
+    // Whatever comes back from the device, parsing should fail rather than panic..
+    #[test]
+    fn parsers_dont_panic(data in prop::collection::vec(any::<u8>(), 0..64)) {
+        let _ = protocol::parse_firmware_version(&data);
+        let _ = protocol::parse_serial_number(&data);
+        let _ = protocol::parse_button_states(&data);
+        let _ = protocol::parse_microphone_level(&data);
+    }
+
+    #[test]
+    fn button_states_match_bits(
+        states: u32,
+        encoders: [i8; 4],
+        volumes: [u8; 4],
+    ) {
+        let mut data = states.to_le_bytes().to_vec();
+        data.extend(encoders.map(|encoder| encoder as u8));
+        data.extend(volumes);
+
+        let parsed = protocol::parse_button_states(&data).unwrap();
+        prop_assert_eq!(parsed.encoders, encoders);
+        prop_assert_eq!(parsed.volumes, volumes);
+        for button in Buttons::iter() {
+            let set = states & (1 << button as u8) != 0;
+            prop_assert_eq!(parsed.pressed.contains(button), set, "{:?}", button);
+        }
+    }
+}
+
+#[test]
+fn oversized_request_body() {
+    let body = vec![0; u16::MAX as usize + 1];
+    assert!(protocol::build_request(Command::SetColourMap(), 0, &body).is_err());
+}
+
+#[test]
+fn firmware_version() {
+    let mut data = vec![];
+    for value in [0x1402, 107, 0, 2, 1234, (4 << 20) | (3 << 12) | 12] {
+        data.extend(u32::to_le_bytes(value));
+    }
+
+    let versions = protocol::parse_firmware_version(&data).unwrap();
+    assert!(versions.firmware == VersionNumber(1, 4, Some(2), Some(107)));
+    assert_eq!(versions.fpga_count, 2);
+    assert!(versions.dice == VersionNumber(4, 3, Some(12), Some(1234)));
+
+    assert!(protocol::parse_firmware_version(&data[..20]).is_err());
+}
+
+#[test]
+fn serial_number() {
+    let mut data = b"S210400000000".to_vec();
+    data.resize(24, 0);
+    data.extend(b"20210401");
+
+    let (serial, date) = protocol::parse_serial_number(&data).unwrap();
+    assert_eq!(serial, "S210400000000");
+    assert_eq!(date, "20210401");
+
+    assert!(protocol::parse_serial_number(&data[..23]).is_err());
+}
+
+#[test]
+fn routing_fits_inside_the_table() {
+    let mut used = HashMap::new();
+
+    for basic in BasicOutputDevice::iter() {
+        let (left, right) = OutputDevice::from_basic(&basic);
+        for output in [left, right] {
+            let position = output.position();
+            assert!(position < 22, "{:?} is outside the routing table", output);
+
+            if let Some(other) = used.insert(position, output) {
+                panic!("{:?} overlaps {:?} in the routing table", output, other);
+            }
+        }
+    }
+
+    for input in InputDevice::iter() {
+        let (left, right) = RoutingInput::from_basic(&input);
+        assert_ne!(left.id(), right.id(), "{:?} has one channel", input);
+    }
+}